@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{Amount, SyncPhase};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type, tauri_specta::Event))]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -19,4 +21,31 @@ pub enum SyncEvent {
     CatInfo,
     DidInfo,
     NftData,
+    CoinsManaged {
+        asset_id: Option<String>,
+        action: CoinManagementAction,
+        coin_count: u32,
+        fee: Amount,
+        transaction_id: String,
+    },
+    MintJob {
+        mint_job_id: u32,
+    },
+    Reorg {
+        fork_height: u32,
+    },
+    SyncProgress {
+        phase: SyncPhase,
+        completed: u64,
+        remaining: u64,
+        eta_seconds: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[serde(rename_all = "snake_case")]
+pub enum CoinManagementAction {
+    Split,
+    Combine,
 }
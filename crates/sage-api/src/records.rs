@@ -1,27 +1,41 @@
+mod clawback;
 mod coin;
 mod derivation;
 mod did;
+mod exchange_offer;
+mod mint_job;
 mod nft;
 mod nft_collection;
+mod nft_mint_warning;
 mod offer;
 mod offer_summary;
 mod option;
 mod peer;
 mod pending_transaction;
+mod royalty;
+mod standing_offer;
+mod sync_progress;
 mod token;
 mod transaction;
 mod transaction_summary;
 
+pub use clawback::*;
 pub use coin::*;
 pub use derivation::*;
 pub use did::*;
+pub use exchange_offer::*;
+pub use mint_job::*;
 pub use nft::*;
 pub use nft_collection::*;
+pub use nft_mint_warning::*;
 pub use offer::*;
 pub use offer_summary::*;
 pub use option::*;
 pub use peer::*;
 pub use pending_transaction::*;
+pub use royalty::*;
+pub use standing_offer::*;
+pub use sync_progress::*;
 pub use token::*;
 pub use transaction::*;
 pub use transaction_summary::*;
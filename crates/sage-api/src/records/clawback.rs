@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, Asset};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClawbackRecord {
+    pub coin_id: String,
+    pub asset: Asset,
+    pub amount: Amount,
+    pub sender_address: String,
+    pub receiver_address: String,
+    pub expiration_timestamp: u64,
+    pub seconds_remaining: u64,
+    pub transaction_id: Option<String>,
+    pub created_height: Option<u32>,
+    pub created_timestamp: Option<u64>,
+}
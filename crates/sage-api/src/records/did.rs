@@ -15,3 +15,18 @@ pub struct DidRecord {
     pub recovery_hash: Option<String>,
     pub created_height: Option<u32>,
 }
+
+/// An approval of a DID recovery by one of the DIDs in its recovery list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DidAttestation {
+    /// The DID that attested to the recovery
+    pub attestor_did_id: String,
+    /// Parent coin ID of the attestor DID coin that created the message coin
+    pub parent_coin_id: String,
+    /// Inner puzzle hash of the attestor DID coin
+    pub inner_puzzle_hash: String,
+    /// Amount of the attestor DID coin
+    pub amount: Amount,
+}
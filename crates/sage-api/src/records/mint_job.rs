@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Amount;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MintJobRecord {
+    pub mint_job_id: u32,
+    pub did_id: String,
+    pub fee: Amount,
+    pub batch_size: u32,
+    pub is_active: bool,
+    pub item_count: u32,
+    pub submitted_count: u32,
+    pub minted_count: u32,
+    pub creation_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MintJobItemStatus {
+    Pending,
+    Submitted,
+    Minted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MintJobItemRecord {
+    pub row_index: u32,
+    pub status: MintJobItemStatus,
+    pub nft_id: Option<String>,
+    pub transaction_id: Option<String>,
+}
@@ -8,6 +8,7 @@ pub struct NftCollectionRecord {
     pub did_id: String,
     pub metadata_collection_id: String,
     pub visible: bool,
+    pub verified: bool,
     pub name: Option<String>,
     pub icon: Option<String>,
 }
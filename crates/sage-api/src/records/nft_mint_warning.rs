@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum NftMintWarningKind {
+    UnreachableUri,
+    HashMismatch,
+    InconsistentUris,
+    MissingUris,
+    InvalidMetadataJson,
+    InvalidMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftMintWarning {
+    pub kind: NftMintWarningKind,
+    pub uri: Option<String>,
+    pub field: Option<String>,
+    pub message: String,
+}
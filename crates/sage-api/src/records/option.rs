@@ -21,3 +21,47 @@ pub struct OptionRecord {
     pub created_height: Option<u32>,
     pub created_timestamp: Option<u64>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OptionStatus {
+    Live,
+    Exercised,
+    Expired,
+    Reclaimed,
+    /// The underlying was spent, but the timestamp of the block isn't known yet, so it's unclear
+    /// whether it was exercised or reclaimed.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OptionChainRecord {
+    pub launcher_id: String,
+    pub name: Option<String>,
+    pub underlying_amount: Amount,
+    pub strike_amount: Amount,
+    pub status: OptionStatus,
+    pub is_owned: bool,
+    pub is_creator: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OptionChainExpiration {
+    pub expiration_seconds: u64,
+    pub options: Vec<OptionChainRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OptionChainGroup {
+    pub underlying_asset: Asset,
+    pub strike_asset: Asset,
+    pub expirations: Vec<OptionChainExpiration>,
+}
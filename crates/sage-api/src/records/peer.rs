@@ -9,3 +9,27 @@ pub struct PeerRecord {
     pub peak_height: u32,
     pub user_managed: bool,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PeerSource {
+    Dns,
+    Introducer,
+    PeerExchange,
+    User,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddressBookRecord {
+    pub ip_addr: String,
+    pub port: u16,
+    pub source: PeerSource,
+    pub last_seen: u64,
+    pub last_success: Option<u64>,
+    pub failures: u32,
+    pub peak_height: Option<u32>,
+}
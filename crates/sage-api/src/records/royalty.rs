@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, Asset};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RoyaltyIncomeRecord {
+    pub asset: Asset,
+    pub amount: Amount,
+    pub payment_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftRoyaltyIncomeRecord {
+    pub nft_id: String,
+    pub name: Option<String>,
+    pub collection_id: Option<String>,
+    pub income: Vec<RoyaltyIncomeRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CollectionRoyaltyIncomeRecord {
+    pub collection_id: Option<String>,
+    pub name: Option<String>,
+    pub income: Vec<RoyaltyIncomeRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RoyaltyIncomePeriodRecord {
+    pub start_timestamp: u64,
+    pub income: Vec<RoyaltyIncomeRecord>,
+}
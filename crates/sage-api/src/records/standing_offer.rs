@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, Asset};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StandingOfferRecord {
+    pub standing_offer_id: u32,
+    pub offered_asset: Asset,
+    pub offered_amount: Amount,
+    pub requested_asset: Asset,
+    pub requested_amount: Amount,
+    pub fee: Amount,
+    pub expiration_seconds: Option<u64>,
+    pub max_fills: Option<u32>,
+    pub max_spend: Option<Amount>,
+    pub is_active: bool,
+    pub fill_count: u32,
+    pub total_spent: Amount,
+    pub current_offer_id: Option<String>,
+    pub creation_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StandingOfferFillRecord {
+    pub standing_offer_id: u32,
+    pub offer_id: String,
+    pub offered_amount: Amount,
+    pub requested_amount: Amount,
+    pub creation_timestamp: u64,
+    pub fill_timestamp: Option<u64>,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    PuzzleSubscriptions,
+    CoinSubscriptions,
+    PuzzleLookups,
+    NftUris,
+    CatInfo,
+    BlockTimes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SyncPhaseRecord {
+    pub phase: SyncPhase,
+    pub completed: u64,
+    pub remaining: u64,
+    /// Items completed per second since the phase last started
+    pub rate: f64,
+    pub eta_seconds: Option<u64>,
+}
@@ -17,3 +17,36 @@ pub struct TokenRecord {
     pub selectable_balance: Amount,
     pub revocation_address: Option<String>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CatIssuanceKind {
+    SingleIssuance,
+    MultiIssuance,
+    Custom,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IssuedCatRecord {
+    pub asset_id: String,
+    pub name: Option<String>,
+    pub ticker: Option<String>,
+    pub tail: String,
+    pub issuance_kind: CatIssuanceKind,
+    pub total_issued: Amount,
+    pub total_melted: Amount,
+    pub created_timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CatHolderRecord {
+    pub address: String,
+    pub amount: Amount,
+    pub coin_ids: Vec<String>,
+}
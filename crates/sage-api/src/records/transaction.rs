@@ -21,4 +21,5 @@ pub struct TransactionCoinRecord {
     pub address: Option<String>,
     pub address_kind: AddressKind,
     pub asset: Asset,
+    pub royalty_nft_id: Option<String>,
 }
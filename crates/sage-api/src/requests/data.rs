@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Amount, CoinRecord, DerivationRecord, DidRecord, NftCollectionRecord, NftData, NftRecord,
-    OptionRecord, PendingTransactionRecord, TokenRecord, TransactionRecord, Unit,
+    Amount, CatHolderRecord, ClawbackRecord, CoinRecord, CollectionRoyaltyIncomeRecord,
+    DerivationRecord, DidRecord, IssuedCatRecord, NftCollectionRecord, NftData, NftRecord,
+    NftRoyaltyIncomeRecord, OptionChainGroup, OptionRecord, PendingTransactionRecord,
+    RoyaltyIncomePeriodRecord, RoyaltyIncomeRecord, SyncPhaseRecord, TokenRecord,
+    TransactionRecord, Unit,
 };
 
 /// Validate and check an address
@@ -182,6 +185,33 @@ pub struct GetSyncStatusResponse {
     pub total_files: u32,
     /// Database size in bytes
     pub database_size: u64,
+    /// Highest block whose header has been validated, if header validation is enabled
+    pub validated_height: Option<u32>,
+}
+
+/// Get the progress of each sync phase
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "System & Sync",
+        description = "Get per-phase sync progress, including rates and estimated time remaining."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetSyncProgress {}
+
+/// Response with the progress of each sync phase
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "System & Sync"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetSyncProgressResponse {
+    /// Phases which have had work since the wallet was loaded
+    pub phases: Vec<SyncPhaseRecord>,
+    /// Estimated seconds until every phase has caught up, if it can be estimated
+    pub eta_seconds: Option<u64>,
 }
 
 /// Get the wallet version
@@ -360,6 +390,31 @@ pub struct GetCoinsByIdsResponse {
     pub coins: Vec<CoinRecord>,
 }
 
+/// List pending clawbacks
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Coins",
+        description = "List unspent clawback coins sent by or to this wallet, with the time remaining before each expires."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetClawbacks {}
+
+/// Response with pending clawbacks
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Coins"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetClawbacksResponse {
+    /// Clawbacks sent by this wallet which can still be clawed back
+    pub outgoing: Vec<ClawbackRecord>,
+    /// Clawbacks sent to this wallet which can be finalized once expired
+    pub incoming: Vec<ClawbackRecord>,
+}
+
 /// Get all known CAT tokens
 #[cfg_attr(
     feature = "openapi",
@@ -405,6 +460,65 @@ pub struct GetCatsResponse {
     pub cats: Vec<TokenRecord>,
 }
 
+/// Get CAT tokens issued by this wallet
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "CAT Tokens",
+        description = "List CAT tokens issued by this wallet, with their TAIL and total issued and melted supply."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetIssuedCats {}
+
+/// Response with issued CAT tokens
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "CAT Tokens"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetIssuedCatsResponse {
+    pub cats: Vec<IssuedCatRecord>,
+}
+
+/// Get the holders of a CAT token issued by this wallet
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "CAT Tokens",
+        description = "Trace the outstanding supply of a CAT token issued by this wallet and group it by holder address."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetCatHolders {
+    /// Asset ID of the CAT
+    pub asset_id: String,
+    /// Coin IDs to continue tracing from, as returned by a previous request. Defaults to the
+    /// coins the CAT was issued with.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub coin_ids: Option<Vec<String>>,
+    /// Maximum number of coins to look up
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub limit: Option<u32>,
+}
+
+/// Response with the holders of a CAT token
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "CAT Tokens"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetCatHoldersResponse {
+    /// Holders found within the limit
+    pub holders: Vec<CatHolderRecord>,
+    /// Coin IDs which weren't traced because of the limit, which can be passed to continue
+    pub remaining_coin_ids: Vec<String>,
+}
+
 /// Get detailed token information
 #[cfg_attr(
     feature = "openapi",
@@ -488,6 +602,69 @@ pub struct GetMinterDidIdsResponse {
     pub total: u32,
 }
 
+/// Sign a message with a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Sign a message with the key that owns a DID, to prove ownership of it off-chain."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SignMessageWithDid {
+    /// DID ID to sign with
+    pub did_id: String,
+    /// Message to sign
+    pub message: String,
+}
+
+/// Response with the DID signature
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SignMessageWithDidResponse {
+    /// Public key that owns the DID
+    pub public_key: String,
+    /// Signature
+    pub signature: String,
+}
+
+/// Verify a message signed with a DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Verify that a message was signed by the key that currently owns a DID."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VerifyDidSignature {
+    /// DID ID that signed the message
+    pub did_id: String,
+    /// Message that was signed
+    pub message: String,
+    /// Public key that signed the message
+    pub public_key: String,
+    /// Signature
+    pub signature: String,
+}
+
+/// Response with the DID signature verification result
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VerifyDidSignatureResponse {
+    /// Whether the signature is valid and the public key owns the DID
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub is_valid: bool,
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -572,6 +749,34 @@ pub struct GetOptionResponse {
     pub option: Option<OptionRecord>,
 }
 
+/// Get the option chain
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Options",
+        description = "List every known option grouped by underlying and strike asset, then by expiration, along with its status."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetOptionChain {
+    /// Include hidden options
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub include_hidden: bool,
+}
+
+/// Response with the option chain
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Options"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetOptionChainResponse {
+    /// Options grouped by underlying and strike asset
+    pub groups: Vec<OptionChainGroup>,
+}
+
 /// Get pending transactions
 #[cfg_attr(
     feature = "openapi",
@@ -660,6 +865,58 @@ pub struct GetTransactionsResponse {
     pub total: u32,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RoyaltyIncomeInterval {
+    Day,
+    Week,
+    #[default]
+    Month,
+}
+
+/// Get royalty income from NFT sales
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Get the royalties received from offer settlements, grouped by collection, NFT and asset, with totals over time."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetRoyaltyIncome {
+    /// Only include payments received at or after this timestamp
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub start_timestamp: Option<u64>,
+    /// Only include payments received before this timestamp
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub end_timestamp: Option<u64>,
+    /// Interval to group the totals over time by, in UTC
+    #[serde(default)]
+    pub interval: RoyaltyIncomeInterval,
+}
+
+/// Response with royalty income
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetRoyaltyIncomeResponse {
+    /// Total income for each asset
+    pub total: Vec<RoyaltyIncomeRecord>,
+    /// Income for each collection
+    pub collections: Vec<CollectionRoyaltyIncomeRecord>,
+    /// Income for each NFT
+    pub nfts: Vec<NftRoyaltyIncomeRecord>,
+    /// Income for each interval, oldest first
+    pub periods: Vec<RoyaltyIncomePeriodRecord>,
+}
+
 /// List NFT collections
 #[cfg_attr(
     feature = "openapi",
@@ -756,9 +1013,12 @@ pub struct GetNfts {
     pub limit: u32,
     /// Sort mode
     pub sort_mode: NftSortMode,
-    /// Include hidden NFTs
+    /// Include hidden NFTs, including those blocklisted by a reputation source
     #[cfg_attr(feature = "openapi", schema(example = false))]
     pub include_hidden: bool,
+    /// Filter by whether the collection or minter DID is on a reputation allowlist
+    #[serde(default)]
+    pub verification: NftVerificationFilter,
 }
 
 /// Response with NFTs list
@@ -782,6 +1042,17 @@ pub enum NftSortMode {
     Recent,
 }
 
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum NftVerificationFilter {
+    #[default]
+    All,
+    Verified,
+    Unverified,
+}
+
 /// Get a specific NFT
 #[cfg_attr(
     feature = "openapi",
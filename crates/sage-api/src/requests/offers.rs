@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Amount, OfferRecord, OfferRecordStatus, OfferSummary, SpendBundleJson, TransactionSummary,
+    Amount, ExchangeOfferRecord, OfferRecord, OfferRecordStatus, OfferSummary, SpendBundleJson,
+    StandingOfferFillRecord, StandingOfferRecord, TransactionSummary,
 };
 
 use super::TransactionResponse;
@@ -40,6 +41,10 @@ pub struct MakeOffer {
     #[serde(default)]
     #[cfg_attr(feature = "openapi", schema(nullable = true))]
     pub coin_ids: Option<Vec<String>>,
+    /// Optional offer exchange to post the offer to, before it's imported
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub post_to: Option<OfferExchangeProvider>,
 }
 
 /// Asset amount in an offer
@@ -70,6 +75,74 @@ pub struct MakeOfferResponse {
     pub offer: String,
     /// Offer ID
     pub offer_id: String,
+    /// ID assigned by the offer exchange, if the offer was posted to one
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub exchange_offer_id: Option<String>,
+}
+
+/// Create a ladder of offers at increasing prices
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Create one offer per price rung, each made from its own input coin. Coins are split first if needed."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MakeOfferLadder {
+    /// Asset ID to offer (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub offered_asset_id: Option<String>,
+    /// Asset ID to request (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub requested_asset_id: Option<String>,
+    /// Amount offered in each rung
+    pub amount_per_rung: Amount,
+    /// Amount requested in the lowest rung
+    pub min_price: Amount,
+    /// Amount requested in the highest rung
+    pub max_price: Amount,
+    /// Increase in the requested amount between rungs
+    pub price_step: Amount,
+    /// Total fee shared between the split transaction and the offers
+    pub fee: Amount,
+    /// Optional expiration timestamp
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expires_at_second: Option<u64>,
+}
+
+/// Offer created as part of a ladder
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LadderOffer {
+    /// Offer string (bech32 encoded)
+    pub offer: String,
+    /// Offer ID
+    pub offer_id: String,
+    /// Amount requested in this rung
+    pub price: Amount,
+}
+
+/// Response with the created offer ladder
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MakeOfferLadderResponse {
+    /// Offers from the lowest to the highest price
+    pub offers: Vec<LadderOffer>,
+    /// Coin IDs set aside for the offers
+    pub coin_ids: Vec<String>,
+    /// ID of the transaction which split the coins, if one was needed
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub split_transaction_id: Option<String>,
 }
 
 /// Accept an offer
@@ -336,6 +409,260 @@ pub struct CancelOffers {
 
 pub type CancelOffersResponse = TransactionResponse;
 
+/// Offer exchange to upload offers to or download offers from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OfferExchangeProvider {
+    /// Dexie, for the current network
+    Dexie,
+    /// Any service which implements the Dexie API
+    DexieCompatible { url: String },
+    /// Any service which implements the generic offer exchange API
+    Http { url: String },
+}
+
+/// Post an offer to an exchange
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Upload an offer to an offer exchange such as Dexie."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PostOffer {
+    /// Offer string (bech32 encoded)
+    pub offer: String,
+    /// Exchange to post the offer to
+    pub exchange: OfferExchangeProvider,
+}
+
+/// Response with the posted offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PostOfferResponse {
+    /// ID assigned by the offer exchange
+    pub id: String,
+}
+
+/// Search an exchange for offers
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Search an offer exchange for open offers between a pair of assets."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchExchangeOffers {
+    /// Exchange to search
+    pub exchange: OfferExchangeProvider,
+    /// Asset ID being offered (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub offered_asset_id: Option<String>,
+    /// Asset ID being requested (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub requested_asset_id: Option<String>,
+}
+
+/// Response with offers found on the exchange
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchExchangeOffersResponse {
+    /// List of offers
+    pub offers: Vec<ExchangeOfferRecord>,
+}
+
+/// Fetch an offer from an exchange
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Download an offer from an offer exchange by its id or short code."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FetchExchangeOffer {
+    /// Exchange to fetch the offer from
+    pub exchange: OfferExchangeProvider,
+    /// Offer ID or short code
+    pub id: String,
+}
+
+/// Response with the fetched offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FetchExchangeOfferResponse {
+    /// The offer
+    pub offer: ExchangeOfferRecord,
+}
+
+/// Create a standing offer
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Create an offer template which is turned back into a fresh offer whenever the previous one completes or expires."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateStandingOffer {
+    /// Asset ID to offer (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub offered_asset_id: Option<String>,
+    /// Amount offered in each offer
+    pub offered_amount: Amount,
+    /// Asset ID to request (null for XCH)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub requested_asset_id: Option<String>,
+    /// Amount requested in each offer
+    pub requested_amount: Amount,
+    /// Fee included in each offer
+    pub fee: Amount,
+    /// How many seconds each offer is valid for (null for no expiration)
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub expiration_seconds: Option<u64>,
+    /// Maximum number of times the offer can be filled
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub max_fills: Option<u32>,
+    /// Maximum total amount of the offered asset to spend across all fills, including fees when
+    /// the offered asset is XCH
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub max_spend: Option<Amount>,
+}
+
+/// Response with the created standing offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateStandingOfferResponse {
+    /// Standing offer ID
+    pub standing_offer_id: u32,
+}
+
+/// List standing offers
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "List all standing offers along with their fill counts and current offer."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetStandingOffers {}
+
+/// Response with standing offers
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetStandingOffersResponse {
+    /// List of standing offers
+    pub standing_offers: Vec<StandingOfferRecord>,
+}
+
+/// Pause or resume a standing offer
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Pause or resume a standing offer. The current offer is left as is."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetStandingOfferActive {
+    /// Standing offer ID
+    pub standing_offer_id: u32,
+    /// Whether new offers should be created
+    pub is_active: bool,
+}
+
+/// Response for pausing or resuming a standing offer
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetStandingOfferActiveResponse {}
+
+/// Delete a standing offer
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "Delete a standing offer and its fill history (doesn't cancel the current offer)."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteStandingOffer {
+    /// Standing offer ID to delete
+    pub standing_offer_id: u32,
+}
+
+/// Response for standing offer deletion
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteStandingOfferResponse {}
+
+/// List the fill history of standing offers
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Offers",
+        description = "List every completed offer created from standing offers, newest first."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetStandingOfferFills {
+    /// Only include fills for this standing offer
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub standing_offer_id: Option<u32>,
+}
+
+/// Response with standing offer fills
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Offers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetStandingOfferFillsResponse {
+    /// List of fills
+    pub fills: Vec<StandingOfferFillRecord>,
+}
+
 fn yes() -> bool {
     true
 }
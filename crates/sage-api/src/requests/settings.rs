@@ -1,7 +1,7 @@
 use sage_config::{Network, NetworkList};
 use serde::{Deserialize, Serialize};
 
-use crate::PeerRecord;
+use crate::{AddressBookRecord, Amount, PeerRecord};
 
 /// List all network peers
 #[cfg_attr(
@@ -26,6 +26,29 @@ pub struct GetPeersResponse {
     pub peers: Vec<PeerRecord>,
 }
 
+/// List every peer in the address book
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Peers",
+        description = "List every peer the wallet has discovered or connected to on the current network, including how reliable it has been."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPeerAddressBook {}
+
+/// Response containing the peer address book
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Peers"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPeerAddressBookResponse {
+    /// Known peers, ordered by IP address
+    pub peers: Vec<AddressBookRecord>,
+}
+
 /// Remove a peer from the connection list
 #[cfg_attr(
     feature = "openapi",
@@ -83,6 +106,78 @@ pub struct SetDiscoverPeers {
     pub discover_peers: bool,
 }
 
+/// Enable or disable paranoid sync
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Peers",
+        description = "Enable or disable checking coin states against a second peer before they are saved.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetParanoidSync {
+    /// Whether to verify coin states against a second peer
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub paranoid_sync: bool,
+}
+
+/// Enable or disable header validation
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Peers",
+        description = "Enable or disable following the header chain to detect and roll back reorgs.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetHeaderValidation {
+    /// Whether to validate the header chain
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub header_validation: bool,
+}
+
+/// Set the SOCKS5 proxy for network connections
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Peers",
+        description = "Send peer connections and HTTP requests through a SOCKS5 proxy, such as Tor.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetProxy {
+    /// Address of the SOCKS5 proxy, or null to connect directly
+    #[cfg_attr(feature = "openapi", schema(example = "127.0.0.1:9050"))]
+    pub proxy: Option<String>,
+}
+
+/// Enable or disable DNS peer discovery
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Peers",
+        description = "Enable or disable finding peers through DNS and peer introducers.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetDnsDiscovery {
+    /// Whether to use DNS and peer introducers
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub dns_discovery: bool,
+}
+
 /// Set target number of peers to maintain
 #[cfg_attr(
     feature = "openapi",
@@ -140,6 +235,24 @@ pub struct SetNetworkOverride {
     pub name: Option<String>,
 }
 
+/// Add a custom network
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Network Settings",
+        description = "Add a custom network, such as a simulator or private testnet, with its own consensus constants.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddNetwork {
+    /// Network configuration, with a name that isn't already in use
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub network: Network,
+}
+
 /// List available networks
 #[cfg_attr(
     feature = "openapi",
@@ -227,6 +340,27 @@ pub struct SetDeltaSyncOverride {
     pub delta_sync: Option<bool>,
 }
 
+/// Keep a wallet syncing while it isn't logged in
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Network Settings",
+        description = "Keep a wallet syncing in the background alongside the logged in wallet, so that it can be queried with the fingerprint parameter.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetBackgroundSync {
+    /// Wallet fingerprint
+    #[cfg_attr(feature = "openapi", schema(example = 1_234_567_890))]
+    pub fingerprint: u32,
+    /// Whether to sync the wallet in the background
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub background_sync: bool,
+}
+
 /// Set the change address for transactions
 #[cfg_attr(
     feature = "openapi",
@@ -248,6 +382,215 @@ pub struct SetChangeAddress {
     pub change_address: Option<String>,
 }
 
+/// Policy for handling clawbacks automatically in the background
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClawbackPolicy {
+    /// Whether to finalize incoming clawbacks once they have expired
+    pub finalize_incoming: bool,
+    /// Whether to claw back outgoing clawbacks shortly before they expire
+    pub reclaim_outgoing: bool,
+    /// How many seconds before expiration outgoing clawbacks are reclaimed
+    pub reclaim_window_seconds: u64,
+    /// Fee to use for each automatic transaction
+    pub fee: Amount,
+}
+
+/// Get the automatic clawback policy
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Wallet Settings",
+        description = "Get the policy used to finalize or reclaim clawbacks in the background."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetClawbackPolicy {
+    /// Wallet fingerprint
+    #[cfg_attr(feature = "openapi", schema(example = 1_234_567_890))]
+    pub fingerprint: u32,
+}
+
+/// Response with the automatic clawback policy
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Wallet Settings"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetClawbackPolicyResponse {
+    /// Clawback policy (null if clawbacks are not handled automatically)
+    pub policy: Option<ClawbackPolicy>,
+}
+
+/// Set the automatic clawback policy
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Wallet Settings",
+        description = "Set the policy used to finalize or reclaim clawbacks in the background.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetClawbackPolicy {
+    /// Wallet fingerprint
+    #[cfg_attr(feature = "openapi", schema(example = 1_234_567_890))]
+    pub fingerprint: u32,
+    /// Clawback policy (null to disable automatic clawback handling)
+    pub policy: Option<ClawbackPolicy>,
+}
+
+/// Standing policies for automatically splitting and combining coins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CoinManagement {
+    /// Fee to use for each automatic transaction
+    pub fee: Amount,
+    /// Maximum total fees to spend in any 24 hour period
+    pub daily_fee_budget: Amount,
+    /// Policies for each asset
+    pub policies: Vec<CoinPolicy>,
+}
+
+/// Coin policy for a single asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CoinPolicy {
+    /// CAT asset ID (null for XCH)
+    pub asset_id: Option<String>,
+    /// Keep a minimum number of coins of a given size
+    pub split: Option<SplitPolicy>,
+    /// Combine small coins once there are too many
+    pub combine: Option<CombinePolicy>,
+}
+
+/// Policy for keeping a minimum number of coins of a given size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SplitPolicy {
+    /// Minimum number of coins to keep
+    pub min_coins: u32,
+    /// Target size of each coin (coins within half of this amount are counted)
+    pub coin_amount: Amount,
+}
+
+/// Policy for combining small coins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CombinePolicy {
+    /// Coins at or below this amount are combined
+    pub max_coin_amount: Amount,
+    /// Only combine when the asset has more coins than this
+    pub coin_count_threshold: u32,
+}
+
+/// Get the automatic coin management policies
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Wallet Settings",
+        description = "Get the standing policies used to split and combine coins in the background."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetCoinManagement {
+    /// Wallet fingerprint
+    #[cfg_attr(feature = "openapi", schema(example = 1_234_567_890))]
+    pub fingerprint: u32,
+}
+
+/// Response with the automatic coin management policies
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Wallet Settings"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetCoinManagementResponse {
+    /// Coin management policies (null if coins are not managed automatically)
+    pub coin_management: Option<CoinManagement>,
+}
+
+/// Set the automatic coin management policies
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Wallet Settings",
+        description = "Set the standing policies used to split and combine coins in the background.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetCoinManagement {
+    /// Wallet fingerprint
+    #[cfg_attr(feature = "openapi", schema(example = 1_234_567_890))]
+    pub fingerprint: u32,
+    /// Coin management policies (null to disable automatic coin management)
+    pub coin_management: Option<CoinManagement>,
+}
+
+/// Source of a collection allowlist or blocklist
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReputationSource {
+    /// JSON file on disk, relative to the data directory unless absolute
+    File { path: String },
+    /// JSON feed fetched over HTTP
+    Http { url: String },
+}
+
+/// Get the collection reputation sources
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Get the allowlists and blocklists used to verify NFT collections and hide spam."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetReputationSources {}
+
+/// Response with the collection reputation sources
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetReputationSourcesResponse {
+    /// Reputation sources, which are combined with blocklist entries taking precedence
+    pub sources: Vec<ReputationSource>,
+}
+
+/// Set the collection reputation sources
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Set the allowlists and blocklists used to verify NFT collections and hide spam.",
+        response_type = "EmptyResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetReputationSources {
+    /// Reputation sources (empty to disable collection verification and spam filtering)
+    pub sources: Vec<ReputationSource>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -256,9 +599,18 @@ pub struct EmptyResponse {}
 pub type AddPeerResponse = EmptyResponse;
 pub type RemovePeerResponse = EmptyResponse;
 pub type SetDiscoverPeersResponse = EmptyResponse;
+pub type SetParanoidSyncResponse = EmptyResponse;
+pub type SetHeaderValidationResponse = EmptyResponse;
+pub type SetProxyResponse = EmptyResponse;
+pub type SetDnsDiscoveryResponse = EmptyResponse;
 pub type SetTargetPeersResponse = EmptyResponse;
 pub type SetNetworkResponse = EmptyResponse;
+pub type AddNetworkResponse = EmptyResponse;
 pub type SetNetworkOverrideResponse = EmptyResponse;
 pub type SetDeltaSyncResponse = EmptyResponse;
 pub type SetDeltaSyncOverrideResponse = EmptyResponse;
+pub type SetBackgroundSyncResponse = EmptyResponse;
 pub type SetChangeAddressResponse = EmptyResponse;
+pub type SetClawbackPolicyResponse = EmptyResponse;
+pub type SetCoinManagementResponse = EmptyResponse;
+pub type SetReputationSourcesResponse = EmptyResponse;
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{Amount, CoinSpendJson, SpendBundleJson, TransactionSummary};
+use crate::{
+    Amount, CoinSpendJson, DidAttestation, MintJobItemRecord, MintJobRecord, NftMintWarning,
+    SpendBundleJson, TransactionSummary,
+};
 
 /// Send XCH to an address
 #[cfg_attr(
@@ -212,6 +215,132 @@ pub struct IssueCat {
     pub amount: Amount,
     /// Transaction fee
     pub fee: Amount,
+    /// Whether more of the token can be issued or melted later with this wallet's key
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub multi_issuance: bool,
+    /// Whether coins of the token can be revoked back to this wallet
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub revocable: bool,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Issue a new CAT token with a custom TAIL
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "CAT Tokens",
+        description = "Issue (mint) a new CAT token using a caller-supplied compiled TAIL and solution.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IssueCatWithTail {
+    /// Token name
+    pub name: String,
+    /// Token ticker symbol
+    pub ticker: String,
+    /// Initial supply amount
+    pub amount: Amount,
+    /// Serialized TAIL program in hex
+    pub tail: String,
+    /// Serialized TAIL solution in hex, defaults to nil
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub tail_solution: Option<String>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether coins of the token can be revoked back to this wallet
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub revocable: bool,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Issue more of an existing CAT token
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "CAT Tokens",
+        description = "Issue more of a multi-issuance CAT token previously issued by this wallet.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReissueCat {
+    /// Asset ID of the CAT
+    pub asset_id: String,
+    /// Amount to issue
+    pub amount: Amount,
+    /// Serialized TAIL solution in hex, defaults to nil
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub tail_solution: Option<String>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Melt (burn) supply of an existing CAT token
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "CAT Tokens",
+        description = "Melt supply of a multi-issuance CAT token previously issued by this wallet back into XCH.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MeltCat {
+    /// Asset ID of the CAT
+    pub asset_id: String,
+    /// Amount to melt
+    pub amount: Amount,
+    /// Serialized TAIL solution in hex, defaults to nil
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub tail_solution: Option<String>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Revoke CAT coins back to the issuer
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "CAT Tokens",
+        description = "Revoke coins of a revocable CAT token issued by this wallet, sending them back to it.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RevokeCatCoins {
+    /// IDs of the CAT coins to revoke, which can be held by anyone
+    pub coin_ids: Vec<String>,
+    /// Transaction fee
+    pub fee: Amount,
     /// Whether to automatically submit the transaction
     #[serde(default)]
     #[cfg_attr(feature = "openapi", schema(default = false))]
@@ -381,6 +510,10 @@ pub struct BulkMintNfts {
     #[serde(default)]
     #[cfg_attr(feature = "openapi", schema(default = false))]
     pub auto_submit: bool,
+    /// Whether to validate each NFT before minting, failing on the first one with warnings
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub validate: bool,
 }
 
 /// Response for bulk NFT minting
@@ -445,6 +578,195 @@ pub struct NftMint {
     pub royalty_ten_thousandths: u16,
 }
 
+/// Validate an NFT before minting it
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Fetch every URI of an NFT to mint, check the hashes, and validate the metadata against CHIP-0007."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ValidateNftMint {
+    /// NFT to validate
+    pub mint: NftMint,
+}
+
+/// Response with the validation result of an NFT
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ValidateNftMintResponse {
+    /// Whether there were no warnings
+    pub is_valid: bool,
+    /// Hash of the data, computed from the first URI which could be fetched
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub data_hash: Option<String>,
+    /// Hash of the metadata, computed from the first URI which could be fetched
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub metadata_hash: Option<String>,
+    /// Hash of the license, computed from the first URI which could be fetched
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub license_hash: Option<String>,
+    /// Problems found with the URIs, hashes or metadata
+    pub warnings: Vec<NftMintWarning>,
+}
+
+/// Create a mint job from a manifest
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Create a background job which mints every row of a CSV or JSON manifest from a DID, split into batches."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateMintJob {
+    /// DID ID to mint the NFTs from
+    pub did_id: String,
+    /// Contents of the manifest file
+    pub manifest: String,
+    /// Format of the manifest file
+    pub format: MintManifestFormat,
+    /// Royalty address for rows which don't specify one
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub royalty_address: Option<String>,
+    /// Royalty percentage in ten-thousandths for rows which don't specify one
+    #[serde(default)]
+    pub royalty_ten_thousandths: u16,
+    /// Transaction fee for each batch
+    pub fee: Amount,
+    /// Maximum number of NFTs minted in each transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub batch_size: Option<u32>,
+}
+
+/// Format of a mint manifest
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MintManifestFormat {
+    Csv,
+    Json,
+}
+
+/// Response with the created mint job
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateMintJobResponse {
+    /// Mint job ID
+    pub mint_job_id: u32,
+    /// Number of rows in the manifest
+    pub item_count: u32,
+}
+
+/// List mint jobs
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "List all mint jobs along with their progress."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobs {}
+
+/// Response with mint jobs
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobsResponse {
+    /// List of mint jobs
+    pub mint_jobs: Vec<MintJobRecord>,
+}
+
+/// List the rows of a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "List the rows of a mint job's manifest, along with the NFT minted for each of them."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobItems {
+    /// Mint job ID
+    pub mint_job_id: u32,
+}
+
+/// Response with the rows of a mint job
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetMintJobItemsResponse {
+    /// Rows of the manifest, in order
+    pub items: Vec<MintJobItemRecord>,
+}
+
+/// Pause or resume a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Pause or resume a mint job. Batches which have already been submitted are left as is."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetMintJobActive {
+    /// Mint job ID
+    pub mint_job_id: u32,
+    /// Whether new batches should be submitted
+    pub is_active: bool,
+}
+
+/// Response for pausing or resuming a mint job
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetMintJobActiveResponse {}
+
+/// Delete a mint job
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Delete a mint job and its rows (NFTs which have already been minted are kept)."
+    )
+)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteMintJob {
+    /// Mint job ID to delete
+    pub mint_job_id: u32,
+}
+
+/// Response for mint job deletion
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteMintJobResponse {}
+
 /// Transfer NFTs to a new owner
 #[cfg_attr(
     feature = "openapi",
@@ -513,6 +835,84 @@ pub enum NftUriKind {
     License,
 }
 
+/// Add a mirror URI to every NFT in a collection
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "NFTs",
+        description = "Add a URI to every owned NFT in a collection, batched into a few transactions. Use a dry run to preview the affected NFTs."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddNftUris {
+    /// Collection ID of the NFTs
+    pub collection_id: String,
+    /// URI template, where `{file_name}`, `{edition_number}` and `{nft_id}` are replaced for each NFT
+    pub uri: String,
+    /// Type of URI
+    pub kind: NftUriKind,
+    /// Puzzle reveal of a custom metadata updater, for NFTs which don't use the default one.
+    /// It's passed the same solution as the default updater (`("u" . uri)`, `("mu" . uri)` or
+    /// `("lu" . uri)`), so only updaters which accept that solution are supported.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub metadata_updater_puzzle: Option<String>,
+    /// Maximum number of NFTs to update in each transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub batch_size: Option<u32>,
+    /// Transaction fee, which is paid by each transaction
+    pub fee: Amount,
+    /// Whether to only list the affected NFTs, without creating any transactions
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub dry_run: bool,
+    /// Whether to automatically submit the transactions
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Response with the NFTs which a URI was added to
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AddNftUrisResponse {
+    /// NFTs which the URI is added to
+    pub updates: Vec<NftUriUpdate>,
+    /// NFTs in the collection which can't be updated
+    pub skipped: Vec<NftUriSkip>,
+    /// Transactions which update each batch of NFTs, or empty for a dry run
+    pub transactions: Vec<TransactionResponse>,
+}
+
+/// A URI which will be added to an NFT
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftUriUpdate {
+    /// NFT ID
+    pub nft_id: String,
+    /// URI being added
+    pub uri: String,
+}
+
+/// An NFT which a URI can't be added to
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "NFTs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NftUriSkip {
+    /// NFT ID
+    pub nft_id: String,
+    /// Why the NFT was skipped
+    pub reason: String,
+}
+
 /// Assign NFTs to a DID
 #[cfg_attr(
     feature = "openapi",
@@ -591,16 +991,178 @@ pub struct NormalizeDids {
     pub auto_submit: bool,
 }
 
+/// Update a DID's profile metadata
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Replace a DID's on-chain metadata with a display name, avatar NFT and links.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateDidProfile {
+    /// DID ID to update
+    pub did_id: String,
+    /// Display name
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub display_name: Option<String>,
+    /// NFT ID to use as the avatar
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(nullable = true))]
+    pub avatar_nft_id: Option<String>,
+    /// Links, such as a website or social profiles
+    #[serde(default)]
+    pub links: Vec<String>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Set a DID's recovery list
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Set the DIDs that can recover a DID, and how many of them must attest to a recovery.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SetDidRecoveryList {
+    /// DID ID to update
+    pub did_id: String,
+    /// DIDs that can attest to a recovery, or empty to disable recovery
+    pub recovery_did_ids: Vec<String>,
+    /// Number of attestations required for a recovery
+    pub num_verifications_required: u64,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Start recovering a DID into this wallet
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Get the details that the recovery DIDs need to attest to, in order to recover a DID into this wallet."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetDidRecoveryRequest {
+    /// DID ID to recover
+    pub did_id: String,
+}
+
+/// Response with the details of a DID recovery
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetDidRecoveryRequestResponse {
+    /// ID of the DID coin being recovered
+    pub coin_id: String,
+    /// Inner puzzle hash of the DID once it has been recovered
+    pub new_inner_puzzle_hash: String,
+    /// Public key that the DID is recovered to
+    pub public_key: String,
+}
+
+/// Attest to the recovery of another DID
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Approve a recovery from a DID in the recovery list, by creating the message coin it consumes."
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AttestDidRecovery {
+    /// DID ID to attest with
+    pub did_id: String,
+    /// ID of the DID coin being recovered
+    pub coin_id: String,
+    /// Inner puzzle hash of the DID once it has been recovered
+    pub new_inner_puzzle_hash: String,
+    /// Public key that the DID is recovered to
+    pub public_key: String,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
+/// Response for attesting to a DID recovery
+#[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "DIDs"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AttestDidRecoveryResponse {
+    /// Attestation to send to the wallet recovering the DID
+    pub attestation: DidAttestation,
+    /// Transaction summary
+    pub summary: TransactionSummary,
+    /// Coin spends in the transaction
+    pub coin_spends: Vec<CoinSpendJson>,
+}
+
+/// Recover a DID into this wallet
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "DIDs",
+        description = "Recover a DID into this wallet, once every DID in its recovery list has attested.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RecoverDid {
+    /// DID ID to recover
+    pub did_id: String,
+    /// Public key from the recovery request
+    pub public_key: String,
+    /// The DID's recovery list, in order
+    pub recovery_did_ids: Vec<String>,
+    /// Attestations from the recovery DIDs
+    pub attestations: Vec<DidAttestation>,
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
 /// Asset specification for options
 #[cfg_attr(feature = "openapi", crate::openapi_attr(tag = "Options"))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "tauri", derive(specta::Type))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct OptionAsset {
-    /// Asset ID (null for XCH)
+    /// Asset ID or NFT ID (null for XCH)
     #[cfg_attr(feature = "openapi", schema(nullable = true))]
     pub asset_id: Option<String>,
-    /// Amount
+    /// Amount (must be 1 for NFTs)
     pub amount: Amount,
 }
 
@@ -667,6 +1229,27 @@ pub struct ExerciseOptions {
     pub auto_submit: bool,
 }
 
+/// Reclaim expired options
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "Options",
+        description = "Spend the underlying coins of every expired option minted by this wallet back to it.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReclaimExpiredOptions {
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
 /// Transfer options to another address
 #[cfg_attr(
     feature = "openapi",
@@ -719,6 +1302,27 @@ pub struct FinalizeClawback {
     pub auto_submit: bool,
 }
 
+/// Claw back all outgoing clawbacks
+#[cfg_attr(
+    feature = "openapi",
+    crate::openapi_attr(
+        tag = "XCH Transactions",
+        description = "Claw back every outgoing clawback coin which has not yet expired or been claimed.",
+        response_type = "TransactionResponse"
+    )
+)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "tauri", derive(specta::Type))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClawBackAllOutgoing {
+    /// Transaction fee
+    pub fee: Amount,
+    /// Whether to automatically submit the transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(default = false))]
+    pub auto_submit: bool,
+}
+
 /// Sign coin spends to create a transaction
 #[cfg_attr(
     feature = "openapi",
@@ -819,6 +1423,10 @@ pub type BulkSendXchResponse = TransactionResponse;
 pub type CombineResponse = TransactionResponse;
 pub type SplitResponse = TransactionResponse;
 pub type IssueCatResponse = TransactionResponse;
+pub type IssueCatWithTailResponse = TransactionResponse;
+pub type ReissueCatResponse = TransactionResponse;
+pub type MeltCatResponse = TransactionResponse;
+pub type RevokeCatCoinsResponse = TransactionResponse;
 pub type SendCatResponse = TransactionResponse;
 pub type BulkSendCatResponse = TransactionResponse;
 pub type MultiSendResponse = TransactionResponse;
@@ -828,6 +1436,11 @@ pub type AddNftUriResponse = TransactionResponse;
 pub type AssignNftsToDidResponse = TransactionResponse;
 pub type TransferDidsResponse = TransactionResponse;
 pub type NormalizeDidsResponse = TransactionResponse;
+pub type UpdateDidProfileResponse = TransactionResponse;
+pub type SetDidRecoveryListResponse = TransactionResponse;
+pub type RecoverDidResponse = TransactionResponse;
 pub type TransferOptionsResponse = TransactionResponse;
 pub type ExerciseOptionsResponse = TransactionResponse;
+pub type ReclaimExpiredOptionsResponse = TransactionResponse;
 pub type FinalizeClawbackResponse = TransactionResponse;
+pub type ClawBackAllOutgoingResponse = TransactionResponse;
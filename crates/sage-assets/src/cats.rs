@@ -1,7 +1,7 @@
 use chia_wallet_sdk::prelude::*;
 use serde::Deserialize;
 
-use crate::UriError;
+use crate::{UriError, http_client};
 
 #[derive(Debug, Clone)]
 pub struct DexieCat {
@@ -14,18 +14,22 @@ pub struct DexieCat {
 }
 
 impl DexieCat {
-    pub async fn fetch_all(testnet: bool) -> Result<Vec<Self>, UriError> {
+    /// Fetches every CAT listed by a Dexie API, such as `https://api.dexie.space/v1`. Icons are
+    /// only linked if there is an icon server for the network.
+    pub async fn fetch_all(api_url: &str, icons_url: Option<&str>) -> Result<Vec<Self>, UriError> {
+        let api_url = api_url.trim_end_matches('/');
         let mut page = 1;
         let mut assets = Vec::new();
 
         loop {
-            let response = reqwest::get(format!(
-                "{}/assets?page_size=100&page={page}&type=cat",
-                dexie_base_url(testnet)
-            ))
-            .await?
-            .json::<AssetResponse>()
-            .await?;
+            let response = http_client()
+                .get(format!(
+                    "{api_url}/assets?page_size=100&page={page}&type=cat"
+                ))
+                .send()
+                .await?
+                .json::<AssetResponse>()
+                .await?;
 
             if response.assets.is_empty() {
                 break;
@@ -35,11 +39,7 @@ impl DexieCat {
                 assets.push(Self {
                     hash: asset.id,
                     name: asset.name,
-                    icon_url: Some(format!(
-                        "{}/{}.webp",
-                        dexie_image_base_url(testnet),
-                        asset.id
-                    )),
+                    icon_url: icon_url(icons_url, asset.id),
                     description: asset.description,
                     ticker: asset.code,
                     hidden_puzzle_hash: asset.hidden_puzzle_hash,
@@ -52,21 +52,27 @@ impl DexieCat {
         Ok(assets)
     }
 
-    pub async fn fetch(asset_id: Bytes32, testnet: bool) -> Result<Self, UriError> {
-        let response = reqwest::get(format!(
-            "{}/assets?page_size=25&page=1&type=cat&code={asset_id}",
-            dexie_base_url(testnet)
-        ))
-        .await?
-        .json::<AssetResponse>()
-        .await?;
+    pub async fn fetch(
+        asset_id: Bytes32,
+        api_url: &str,
+        icons_url: Option<&str>,
+    ) -> Result<Self, UriError> {
+        let api_url = api_url.trim_end_matches('/');
+        let response = http_client()
+            .get(format!(
+                "{api_url}/assets?page_size=25&page=1&type=cat&code={asset_id}"
+            ))
+            .send()
+            .await?
+            .json::<AssetResponse>()
+            .await?;
 
         let asset = response.assets.first().cloned().unwrap_or_default();
-        let icon_url = (!response.assets.is_empty()).then_some(format!(
-            "{}/{}.webp",
-            dexie_image_base_url(testnet),
-            asset.id
-        ));
+        let icon_url = if response.assets.is_empty() {
+            None
+        } else {
+            icon_url(icons_url, asset.id)
+        };
 
         Ok(Self {
             hash: asset_id,
@@ -94,18 +100,6 @@ struct AssetData {
     hidden_puzzle_hash: Option<Bytes32>,
 }
 
-fn dexie_base_url(testnet: bool) -> &'static str {
-    if testnet {
-        "https://api-testnet.dexie.space/v1"
-    } else {
-        "https://api.dexie.space/v1"
-    }
-}
-
-fn dexie_image_base_url(testnet: bool) -> &'static str {
-    if testnet {
-        "https://icons-testnet.dexie.space"
-    } else {
-        "https://icons.dexie.space"
-    }
+fn icon_url(icons_url: Option<&str>, asset_id: Bytes32) -> Option<String> {
+    icons_url.map(|icons_url| format!("{}/{asset_id}.webp", icons_url.trim_end_matches('/')))
 }
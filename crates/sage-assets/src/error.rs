@@ -23,3 +23,33 @@ pub enum UriError {
     #[error("Failed to create thumbnail: {0}")]
     Thumbnail(#[from] ThumbnailError),
 }
+
+#[derive(Debug, Error)]
+pub enum ExchangeError {
+    #[error("Failed to reach offer exchange: {0}")]
+    Fetch(#[from] reqwest::Error),
+
+    #[error("Offer exchange rejected the request: {0}")]
+    Rejected(String),
+
+    #[error("Offer exchange returned an invalid response")]
+    InvalidResponse,
+}
+
+#[derive(Debug, Error)]
+pub enum ProxyError {
+    #[error("Failed to configure proxy: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ReputationError {
+    #[error("Failed to fetch reputation list: {0}")]
+    Fetch(#[from] reqwest::Error),
+
+    #[error("Failed to read reputation list: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid reputation list: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,388 @@
+use std::future::Future;
+
+use chia_wallet_sdk::prelude::*;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::{ExchangeError, http_client};
+
+/// An offer which has been posted to an exchange.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeOffer {
+    pub id: String,
+    pub offer: String,
+}
+
+/// A service which offers can be uploaded to and downloaded from.
+/// Assets are identified by their asset id, with `None` referring to XCH.
+pub trait OfferExchange {
+    /// Posts an offer, returning the id the exchange has assigned to it.
+    fn post_offer(&self, offer: &str)
+    -> impl Future<Output = Result<String, ExchangeError>> + Send;
+
+    /// Searches for open offers which offer one asset in exchange for the other.
+    fn search_offers(
+        &self,
+        offered: Option<Bytes32>,
+        requested: Option<Bytes32>,
+    ) -> impl Future<Output = Result<Vec<ExchangeOffer>, ExchangeError>> + Send;
+
+    /// Fetches an offer by its id or short code.
+    fn fetch_offer(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<ExchangeOffer, ExchangeError>> + Send;
+}
+
+/// An exchange which implements the Dexie API.
+#[derive(Debug, Clone)]
+pub struct DexieExchange {
+    client: Client,
+    base_url: String,
+}
+
+impl DexieExchange {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PostOfferRequest<'a> {
+    offer: &'a str,
+}
+
+#[derive(Deserialize)]
+struct DexieResponse {
+    success: bool,
+    #[serde(default)]
+    error_message: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    offer: Option<ExchangeOffer>,
+    #[serde(default)]
+    offers: Vec<ExchangeOffer>,
+}
+
+impl DexieResponse {
+    fn check(self) -> Result<Self, ExchangeError> {
+        if self.success {
+            Ok(self)
+        } else {
+            Err(ExchangeError::Rejected(
+                self.error_message
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            ))
+        }
+    }
+}
+
+fn asset_param(asset_id: Option<Bytes32>) -> String {
+    asset_id.map_or_else(|| "xch".to_string(), |asset_id| asset_id.to_string())
+}
+
+impl OfferExchange for DexieExchange {
+    async fn post_offer(&self, offer: &str) -> Result<String, ExchangeError> {
+        let response = self
+            .client
+            .post(format!("{}/offers", self.base_url))
+            .json(&PostOfferRequest { offer })
+            .send()
+            .await?
+            .json::<DexieResponse>()
+            .await?
+            .check()?;
+
+        response
+            .id
+            .or(response.offer.map(|offer| offer.id))
+            .ok_or(ExchangeError::InvalidResponse)
+    }
+
+    async fn search_offers(
+        &self,
+        offered: Option<Bytes32>,
+        requested: Option<Bytes32>,
+    ) -> Result<Vec<ExchangeOffer>, ExchangeError> {
+        let response = self
+            .client
+            .get(format!("{}/offers", self.base_url))
+            .query(&[
+                ("offered", asset_param(offered)),
+                ("requested", asset_param(requested)),
+                ("status", "0".to_string()),
+                ("page_size", "100".to_string()),
+            ])
+            .send()
+            .await?
+            .json::<DexieResponse>()
+            .await?
+            .check()?;
+
+        Ok(response.offers)
+    }
+
+    async fn fetch_offer(&self, id: &str) -> Result<ExchangeOffer, ExchangeError> {
+        let response = self
+            .client
+            .get(format!("{}/offers/{id}", self.base_url))
+            .send()
+            .await?
+            .json::<DexieResponse>()
+            .await?
+            .check()?;
+
+        response.offer.ok_or(ExchangeError::InvalidResponse)
+    }
+}
+
+/// An exchange with a minimal REST API, for self-hosted or third party services which
+/// don't implement the Dexie API. Failed requests are indicated by the status code,
+/// and assets are referred to by their asset id or `xch`.
+///
+/// - `POST {base_url}/offers` with `{ "offer": "..." }` responds with `{ "id": "...", "offer": "..." }`
+/// - `GET {base_url}/offers?offered=...&requested=...` responds with `[{ "id": "...", "offer": "..." }]`
+/// - `GET {base_url}/offers/{id}` responds with `{ "id": "...", "offer": "..." }`
+#[derive(Debug, Clone)]
+pub struct HttpExchange {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpExchange {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl OfferExchange for HttpExchange {
+    async fn post_offer(&self, offer: &str) -> Result<String, ExchangeError> {
+        let response = self
+            .client
+            .post(format!("{}/offers", self.base_url))
+            .json(&PostOfferRequest { offer })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ExchangeOffer>()
+            .await?;
+
+        Ok(response.id)
+    }
+
+    async fn search_offers(
+        &self,
+        offered: Option<Bytes32>,
+        requested: Option<Bytes32>,
+    ) -> Result<Vec<ExchangeOffer>, ExchangeError> {
+        Ok(self
+            .client
+            .get(format!("{}/offers", self.base_url))
+            .query(&[
+                ("offered", asset_param(offered)),
+                ("requested", asset_param(requested)),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<ExchangeOffer>>()
+            .await?)
+    }
+
+    async fn fetch_offer(&self, id: &str) -> Result<ExchangeOffer, ExchangeError> {
+        Ok(self
+            .client
+            .get(format!("{}/offers/{id}", self.base_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ExchangeOffer>()
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        Json, Router,
+        extract::{Path, Query, State},
+        http::StatusCode,
+        routing::get,
+    };
+    use serde_json::{Value, json};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    type Offers = Arc<Mutex<Vec<ExchangeOffer>>>;
+
+    async fn serve(router: Router) -> anyhow::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Ok(format!("http://{addr}"))
+    }
+
+    fn store(offers: &Offers, offer: String) -> ExchangeOffer {
+        let mut offers = offers.lock().unwrap();
+
+        let offer = ExchangeOffer {
+            id: format!("offer{}", offers.len()),
+            offer,
+        };
+
+        offers.push(offer.clone());
+        offer
+    }
+
+    fn find(offers: &Offers, id: &str) -> Option<ExchangeOffer> {
+        offers
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|offer| offer.id == id)
+            .cloned()
+    }
+
+    fn dexie_router(offers: Offers) -> Router {
+        Router::new()
+            .route(
+                "/v1/offers",
+                get(
+                    |State(offers): State<Offers>,
+                     Query(query): Query<HashMap<String, String>>| async move {
+                        assert_eq!(query["offered"], "xch");
+                        assert_eq!(query["status"], "0");
+
+                        Json(json!({
+                            "success": true,
+                            "count": 1,
+                            "offers": offers.lock().unwrap().clone(),
+                        }))
+                    },
+                )
+                .post(
+                    |State(offers): State<Offers>, Json(body): Json<Value>| async move {
+                        let offer = body["offer"].as_str().unwrap().to_string();
+
+                        if !offer.starts_with("offer1") {
+                            return Json(json!({
+                                "success": false,
+                                "error_message": "Invalid offer",
+                            }));
+                        }
+
+                        let offer = store(&offers, offer);
+
+                        Json(json!({ "success": true, "id": offer.id, "known": false }))
+                    },
+                ),
+            )
+            .route(
+                "/v1/offers/:id",
+                get(
+                    |State(offers): State<Offers>, Path(id): Path<String>| async move {
+                        match find(&offers, &id) {
+                            Some(offer) => Json(json!({ "success": true, "offer": offer })),
+                            None => Json(json!({ "success": false, "error_message": "Not found" })),
+                        }
+                    },
+                ),
+            )
+            .with_state(offers)
+    }
+
+    fn http_router(offers: Offers) -> Router {
+        Router::new()
+            .route(
+                "/offers",
+                get(
+                    |State(offers): State<Offers>,
+                     Query(query): Query<HashMap<String, String>>| async move {
+                        assert_eq!(query["requested"], "xch");
+                        Json(offers.lock().unwrap().clone())
+                    },
+                )
+                .post(
+                    |State(offers): State<Offers>, Json(body): Json<Value>| async move {
+                        Json(store(&offers, body["offer"].as_str().unwrap().to_string()))
+                    },
+                ),
+            )
+            .route(
+                "/offers/:id",
+                get(
+                    |State(offers): State<Offers>, Path(id): Path<String>| async move {
+                        find(&offers, &id).map(Json).ok_or(StatusCode::NOT_FOUND)
+                    },
+                ),
+            )
+            .with_state(offers)
+    }
+
+    #[tokio::test]
+    async fn test_dexie_exchange() -> anyhow::Result<()> {
+        let offers = Offers::default();
+        let url = serve(dexie_router(offers.clone())).await?;
+        let exchange = DexieExchange::new(format!("{url}/v1/"));
+
+        let id = exchange.post_offer("offer1abc").await?;
+        assert_eq!(id, "offer0");
+
+        assert!(matches!(
+            exchange.post_offer("invalid").await,
+            Err(ExchangeError::Rejected(message)) if message == "Invalid offer"
+        ));
+
+        let found = exchange
+            .search_offers(None, Some(Bytes32::default()))
+            .await?;
+        assert_eq!(found, offers.lock().unwrap().clone());
+
+        let offer = exchange.fetch_offer(&id).await?;
+        assert_eq!(offer.offer, "offer1abc");
+
+        assert!(exchange.fetch_offer("missing").await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_exchange() -> anyhow::Result<()> {
+        let offers = Offers::default();
+        let url = serve(http_router(offers.clone())).await?;
+        let exchange = HttpExchange::new(url);
+
+        let id = exchange.post_offer("offer1abc").await?;
+
+        let found = exchange
+            .search_offers(Some(Bytes32::default()), None)
+            .await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, id);
+
+        let offer = exchange.fetch_offer(&id).await?;
+        assert_eq!(offer.offer, "offer1abc");
+
+        assert!(matches!(
+            exchange.fetch_offer("missing").await,
+            Err(ExchangeError::Fetch(error)) if error.status() == Some(reqwest::StatusCode::NOT_FOUND)
+        ));
+
+        Ok(())
+    }
+}
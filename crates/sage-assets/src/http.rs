@@ -0,0 +1,32 @@
+use std::{
+    net::SocketAddr,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+
+use reqwest::{Client, Proxy};
+
+use crate::ProxyError;
+
+static CLIENT: LazyLock<RwLock<Client>> = LazyLock::new(|| RwLock::new(Client::new()));
+
+/// Sends all HTTP requests through a SOCKS5 proxy, or directly if it's `None`. Host names are
+/// resolved by the proxy, so they don't leak through local DNS either.
+pub fn set_proxy(proxy: Option<SocketAddr>) -> Result<(), ProxyError> {
+    let mut builder = Client::builder();
+
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(Proxy::all(format!("socks5h://{proxy}"))?);
+    }
+
+    *CLIENT.write().unwrap_or_else(PoisonError::into_inner) = builder.build()?;
+
+    Ok(())
+}
+
+/// The client that should be used for any HTTP request, so that the proxy setting is respected.
+pub fn http_client() -> Client {
+    CLIENT
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
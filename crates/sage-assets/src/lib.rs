@@ -1,7 +1,13 @@
 mod cats;
 mod error;
+mod exchanges;
+mod http;
 mod nfts;
+mod reputation;
 
 pub use cats::*;
 pub use error::*;
+pub use exchanges::*;
+pub use http::*;
 pub use nfts::*;
+pub use reputation::*;
@@ -1,9 +1,11 @@
 mod chip0007_metadata;
+mod chip0007_validation;
 mod data_uri;
 mod fetch_nft_uri;
 mod thumbnail;
 
 pub use chip0007_metadata::*;
+pub use chip0007_validation::*;
 pub use data_uri::*;
 pub use fetch_nft_uri::*;
 pub use thumbnail::*;
@@ -0,0 +1,278 @@
+use serde_json::{Map, Value};
+
+/// A way in which off-chain metadata doesn't conform to the [CHIP-0007](https://github.com/Chia-Network/chips/blob/main/CHIPs/chip-0007.md) schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chip0007Issue {
+    /// The path to the offending field, such as `attributes[2].value`, or empty for the whole document.
+    pub field: String,
+    pub message: String,
+}
+
+impl Chip0007Issue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Checks metadata against the CHIP-0007 schema, including that the series matches the on-chain edition.
+/// Unlike [`Chip0007Metadata`](super::Chip0007Metadata), which is lenient so that existing NFTs can be displayed,
+/// this reports every field that a strict reader would reject.
+pub fn validate_chip0007_metadata(
+    bytes: &[u8],
+    edition_number: u64,
+    edition_total: u64,
+) -> Result<Vec<Chip0007Issue>, serde_json::Error> {
+    let value: Value = serde_json::from_slice(bytes)?;
+    let mut issues = Vec::new();
+
+    let Some(object) = value.as_object() else {
+        issues.push(Chip0007Issue::new("", "Metadata must be a JSON object"));
+        return Ok(issues);
+    };
+
+    match object.get("format") {
+        Some(Value::String(format)) if format == "CHIP-0007" => {}
+        Some(_) => issues.push(Chip0007Issue::new("format", "Must be \"CHIP-0007\"")),
+        None => issues.push(Chip0007Issue::new("format", "Missing required field")),
+    }
+
+    check_string(object, "name", true, &mut issues);
+    check_string(object, "description", true, &mut issues);
+    check_string(object, "minting_tool", false, &mut issues);
+
+    if let Some(sensitive_content) = object.get("sensitive_content") {
+        let is_valid = match sensitive_content {
+            Value::Bool(..) => true,
+            Value::Array(items) => items.iter().all(Value::is_string),
+            _ => false,
+        };
+
+        if !is_valid {
+            issues.push(Chip0007Issue::new(
+                "sensitive_content",
+                "Must be a boolean or a list of strings",
+            ));
+        }
+    }
+
+    let series_number = check_series(object, "series_number", edition_number, &mut issues);
+    let series_total = check_series(object, "series_total", edition_total, &mut issues);
+
+    if let (Some(series_number), Some(series_total)) = (series_number, series_total)
+        && series_number > series_total
+    {
+        issues.push(Chip0007Issue::new(
+            "series_number",
+            format!("Exceeds the series total of {series_total}"),
+        ));
+    }
+
+    if let Some(attributes) = object.get("attributes") {
+        check_attributes(attributes, "attributes", "trait_type", &mut issues);
+    }
+
+    if let Some(collection) = object.get("collection") {
+        check_collection(collection, &mut issues);
+    }
+
+    if let Some(data) = object.get("data")
+        && !data.is_object()
+    {
+        issues.push(Chip0007Issue::new("data", "Must be an object"));
+    }
+
+    Ok(issues)
+}
+
+fn check_string(
+    object: &Map<String, Value>,
+    field: &str,
+    required: bool,
+    issues: &mut Vec<Chip0007Issue>,
+) {
+    match object.get(field) {
+        Some(Value::String(..)) => {}
+        Some(_) => issues.push(Chip0007Issue::new(field, "Must be a string")),
+        None if required => issues.push(Chip0007Issue::new(field, "Missing required field")),
+        None => {}
+    }
+}
+
+fn check_series(
+    object: &Map<String, Value>,
+    field: &str,
+    edition: u64,
+    issues: &mut Vec<Chip0007Issue>,
+) -> Option<u64> {
+    let value = object.get(field)?;
+
+    let Some(series) = value.as_u64().filter(|&series| series > 0) else {
+        issues.push(Chip0007Issue::new(field, "Must be a positive integer"));
+        return None;
+    };
+
+    if series != edition {
+        issues.push(Chip0007Issue::new(
+            field,
+            format!("Is {series}, but the on-chain edition is {edition}"),
+        ));
+    }
+
+    Some(series)
+}
+
+fn check_attributes(
+    attributes: &Value,
+    field: &str,
+    name_key: &str,
+    issues: &mut Vec<Chip0007Issue>,
+) {
+    let Some(attributes) = attributes.as_array() else {
+        issues.push(Chip0007Issue::new(field, "Must be a list"));
+        return;
+    };
+
+    for (index, attribute) in attributes.iter().enumerate() {
+        let path = format!("{field}[{index}]");
+
+        let Some(attribute) = attribute.as_object() else {
+            issues.push(Chip0007Issue::new(path, "Must be an object"));
+            continue;
+        };
+
+        for key in [name_key, "value"] {
+            match attribute.get(key) {
+                Some(Value::String(..) | Value::Number(..)) => {}
+                Some(_) => issues.push(Chip0007Issue::new(
+                    format!("{path}.{key}"),
+                    "Must be a string or a number",
+                )),
+                None => issues.push(Chip0007Issue::new(
+                    format!("{path}.{key}"),
+                    "Missing required field",
+                )),
+            }
+        }
+
+        for key in ["min_value", "max_value"] {
+            if let Some(value) = attribute.get(key)
+                && !value.is_number()
+            {
+                issues.push(Chip0007Issue::new(
+                    format!("{path}.{key}"),
+                    "Must be a number",
+                ));
+            }
+        }
+    }
+}
+
+fn check_collection(collection: &Value, issues: &mut Vec<Chip0007Issue>) {
+    let Some(collection) = collection.as_object() else {
+        issues.push(Chip0007Issue::new("collection", "Must be an object"));
+        return;
+    };
+
+    match collection.get("id") {
+        Some(Value::String(id)) if is_uuid(id) => {}
+        Some(_) => issues.push(Chip0007Issue::new("collection.id", "Must be a UUID")),
+        None => issues.push(Chip0007Issue::new(
+            "collection.id",
+            "Missing required field",
+        )),
+    }
+
+    match collection.get("name") {
+        Some(Value::String(..)) => {}
+        Some(_) => issues.push(Chip0007Issue::new("collection.name", "Must be a string")),
+        None => issues.push(Chip0007Issue::new(
+            "collection.name",
+            "Missing required field",
+        )),
+    }
+
+    if let Some(attributes) = collection.get("attributes") {
+        check_attributes(attributes, "collection.attributes", "type", issues);
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(index, char)| {
+            if matches!(index, 8 | 13 | 18 | 23) {
+                char == '-'
+            } else {
+                char.is_ascii_hexdigit()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_metadata() -> anyhow::Result<()> {
+        let metadata = r#"{
+            "format": "CHIP-0007",
+            "name": "Example #2",
+            "description": "An example NFT",
+            "sensitive_content": false,
+            "series_number": 2,
+            "series_total": 10,
+            "attributes": [
+                { "trait_type": "Color", "value": "Blue" },
+                { "trait_type": "Speed", "value": 5, "min_value": 0, "max_value": 10 }
+            ],
+            "collection": {
+                "id": "e43fcfe6-1d5e-4ee3-8e2e-0c2d8a1b9f3c",
+                "name": "Examples",
+                "attributes": [{ "type": "website", "value": "https://example.com" }]
+            }
+        }"#;
+
+        assert_eq!(
+            validate_chip0007_metadata(metadata.as_bytes(), 2, 10)?,
+            Vec::new()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_metadata() -> anyhow::Result<()> {
+        let metadata = r#"{
+            "format": "CHIP-0001",
+            "description": 5,
+            "series_number": 3,
+            "series_total": 2,
+            "attributes": [{ "trait_type": "Color" }],
+            "collection": { "id": "not-a-uuid", "name": "Examples" }
+        }"#;
+
+        let fields: Vec<String> = validate_chip0007_metadata(metadata.as_bytes(), 1, 2)?
+            .into_iter()
+            .map(|issue| issue.field)
+            .collect();
+
+        assert_eq!(
+            fields,
+            [
+                "format",
+                "name",
+                "description",
+                "series_number",
+                "series_number",
+                "attributes[0].value",
+                "collection.id",
+            ]
+        );
+
+        assert!(validate_chip0007_metadata(b"not json", 1, 1).is_err());
+
+        Ok(())
+    }
+}
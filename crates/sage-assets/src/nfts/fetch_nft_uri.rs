@@ -8,7 +8,7 @@ use reqwest::{StatusCode, header::CONTENT_TYPE};
 use tokio::task::spawn_blocking;
 use tracing::debug;
 
-use crate::UriError;
+use crate::{UriError, http_client};
 
 use super::{Thumbnail, thumbnail as make_thumbnail};
 
@@ -20,8 +20,10 @@ pub struct Data {
     pub thumbnail: Option<Thumbnail>,
 }
 
-pub async fn fetch_uri(uri: String, testnet: bool) -> Result<Data, UriError> {
-    let response = reqwest::get(&uri).await?;
+/// Fetches the data at a URI. The thumbnail is taken from MintGarden's asset server if there is
+/// one for the network, and is otherwise generated from the data.
+pub async fn fetch_uri(uri: String, mintgarden_url: Option<&str>) -> Result<Data, UriError> {
+    let response = http_client().get(&uri).send().await?;
 
     let mime_type = match response.headers().get(CONTENT_TYPE) {
         Some(header) => Some(
@@ -48,12 +50,15 @@ pub async fn fetch_uri(uri: String, testnet: bool) -> Result<Data, UriError> {
     hasher.update(&blob);
     let hash = Bytes32::new(hasher.finalize());
 
-    let mut thumbnail = match mintgarden_thumbnail(hash, testnet).await {
-        Ok(thumbnail) => thumbnail,
-        Err(error) => {
-            debug!("Failed to fetch MintGarden thumbnail for {uri}: {error}");
-            None
-        }
+    let mut thumbnail = match mintgarden_url {
+        Some(mintgarden_url) => match mintgarden_thumbnail(mintgarden_url, hash).await {
+            Ok(thumbnail) => thumbnail,
+            Err(error) => {
+                debug!("Failed to fetch MintGarden thumbnail for {uri}: {error}");
+                None
+            }
+        },
+        None => None,
     };
 
     if thumbnail.is_none() {
@@ -90,11 +95,14 @@ pub async fn fetch_uri(uri: String, testnet: bool) -> Result<Data, UriError> {
     })
 }
 
-pub async fn fetch_uris_without_hash(uris: Vec<String>, testnet: bool) -> Result<Data, UriError> {
+pub async fn fetch_uris_without_hash(
+    uris: Vec<String>,
+    mintgarden_url: Option<&str>,
+) -> Result<Data, UriError> {
     let mut futures = FuturesUnordered::new();
 
     for uri in uris {
-        futures.push(fetch_uri(uri, testnet));
+        futures.push(fetch_uri(uri, mintgarden_url));
     }
 
     let mut data = None;
@@ -127,11 +135,15 @@ pub async fn fetch_uris_without_hash(uris: Vec<String>, testnet: bool) -> Result
     data.ok_or(UriError::NoUris)
 }
 
-pub async fn fetch_uris_with_hash(uris: Vec<String>, hash: Bytes32, testnet: bool) -> Option<Data> {
+pub async fn fetch_uris_with_hash(
+    uris: Vec<String>,
+    hash: Bytes32,
+    mintgarden_url: Option<&str>,
+) -> Option<Data> {
     let mut futures = FuturesUnordered::new();
 
     for uri in uris {
-        futures.push(async move { (uri.clone(), fetch_uri(uri, testnet).await) });
+        futures.push(async move { (uri.clone(), fetch_uri(uri, mintgarden_url).await) });
     }
 
     while let Some((uri, result)) = futures.next().await {
@@ -151,15 +163,15 @@ pub async fn fetch_uris_with_hash(uris: Vec<String>, hash: Bytes32, testnet: boo
 }
 
 pub async fn mintgarden_thumbnail(
+    mintgarden_url: &str,
     data_hash: Bytes32,
-    testnet: bool,
 ) -> Result<Option<Thumbnail>, UriError> {
     let url = format!(
-        "https://assets.{}.mintgarden.io/thumbnails/{data_hash}_512.webp",
-        if testnet { "testnet" } else { "mainnet" }
+        "{}/thumbnails/{data_hash}_512.webp",
+        mintgarden_url.trim_end_matches('/')
     );
 
-    let response = reqwest::get(&url).await?;
+    let response = http_client().get(&url).send().await?;
 
     if response.status() != StatusCode::OK {
         return Ok(None);
@@ -0,0 +1,145 @@
+use std::{future::Future, path::PathBuf};
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::{ReputationError, http_client};
+
+/// A list of collection ids (`col1...`) and minter DIDs (`did:chia:1...`) which are trusted or
+/// considered spam. Entries are left encoded, since they are only validated once all of the
+/// lists have been fetched.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReputationList {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub block: Vec<String>,
+}
+
+/// A source which collection reputation lists can be fetched from.
+pub trait ReputationSource {
+    fn fetch_list(&self) -> impl Future<Output = Result<ReputationList, ReputationError>> + Send;
+}
+
+/// A reputation list stored as a JSON file on disk.
+#[derive(Debug, Clone)]
+pub struct FileReputationSource {
+    path: PathBuf,
+}
+
+impl FileReputationSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ReputationSource for FileReputationSource {
+    async fn fetch_list(&self) -> Result<ReputationList, ReputationError> {
+        let data = tokio::fs::read(&self.path).await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+/// A reputation list served as JSON by an HTTP feed, in the same format as the file source.
+#[derive(Debug, Clone)]
+pub struct HttpReputationSource {
+    client: Client,
+    url: String,
+}
+
+impl HttpReputationSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            url: url.into(),
+        }
+    }
+}
+
+impl ReputationSource for HttpReputationSource {
+    async fn fetch_list(&self) -> Result<ReputationList, ReputationError> {
+        Ok(self
+            .client
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ReputationList>()
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, Router, http::StatusCode, routing::get};
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    async fn serve(router: Router) -> anyhow::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Ok(format!("http://{addr}"))
+    }
+
+    #[tokio::test]
+    async fn test_file_reputation_source() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!("reputation-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{ "block": ["did:chia:1spam"] }"#)?;
+
+        let list = FileReputationSource::new(&path).fetch_list().await;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(
+            list?,
+            ReputationList {
+                allow: Vec::new(),
+                block: vec!["did:chia:1spam".to_string()],
+            }
+        );
+
+        assert!(matches!(
+            FileReputationSource::new(&path).fetch_list().await,
+            Err(ReputationError::Io(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_reputation_source() -> anyhow::Result<()> {
+        let url = serve(
+            Router::new()
+                .route(
+                    "/list.json",
+                    get(|| async {
+                        Json(json!({
+                            "allow": ["col1trusted"],
+                            "block": ["col1spam", "did:chia:1spam"],
+                        }))
+                    }),
+                )
+                .route("/missing.json", get(|| async { StatusCode::NOT_FOUND })),
+        )
+        .await?;
+
+        let list = HttpReputationSource::new(format!("{url}/list.json"))
+            .fetch_list()
+            .await?;
+        assert_eq!(list.allow, ["col1trusted"]);
+        assert_eq!(list.block, ["col1spam", "did:chia:1spam"]);
+
+        assert!(matches!(
+            HttpReputationSource::new(format!("{url}/missing.json"))
+                .fetch_list()
+                .await,
+            Err(ReputationError::Fetch(error)) if error.status() == Some(reqwest::StatusCode::NOT_FOUND)
+        ));
+
+        Ok(())
+    }
+}
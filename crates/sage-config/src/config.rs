@@ -8,6 +8,7 @@ pub struct Config {
     pub global: GlobalConfig,
     pub network: NetworkConfig,
     pub rpc: RpcConfig,
+    pub reputation: ReputationConfig,
 }
 
 impl Default for Config {
@@ -17,6 +18,7 @@ impl Default for Config {
             global: GlobalConfig::default(),
             network: NetworkConfig::default(),
             rpc: RpcConfig::default(),
+            reputation: ReputationConfig::default(),
         }
     }
 }
@@ -43,6 +45,10 @@ pub struct NetworkConfig {
     pub default_network: String,
     pub target_peers: u32,
     pub discover_peers: bool,
+    pub paranoid_sync: bool,
+    pub header_validation: bool,
+    pub proxy: Option<String>,
+    pub dns_discovery: bool,
 }
 
 impl Default for NetworkConfig {
@@ -51,6 +57,10 @@ impl Default for NetworkConfig {
             default_network: "mainnet".to_string(),
             target_peers: 5,
             discover_peers: true,
+            paranoid_sync: false,
+            header_validation: false,
+            proxy: None,
+            dns_discovery: true,
         }
     }
 }
@@ -60,6 +70,7 @@ impl Default for NetworkConfig {
 pub struct RpcConfig {
     pub enabled: bool,
     pub port: u16,
+    pub metrics: MetricsConfig,
 }
 
 impl Default for RpcConfig {
@@ -67,6 +78,31 @@ impl Default for RpcConfig {
         Self {
             enabled: false,
             port: 9257,
+            metrics: MetricsConfig::default(),
         }
     }
 }
+
+/// Serves Prometheus metrics at `/metrics` on the RPC server.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Copy, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    /// Balances are left out unless enabled, since anyone who can scrape the metrics can
+    /// see them.
+    pub balances: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct ReputationConfig {
+    pub sources: Vec<ReputationSource>,
+}
+
+/// Where a collection allowlist or blocklist is loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ReputationSource {
+    File { path: String },
+    Http { url: String },
+}
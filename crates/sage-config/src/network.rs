@@ -1,6 +1,10 @@
-use std::sync::LazyLock;
+use std::{
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::LazyLock,
+};
 
-use chia_wallet_sdk::prelude::*;
+use chia_wallet_sdk::{chia::consensus::consensus_constants::ConsensusConstants, prelude::*};
 use serde::{Deserialize, Serialize};
 use serde_with::{hex::Hex, serde_as};
 use specta::Type;
@@ -22,11 +26,13 @@ impl Default for NetworkList {
                 Network {
                     additional_dns_introducers: Vec::new(),
                     additional_peer_introducers: Vec::new(),
+                    services: NetworkServices::default(),
                     ..MAINNET.clone()
                 },
                 Network {
                     additional_dns_introducers: Vec::new(),
                     additional_peer_introducers: Vec::new(),
+                    services: NetworkServices::default(),
                     ..TESTNET11.clone()
                 },
             ],
@@ -61,6 +67,10 @@ pub struct Network {
     #[serde_as(as = "Option<Hex>")]
     #[specta(type = Option<String>)]
     pub agg_sig_me: Option<Bytes32>,
+    /// Overrides every `AGG_SIG_*` additional data value, rather than deriving them from
+    /// `agg_sig_me`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agg_sig: Option<AggSigData>,
     #[serde(
         default,
         skip_serializing_if = "Vec::is_empty",
@@ -73,10 +83,86 @@ pub struct Network {
         rename = "peer_introducers"
     )]
     pub additional_peer_introducers: Vec<String>,
+    /// Full nodes, as `ip` or `ip:port`, which are synced from exclusively instead of
+    /// discovering peers. They are never banned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_peers: Vec<String>,
+    /// The RPC of a trusted full node, which coin records are read from if no peer is connected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_node_rpc: Option<FullNodeRpc>,
+    #[serde(default, skip_serializing_if = "NetworkServices::is_empty")]
+    pub services: NetworkServices,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub inherit: Option<InheritedNetwork>,
 }
 
+/// The additional data appended to each kind of `AGG_SIG_*` message before it's signed.
+/// Forks of Chia change these to prevent signatures from being replayed across networks.
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct AggSigData {
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub me: Bytes32,
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub parent: Bytes32,
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub puzzle: Bytes32,
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub amount: Bytes32,
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub puzzle_amount: Bytes32,
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub parent_amount: Bytes32,
+    #[serde_as(as = "Hex")]
+    #[specta(type = String)]
+    pub parent_puzzle: Bytes32,
+}
+
+/// Base URLs of the indexers used for asset metadata. Any which aren't set are inherited from
+/// the network this one is based on, and are otherwise not used.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct NetworkServices {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dexie_api: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dexie_icons: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mintgarden_assets: Option<String>,
+}
+
+impl NetworkServices {
+    pub fn is_empty(&self) -> bool {
+        self.dexie_api.is_none() && self.dexie_icons.is_none() && self.mintgarden_assets.is_none()
+    }
+
+    fn or(self, other: &Self) -> Self {
+        Self {
+            dexie_api: self.dexie_api.or_else(|| other.dexie_api.clone()),
+            dexie_icons: self.dexie_icons.or_else(|| other.dexie_icons.clone()),
+            mintgarden_assets: self
+                .mintgarden_assets
+                .or_else(|| other.mintgarden_assets.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct FullNodeRpc {
+    /// Defaults to the local full node.
+    #[serde(default = "default_full_node_rpc_url")]
+    pub url: String,
+    /// Path to the full node's private RPC certificate, `private_full_node.crt`.
+    pub cert_path: String,
+    /// Path to the full node's private RPC key, `private_full_node.key`.
+    pub key_path: String,
+}
+
 impl Network {
     pub fn prefix(&self) -> String {
         self.prefix
@@ -89,7 +175,49 @@ impl Network {
     }
 
     pub fn agg_sig_me(&self) -> Bytes32 {
-        self.agg_sig_me.unwrap_or(self.genesis_challenge)
+        self.agg_sig.map_or_else(
+            || self.agg_sig_me.unwrap_or(self.genesis_challenge),
+            |agg_sig| agg_sig.me,
+        )
+    }
+
+    pub fn agg_sig_constants(&self) -> AggSigConstants {
+        let Some(agg_sig) = self.agg_sig else {
+            return AggSigConstants::new(self.agg_sig_me());
+        };
+
+        AggSigConstants::from(&ConsensusConstants {
+            agg_sig_me_additional_data: agg_sig.me,
+            agg_sig_parent_additional_data: agg_sig.parent,
+            agg_sig_puzzle_additional_data: agg_sig.puzzle,
+            agg_sig_amount_additional_data: agg_sig.amount,
+            agg_sig_puzzle_amount_additional_data: agg_sig.puzzle_amount,
+            agg_sig_parent_amount_additional_data: agg_sig.parent_amount,
+            agg_sig_parent_puzzle_additional_data: agg_sig.parent_puzzle,
+            ..TESTNET11_CONSTANTS.clone()
+        })
+    }
+
+    pub fn services(&self) -> NetworkServices {
+        match self.inherit {
+            Some(InheritedNetwork::Mainnet) => self.services.clone().or(&MAINNET.services),
+            Some(InheritedNetwork::Testnet11) => self.services.clone().or(&TESTNET11.services),
+            None => self.services.clone(),
+        }
+    }
+
+    /// Trusted peers which can't be parsed are skipped.
+    pub fn trusted_peer_addrs(&self) -> Vec<SocketAddr> {
+        self.trusted_peers
+            .iter()
+            .filter_map(|peer| {
+                SocketAddr::from_str(peer).ok().or_else(|| {
+                    IpAddr::from_str(peer)
+                        .ok()
+                        .map(|ip| SocketAddr::new(ip, self.default_port))
+                })
+            })
+            .collect()
     }
 
     pub fn dns_introducers(&self) -> Vec<String> {
@@ -141,6 +269,10 @@ impl Network {
     }
 }
 
+fn default_full_node_rpc_url() -> String {
+    "https://localhost:8555".to_string()
+}
+
 fn default_precision() -> u8 {
     12
 }
@@ -167,6 +299,7 @@ pub static MAINNET: LazyLock<Network> = LazyLock::new(|| Network {
     precision: 12,
     genesis_challenge: MAINNET_CONSTANTS.genesis_challenge,
     agg_sig_me: None,
+    agg_sig: None,
     additional_dns_introducers: vec![
         "dns-introducer.chia.net".to_string(),
         "chia.ctrlaltdel.ch".to_string(),
@@ -174,6 +307,13 @@ pub static MAINNET: LazyLock<Network> = LazyLock::new(|| Network {
         "chia.hoffmang.com".to_string(),
     ],
     additional_peer_introducers: vec!["introducer.chia.net".to_string()],
+    trusted_peers: Vec::new(),
+    full_node_rpc: None,
+    services: NetworkServices {
+        dexie_api: Some("https://api.dexie.space/v1".to_string()),
+        dexie_icons: Some("https://icons.dexie.space".to_string()),
+        mintgarden_assets: Some("https://assets.mainnet.mintgarden.io".to_string()),
+    },
     inherit: Some(InheritedNetwork::Mainnet),
 });
 
@@ -186,7 +326,65 @@ pub static TESTNET11: LazyLock<Network> = LazyLock::new(|| Network {
     precision: 12,
     genesis_challenge: TESTNET11_CONSTANTS.genesis_challenge,
     agg_sig_me: None,
+    agg_sig: None,
     additional_dns_introducers: vec!["dns-introducer-testnet11.chia.net".to_string()],
     additional_peer_introducers: vec!["introducer-testnet11.chia.net".to_string()],
+    trusted_peers: Vec::new(),
+    full_node_rpc: None,
+    services: NetworkServices {
+        dexie_api: Some("https://api-testnet.dexie.space/v1".to_string()),
+        dexie_icons: Some("https://icons-testnet.dexie.space".to_string()),
+        mintgarden_assets: Some("https://assets.testnet.mintgarden.io".to_string()),
+    },
     inherit: Some(InheritedNetwork::Testnet11),
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_agg_sig_constants() {
+        let derived = AggSigConstants::new(Bytes32::new([1; 32]));
+
+        let network = Network {
+            name: "custom".to_string(),
+            agg_sig_me: None,
+            agg_sig: Some(AggSigData {
+                me: derived.me(),
+                parent: derived.parent(),
+                puzzle: derived.puzzle(),
+                amount: derived.amount(),
+                puzzle_amount: derived.puzzle_amount(),
+                parent_amount: derived.parent_amount(),
+                parent_puzzle: derived.parent_puzzle(),
+            }),
+            inherit: None,
+            ..TESTNET11.clone()
+        };
+
+        assert_eq!(network.agg_sig_me(), Bytes32::new([1; 32]));
+        assert_eq!(network.agg_sig_constants(), derived);
+    }
+
+    #[test]
+    fn test_inherited_services() {
+        let network = Network {
+            services: NetworkServices {
+                dexie_api: Some("http://localhost:3000".to_string()),
+                ..NetworkServices::default()
+            },
+            ..NetworkList::default().networks[0].clone()
+        };
+
+        let services = network.services();
+        assert_eq!(services.dexie_api.as_deref(), Some("http://localhost:3000"));
+        assert_eq!(services.dexie_icons, MAINNET.services.dexie_icons);
+
+        let custom = Network {
+            inherit: None,
+            ..network
+        };
+        assert_eq!(custom.services().mintgarden_assets, None);
+    }
+}
@@ -7,8 +7,8 @@ use serde_with::{hex::Hex, serde_as};
 use specta::Type;
 
 use crate::{
-    Config, GlobalConfig, InheritedNetwork, Network, NetworkConfig, NetworkList, RpcConfig, Wallet,
-    WalletConfig, WalletDefaults,
+    Config, GlobalConfig, InheritedNetwork, MetricsConfig, Network, NetworkConfig, NetworkList,
+    NetworkServices, ReputationConfig, RpcConfig, Wallet, WalletConfig, WalletDefaults,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Type)]
@@ -135,11 +135,17 @@ pub fn migrate_config(old: OldConfig) -> Result<(Config, WalletConfig), ParseInt
             default_network: old.network.network_id,
             target_peers: old.network.target_peers,
             discover_peers: old.network.discover_peers,
+            paranoid_sync: false,
+            header_validation: false,
+            proxy: None,
+            dns_discovery: true,
         },
         rpc: RpcConfig {
             enabled: old.rpc.run_on_startup,
             port: old.rpc.server_port,
+            metrics: MetricsConfig::default(),
         },
+        reputation: ReputationConfig::default(),
     };
 
     let mut wallet_config = WalletConfig {
@@ -155,6 +161,9 @@ pub fn migrate_config(old: OldConfig) -> Result<(Config, WalletConfig), ParseInt
             delta_sync: None,
             emoji: None,
             change_address: None,
+            clawback_policy: None,
+            coin_management: None,
+            background_sync: false,
         });
     }
 
@@ -190,8 +199,12 @@ pub fn migrate_networks(old: IndexMap<String, OldNetwork>) -> NetworkList {
                     } else {
                         Some(network.agg_sig_me)
                     },
+                    agg_sig: None,
                     additional_dns_introducers: network.dns_introducers,
                     additional_peer_introducers: vec![],
+                    trusted_peers: Vec::new(),
+                    full_node_rpc: None,
+                    services: NetworkServices::default(),
                     inherit,
                 }
             })
@@ -1,4 +1,6 @@
+use chia_wallet_sdk::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_with::{hex::Hex, serde_as};
 use specta::Type;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, Type)]
@@ -31,6 +33,13 @@ pub struct Wallet {
     pub emoji: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub change_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clawback_policy: Option<ClawbackPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_management: Option<CoinManagement>,
+    /// Keeps the wallet syncing in the background while another wallet is logged in.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub background_sync: bool,
 }
 
 impl Wallet {
@@ -48,10 +57,73 @@ impl Default for Wallet {
             delta_sync: None,
             emoji: None,
             change_address: None,
+            clawback_policy: None,
+            coin_management: None,
+            background_sync: false,
         }
     }
 }
 
+// The API has its own `ClawbackPolicy` with amounts, so this is renamed in the bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+#[specta(rename = "WalletClawbackPolicy")]
+pub struct ClawbackPolicy {
+    pub finalize_incoming: bool,
+    pub reclaim_outgoing: bool,
+    pub reclaim_window_seconds: u64,
+    pub fee: u64,
+}
+
+impl Default for ClawbackPolicy {
+    fn default() -> Self {
+        Self {
+            finalize_incoming: true,
+            reclaim_outgoing: false,
+            reclaim_window_seconds: 3600,
+            fee: 0,
+        }
+    }
+}
+
+// These are also renamed in the bindings, to avoid clashing with the API's coin policies.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+#[specta(rename = "WalletCoinManagement")]
+pub struct CoinManagement {
+    pub fee: u64,
+    pub daily_fee_budget: u64,
+    pub policies: Vec<CoinPolicy>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[specta(rename = "WalletCoinPolicy")]
+pub struct CoinPolicy {
+    #[serde_as(as = "Option<Hex>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[specta(type = Option<String>)]
+    pub asset_id: Option<Bytes32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split: Option<SplitPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub combine: Option<CombinePolicy>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[specta(rename = "WalletSplitPolicy")]
+pub struct SplitPolicy {
+    pub min_coins: u32,
+    pub coin_amount: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[specta(rename = "WalletCombinePolicy")]
+pub struct CombinePolicy {
+    pub max_coin_amount: u64,
+    pub coin_count_threshold: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use expect_test::{Expect, expect};
@@ -68,6 +140,9 @@ mod tests {
             change_address: Some(
                 "xch1dtfukqqka3ftqtdlhmc5spc5vd44h7ejrtnjcewxlueam5yrnnqqyczg8t".to_string(),
             ),
+            clawback_policy: None,
+            coin_management: None,
+            background_sync: false,
         }
     }
 
@@ -143,4 +218,152 @@ mod tests {
                 }"#]],
         );
     }
+
+    #[test]
+    fn test_wallet_config_clawback_policy() {
+        let config = Wallet {
+            clawback_policy: Some(ClawbackPolicy::default()),
+            ..default()
+        };
+        check(
+            config,
+            &expect![[r#"
+            [defaults]
+            delta_sync = true
+
+            [[wallets]]
+            name = "Main"
+            fingerprint = 1000000
+            change_address = "xch1dtfukqqka3ftqtdlhmc5spc5vd44h7ejrtnjcewxlueam5yrnnqqyczg8t"
+
+            [wallets.clawback_policy]
+            finalize_incoming = true
+            reclaim_outgoing = false
+            reclaim_window_seconds = 3600
+            fee = 0
+        "#]],
+            &expect![[r#"
+            {
+              "defaults": {
+                "delta_sync": true
+              },
+              "wallets": [
+                {
+                  "name": "Main",
+                  "fingerprint": 1000000,
+                  "delta_sync": null,
+                  "change_address": "xch1dtfukqqka3ftqtdlhmc5spc5vd44h7ejrtnjcewxlueam5yrnnqqyczg8t",
+                  "clawback_policy": {
+                    "finalize_incoming": true,
+                    "reclaim_outgoing": false,
+                    "reclaim_window_seconds": 3600,
+                    "fee": 0
+                  }
+                }
+              ]
+            }"#]],
+        );
+    }
+
+    #[test]
+    fn test_wallet_config_coin_management() {
+        let config = Wallet {
+            coin_management: Some(CoinManagement {
+                fee: 100_000,
+                daily_fee_budget: 10_000_000,
+                policies: vec![
+                    CoinPolicy {
+                        asset_id: None,
+                        split: Some(SplitPolicy {
+                            min_coins: 20,
+                            coin_amount: 10_000_000_000_000,
+                        }),
+                        combine: Some(CombinePolicy {
+                            max_coin_amount: 1_000_000_000,
+                            coin_count_threshold: 200,
+                        }),
+                    },
+                    CoinPolicy {
+                        asset_id: Some(Bytes32::new([1; 32])),
+                        split: None,
+                        combine: Some(CombinePolicy {
+                            max_coin_amount: 1000,
+                            coin_count_threshold: 50,
+                        }),
+                    },
+                ],
+            }),
+            ..default()
+        };
+        check(
+            config,
+            &expect![[r#"
+            [defaults]
+            delta_sync = true
+
+            [[wallets]]
+            name = "Main"
+            fingerprint = 1000000
+            change_address = "xch1dtfukqqka3ftqtdlhmc5spc5vd44h7ejrtnjcewxlueam5yrnnqqyczg8t"
+
+            [wallets.coin_management]
+            fee = 100000
+            daily_fee_budget = 10000000
+
+            [[wallets.coin_management.policies]]
+
+            [wallets.coin_management.policies.split]
+            min_coins = 20
+            coin_amount = 10000000000000
+
+            [wallets.coin_management.policies.combine]
+            max_coin_amount = 1000000000
+            coin_count_threshold = 200
+
+            [[wallets.coin_management.policies]]
+            asset_id = "0101010101010101010101010101010101010101010101010101010101010101"
+
+            [wallets.coin_management.policies.combine]
+            max_coin_amount = 1000
+            coin_count_threshold = 50
+        "#]],
+            &expect![[r#"
+            {
+              "defaults": {
+                "delta_sync": true
+              },
+              "wallets": [
+                {
+                  "name": "Main",
+                  "fingerprint": 1000000,
+                  "delta_sync": null,
+                  "change_address": "xch1dtfukqqka3ftqtdlhmc5spc5vd44h7ejrtnjcewxlueam5yrnnqqyczg8t",
+                  "coin_management": {
+                    "fee": 100000,
+                    "daily_fee_budget": 10000000,
+                    "policies": [
+                      {
+                        "split": {
+                          "min_coins": 20,
+                          "coin_amount": 10000000000000
+                        },
+                        "combine": {
+                          "max_coin_amount": 1000000000,
+                          "coin_count_threshold": 200
+                        }
+                      },
+                      {
+                        "asset_id": "0101010101010101010101010101010101010101010101010101010101010101",
+                        "combine": {
+                          "max_coin_amount": 1000,
+                          "coin_count_threshold": 50
+                        }
+                      }
+                    ]
+                  }
+                }
+              ]
+            }"#]],
+        );
+    }
 }
@@ -1,18 +1,33 @@
 mod assets;
 mod blocks;
+mod clawbacks;
+mod coin_management_fees;
 mod coins;
+mod collection_reputations;
 mod collections;
 mod files;
+mod issued_cats;
 mod mempool_items;
+mod mint_jobs;
 mod offers;
 mod p2_puzzles;
+mod royalty_payments;
+mod standing_offers;
 mod transactions;
 
 pub use assets::*;
+pub use blocks::*;
+pub use clawbacks::*;
+pub use coin_management_fees::*;
 pub use coins::*;
+pub use collection_reputations::*;
 pub use collections::*;
 pub use files::*;
+pub use issued_cats::*;
 pub use mempool_items::*;
+pub use mint_jobs::*;
 pub use offers::*;
 pub use p2_puzzles::*;
+pub use royalty_payments::*;
+pub use standing_offers::*;
 pub use transactions::*;
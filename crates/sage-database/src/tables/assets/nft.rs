@@ -19,6 +19,22 @@ pub enum NftGroupSearch {
     NoOwnerDid,
 }
 
+/// Whether a reputation source has blocklisted (or allowlisted) an owned NFT's collection, the DID
+/// which minted the collection, or the DID which minted the NFT itself.
+fn nft_reputation(is_blocked: bool) -> String {
+    format!(
+        "
+        EXISTS (
+            SELECT 1 FROM collection_reputations
+            WHERE is_blocked = {is_blocked}
+            AND collection_reputations.hash IN (
+                collections.hash, collections.minter_hash, owned_nfts.minter_hash
+            )
+        )
+        "
+    )
+}
+
 #[derive(Debug, Clone)]
 pub struct NftCoinInfo {
     pub collection_hash: Bytes32,
@@ -133,21 +149,27 @@ impl Database {
         .transpose()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn owned_nfts(
         &self,
         name_search: Option<String>,
         group_search: Option<NftGroupSearch>,
         sort_mode: NftSortMode,
         include_hidden: bool,
+        verified: Option<bool>,
         limit: u32,
         offset: u32,
     ) -> Result<(Vec<NftRow>, u32)> {
-        let mut query = sqlx::QueryBuilder::new(
+        let is_blocked = nft_reputation(true);
+        let is_allowed = nft_reputation(false);
+
+        let mut query = sqlx::QueryBuilder::new(format!(
             "
             SELECT        
                 asset_hash, asset_name, asset_ticker, asset_precision, asset_icon_url,
                 asset_description, asset_is_sensitive_content, asset_hidden_puzzle_hash,
-                asset_is_visible AND (collections.id IS NULL OR collections.is_visible) as is_visible,
+                asset_is_visible AND (collections.id IS NULL OR collections.is_visible)
+                    AND NOT {is_blocked} as is_visible,
                 collections.hash AS collection_hash, collections.name AS collection_name, 
                 owned_nfts.minter_hash, owner_hash, metadata, metadata_updater_puzzle_hash,
                 royalty_puzzle_hash, royalty_basis_points, data_hash, metadata_hash, license_hash,                
@@ -157,11 +179,17 @@ impl Database {
             FROM owned_nfts
             LEFT JOIN collections ON collections.id = owned_nfts.collection_id
             WHERE 1=1
-            ",
-        );
+            "
+        ));
 
         if !include_hidden {
             query.push("AND (asset_is_visible = 1 AND (owned_nfts.collection_id IS NULL OR collections.is_visible = 1))");
+            query.push(format!(" AND NOT {is_blocked}"));
+        }
+
+        if let Some(verified) = verified {
+            query.push(if verified { " AND " } else { " AND NOT " });
+            query.push(format!("({is_allowed} AND NOT {is_blocked})"));
         }
 
         if let Some(name_search) = name_search {
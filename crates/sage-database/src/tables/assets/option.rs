@@ -2,8 +2,8 @@ use chia_wallet_sdk::prelude::*;
 use sqlx::{Row, SqliteExecutor, query};
 
 use crate::{
-    Asset, AssetKind, CoinKind, CoinRow, Convert, Database, DatabaseTx, Result, is_valid_asset_id,
-    puzzle_hash_from_address,
+    Asset, AssetKind, CoinKind, CoinRow, Convert, Database, DatabaseError, DatabaseTx, Result,
+    is_valid_asset_id, puzzle_hash_from_address,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -19,6 +19,8 @@ pub struct OptionCoinInfo {
     pub underlying_delegated_puzzle_hash: Bytes32,
     pub strike_asset_hash: Bytes32,
     pub strike_amount: u64,
+    /// Only set for NFT strikes, since the NFT's settlement puzzle hash depends on its metadata.
+    pub strike_settlement_puzzle_hash: Option<Bytes32>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +50,22 @@ pub struct OptionAssetsRow {
     pub expiration_seconds: u64,
 }
 
+#[derive(Debug, Clone)]
+pub struct OptionChainRow {
+    pub launcher_id: Bytes32,
+    pub name: Option<String>,
+    pub is_visible: bool,
+    pub underlying_asset: Asset,
+    pub underlying_amount: u64,
+    pub strike_asset: Asset,
+    pub strike_amount: u64,
+    pub expiration_seconds: u64,
+    pub underlying_spent_height: Option<u32>,
+    pub underlying_spent_timestamp: Option<u64>,
+    pub is_owned: bool,
+    pub is_creator: bool,
+}
+
 impl Database {
     pub async fn owned_options(
         &self,
@@ -189,7 +207,8 @@ impl Database {
                     WHERE coins.p2_puzzle_id = p2_options.p2_puzzle_id LIMIT 1
                 ) AS underlying_amount,
                 (SELECT hash FROM assets WHERE id = strike_asset_id) AS strike_asset_hash,
-                strike_amount, strike_assets.hidden_puzzle_hash AS strike_hidden_puzzle_hash
+                strike_amount, strike_assets.hidden_puzzle_hash AS strike_hidden_puzzle_hash,
+                strike_assets.kind AS strike_kind, strike_settlement_puzzle_hash
             FROM p2_options
             INNER JOIN options ON options.asset_id = p2_options.option_asset_id
             INNER JOIN assets AS strike_assets ON strike_assets.id = options.strike_asset_id
@@ -206,13 +225,23 @@ impl Database {
         let asset_hash: Bytes32 = row.strike_asset_hash.convert()?;
         let amount: u64 = row.strike_amount.convert()?;
         let hidden_puzzle_hash: Option<Bytes32> = row.strike_hidden_puzzle_hash.convert()?;
+        let kind: AssetKind = row.strike_kind.convert()?;
+        let settlement_puzzle_hash: Option<Bytes32> =
+            row.strike_settlement_puzzle_hash.convert()?;
 
         Ok(Some(OptionUnderlying::new(
             launcher_id,
             row.creator_puzzle_hash.convert()?,
             row.expiration_seconds.convert()?,
             row.underlying_amount.convert()?,
-            if asset_hash == Bytes32::default() {
+            if kind == AssetKind::Nft {
+                OptionType::Nft {
+                    launcher_id: asset_hash,
+                    settlement_puzzle_hash: settlement_puzzle_hash
+                        .ok_or(DatabaseError::OptionUnderlyingNotFound)?,
+                    amount,
+                }
+            } else if asset_hash == Bytes32::default() {
                 OptionType::Xch { amount }
             } else if let Some(hidden_puzzle_hash) = hidden_puzzle_hash {
                 OptionType::RevocableCat {
@@ -229,6 +258,108 @@ impl Database {
         )))
     }
 
+    /// Every option contract we know about, regardless of whether we still hold it.
+    pub async fn option_chain(&self) -> Result<Vec<OptionChainRow>> {
+        query!(
+            "
+            SELECT
+                option_asset.hash AS option_hash, option_asset.name AS option_name,
+                option_asset.is_visible AS option_is_visible,
+
+                strike_asset.hash AS strike_asset_hash, strike_asset.name AS strike_asset_name,
+                strike_asset.ticker AS strike_asset_ticker, strike_asset.precision AS strike_asset_precision,
+                strike_asset.icon_url AS strike_asset_icon_url, strike_asset.description AS strike_asset_description,
+                strike_asset.is_visible AS strike_asset_is_visible, strike_asset.is_sensitive_content AS strike_asset_is_sensitive_content,
+                strike_asset.hidden_puzzle_hash AS strike_asset_hidden_puzzle_hash, strike_asset.kind AS strike_asset_kind,
+
+                underlying_asset.hash AS underlying_asset_hash, underlying_asset.name AS underlying_asset_name,
+                underlying_asset.ticker AS underlying_asset_ticker, underlying_asset.precision AS underlying_asset_precision,
+                underlying_asset.icon_url AS underlying_asset_icon_url, underlying_asset.description AS underlying_asset_description,
+                underlying_asset.is_visible AS underlying_asset_is_visible, underlying_asset.is_sensitive_content AS underlying_asset_is_sensitive_content,
+                underlying_asset.hidden_puzzle_hash AS underlying_asset_hidden_puzzle_hash, underlying_asset.kind AS underlying_asset_kind,
+
+                p2_options.expiration_seconds AS expiration_seconds,
+                strike_amount,
+                underlying_coin.amount AS underlying_amount,
+                underlying_coin.spent_height AS underlying_spent_height,
+                spent_blocks.timestamp AS underlying_spent_timestamp,
+                EXISTS (
+                    SELECT 1 FROM owned_coins WHERE owned_coins.asset_id = options.asset_id
+                ) AS 'is_owned!: bool',
+                EXISTS (
+                    SELECT 1 FROM p2_puzzles WHERE p2_puzzles.hash = p2_options.creator_puzzle_hash
+                ) AS 'is_creator!: bool'
+            FROM options
+            INNER JOIN assets AS option_asset ON option_asset.id = options.asset_id
+            INNER JOIN coins AS underlying_coin ON underlying_coin.id = options.underlying_coin_id
+            INNER JOIN p2_options ON p2_options.option_asset_id = options.asset_id
+            INNER JOIN assets AS strike_asset ON strike_asset.id = options.strike_asset_id
+            INNER JOIN assets AS underlying_asset ON underlying_asset.id = underlying_coin.asset_id
+            LEFT JOIN blocks AS spent_blocks ON spent_blocks.height = underlying_coin.spent_height
+            ORDER BY underlying_asset.hash, strike_asset.hash, p2_options.expiration_seconds
+            "
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(OptionChainRow {
+                launcher_id: row.option_hash.convert()?,
+                name: row.option_name,
+                is_visible: row.option_is_visible,
+                underlying_asset: Asset {
+                    hash: row.underlying_asset_hash.convert()?,
+                    name: row.underlying_asset_name,
+                    ticker: row.underlying_asset_ticker,
+                    precision: row.underlying_asset_precision.convert()?,
+                    icon_url: row.underlying_asset_icon_url,
+                    description: row.underlying_asset_description,
+                    is_visible: row.underlying_asset_is_visible,
+                    is_sensitive_content: row.underlying_asset_is_sensitive_content,
+                    hidden_puzzle_hash: row.underlying_asset_hidden_puzzle_hash.convert()?,
+                    kind: row.underlying_asset_kind.convert()?,
+                },
+                underlying_amount: row.underlying_amount.convert()?,
+                strike_asset: Asset {
+                    hash: row.strike_asset_hash.convert()?,
+                    name: row.strike_asset_name,
+                    ticker: row.strike_asset_ticker,
+                    precision: row.strike_asset_precision.convert()?,
+                    icon_url: row.strike_asset_icon_url,
+                    description: row.strike_asset_description,
+                    is_visible: row.strike_asset_is_visible,
+                    is_sensitive_content: row.strike_asset_is_sensitive_content,
+                    hidden_puzzle_hash: row.strike_asset_hidden_puzzle_hash.convert()?,
+                    kind: row.strike_asset_kind.convert()?,
+                },
+                strike_amount: row.strike_amount.convert()?,
+                expiration_seconds: row.expiration_seconds.convert()?,
+                underlying_spent_height: row.underlying_spent_height.convert()?,
+                underlying_spent_timestamp: row.underlying_spent_timestamp.convert()?,
+                is_owned: row.is_owned,
+                is_creator: row.is_creator,
+            })
+        })
+        .collect()
+    }
+
+    /// The underlying coins of option contracts we created which have expired and can be reclaimed.
+    pub async fn expired_option_underlying_coin_ids(&self) -> Result<Vec<Bytes32>> {
+        query!(
+            "
+            SELECT coin_hash FROM spendable_coins
+            WHERE option_expiration_seconds IS NOT NULL
+            AND offer_hash IS NULL
+            ORDER BY option_expiration_seconds ASC
+            "
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| row.coin_hash.convert())
+        .collect()
+    }
+
     pub async fn offer_option_info(&self, hash: Bytes32) -> Result<Option<OptionOfferInfo>> {
         let hash = hash.as_ref();
 
@@ -329,17 +460,20 @@ impl DatabaseTx<'_> {
         let underlying_delegated_puzzle_hash = coin_info.underlying_delegated_puzzle_hash.as_ref();
         let strike_asset_hash = coin_info.strike_asset_hash.as_ref();
         let strike_amount = coin_info.strike_amount.to_be_bytes().to_vec();
+        let strike_settlement_puzzle_hash = coin_info.strike_settlement_puzzle_hash.as_deref();
 
         query!(
             "
             INSERT OR IGNORE INTO options (
-                asset_id, underlying_coin_id, underlying_delegated_puzzle_hash, strike_asset_id, strike_amount
+                asset_id, underlying_coin_id, underlying_delegated_puzzle_hash, strike_asset_id, strike_amount,
+                strike_settlement_puzzle_hash
             )
             VALUES (
                 (SELECT id FROM assets WHERE hash = ?),
                 (SELECT id FROM coins WHERE hash = ?),
                 ?,
                 (SELECT id FROM assets WHERE hash = ?),
+                ?,
                 ?
             )
             ",
@@ -347,7 +481,8 @@ impl DatabaseTx<'_> {
             underlying_coin_hash,
             underlying_delegated_puzzle_hash,
             strike_asset_hash,
-            strike_amount
+            strike_amount,
+            strike_settlement_puzzle_hash
         )
         .execute(&mut *self.tx)
         .await?;
@@ -1,12 +1,24 @@
 use crate::{Convert, Database, DatabaseTx, Result};
 use chia_wallet_sdk::prelude::*;
-use sqlx::SqliteExecutor;
+use sqlx::{SqliteConnection, SqliteExecutor};
+
+/// A block on the header chain that has been linked to its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidatedBlock {
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub weight: u128,
+}
 
 impl Database {
     pub async fn unsynced_blocks(&self, limit: u32) -> Result<Vec<u32>> {
         unsynced_blocks(&self.pool, limit).await
     }
 
+    pub async fn unsynced_block_count(&self) -> Result<u64> {
+        unsynced_block_count(&self.pool).await
+    }
+
     pub async fn insert_block(
         &self,
         height: u32,
@@ -20,12 +32,46 @@ impl Database {
     pub async fn latest_peak(&self) -> Result<Option<(u32, Bytes32)>> {
         latest_peak(&self.pool).await
     }
+
+    pub async fn validated_peak(&self) -> Result<Option<ValidatedBlock>> {
+        validated_peak(&self.pool).await
+    }
+
+    pub async fn validated_block(&self, height: u32) -> Result<Option<ValidatedBlock>> {
+        validated_block(&self.pool, height).await
+    }
+
+    pub async fn lowest_validated_height(&self) -> Result<Option<u32>> {
+        lowest_validated_height(&self.pool).await
+    }
+
+    /// Forgets which blocks were validated, so that peaks from peers are recorded as they are
+    /// again and the header chain starts over if validation is turned back on.
+    pub async fn clear_validated_blocks(&self) -> Result<()> {
+        clear_validated_blocks(&self.pool).await
+    }
 }
 
 impl DatabaseTx<'_> {
     pub async fn insert_height(&mut self, height: u32) -> Result<()> {
         insert_height(&mut *self.tx, height).await
     }
+
+    pub async fn insert_validated_block(
+        &mut self,
+        height: u32,
+        header_hash: Bytes32,
+        prev_header_hash: Bytes32,
+        weight: u128,
+    ) -> Result<()> {
+        insert_validated_block(&mut *self.tx, height, header_hash, prev_header_hash, weight).await
+    }
+
+    /// Undoes everything that happened above the fork height, and returns the ids of the
+    /// coins whose state changed as a result so they can be requested again.
+    pub async fn rollback_to_height(&mut self, fork_height: u32) -> Result<Vec<Bytes32>> {
+        rollback_to_height(&mut self.tx, fork_height).await
+    }
 }
 
 async fn insert_height(conn: impl SqliteExecutor<'_>, height: u32) -> Result<()> {
@@ -62,6 +108,23 @@ async fn unsynced_blocks(conn: impl SqliteExecutor<'_>, limit: u32) -> Result<Ve
         .collect()
 }
 
+async fn unsynced_block_count(conn: impl SqliteExecutor<'_>) -> Result<u64> {
+    sqlx::query!(
+        "
+        SELECT COUNT(*) AS count FROM blocks
+        WHERE timestamp IS NULL
+        AND (
+            EXISTS (SELECT 1 FROM coins WHERE coins.created_height = blocks.height)
+            OR EXISTS (SELECT 1 FROM coins WHERE coins.spent_height = blocks.height)
+        )
+        "
+    )
+    .fetch_one(conn)
+    .await?
+    .count
+    .convert()
+}
+
 async fn insert_block(
     conn: impl SqliteExecutor<'_>,
     height: u32,
@@ -70,12 +133,15 @@ async fn insert_block(
     is_peak: bool,
 ) -> Result<()> {
     let header_hash = header_hash.as_ref();
+
+    // Validated blocks are only replaced by the header queue, so that a peak or block record
+    // from a single peer can't overwrite the chain that has been checked.
     sqlx::query!(
         "
         INSERT INTO blocks (height, timestamp, header_hash, is_peak) VALUES (?, ?, ?, ?)
         ON CONFLICT (height) DO UPDATE SET
             timestamp = COALESCE(excluded.timestamp, timestamp),
-            header_hash = excluded.header_hash,
+            header_hash = IIF(is_validated, header_hash, excluded.header_hash),
             is_peak = (excluded.is_peak OR is_peak)
         ",
         height,
@@ -107,3 +173,161 @@ async fn latest_peak(conn: impl SqliteExecutor<'_>) -> Result<Option<(u32, Bytes
     })
     .transpose()
 }
+
+async fn validated_peak(conn: impl SqliteExecutor<'_>) -> Result<Option<ValidatedBlock>> {
+    sqlx::query!(
+        "
+        SELECT height, header_hash AS validated_header_hash, weight
+        FROM blocks
+        WHERE is_validated = TRUE
+        ORDER BY height DESC
+        LIMIT 1
+        "
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(ValidatedBlock {
+            height: row.height.convert()?,
+            header_hash: row.validated_header_hash.unwrap_or_default().convert()?,
+            weight: row.weight.unwrap_or_default().convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn validated_block(
+    conn: impl SqliteExecutor<'_>,
+    height: u32,
+) -> Result<Option<ValidatedBlock>> {
+    sqlx::query!(
+        "
+        SELECT height, header_hash AS validated_header_hash, weight
+        FROM blocks
+        WHERE height = ? AND is_validated = TRUE
+        ",
+        height
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(ValidatedBlock {
+            height: row.height.convert()?,
+            header_hash: row.validated_header_hash.unwrap_or_default().convert()?,
+            weight: row.weight.unwrap_or_default().convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn lowest_validated_height(conn: impl SqliteExecutor<'_>) -> Result<Option<u32>> {
+    sqlx::query_scalar!("SELECT MIN(height) FROM blocks WHERE is_validated = TRUE")
+        .fetch_one(conn)
+        .await?
+        .convert()
+}
+
+async fn clear_validated_blocks(conn: impl SqliteExecutor<'_>) -> Result<()> {
+    sqlx::query!(
+        "
+        UPDATE blocks SET prev_header_hash = NULL, weight = NULL, is_validated = FALSE
+        WHERE is_validated = TRUE
+        "
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_validated_block(
+    conn: impl SqliteExecutor<'_>,
+    height: u32,
+    header_hash: Bytes32,
+    prev_header_hash: Bytes32,
+    weight: u128,
+) -> Result<()> {
+    let header_hash = header_hash.as_ref();
+    let prev_header_hash = prev_header_hash.as_ref();
+    let weight = weight.to_be_bytes().to_vec();
+
+    sqlx::query!(
+        "
+        INSERT INTO blocks (height, header_hash, prev_header_hash, weight, is_validated, is_peak)
+        VALUES (?, ?, ?, ?, TRUE, FALSE)
+        ON CONFLICT (height) DO UPDATE SET
+            header_hash = excluded.header_hash,
+            prev_header_hash = excluded.prev_header_hash,
+            weight = excluded.weight,
+            is_validated = TRUE
+        ",
+        height,
+        header_hash,
+        prev_header_hash,
+        weight
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn rollback_to_height(conn: &mut SqliteConnection, fork_height: u32) -> Result<Vec<Bytes32>> {
+    let coin_ids = sqlx::query_scalar!(
+        "SELECT hash FROM coins WHERE created_height > ? OR spent_height > ?",
+        fork_height,
+        fork_height
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Offers which were completed or cancelled by a spend that no longer exists are active
+    // again, until the offer queue sees the spend on the new chain.
+    sqlx::query!(
+        "
+        UPDATE offers SET status = 1
+        WHERE status IN (2, 3) AND id IN (
+            SELECT offer_id FROM offer_coins
+            INNER JOIN coins ON coins.id = offer_coins.coin_id
+            WHERE coins.spent_height > ?
+        )
+        ",
+        fork_height
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        "
+        DELETE FROM lineage_proofs WHERE coin_id IN (
+            SELECT id FROM coins WHERE created_height > ?
+        )
+        ",
+        fork_height
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Coins created above the fork height are removed along with their blocks, and coins
+    // spent above it become unspent again. Since the transaction history is derived from
+    // these heights, this also removes any transactions above the fork.
+    sqlx::query!(
+        "
+        UPDATE coins SET spent_height = NULL, is_children_synced = FALSE
+        WHERE spent_height > ?
+        ",
+        fork_height
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!("DELETE FROM coins WHERE created_height > ?", fork_height)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query!("DELETE FROM blocks WHERE height > ?", fork_height)
+        .execute(conn)
+        .await?;
+
+    coin_ids.into_iter().map(Convert::convert).collect()
+}
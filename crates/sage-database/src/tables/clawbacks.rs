@@ -0,0 +1,80 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{Asset, Convert, Database, Result};
+
+#[derive(Debug, Clone)]
+pub struct ClawbackCoin {
+    pub coin: Coin,
+    pub asset: Asset,
+    pub p2_puzzle_hash: Bytes32,
+    pub sender_puzzle_hash: Bytes32,
+    pub receiver_puzzle_hash: Bytes32,
+    pub expiration_seconds: u64,
+    pub is_outgoing: bool,
+    pub is_incoming: bool,
+    pub mempool_item_hash: Option<Bytes32>,
+    pub created_height: Option<u32>,
+    pub created_timestamp: Option<u64>,
+}
+
+impl Database {
+    /// All unspent clawback coins, whether we are the sender or the receiver, ordered by expiration.
+    pub async fn clawback_coins(&self) -> Result<Vec<ClawbackCoin>> {
+        clawback_coins(&self.pool).await
+    }
+}
+
+async fn clawback_coins(conn: impl SqliteExecutor<'_>) -> Result<Vec<ClawbackCoin>> {
+    query!(
+        "
+        SELECT
+            parent_coin_hash, puzzle_hash, amount, p2_puzzle_hash,
+            asset_hash, asset_name, asset_ticker, asset_precision, asset_icon_url,
+            asset_description, asset_is_sensitive_content, asset_is_visible,
+            asset_hidden_puzzle_hash, asset_kind,
+            clawback_sender_puzzle_hash AS 'sender_puzzle_hash!: Vec<u8>',
+            clawback_receiver_puzzle_hash AS 'receiver_puzzle_hash!: Vec<u8>',
+            clawback_expiration_seconds AS 'expiration_seconds!: i64',
+            clawback_sender_p2_puzzle_id IS NOT NULL AS 'is_outgoing!: bool',
+            clawback_receiver_p2_puzzle_id IS NOT NULL AS 'is_incoming!: bool',
+            mempool_item_hash, created_height, created_timestamp
+        FROM clawback_coins
+        ORDER BY clawback_expiration_seconds ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ClawbackCoin {
+            coin: Coin::new(
+                row.parent_coin_hash.convert()?,
+                row.puzzle_hash.convert()?,
+                row.amount.convert()?,
+            ),
+            asset: Asset {
+                hash: row.asset_hash.convert()?,
+                name: row.asset_name,
+                ticker: row.asset_ticker,
+                precision: row.asset_precision.convert()?,
+                icon_url: row.asset_icon_url,
+                description: row.asset_description,
+                is_sensitive_content: row.asset_is_sensitive_content,
+                is_visible: row.asset_is_visible,
+                hidden_puzzle_hash: row.asset_hidden_puzzle_hash.convert()?,
+                kind: row.asset_kind.convert()?,
+            },
+            p2_puzzle_hash: row.p2_puzzle_hash.convert()?,
+            sender_puzzle_hash: row.sender_puzzle_hash.convert()?,
+            receiver_puzzle_hash: row.receiver_puzzle_hash.convert()?,
+            expiration_seconds: row.expiration_seconds.convert()?,
+            is_outgoing: row.is_outgoing,
+            is_incoming: row.is_incoming,
+            mempool_item_hash: row.mempool_item_hash.convert()?,
+            created_height: row.created_height.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
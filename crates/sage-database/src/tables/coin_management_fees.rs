@@ -0,0 +1,62 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{Convert, Database, Result};
+
+impl Database {
+    pub async fn insert_coin_management_fee(
+        &self,
+        transaction_id: Bytes32,
+        fee: u64,
+        paid_timestamp: u64,
+    ) -> Result<()> {
+        insert_coin_management_fee(&self.pool, transaction_id, fee, paid_timestamp).await
+    }
+
+    /// The fees paid by coin management transactions since the given timestamp.
+    pub async fn coin_management_fees_since(&self, timestamp: u64) -> Result<Vec<u64>> {
+        coin_management_fees_since(&self.pool, timestamp).await
+    }
+}
+
+async fn insert_coin_management_fee(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+    fee: u64,
+    paid_timestamp: u64,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+    let fee = fee.to_be_bytes().to_vec();
+    let paid_timestamp: i64 = paid_timestamp.try_into()?;
+
+    query!(
+        "
+        INSERT OR IGNORE INTO coin_management_fees (transaction_hash, fee, paid_timestamp)
+        VALUES (?, ?, ?)
+        ",
+        transaction_id,
+        fee,
+        paid_timestamp
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn coin_management_fees_since(
+    conn: impl SqliteExecutor<'_>,
+    timestamp: u64,
+) -> Result<Vec<u64>> {
+    let timestamp: i64 = timestamp.try_into()?;
+
+    query!(
+        "SELECT fee FROM coin_management_fees WHERE paid_timestamp >= ?",
+        timestamp
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.fee.convert())
+    .collect()
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chia_wallet_sdk::{
     chia::puzzle_types::{LineageProof, Proof},
     prelude::*,
@@ -69,6 +71,10 @@ impl Database {
         coins_by_ids(&self.pool, coin_ids).await
     }
 
+    pub async fn is_known_coin(&self, coin_id: Bytes32) -> Result<bool> {
+        is_known_coin(&self.pool, coin_id).await
+    }
+
     pub async fn coin_records(
         &self,
         asset_filter: AssetFilter,
@@ -127,6 +133,10 @@ impl Database {
         subscription_coin_ids(&self.pool).await
     }
 
+    pub async fn unspent_coin_ids(&self) -> Result<Vec<Bytes32>> {
+        unspent_coin_ids(&self.pool).await
+    }
+
     pub async fn xch_balance(&self) -> Result<u128> {
         token_balance(&self.pool, Bytes32::default()).await
     }
@@ -135,6 +145,10 @@ impl Database {
         token_balance(&self.pool, asset_id).await
     }
 
+    pub async fn cat_balances(&self) -> Result<Vec<(Bytes32, u128)>> {
+        cat_balances(&self.pool).await
+    }
+
     pub async fn selectable_xch_balance(&self) -> Result<u128> {
         selectable_token_balance(&self.pool, Bytes32::default()).await
     }
@@ -497,6 +511,15 @@ async fn subscription_coin_ids(conn: impl SqliteExecutor<'_>) -> Result<Vec<Byte
     .collect()
 }
 
+async fn unspent_coin_ids(conn: impl SqliteExecutor<'_>) -> Result<Vec<Bytes32>> {
+    query!("SELECT coin_hash FROM wallet_coins WHERE spent_height IS NULL")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| row.coin_hash.convert())
+        .collect()
+}
+
 async fn selectable_coin_count(conn: impl SqliteExecutor<'_>, asset_id: Bytes32) -> Result<u32> {
     let asset_id_ref = asset_id.as_ref();
 
@@ -549,6 +572,35 @@ async fn token_balance(conn: impl SqliteExecutor<'_>, asset_id: Bytes32) -> Resu
     .sum()
 }
 
+/// Includes every CAT the wallet has owned, so that a spent CAT shows up with a zero balance.
+async fn cat_balances(conn: impl SqliteExecutor<'_>) -> Result<Vec<(Bytes32, u128)>> {
+    let rows = query!(
+        "
+        SELECT assets.hash AS asset_hash, owned_coins.amount
+        FROM assets
+        LEFT JOIN owned_coins ON owned_coins.asset_id = assets.id
+        WHERE assets.kind = 0 AND assets.id != 0
+        AND EXISTS (
+            SELECT 1 FROM coins
+            INNER JOIN p2_puzzles ON p2_puzzles.id = coins.p2_puzzle_id
+            WHERE coins.asset_id = assets.id
+        )
+        "
+    )
+    .fetch_all(conn)
+    .await?;
+
+    let mut balances = BTreeMap::new();
+
+    for row in rows {
+        let asset_hash: Bytes32 = row.asset_hash.convert()?;
+        let amount: Option<u64> = row.amount.convert()?;
+        *balances.entry(asset_hash).or_insert(0) += amount.unwrap_or(0) as u128;
+    }
+
+    Ok(balances.into_iter().collect())
+}
+
 async fn selectable_token_balance(
     conn: impl SqliteExecutor<'_>,
     asset_id: Bytes32,
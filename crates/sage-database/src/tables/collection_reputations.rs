@@ -0,0 +1,48 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{DatabaseTx, Result};
+
+impl DatabaseTx<'_> {
+    pub async fn clear_collection_reputations(&mut self) -> Result<()> {
+        clear_collection_reputations(&mut *self.tx).await
+    }
+
+    pub async fn insert_collection_reputation(
+        &mut self,
+        hash: Bytes32,
+        is_blocked: bool,
+    ) -> Result<()> {
+        insert_collection_reputation(&mut *self.tx, hash, is_blocked).await
+    }
+}
+
+async fn clear_collection_reputations(conn: impl SqliteExecutor<'_>) -> Result<()> {
+    query!("DELETE FROM collection_reputations")
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_collection_reputation(
+    conn: impl SqliteExecutor<'_>,
+    hash: Bytes32,
+    is_blocked: bool,
+) -> Result<()> {
+    let hash = hash.as_ref();
+
+    // Blocking an entry takes precedence over allowing it, regardless of the order.
+    query!(
+        "
+        INSERT INTO collection_reputations (hash, is_blocked) VALUES (?, ?)
+        ON CONFLICT (hash) DO UPDATE SET is_blocked = is_blocked OR excluded.is_blocked
+        ",
+        hash,
+        is_blocked
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
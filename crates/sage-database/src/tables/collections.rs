@@ -12,6 +12,7 @@ pub struct CollectionRow {
     pub banner_url: Option<String>,
     pub description: Option<String>,
     pub is_visible: bool,
+    pub is_verified: bool,
 }
 
 impl Database {
@@ -46,9 +47,20 @@ impl DatabaseTx<'_> {
 async fn collection(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<Option<CollectionRow>> {
     let hash_ref = hash.as_ref();
     let row = query!(
-        "SELECT id, hash, uuid, minter_hash, name, icon_url, banner_url, description, is_visible 
+        "
+        SELECT
+            hash, uuid, minter_hash, name, icon_url, banner_url, description, is_visible,
+            EXISTS (
+                SELECT 1 FROM collection_reputations
+                WHERE is_blocked = TRUE AND collection_reputations.hash IN (collections.hash, minter_hash)
+            ) AS is_blocked,
+            EXISTS (
+                SELECT 1 FROM collection_reputations
+                WHERE is_blocked = FALSE AND collection_reputations.hash IN (collections.hash, minter_hash)
+            ) AS is_allowed
         FROM collections
-        WHERE hash = ?",
+        WHERE hash = ?
+        ",
         hash_ref
     )
     .fetch_optional(conn)
@@ -63,7 +75,9 @@ async fn collection(conn: impl SqliteExecutor<'_>, hash: Bytes32) -> Result<Opti
             icon_url: row.icon_url,
             banner_url: row.banner_url,
             description: row.description,
-            is_visible: row.is_visible,
+            // Blocklisted collections are hidden, even if they've been manually shown.
+            is_visible: row.is_visible && row.is_blocked == 0,
+            is_verified: row.is_allowed != 0 && row.is_blocked == 0,
         })
     })
     .transpose()
@@ -78,11 +92,23 @@ async fn collections(
     // we only return collections that have nfts
     let rows = query!(
         "SELECT collections.hash, uuid, collections.minter_hash, collections.name, collections.icon_url, 
-        collections.banner_url, collections.description, collections.is_visible, COUNT(*) OVER() as total_count
+        collections.banner_url, collections.description, collections.is_visible,
+        EXISTS (
+            SELECT 1 FROM collection_reputations
+            WHERE is_blocked = TRUE AND collection_reputations.hash IN (collections.hash, collections.minter_hash)
+        ) AS is_blocked,
+        EXISTS (
+            SELECT 1 FROM collection_reputations
+            WHERE is_blocked = FALSE AND collection_reputations.hash IN (collections.hash, collections.minter_hash)
+        ) AS is_allowed,
+        COUNT(*) OVER() as total_count
         FROM collections
         WHERE 1=1
         AND EXISTS (SELECT 1 FROM owned_nfts WHERE owned_nfts.collection_id = collections.id)
-        AND (? OR is_visible = 1)
+        AND (? OR (is_visible = 1 AND NOT EXISTS (
+            SELECT 1 FROM collection_reputations
+            WHERE is_blocked = TRUE AND collection_reputations.hash IN (collections.hash, collections.minter_hash)
+        )))
         ORDER BY CASE WHEN collections.id = 0 THEN 1 ELSE 0 END, name ASC
         LIMIT ?
         OFFSET ?",
@@ -108,7 +134,8 @@ async fn collections(
                 icon_url: row.icon_url,
                 banner_url: row.banner_url,
                 description: row.description,
-                is_visible: row.is_visible,
+                is_visible: row.is_visible && row.is_blocked == 0,
+                is_verified: row.is_allowed != 0 && row.is_blocked == 0,
             })
         })
         .collect::<Result<Vec<_>>>()?;
@@ -0,0 +1,310 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{Convert, Database, DatabaseError, DatabaseTx, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CatIssuanceKind {
+    SingleIssuance = 0,
+    MultiIssuance = 1,
+    Custom = 2,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedCat {
+    pub asset_id: Bytes32,
+    pub name: Option<String>,
+    pub ticker: Option<String>,
+    pub tail_reveal: Program,
+    pub issuance_kind: CatIssuanceKind,
+    pub total_issued: u64,
+    pub total_melted: u64,
+    pub created_timestamp: u64,
+}
+
+impl Database {
+    pub async fn issued_cats(&self) -> Result<Vec<IssuedCat>> {
+        let mut cats = issued_cats(&self.pool).await?;
+        apply_supply_changes(&mut cats, &confirmed_supply_changes(&self.pool).await?);
+        Ok(cats)
+    }
+
+    pub async fn issued_cat(&self, asset_id: Bytes32) -> Result<Option<IssuedCat>> {
+        let mut cats: Vec<IssuedCat> = issued_cat(&self.pool, asset_id)
+            .await?
+            .into_iter()
+            .collect();
+        apply_supply_changes(&mut cats, &confirmed_supply_changes(&self.pool).await?);
+        Ok(cats.pop())
+    }
+
+    pub async fn issued_cat_coin_ids(&self, asset_id: Bytes32) -> Result<Vec<Bytes32>> {
+        issued_cat_coin_ids(&self.pool, asset_id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn issued_cat(&mut self, asset_id: Bytes32) -> Result<Option<IssuedCat>> {
+        let mut cats: Vec<IssuedCat> = issued_cat(&mut *self.tx, asset_id)
+            .await?
+            .into_iter()
+            .collect();
+        apply_supply_changes(&mut cats, &confirmed_supply_changes(&mut *self.tx).await?);
+        Ok(cats.pop())
+    }
+
+    /// Starts tracking a CAT issued by this wallet. Its supply is only counted once the
+    /// issuance has been recorded with [`DatabaseTx::insert_cat_supply_change`] and confirmed.
+    pub async fn insert_issued_cat(
+        &mut self,
+        asset_id: Bytes32,
+        tail_reveal: Program,
+        issuance_kind: CatIssuanceKind,
+    ) -> Result<()> {
+        insert_issued_cat(&mut *self.tx, asset_id, tail_reveal, issuance_kind).await
+    }
+
+    /// Records a change to the supply of an issued CAT, which is applied once the given coin
+    /// created by its transaction has been synced.
+    pub async fn insert_cat_supply_change(
+        &mut self,
+        asset_id: Bytes32,
+        coin_id: Bytes32,
+        issued: u64,
+        melted: u64,
+    ) -> Result<()> {
+        insert_cat_supply_change(&mut *self.tx, asset_id, coin_id, issued, melted).await
+    }
+
+    pub async fn insert_issued_cat_coin(
+        &mut self,
+        asset_id: Bytes32,
+        coin_id: Bytes32,
+    ) -> Result<()> {
+        insert_issued_cat_coin(&mut *self.tx, asset_id, coin_id).await
+    }
+}
+
+fn issuance_kind(kind: i64) -> Result<CatIssuanceKind> {
+    Ok(match kind {
+        0 => CatIssuanceKind::SingleIssuance,
+        1 => CatIssuanceKind::MultiIssuance,
+        2 => CatIssuanceKind::Custom,
+        _ => return Err(DatabaseError::InvalidEnumVariant),
+    })
+}
+
+async fn issued_cats(conn: impl SqliteExecutor<'_>) -> Result<Vec<IssuedCat>> {
+    query!(
+        "
+        SELECT
+            assets.hash AS asset_id, assets.name, assets.ticker, tail_reveal,
+            issuance_kind, total_issued, total_melted, created_timestamp
+        FROM issued_cats
+        INNER JOIN assets ON assets.id = issued_cats.asset_id
+        ORDER BY issued_cats.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(IssuedCat {
+            asset_id: row.asset_id.convert()?,
+            name: row.name,
+            ticker: row.ticker,
+            tail_reveal: Program::from(row.tail_reveal),
+            issuance_kind: issuance_kind(row.issuance_kind)?,
+            total_issued: row.total_issued.convert()?,
+            total_melted: row.total_melted.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn issued_cat(conn: impl SqliteExecutor<'_>, asset_id: Bytes32) -> Result<Option<IssuedCat>> {
+    let asset_id = asset_id.as_ref();
+
+    query!(
+        "
+        SELECT
+            assets.hash AS asset_id, assets.name, assets.ticker, tail_reveal,
+            issuance_kind, total_issued, total_melted, created_timestamp
+        FROM issued_cats
+        INNER JOIN assets ON assets.id = issued_cats.asset_id
+        WHERE assets.hash = ?
+        ",
+        asset_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        Ok(IssuedCat {
+            asset_id: row.asset_id.convert()?,
+            name: row.name,
+            ticker: row.ticker,
+            tail_reveal: Program::from(row.tail_reveal),
+            issuance_kind: issuance_kind(row.issuance_kind)?,
+            total_issued: row.total_issued.convert()?,
+            total_melted: row.total_melted.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .transpose()
+}
+
+async fn insert_issued_cat(
+    conn: impl SqliteExecutor<'_>,
+    asset_id: Bytes32,
+    tail_reveal: Program,
+    issuance_kind: CatIssuanceKind,
+) -> Result<()> {
+    let asset_id = asset_id.as_ref();
+    let tail_reveal = tail_reveal.as_ref();
+    let issuance_kind = issuance_kind as u8;
+    let total_issued = 0u64.to_be_bytes().to_vec();
+    let total_melted = 0u64.to_be_bytes().to_vec();
+
+    query!(
+        "
+        INSERT OR IGNORE INTO issued_cats (asset_id, tail_reveal, issuance_kind, total_issued, total_melted)
+        VALUES ((SELECT id FROM assets WHERE hash = ?), ?, ?, ?, ?)
+        ",
+        asset_id,
+        tail_reveal,
+        issuance_kind,
+        total_issued,
+        total_melted
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_cat_supply_change(
+    conn: impl SqliteExecutor<'_>,
+    asset_id: Bytes32,
+    coin_id: Bytes32,
+    issued: u64,
+    melted: u64,
+) -> Result<()> {
+    let asset_id = asset_id.as_ref();
+    let coin_id = coin_id.as_ref();
+    let issued = issued.to_be_bytes().to_vec();
+    let melted = melted.to_be_bytes().to_vec();
+
+    query!(
+        "
+        INSERT OR IGNORE INTO cat_supply_changes (issued_cat_id, coin_hash, issued, melted)
+        VALUES (
+            (
+                SELECT issued_cats.id FROM issued_cats
+                INNER JOIN assets ON assets.id = issued_cats.asset_id
+                WHERE assets.hash = ?
+            ),
+            ?, ?, ?
+        )
+        ",
+        asset_id,
+        coin_id,
+        issued,
+        melted
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// The supply changes whose coins have been created on chain, along with their asset ids.
+async fn confirmed_supply_changes(
+    conn: impl SqliteExecutor<'_>,
+) -> Result<Vec<(Bytes32, u64, u64)>> {
+    query!(
+        "
+        SELECT assets.hash AS asset_id, issued, melted
+        FROM cat_supply_changes
+        INNER JOIN issued_cats ON issued_cats.id = cat_supply_changes.issued_cat_id
+        INNER JOIN assets ON assets.id = issued_cats.asset_id
+        INNER JOIN coins ON coins.hash = cat_supply_changes.coin_hash
+        WHERE coins.created_height IS NOT NULL
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok((
+            row.asset_id.convert()?,
+            row.issued.convert()?,
+            row.melted.convert()?,
+        ))
+    })
+    .collect()
+}
+
+fn apply_supply_changes(cats: &mut [IssuedCat], changes: &[(Bytes32, u64, u64)]) {
+    for &(asset_id, issued, melted) in changes {
+        let Some(cat) = cats.iter_mut().find(|cat| cat.asset_id == asset_id) else {
+            continue;
+        };
+
+        cat.total_issued = cat.total_issued.saturating_add(issued);
+        cat.total_melted = cat.total_melted.saturating_add(melted);
+    }
+}
+
+async fn issued_cat_coin_ids(
+    conn: impl SqliteExecutor<'_>,
+    asset_id: Bytes32,
+) -> Result<Vec<Bytes32>> {
+    let asset_id = asset_id.as_ref();
+
+    query!(
+        "
+        SELECT coin_hash FROM issued_cat_coins
+        INNER JOIN issued_cats ON issued_cats.id = issued_cat_coins.issued_cat_id
+        INNER JOIN assets ON assets.id = issued_cats.asset_id
+        WHERE assets.hash = ?
+        ORDER BY issued_cat_coins.id ASC
+        ",
+        asset_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.coin_hash.convert())
+    .collect()
+}
+
+async fn insert_issued_cat_coin(
+    conn: impl SqliteExecutor<'_>,
+    asset_id: Bytes32,
+    coin_id: Bytes32,
+) -> Result<()> {
+    let asset_id = asset_id.as_ref();
+    let coin_id = coin_id.as_ref();
+
+    query!(
+        "
+        INSERT OR IGNORE INTO issued_cat_coins (issued_cat_id, coin_hash)
+        VALUES (
+            (
+                SELECT issued_cats.id FROM issued_cats
+                INNER JOIN assets ON assets.id = issued_cats.asset_id
+                WHERE assets.hash = ?
+            ),
+            ?
+        )
+        ",
+        asset_id,
+        coin_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
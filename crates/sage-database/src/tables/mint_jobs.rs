@@ -0,0 +1,370 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{Convert, Database, DatabaseError, DatabaseTx, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MintJobItemStatus {
+    Pending = 0,
+    Submitted = 1,
+    Minted = 2,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MintJob {
+    pub id: u32,
+    pub did_id: Bytes32,
+    pub fee: u64,
+    pub batch_size: u32,
+    pub is_active: bool,
+    pub created_timestamp: u64,
+    pub item_count: u32,
+    pub submitted_count: u32,
+    pub minted_count: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NewMintJob {
+    pub did_id: Bytes32,
+    pub fee: u64,
+    pub batch_size: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MintJobItem {
+    pub mint_job_id: u32,
+    pub row_index: u32,
+    pub metadata: Program,
+    pub p2_puzzle_hash: Option<Bytes32>,
+    pub royalty_puzzle_hash: Option<Bytes32>,
+    pub royalty_basis_points: u16,
+    pub status: MintJobItemStatus,
+    pub transaction_id: Option<Bytes32>,
+    pub launcher_id: Option<Bytes32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewMintJobItem {
+    pub row_index: u32,
+    pub metadata: Program,
+    pub p2_puzzle_hash: Option<Bytes32>,
+    pub royalty_puzzle_hash: Option<Bytes32>,
+    pub royalty_basis_points: u16,
+}
+
+impl MintJob {
+    /// Whether every row of the manifest has been minted.
+    pub fn is_complete(&self) -> bool {
+        self.minted_count == self.item_count
+    }
+}
+
+impl Database {
+    pub async fn mint_jobs(&self) -> Result<Vec<MintJob>> {
+        mint_jobs(&self.pool).await
+    }
+
+    pub async fn mint_job(&self, id: u32) -> Result<Option<MintJob>> {
+        mint_job(&self.pool, id).await
+    }
+
+    pub async fn mint_job_items(
+        &self,
+        mint_job_id: u32,
+        status: Option<MintJobItemStatus>,
+        limit: Option<u32>,
+    ) -> Result<Vec<MintJobItem>> {
+        mint_job_items(&self.pool, mint_job_id, status, limit).await
+    }
+
+    pub async fn set_mint_job_active(&self, id: u32, is_active: bool) -> Result<()> {
+        set_mint_job_active(&self.pool, id, is_active).await
+    }
+
+    pub async fn delete_mint_job(&self, id: u32) -> Result<()> {
+        delete_mint_job(&self.pool, id).await
+    }
+
+    pub async fn complete_mint_job_items(&self, transaction_id: Bytes32) -> Result<()> {
+        complete_mint_job_items(&self.pool, transaction_id).await
+    }
+
+    pub async fn reset_mint_job_items(&self, transaction_id: Bytes32) -> Result<()> {
+        reset_mint_job_items(&self.pool, transaction_id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_mint_job(&mut self, mint_job: NewMintJob) -> Result<u32> {
+        insert_mint_job(&mut *self.tx, mint_job).await
+    }
+
+    pub async fn insert_mint_job_item(
+        &mut self,
+        mint_job_id: u32,
+        item: NewMintJobItem,
+    ) -> Result<()> {
+        insert_mint_job_item(&mut *self.tx, mint_job_id, item).await
+    }
+
+    pub async fn submit_mint_job_item(
+        &mut self,
+        mint_job_id: u32,
+        row_index: u32,
+        transaction_id: Bytes32,
+        launcher_id: Bytes32,
+    ) -> Result<()> {
+        submit_mint_job_item(
+            &mut *self.tx,
+            mint_job_id,
+            row_index,
+            transaction_id,
+            launcher_id,
+        )
+        .await
+    }
+}
+
+fn mint_job_item_status(status: i64) -> Result<MintJobItemStatus> {
+    Ok(match status {
+        0 => MintJobItemStatus::Pending,
+        1 => MintJobItemStatus::Submitted,
+        2 => MintJobItemStatus::Minted,
+        _ => return Err(DatabaseError::InvalidEnumVariant),
+    })
+}
+
+async fn mint_jobs(conn: impl SqliteExecutor<'_>) -> Result<Vec<MintJob>> {
+    query!(
+        "
+        SELECT
+            id, did_hash, fee, batch_size, is_active, created_timestamp,
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_id = mint_jobs.id
+            ) AS 'item_count!: i64',
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_id = mint_jobs.id AND status = 1
+            ) AS 'submitted_count!: i64',
+            (
+                SELECT COUNT(*) FROM mint_job_items
+                WHERE mint_job_id = mint_jobs.id AND status = 2
+            ) AS 'minted_count!: i64'
+        FROM mint_jobs
+        ORDER BY id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(MintJob {
+            id: row.id.convert()?,
+            did_id: row.did_hash.convert()?,
+            fee: row.fee.convert()?,
+            batch_size: row.batch_size.convert()?,
+            is_active: row.is_active,
+            created_timestamp: row.created_timestamp.convert()?,
+            item_count: row.item_count.convert()?,
+            submitted_count: row.submitted_count.convert()?,
+            minted_count: row.minted_count.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn mint_job(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<MintJob>> {
+    Ok(mint_jobs(conn)
+        .await?
+        .into_iter()
+        .find(|mint_job| mint_job.id == id))
+}
+
+async fn mint_job_items(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+    status: Option<MintJobItemStatus>,
+    limit: Option<u32>,
+) -> Result<Vec<MintJobItem>> {
+    let status = status.map(|status| status as u8);
+
+    // A negative limit means there is no limit in SQLite.
+    let limit = limit.map_or(-1, i64::from);
+
+    query!(
+        "
+        SELECT
+            mint_job_id, row_index, metadata, p2_puzzle_hash, royalty_puzzle_hash,
+            royalty_basis_points, status, transaction_hash, launcher_hash
+        FROM mint_job_items
+        WHERE mint_job_id = ? AND (? IS NULL OR status = ?)
+        ORDER BY row_index ASC
+        LIMIT ?
+        ",
+        mint_job_id,
+        status,
+        status,
+        limit
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(MintJobItem {
+            mint_job_id: row.mint_job_id.convert()?,
+            row_index: row.row_index.convert()?,
+            metadata: Program::from(row.metadata),
+            p2_puzzle_hash: row.p2_puzzle_hash.convert()?,
+            royalty_puzzle_hash: row.royalty_puzzle_hash.convert()?,
+            royalty_basis_points: row.royalty_basis_points.convert()?,
+            status: mint_job_item_status(row.status)?,
+            transaction_id: row.transaction_hash.convert()?,
+            launcher_id: row.launcher_hash.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn insert_mint_job(conn: impl SqliteExecutor<'_>, mint_job: NewMintJob) -> Result<u32> {
+    let did_id = mint_job.did_id.as_ref();
+    let fee = mint_job.fee.to_be_bytes().to_vec();
+
+    let id = query!(
+        "
+        INSERT INTO mint_jobs (did_hash, fee, batch_size)
+        VALUES (?, ?, ?)
+        RETURNING id
+        ",
+        did_id,
+        fee,
+        mint_job.batch_size
+    )
+    .fetch_one(conn)
+    .await?
+    .id;
+
+    id.convert()
+}
+
+async fn insert_mint_job_item(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+    item: NewMintJobItem,
+) -> Result<()> {
+    let metadata = item.metadata.as_ref();
+    let p2_puzzle_hash = item.p2_puzzle_hash.as_deref();
+    let royalty_puzzle_hash = item.royalty_puzzle_hash.as_deref();
+
+    query!(
+        "
+        INSERT INTO mint_job_items (
+            mint_job_id, row_index, metadata, p2_puzzle_hash,
+            royalty_puzzle_hash, royalty_basis_points
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        ",
+        mint_job_id,
+        item.row_index,
+        metadata,
+        p2_puzzle_hash,
+        royalty_puzzle_hash,
+        item.royalty_basis_points
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_mint_job_active(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    is_active: bool,
+) -> Result<()> {
+    query!(
+        "UPDATE mint_jobs SET is_active = ? WHERE id = ?",
+        is_active,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_mint_job(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    query!("DELETE FROM mint_jobs WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn submit_mint_job_item(
+    conn: impl SqliteExecutor<'_>,
+    mint_job_id: u32,
+    row_index: u32,
+    transaction_id: Bytes32,
+    launcher_id: Bytes32,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+    let launcher_id = launcher_id.as_ref();
+    let status = MintJobItemStatus::Submitted as u8;
+
+    query!(
+        "
+        UPDATE mint_job_items SET status = ?, transaction_hash = ?, launcher_hash = ?
+        WHERE mint_job_id = ? AND row_index = ?
+        ",
+        status,
+        transaction_id,
+        launcher_id,
+        mint_job_id,
+        row_index
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn complete_mint_job_items(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+    let status = MintJobItemStatus::Minted as u8;
+
+    query!(
+        "UPDATE mint_job_items SET status = ? WHERE transaction_hash = ?",
+        status,
+        transaction_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn reset_mint_job_items(
+    conn: impl SqliteExecutor<'_>,
+    transaction_id: Bytes32,
+) -> Result<()> {
+    let transaction_id = transaction_id.as_ref();
+    let status = MintJobItemStatus::Pending as u8;
+
+    query!(
+        "
+        UPDATE mint_job_items SET status = ?, transaction_hash = NULL, launcher_hash = NULL
+        WHERE transaction_hash = ?
+        ",
+        status,
+        transaction_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{Asset, Convert, Database, DatabaseTx, Result};
+
+/// A received coin which hasn't been checked for whether it's a royalty payment yet.
+#[derive(Debug, Clone, Copy)]
+pub struct RoyaltyCandidate {
+    pub coin: Coin,
+    pub asset_id: Bytes32,
+    pub hidden_puzzle_hash: Option<Bytes32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoyaltyPayment {
+    pub coin: Coin,
+    pub asset: Asset,
+    pub launcher_id: Bytes32,
+    pub nft_name: Option<String>,
+    pub collection_id: Option<Bytes32>,
+    pub collection_name: Option<String>,
+    pub created_height: Option<u32>,
+    pub created_timestamp: Option<u64>,
+}
+
+impl Database {
+    pub async fn unchecked_royalty_coins(&self, limit: u32) -> Result<Vec<RoyaltyCandidate>> {
+        unchecked_royalty_coins(&self.pool, limit).await
+    }
+
+    pub async fn royalty_payments(&self) -> Result<Vec<RoyaltyPayment>> {
+        royalty_payments(&self.pool).await
+    }
+
+    pub async fn royalty_payment_nft(&self, coin_id: Bytes32) -> Result<Option<Bytes32>> {
+        royalty_payment_nft(&self.pool, coin_id).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_royalty_payment(
+        &mut self,
+        coin_id: Bytes32,
+        launcher_id: Bytes32,
+    ) -> Result<()> {
+        insert_royalty_payment(&mut *self.tx, coin_id, launcher_id).await
+    }
+
+    pub async fn set_royalty_checked(&mut self, coin_id: Bytes32) -> Result<()> {
+        set_royalty_checked(&mut *self.tx, coin_id).await
+    }
+
+    pub async fn record_royalty_check_failure(
+        &mut self,
+        coin_id: Bytes32,
+        max_failures: u32,
+    ) -> Result<()> {
+        record_royalty_check_failure(&mut *self.tx, coin_id, max_failures).await
+    }
+}
+
+async fn unchecked_royalty_coins(
+    conn: impl SqliteExecutor<'_>,
+    limit: u32,
+) -> Result<Vec<RoyaltyCandidate>> {
+    query!(
+        "
+        SELECT
+            coins.parent_coin_hash, coins.puzzle_hash, coins.amount,
+            assets.hash AS asset_hash, assets.hidden_puzzle_hash
+        FROM coins
+        INNER JOIN assets ON assets.id = coins.asset_id
+        WHERE coins.is_royalty_checked = FALSE
+            AND coins.p2_puzzle_id IS NOT NULL
+            AND coins.created_height IS NOT NULL
+            AND assets.kind = 0
+        ORDER BY coins.royalty_check_failures ASC, coins.created_height DESC
+        LIMIT ?
+        ",
+        limit
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(RoyaltyCandidate {
+            coin: Coin::new(
+                row.parent_coin_hash.convert()?,
+                row.puzzle_hash.convert()?,
+                row.amount.convert()?,
+            ),
+            asset_id: row.asset_hash.convert()?,
+            hidden_puzzle_hash: row.hidden_puzzle_hash.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn royalty_payments(conn: impl SqliteExecutor<'_>) -> Result<Vec<RoyaltyPayment>> {
+    query!(
+        "
+        SELECT
+            coins.parent_coin_hash, coins.puzzle_hash, coins.amount, coins.created_height,
+            blocks.timestamp AS created_timestamp, royalty_payments.nft_hash,
+            assets.hash AS asset_hash, assets.name AS asset_name, assets.ticker AS asset_ticker,
+            assets.precision AS asset_precision, assets.icon_url AS asset_icon_url,
+            assets.description AS asset_description,
+            assets.is_sensitive_content AS asset_is_sensitive_content,
+            assets.is_visible AS asset_is_visible, assets.kind AS asset_kind,
+            assets.hidden_puzzle_hash AS asset_hidden_puzzle_hash,
+            nft_assets.name AS nft_name, collections.hash AS collection_hash,
+            collections.name AS collection_name
+        FROM royalty_payments
+        INNER JOIN coins ON coins.id = royalty_payments.coin_id
+        INNER JOIN assets ON assets.id = coins.asset_id
+        LEFT JOIN blocks ON blocks.height = coins.created_height
+        LEFT JOIN assets AS nft_assets ON nft_assets.hash = royalty_payments.nft_hash
+        LEFT JOIN nfts ON nfts.asset_id = nft_assets.id
+        LEFT JOIN collections ON collections.id = nfts.collection_id
+        ORDER BY coins.created_height ASC, royalty_payments.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(RoyaltyPayment {
+            coin: Coin::new(
+                row.parent_coin_hash.convert()?,
+                row.puzzle_hash.convert()?,
+                row.amount.convert()?,
+            ),
+            asset: Asset {
+                hash: row.asset_hash.convert()?,
+                name: row.asset_name,
+                ticker: row.asset_ticker,
+                precision: row.asset_precision.convert()?,
+                icon_url: row.asset_icon_url,
+                description: row.asset_description,
+                is_sensitive_content: row.asset_is_sensitive_content,
+                is_visible: row.asset_is_visible,
+                kind: row.asset_kind.convert()?,
+                hidden_puzzle_hash: row.asset_hidden_puzzle_hash.convert()?,
+            },
+            launcher_id: row.nft_hash.convert()?,
+            nft_name: row.nft_name,
+            collection_id: row.collection_hash.convert()?,
+            collection_name: row.collection_name,
+            created_height: row.created_height.convert()?,
+            created_timestamp: row.created_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn royalty_payment_nft(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+) -> Result<Option<Bytes32>> {
+    let coin_id = coin_id.as_ref();
+
+    query!(
+        "
+        SELECT nft_hash FROM royalty_payments
+        INNER JOIN coins ON coins.id = royalty_payments.coin_id
+        WHERE coins.hash = ?
+        ",
+        coin_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .map(|row| row.nft_hash.convert())
+    .transpose()
+}
+
+async fn insert_royalty_payment(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+    launcher_id: Bytes32,
+) -> Result<()> {
+    let coin_id = coin_id.as_ref();
+    let launcher_id = launcher_id.as_ref();
+
+    query!(
+        "
+        INSERT OR IGNORE INTO royalty_payments (coin_id, nft_hash)
+        VALUES ((SELECT id FROM coins WHERE hash = ?), ?)
+        ",
+        coin_id,
+        launcher_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn set_royalty_checked(conn: impl SqliteExecutor<'_>, coin_id: Bytes32) -> Result<()> {
+    let coin_id = coin_id.as_ref();
+
+    query!(
+        "UPDATE coins SET is_royalty_checked = TRUE WHERE hash = ?",
+        coin_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Coins which failed to be checked are retried after the others, and are marked as checked
+/// once they have failed too many times so that they don't keep filling up the batch.
+async fn record_royalty_check_failure(
+    conn: impl SqliteExecutor<'_>,
+    coin_id: Bytes32,
+    max_failures: u32,
+) -> Result<()> {
+    let coin_id = coin_id.as_ref();
+
+    query!(
+        "
+        UPDATE coins SET
+            royalty_check_failures = royalty_check_failures + 1,
+            is_royalty_checked = royalty_check_failures + 1 >= ?
+        WHERE hash = ?
+        ",
+        max_failures,
+        coin_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,334 @@
+use chia_wallet_sdk::prelude::*;
+use sqlx::{SqliteExecutor, query};
+
+use crate::{Convert, Database, DatabaseError, DatabaseTx, OfferStatus, Result};
+
+#[derive(Debug, Clone, Copy)]
+pub struct StandingOffer {
+    pub id: u32,
+    pub offered_asset_id: Bytes32,
+    pub offered_amount: u64,
+    pub requested_asset_id: Bytes32,
+    pub requested_amount: u64,
+    pub fee: u64,
+    pub expiration_seconds: Option<u64>,
+    pub max_fills: Option<u32>,
+    pub max_spend: Option<u64>,
+    pub is_active: bool,
+    pub created_timestamp: u64,
+    pub fill_count: u32,
+    pub current_offer_id: Option<Bytes32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NewStandingOffer {
+    pub offered_asset_id: Bytes32,
+    pub offered_amount: u64,
+    pub requested_asset_id: Bytes32,
+    pub requested_amount: u64,
+    pub fee: u64,
+    pub expiration_seconds: Option<u64>,
+    pub max_fills: Option<u32>,
+    pub max_spend: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StandingOfferHistory {
+    pub standing_offer_id: u32,
+    pub offer_id: Bytes32,
+    pub status: OfferStatus,
+    pub created_timestamp: u64,
+    pub finished_timestamp: Option<u64>,
+}
+
+impl StandingOffer {
+    /// The amount spent by each fill. The fee is paid in XCH, so it only counts towards the
+    /// spend cap when XCH is the offered asset.
+    pub fn fill_cost(&self) -> u64 {
+        if self.offered_asset_id == Bytes32::default() {
+            self.offered_amount.saturating_add(self.fee)
+        } else {
+            self.offered_amount
+        }
+    }
+
+    pub fn total_spent(&self) -> u64 {
+        u64::from(self.fill_count).saturating_mul(self.fill_cost())
+    }
+
+    /// Whether another offer can be created without exceeding the fill or spend caps.
+    pub fn has_capacity(&self) -> bool {
+        let within_fills = self
+            .max_fills
+            .is_none_or(|max_fills| self.fill_count < max_fills);
+
+        let within_spend = self.max_spend.is_none_or(|max_spend| {
+            u64::from(self.fill_count + 1).saturating_mul(self.fill_cost()) <= max_spend
+        });
+
+        within_fills && within_spend
+    }
+}
+
+impl Database {
+    pub async fn standing_offers(&self) -> Result<Vec<StandingOffer>> {
+        standing_offers(&self.pool).await
+    }
+
+    pub async fn standing_offer(&self, id: u32) -> Result<Option<StandingOffer>> {
+        standing_offer(&self.pool, id).await
+    }
+
+    pub async fn standing_offer_history(
+        &self,
+        standing_offer_id: Option<u32>,
+    ) -> Result<Vec<StandingOfferHistory>> {
+        standing_offer_history(&self.pool, standing_offer_id).await
+    }
+
+    pub async fn insert_standing_offer(&self, standing_offer: NewStandingOffer) -> Result<u32> {
+        insert_standing_offer(&self.pool, standing_offer).await
+    }
+
+    pub async fn set_standing_offer_active(&self, id: u32, is_active: bool) -> Result<()> {
+        set_standing_offer_active(&self.pool, id, is_active).await
+    }
+
+    pub async fn delete_standing_offer(&self, id: u32) -> Result<()> {
+        delete_standing_offer(&self.pool, id).await
+    }
+
+    pub async fn finish_standing_offer_history(
+        &self,
+        offer_id: Bytes32,
+        status: OfferStatus,
+        timestamp: u64,
+    ) -> Result<()> {
+        finish_standing_offer_history(&self.pool, offer_id, status, timestamp).await
+    }
+}
+
+impl DatabaseTx<'_> {
+    pub async fn insert_standing_offer_history(
+        &mut self,
+        standing_offer_id: u32,
+        offer_id: Bytes32,
+        timestamp: u64,
+    ) -> Result<()> {
+        insert_standing_offer_history(&mut *self.tx, standing_offer_id, offer_id, timestamp).await
+    }
+}
+
+fn offer_status(status: i64) -> Result<OfferStatus> {
+    Ok(match status {
+        0 => OfferStatus::Pending,
+        1 => OfferStatus::Active,
+        2 => OfferStatus::Completed,
+        3 => OfferStatus::Cancelled,
+        4 => OfferStatus::Expired,
+        _ => return Err(DatabaseError::InvalidEnumVariant),
+    })
+}
+
+async fn standing_offers(conn: impl SqliteExecutor<'_>) -> Result<Vec<StandingOffer>> {
+    query!(
+        "
+        SELECT
+            standing_offers.id, offered.hash AS offered_asset_id, offered_amount,
+            requested.hash AS requested_asset_id, requested_amount, fee,
+            expiration_seconds, max_fills, max_spend, is_active, created_timestamp,
+            (
+                SELECT COUNT(*) FROM standing_offer_history
+                WHERE standing_offer_id = standing_offers.id AND status = 2
+            ) AS 'fill_count!: i64',
+            (
+                SELECT offer_hash FROM standing_offer_history
+                WHERE standing_offer_id = standing_offers.id AND finished_timestamp IS NULL
+                ORDER BY standing_offer_history.id DESC
+                LIMIT 1
+            ) AS 'current_offer_id?: Vec<u8>'
+        FROM standing_offers
+        INNER JOIN assets AS offered ON offered.id = standing_offers.offered_asset_id
+        INNER JOIN assets AS requested ON requested.id = standing_offers.requested_asset_id
+        ORDER BY standing_offers.id ASC
+        "
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(StandingOffer {
+            id: row.id.convert()?,
+            offered_asset_id: row.offered_asset_id.convert()?,
+            offered_amount: row.offered_amount.convert()?,
+            requested_asset_id: row.requested_asset_id.convert()?,
+            requested_amount: row.requested_amount.convert()?,
+            fee: row.fee.convert()?,
+            expiration_seconds: row.expiration_seconds.convert()?,
+            max_fills: row.max_fills.convert()?,
+            max_spend: row.max_spend.convert()?,
+            is_active: row.is_active,
+            created_timestamp: row.created_timestamp.convert()?,
+            fill_count: row.fill_count.convert()?,
+            current_offer_id: row.current_offer_id.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn standing_offer(conn: impl SqliteExecutor<'_>, id: u32) -> Result<Option<StandingOffer>> {
+    Ok(standing_offers(conn)
+        .await?
+        .into_iter()
+        .find(|standing_offer| standing_offer.id == id))
+}
+
+async fn standing_offer_history(
+    conn: impl SqliteExecutor<'_>,
+    standing_offer_id: Option<u32>,
+) -> Result<Vec<StandingOfferHistory>> {
+    query!(
+        "
+        SELECT standing_offer_id, offer_hash, status, created_timestamp, finished_timestamp
+        FROM standing_offer_history
+        WHERE ? IS NULL OR standing_offer_id = ?
+        ORDER BY id DESC
+        ",
+        standing_offer_id,
+        standing_offer_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(StandingOfferHistory {
+            standing_offer_id: row.standing_offer_id.convert()?,
+            offer_id: row.offer_hash.convert()?,
+            status: offer_status(row.status)?,
+            created_timestamp: row.created_timestamp.convert()?,
+            finished_timestamp: row.finished_timestamp.convert()?,
+        })
+    })
+    .collect()
+}
+
+async fn insert_standing_offer(
+    conn: impl SqliteExecutor<'_>,
+    standing_offer: NewStandingOffer,
+) -> Result<u32> {
+    let offered_asset_id = standing_offer.offered_asset_id.as_ref();
+    let offered_amount = standing_offer.offered_amount.to_be_bytes().to_vec();
+    let requested_asset_id = standing_offer.requested_asset_id.as_ref();
+    let requested_amount = standing_offer.requested_amount.to_be_bytes().to_vec();
+    let fee = standing_offer.fee.to_be_bytes().to_vec();
+    let expiration_seconds: Option<i64> = standing_offer
+        .expiration_seconds
+        .map(TryInto::try_into)
+        .transpose()?;
+    let max_spend = standing_offer
+        .max_spend
+        .map(|max_spend| max_spend.to_be_bytes().to_vec());
+
+    let id = query!(
+        "
+        INSERT INTO standing_offers (
+            offered_asset_id, offered_amount, requested_asset_id, requested_amount,
+            fee, expiration_seconds, max_fills, max_spend
+        )
+        VALUES (
+            (SELECT id FROM assets WHERE hash = ?), ?,
+            (SELECT id FROM assets WHERE hash = ?), ?,
+            ?, ?, ?, ?
+        )
+        RETURNING id
+        ",
+        offered_asset_id,
+        offered_amount,
+        requested_asset_id,
+        requested_amount,
+        fee,
+        expiration_seconds,
+        standing_offer.max_fills,
+        max_spend
+    )
+    .fetch_one(conn)
+    .await?
+    .id;
+
+    id.convert()
+}
+
+async fn set_standing_offer_active(
+    conn: impl SqliteExecutor<'_>,
+    id: u32,
+    is_active: bool,
+) -> Result<()> {
+    query!(
+        "UPDATE standing_offers SET is_active = ? WHERE id = ?",
+        is_active,
+        id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_standing_offer(conn: impl SqliteExecutor<'_>, id: u32) -> Result<()> {
+    query!("DELETE FROM standing_offers WHERE id = ?", id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_standing_offer_history(
+    conn: impl SqliteExecutor<'_>,
+    standing_offer_id: u32,
+    offer_id: Bytes32,
+    timestamp: u64,
+) -> Result<()> {
+    let offer_id = offer_id.as_ref();
+    let timestamp: i64 = timestamp.try_into()?;
+    let status = OfferStatus::Active as u8;
+
+    query!(
+        "
+        INSERT INTO standing_offer_history (standing_offer_id, offer_hash, status, created_timestamp)
+        VALUES (?, ?, ?, ?)
+        ",
+        standing_offer_id,
+        offer_id,
+        status,
+        timestamp
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn finish_standing_offer_history(
+    conn: impl SqliteExecutor<'_>,
+    offer_id: Bytes32,
+    status: OfferStatus,
+    timestamp: u64,
+) -> Result<()> {
+    let offer_id = offer_id.as_ref();
+    let timestamp: i64 = timestamp.try_into()?;
+    let status = status as u8;
+
+    query!(
+        "
+        UPDATE standing_offer_history SET status = ?, finished_timestamp = ?
+        WHERE offer_hash = ?
+        ",
+        status,
+        timestamp,
+        offer_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
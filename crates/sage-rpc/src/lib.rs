@@ -1,28 +1,35 @@
 mod cert_verifier;
+mod metrics;
 mod openapi;
 mod rustls_config;
 
 #[cfg(test)]
 mod tests;
 
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::SocketAddr,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
 
 use anyhow::Result;
 use axum::{
     Json, Router,
-    extract::State,
+    extract::{Query, State},
     http::StatusCode,
+    middleware,
     response::{IntoResponse, Response},
-    routing::post,
+    routing::{get, post},
 };
 
 use axum_server::tls_rustls::RustlsConfig;
+use metrics::{RequestMetrics, record_request};
 use rustls_config::load_rustls_config;
 use sage::Sage;
 use sage_api::ErrorKind;
 use sage_api_macro::impl_endpoints;
-use serde::Serialize;
-use tokio::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, MutexGuard};
 use tracing::info;
 
 // Re-export for CLI usage
@@ -31,11 +38,50 @@ pub use openapi::generate_openapi as generate_openapi_spec;
 #[derive(Debug, Clone)]
 struct AppState {
     sage: Arc<Mutex<Sage>>,
+    metrics: Arc<RequestMetrics>,
+}
+
+/// Selects which loaded wallet a request applies to, instead of the logged in wallet.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+struct WalletScope {
+    fingerprint: Option<u32>,
+}
+
+/// Holds the lock on the app for the duration of a request, and clears the wallet scope
+/// again when the request finishes or is cancelled.
+struct ScopedSage<'a>(MutexGuard<'a, Sage>);
+
+impl<'a> ScopedSage<'a> {
+    fn new(mut sage: MutexGuard<'a, Sage>, scope: WalletScope) -> Self {
+        sage.scope_wallet(scope.fingerprint);
+        Self(sage)
+    }
+}
+
+impl Deref for ScopedSage<'_> {
+    type Target = Sage;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ScopedSage<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Drop for ScopedSage<'_> {
+    fn drop(&mut self) {
+        self.0.scope_wallet(None);
+    }
 }
 
 impl_endpoints! {
-    (repeat async fn endpoint(State(state): State<AppState>, Json(req): Json<sage_api::Endpoint>) -> Response {
-        handle(state.sage.lock().await.endpoint(req) maybe_await)
+    (repeat async fn endpoint(State(state): State<AppState>, Query(scope): Query<WalletScope>, Json(req): Json<sage_api::Endpoint>) -> Response {
+        let mut sage = ScopedSage::new(state.sage.lock().await, scope);
+        handle(sage.endpoint(req) maybe_await)
     })
 
     fn api_router() -> Router<AppState> {
@@ -96,5 +142,16 @@ pub async fn start_rpc(sage: Arc<Mutex<Sage>>) -> Result<()> {
 }
 
 pub fn make_router(sage: Arc<Mutex<Sage>>) -> Router {
-    api_router().with_state(AppState { sage })
+    let state = AppState {
+        sage,
+        metrics: Arc::default(),
+    };
+
+    api_router()
+        .route("/metrics", get(metrics::metrics))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            record_request,
+        ))
+        .with_state(state)
 }
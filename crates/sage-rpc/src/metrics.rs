@@ -0,0 +1,337 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+    sync::{Arc, Mutex, PoisonError, atomic::Ordering},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sage_wallet::Wallet;
+
+use crate::AppState;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct EndpointStats {
+    requests: u64,
+    duration: Duration,
+}
+
+/// Request counts and latencies for each endpoint since the RPC server started.
+#[derive(Debug, Default)]
+pub(crate) struct RequestMetrics {
+    endpoints: Mutex<BTreeMap<String, EndpointStats>>,
+}
+
+impl RequestMetrics {
+    fn record(&self, endpoint: &str, duration: Duration) {
+        let mut endpoints = self
+            .endpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let stats = endpoints.entry(endpoint.to_string()).or_default();
+
+        stats.requests += 1;
+        stats.duration += duration;
+    }
+
+    fn endpoints(&self) -> Vec<(String, EndpointStats)> {
+        let endpoints = self
+            .endpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        endpoints
+            .iter()
+            .map(|(endpoint, stats)| (endpoint.clone(), *stats))
+            .collect()
+    }
+}
+
+/// Only requests which matched a route are recorded, so that unknown paths can't add labels.
+pub(crate) async fn record_request(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let endpoint = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().trim_start_matches('/').to_string());
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    if let Some(endpoint) = endpoint {
+        state.metrics.record(&endpoint, start.elapsed());
+    }
+
+    response
+}
+
+pub(crate) async fn metrics(State(state): State<AppState>) -> Response {
+    // The wallet databases are queried after releasing the lock, so a scrape doesn't hold up
+    // other requests.
+    let (config, peer_state, mut wallets) = {
+        let sage = state.sage.lock().await;
+
+        let wallets: Vec<Arc<Wallet>> = sage
+            .wallet
+            .iter()
+            .chain(sage.background_wallets.values())
+            .cloned()
+            .collect();
+
+        (sage.config.rpc.metrics, sage.peer_state.clone(), wallets)
+    };
+
+    if !config.enabled {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    wallets.sort_unstable_by_key(|wallet| wallet.fingerprint);
+    wallets.dedup_by_key(|wallet| wallet.fingerprint);
+
+    let (peers, peak) = {
+        let state = peer_state.lock().await;
+        (state.peers().len(), state.peak())
+    };
+
+    let snapshot = Snapshot {
+        peers,
+        peak_height: peak.map(|(height, _)| height),
+        balances: config.balances,
+    };
+
+    match render(&snapshot, &wallets, &state.metrics).await {
+        Ok(body) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    peers: usize,
+    peak_height: Option<u32>,
+    balances: bool,
+}
+
+#[derive(Debug)]
+struct WalletMetrics {
+    fingerprint: String,
+    synced_height: Option<u32>,
+    mempool_items: usize,
+    transaction_retries: u64,
+    database_size: i64,
+    nft_uri_backlog: u64,
+    balances: Vec<(String, u128)>,
+}
+
+async fn render(
+    snapshot: &Snapshot,
+    handles: &[Arc<Wallet>],
+    requests: &RequestMetrics,
+) -> sage::Result<String> {
+    let mut wallets = Vec::with_capacity(handles.len());
+
+    for wallet in handles {
+        let stats = wallet.db.get_database_stats().await?;
+
+        let mut balances = Vec::new();
+
+        if snapshot.balances {
+            balances.push(("xch".to_string(), wallet.db.xch_balance().await?));
+
+            for (asset_id, balance) in wallet.db.cat_balances().await? {
+                balances.push((asset_id.to_string(), balance));
+            }
+        }
+
+        wallets.push(WalletMetrics {
+            fingerprint: wallet.fingerprint.to_string(),
+            synced_height: wallet.db.latest_peak().await?.map(|(height, _)| height),
+            mempool_items: wallet.db.mempool_items().await?.len(),
+            transaction_retries: wallet.transaction_retries.load(Ordering::Relaxed),
+            database_size: stats.database_size_bytes,
+            nft_uri_backlog: wallet
+                .db
+                .total_files()
+                .await?
+                .saturating_sub(wallet.db.checked_files().await?),
+            balances,
+        });
+    }
+
+    let mut out = MetricsWriter::default();
+
+    out.family("sage_peers", "gauge", "Connected full node peers.");
+    out.sample("sage_peers", &[], snapshot.peers);
+
+    if let Some(height) = snapshot.peak_height {
+        out.family(
+            "sage_peak_height",
+            "gauge",
+            "Highest peak reported by a connected peer.",
+        );
+        out.sample("sage_peak_height", &[], height);
+    }
+
+    out.family(
+        "sage_synced_height",
+        "gauge",
+        "Height of the latest peak the wallet has synced to.",
+    );
+    for wallet in &wallets {
+        if let Some(height) = wallet.synced_height {
+            out.sample(
+                "sage_synced_height",
+                &[("fingerprint", wallet.fingerprint.as_str())],
+                height,
+            );
+        }
+    }
+
+    if snapshot.balances {
+        out.family(
+            "sage_balance_mojos",
+            "gauge",
+            "Balance of each asset, in mojos.",
+        );
+        for wallet in &wallets {
+            for (asset_id, balance) in &wallet.balances {
+                out.sample(
+                    "sage_balance_mojos",
+                    &[
+                        ("fingerprint", wallet.fingerprint.as_str()),
+                        ("asset_id", asset_id.as_str()),
+                    ],
+                    balance,
+                );
+            }
+        }
+    }
+
+    out.family(
+        "sage_mempool_items",
+        "gauge",
+        "Transactions which have been submitted but haven't confirmed yet.",
+    );
+    for wallet in &wallets {
+        out.sample(
+            "sage_mempool_items",
+            &[("fingerprint", wallet.fingerprint.as_str())],
+            wallet.mempool_items,
+        );
+    }
+
+    out.family(
+        "sage_transaction_retries_total",
+        "counter",
+        "Transaction submissions which had to be retried.",
+    );
+    for wallet in &wallets {
+        out.sample(
+            "sage_transaction_retries_total",
+            &[("fingerprint", wallet.fingerprint.as_str())],
+            wallet.transaction_retries,
+        );
+    }
+
+    out.family(
+        "sage_database_size_bytes",
+        "gauge",
+        "Size of the wallet database.",
+    );
+    for wallet in &wallets {
+        out.sample(
+            "sage_database_size_bytes",
+            &[("fingerprint", wallet.fingerprint.as_str())],
+            wallet.database_size,
+        );
+    }
+
+    out.family(
+        "sage_nft_uri_backlog",
+        "gauge",
+        "NFT files which haven't been checked yet.",
+    );
+    for wallet in &wallets {
+        out.sample(
+            "sage_nft_uri_backlog",
+            &[("fingerprint", wallet.fingerprint.as_str())],
+            wallet.nft_uri_backlog,
+        );
+    }
+
+    let endpoints = requests.endpoints();
+
+    out.family(
+        "sage_rpc_requests_total",
+        "counter",
+        "Requests handled by each endpoint.",
+    );
+    for (endpoint, stats) in &endpoints {
+        out.sample(
+            "sage_rpc_requests_total",
+            &[("endpoint", endpoint.as_str())],
+            stats.requests,
+        );
+    }
+
+    out.family(
+        "sage_rpc_request_duration_seconds",
+        "summary",
+        "Time spent handling requests to each endpoint.",
+    );
+    for (endpoint, stats) in &endpoints {
+        out.sample(
+            "sage_rpc_request_duration_seconds_sum",
+            &[("endpoint", endpoint.as_str())],
+            stats.duration.as_secs_f64(),
+        );
+        out.sample(
+            "sage_rpc_request_duration_seconds_count",
+            &[("endpoint", endpoint.as_str())],
+            stats.requests,
+        );
+    }
+
+    Ok(out.0)
+}
+
+/// Writes metrics in the Prometheus text exposition format.
+#[derive(Debug, Default)]
+struct MetricsWriter(String);
+
+impl MetricsWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        writeln!(self.0, "# HELP {name} {help}").expect("writing to a string can't fail");
+        writeln!(self.0, "# TYPE {name} {kind}").expect("writing to a string can't fail");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.0.push_str(name);
+
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect();
+
+            write!(self.0, "{{{}}}", labels.join(",")).expect("writing to a string can't fail");
+        }
+
+        writeln!(self.0, " {value}").expect("writing to a string can't fail");
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
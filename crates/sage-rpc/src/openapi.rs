@@ -1,8 +1,9 @@
 use indexmap::IndexMap;
 use std::collections::BTreeSet;
 use utoipa::openapi::{
-    ComponentsBuilder, ContentBuilder, InfoBuilder, OpenApi, PathsBuilder, RefOr, ResponsesBuilder,
-    path::{HttpMethod, OperationBuilder, PathItemBuilder},
+    ComponentsBuilder, ContentBuilder, InfoBuilder, OpenApi, PathsBuilder, RefOr, Required,
+    ResponsesBuilder,
+    path::{HttpMethod, OperationBuilder, ParameterBuilder, ParameterIn, PathItemBuilder},
     request_body::RequestBodyBuilder,
     response::ResponseBuilder,
     schema::{ObjectBuilder, Schema, SchemaType, Type},
@@ -60,12 +61,27 @@ pub fn generate_openapi() -> OpenApi {
         .schema_from::<sage_api::Unit>()
         .schema_from::<sage_api::CoinRecord>()
         .schema_from::<sage_api::TokenRecord>()
+        .schema_from::<sage_api::IssuedCatRecord>()
+        .schema_from::<sage_api::CatIssuanceKind>()
+        .schema_from::<sage_api::CatHolderRecord>()
         .schema_from::<sage_api::DidRecord>()
+        .schema_from::<sage_api::DidAttestation>()
         .schema_from::<sage_api::NftRecord>()
         .schema_from::<sage_api::NftCollectionRecord>()
         .schema_from::<sage_api::OptionRecord>()
+        .schema_from::<sage_api::OptionChainGroup>()
+        .schema_from::<sage_api::OptionChainExpiration>()
+        .schema_from::<sage_api::OptionChainRecord>()
+        .schema_from::<sage_api::OptionStatus>()
         .schema_from::<sage_api::TransactionRecord>()
         .schema_from::<sage_api::PendingTransactionRecord>()
+        .schema_from::<sage_api::ClawbackRecord>()
+        .schema_from::<sage_api::ClawbackPolicy>()
+        .schema_from::<sage_api::ReputationSource>()
+        .schema_from::<sage_api::CoinManagement>()
+        .schema_from::<sage_api::CoinPolicy>()
+        .schema_from::<sage_api::SplitPolicy>()
+        .schema_from::<sage_api::CombinePolicy>()
         .schema_from::<sage_api::DerivationRecord>()
         .schema_from::<sage_api::PeerRecord>()
         .schema_from::<sage_api::KeyInfo>()
@@ -80,7 +96,10 @@ pub fn generate_openapi() -> OpenApi {
         .schema_from::<sage_api::CoinFilterMode>()
         .schema_from::<sage_api::OptionSortMode>()
         .schema_from::<sage_api::NftSortMode>()
+        .schema_from::<sage_api::NftVerificationFilter>()
         .schema_from::<sage_api::NftUriKind>()
+        .schema_from::<sage_api::NftUriUpdate>()
+        .schema_from::<sage_api::NftUriSkip>()
         .schema_from::<sage_api::TransactionSummary>()
         .schema_from::<sage_api::TransactionInput>()
         .schema_from::<sage_api::TransactionOutput>()
@@ -90,13 +109,29 @@ pub fn generate_openapi() -> OpenApi {
         .schema_from::<sage_api::CoinJson>()
         .schema_from::<sage_api::OfferRecord>()
         .schema_from::<sage_api::OfferRecordStatus>()
+        .schema_from::<sage_api::StandingOfferRecord>()
+        .schema_from::<sage_api::StandingOfferFillRecord>()
         .schema_from::<sage_api::OfferSummary>()
         .schema_from::<sage_api::OfferAsset>()
         .schema_from::<sage_api::NftRoyalty>()
         .schema_from::<sage_api::OptionAssets>()
         .schema_from::<sage_api::Payment>()
         .schema_from::<sage_api::NftMint>()
+        .schema_from::<sage_api::NftMintWarning>()
+        .schema_from::<sage_api::NftMintWarningKind>()
+        .schema_from::<sage_api::MintManifestFormat>()
+        .schema_from::<sage_api::MintJobRecord>()
+        .schema_from::<sage_api::MintJobItemRecord>()
+        .schema_from::<sage_api::MintJobItemStatus>()
+        .schema_from::<sage_api::RoyaltyIncomeInterval>()
+        .schema_from::<sage_api::RoyaltyIncomeRecord>()
+        .schema_from::<sage_api::NftRoyaltyIncomeRecord>()
+        .schema_from::<sage_api::CollectionRoyaltyIncomeRecord>()
+        .schema_from::<sage_api::RoyaltyIncomePeriodRecord>()
         .schema_from::<sage_api::OfferAmount>()
+        .schema_from::<sage_api::LadderOffer>()
+        .schema_from::<sage_api::OfferExchangeProvider>()
+        .schema_from::<sage_api::ExchangeOfferRecord>()
         .schema_from::<sage_api::OptionAsset>()
         .schema_from::<sage_api::NetworkKind>()
         .schema_from::<sage_api::Id>()
@@ -148,6 +183,20 @@ fn create_endpoint_path(endpoint: &str) -> utoipa::openapi::path::PathItem {
         .tag(tag)
         .summary(Some(format_endpoint_name(endpoint)))
         .description(Some(description))
+        .parameter(
+            ParameterBuilder::new()
+                .name("fingerprint")
+                .parameter_in(ParameterIn::Query)
+                .required(Required::False)
+                .description(Some(
+                    "Fingerprint of a loaded wallet to use instead of the logged in wallet",
+                ))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::Type(Type::Integer))
+                        .build(),
+                )),
+        )
         .request_body(Some(
             RequestBodyBuilder::new()
                 .description(Some(format!(
@@ -157,7 +206,7 @@ fn create_endpoint_path(endpoint: &str) -> utoipa::openapi::path::PathItem {
                     "application/json",
                     ContentBuilder::new().schema(Some(request_schema)).build(),
                 )
-                .required(Some(utoipa::openapi::Required::True))
+                .required(Some(Required::True))
                 .build(),
         ))
         .responses(create_endpoint_responses_with_schema(
@@ -19,9 +19,12 @@ use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use rustls::crypto::aws_lc_rs::default_provider;
 use sage::Sage;
-use sage_api::{Amount, GetKey, GetPeers, GetSyncStatus, GetVersion, ImportKey, Login, SendXch};
+use sage_api::{
+    Amount, GetKey, GetPeers, GetSyncStatus, GetSyncStatusResponse, GetVersion, ImportKey, Login,
+    SendXch, SendXchResponse, SetBackgroundSync,
+};
 use sage_api_macro::impl_endpoints;
-use sage_wallet::{SyncCommand, SyncEvent};
+use sage_wallet::{SyncCommand, SyncEvent, WalletEvent};
 use serde::{Serialize, de::DeserializeOwned};
 use tempfile::TempDir;
 use tokio::{
@@ -38,7 +41,7 @@ struct TestApp {
     router: Router<()>,
     rng: ChaCha8Rng,
     sim: PeerSimulator,
-    events: mpsc::Receiver<SyncEvent>,
+    events: mpsc::Receiver<WalletEvent>,
     _dir: TempDir,
 }
 
@@ -148,6 +151,10 @@ impl TestApp {
     }
 
     async fn consume_until(&mut self, f: impl Fn(SyncEvent) -> bool) {
+        self.consume_wallet_until(|event| f(event.event)).await;
+    }
+
+    async fn consume_wallet_until(&mut self, f: impl Fn(WalletEvent) -> bool) {
         loop {
             let next = timeout(Duration::from_secs(10), self.events.recv())
                 .await
@@ -156,7 +163,7 @@ impl TestApp {
 
             debug!("Consuming event: {next:?}");
 
-            if f(next) {
+            if f(next.event) {
                 return;
             }
         }
@@ -280,3 +287,124 @@ async fn test_send_xch() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_background_sync() -> Result<()> {
+    let mut app = TestApp::new().await?;
+
+    let alice = app.setup_bls(1000).await?;
+
+    app.set_background_sync(SetBackgroundSync {
+        fingerprint: alice,
+        background_sync: true,
+    })
+    .await?;
+
+    // Changing the setting reloads the logged in wallet.
+    app.consume_wallet_until(|event| {
+        event.fingerprint == alice && event.event == SyncEvent::Subscribed
+    })
+    .await;
+
+    let bob = app.setup_bls(0).await?;
+    let bob_address = app.get_sync_status(GetSyncStatus {}).await?.receive_address;
+
+    let status: GetSyncStatusResponse = app
+        .call_rpc(
+            &format!("/get_sync_status?fingerprint={alice}"),
+            GetSyncStatus {},
+        )
+        .await?;
+    assert_eq!(status.selectable_balance.to_u64(), Some(1000));
+
+    // Both wallets share the same peer, so the coin state updates need to be routed to each.
+    let _: SendXchResponse = app
+        .call_rpc(
+            &format!("/send_xch?fingerprint={alice}"),
+            SendXch {
+                address: bob_address,
+                amount: Amount::u64(1000),
+                fee: Amount::u64(0),
+                memos: vec![],
+                clawback: None,
+                auto_submit: true,
+            },
+        )
+        .await?;
+
+    app.consume_wallet_until(|event| {
+        event.fingerprint == bob && event.event == SyncEvent::CoinsUpdated
+    })
+    .await;
+
+    let balance = app
+        .get_sync_status(GetSyncStatus {})
+        .await?
+        .selectable_balance
+        .to_u64();
+    assert_eq!(balance, Some(1000));
+
+    let key = app
+        .get_key(GetKey { fingerprint: None })
+        .await?
+        .key
+        .expect("should be logged in");
+    assert_eq!(key.fingerprint, bob);
+
+    // Wallets which aren't loaded can't be used.
+    assert!(
+        app.call_rpc::<_, GetSyncStatusResponse>(
+            "/get_sync_status?fingerprint=1",
+            GetSyncStatus {}
+        )
+        .await
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metrics() -> Result<()> {
+    let mut app = TestApp::new().await?;
+
+    let fingerprint = app.setup_bls(1000).await?;
+
+    let scrape = |router: Router<()>| async move {
+        let req = Request::builder()
+            .method("GET")
+            .uri("/metrics")
+            .body(Body::empty())?;
+
+        let response = router.oneshot(req).await?;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+
+        anyhow::Ok((status, String::from_utf8(body.to_vec())?))
+    };
+
+    // Metrics are disabled by default.
+    let (status, _) = scrape(app.router.clone()).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    app.sage.lock().await.config.rpc.metrics.enabled = true;
+    app.get_version(GetVersion {}).await?;
+
+    let (status, body) = scrape(app.router.clone()).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("sage_peers 1\n"));
+    assert!(body.contains(&format!(
+        "sage_mempool_items{{fingerprint=\"{fingerprint}\"}} 0\n"
+    )));
+    assert!(body.contains("sage_rpc_requests_total{endpoint=\"get_version\"} 1\n"));
+    assert!(!body.contains("sage_balance_mojos"));
+
+    app.sage.lock().await.config.rpc.metrics.balances = true;
+
+    let (_, body) = scrape(app.router.clone()).await?;
+    assert!(body.contains(&format!(
+        "sage_balance_mojos{{fingerprint=\"{fingerprint}\",asset_id=\"xch\"}} 1000\n"
+    )));
+
+    Ok(())
+}
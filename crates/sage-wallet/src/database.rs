@@ -203,8 +203,8 @@ pub async fn insert_puzzle(
             metadata,
             clawback,
         } => {
-            if underlying_p2_puzzle_hash.is_some() {
-                warn!("Deleting underlying coin {coin_id} because NFTs are unsupported");
+            if underlying_p2_puzzle_hash.is_some() && clawback.is_some() {
+                warn!("Deleting underlying coin {coin_id} because clawbacks are unsupported");
                 tx.delete_coin(coin_id).await?;
                 return Ok(false);
             }
@@ -384,34 +384,51 @@ pub async fn insert_option(
         tx.insert_lineage_proof(coin_id, lineage_proof).await?;
     }
 
-    let (strike_asset_hash, strike_amount) = match context.metadata.strike_type {
-        OptionType::Xch { amount } => (Bytes32::default(), amount),
-        OptionType::Cat { asset_id, amount }
-        | OptionType::RevocableCat {
-            asset_id, amount, ..
-        } => {
-            tx.insert_asset(Asset {
-                hash: asset_id,
-                name: None,
-                ticker: None,
-                precision: 3,
-                icon_url: None,
-                description: None,
-                is_sensitive_content: false,
-                is_visible: true,
-                hidden_puzzle_hash: None,
-                kind: AssetKind::Token,
-            })
-            .await?;
+    let (strike_asset_hash, strike_amount, strike_settlement_puzzle_hash) =
+        match context.metadata.strike_type {
+            OptionType::Xch { amount } => (Bytes32::default(), amount, None),
+            OptionType::Cat { asset_id, amount }
+            | OptionType::RevocableCat {
+                asset_id, amount, ..
+            } => {
+                tx.insert_asset(Asset {
+                    hash: asset_id,
+                    name: None,
+                    ticker: None,
+                    precision: 3,
+                    icon_url: None,
+                    description: None,
+                    is_sensitive_content: false,
+                    is_visible: true,
+                    hidden_puzzle_hash: None,
+                    kind: AssetKind::Token,
+                })
+                .await?;
 
-            (asset_id, amount)
-        }
-        OptionType::Nft { .. } => {
-            warn!("Received an option contract coin with an unsupported strike type, deleting it");
-            tx.delete_coin(coin_id).await?;
-            return Ok(false);
-        }
-    };
+                (asset_id, amount, None)
+            }
+            OptionType::Nft {
+                launcher_id,
+                settlement_puzzle_hash,
+                amount,
+            } => {
+                tx.insert_asset(Asset {
+                    hash: launcher_id,
+                    name: None,
+                    ticker: None,
+                    precision: 1,
+                    icon_url: None,
+                    description: None,
+                    is_sensitive_content: false,
+                    is_visible: true,
+                    hidden_puzzle_hash: None,
+                    kind: AssetKind::Nft,
+                })
+                .await?;
+
+                (launcher_id, amount, Some(settlement_puzzle_hash))
+            }
+        };
 
     let underlying = OptionUnderlying::new(
         info.launcher_id,
@@ -426,6 +443,7 @@ pub async fn insert_option(
         underlying_delegated_puzzle_hash: underlying.delegated_puzzle().tree_hash().into(),
         strike_asset_hash,
         strike_amount,
+        strike_settlement_puzzle_hash,
     };
 
     let mut asset = Asset {
@@ -487,6 +505,7 @@ pub async fn insert_option(
 
     let underlying_asset_hash = match &context.underlying_kind {
         ChildKind::Cat { info, .. } => info.asset_id,
+        ChildKind::Nft { info, .. } => info.launcher_id,
         ChildKind::Unknown => Bytes32::default(),
         _ => return Ok(true),
     };
@@ -495,11 +514,11 @@ pub async fn insert_option(
     let strike_asset = tx.asset(strike_asset_hash).await?;
 
     let underlying_ticker = underlying_asset
-        .and_then(|asset| asset.ticker)
+        .and_then(option_asset_label)
         .unwrap_or("Unknown".to_string());
 
     let strike_ticker = strike_asset
-        .and_then(|asset| asset.ticker)
+        .and_then(option_asset_label)
         .unwrap_or("Unknown".to_string());
 
     asset.name = Some(format!("{underlying_ticker} / {strike_ticker}"));
@@ -509,6 +528,15 @@ pub async fn insert_option(
     Ok(true)
 }
 
+// NFTs don't have a ticker, so they are referred to by name instead
+fn option_asset_label(asset: Asset) -> Option<String> {
+    if asset.kind == AssetKind::Nft {
+        asset.name
+    } else {
+        asset.ticker
+    }
+}
+
 pub async fn insert_transaction(
     db: &Database,
     peer: &WalletPeer,
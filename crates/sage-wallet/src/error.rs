@@ -33,6 +33,9 @@ pub enum WalletError {
     #[error("Request error: {0}")]
     Request(#[from] reqwest::Error),
 
+    #[error("Full node RPC error: {0}")]
+    Rpc(String),
+
     #[error("URI error: {0}")]
     Uri(#[from] UriError),
 
@@ -51,6 +54,9 @@ pub enum WalletError {
     #[error("Subscription limit reached")]
     SubscriptionLimitReached,
 
+    #[error("Coin states could not be verified against another peer")]
+    UnverifiedCoinStates,
+
     #[error("System time error: {0}")]
     SystemTime(#[from] SystemTimeError),
 
@@ -96,6 +102,15 @@ pub enum WalletError {
     #[error("Missing DID with id {0}. It may have been spent recently. Please try again later.")]
     MissingDid(Bytes32),
 
+    #[error("DID with id {0} doesn't have a matching recovery list")]
+    RecoveryListMismatch(Bytes32),
+
+    #[error("Missing recovery attestation from DID with id {0}")]
+    MissingDidAttestation(Bytes32),
+
+    #[error("DID with id {0} can't be recovered")]
+    UnrecoverableDid(Bytes32),
+
     #[error("Missing NFT with id {0}. It may have been spent recently. Please try again later.")]
     MissingNft(Bytes32),
 
@@ -107,15 +122,21 @@ pub enum WalletError {
     #[error("Missing asset with id {0}")]
     MissingAsset(Bytes32),
 
+    #[error("TAIL does not match asset id {0}")]
+    TailMismatch(Bytes32),
+
+    #[error("Melting CAT with asset id {0} wouldn't create any coins")]
+    EmptyMelt(Bytes32),
+
+    #[error("CAT coin with id {0} can't be revoked by this wallet")]
+    UnrevocableCat(Bytes32),
+
     #[error("Uncancellable offer")]
     UncancellableOffer,
 
     #[error("Cannot import offer that doesn't belong to this wallet")]
     CannotImportOffer,
 
-    #[error("NFT option contracts are not supported")]
-    NftOptionNotSupported,
-
     #[error("Unsupported underlying coin kind: {0:?}")]
     UnsupportedUnderlyingCoinKind(CoinKind),
 
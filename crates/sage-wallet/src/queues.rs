@@ -1,13 +1,27 @@
 mod blocktime_queue;
 mod cat_queue;
+mod clawback_queue;
+mod coin_management_queue;
+mod header_queue;
+mod mint_job_queue;
 mod nft_uri_queue;
 mod offer_queue;
 mod puzzle_queue;
+mod reputation_queue;
+mod royalty_queue;
+mod standing_offer_queue;
 mod transaction_queue;
 
 pub use blocktime_queue::*;
 pub use cat_queue::*;
+pub use clawback_queue::*;
+pub use coin_management_queue::*;
+pub use header_queue::*;
+pub use mint_job_queue::*;
 pub use nft_uri_queue::*;
 pub use offer_queue::*;
 pub use puzzle_queue::*;
+pub use reputation_queue::*;
+pub use royalty_queue::*;
+pub use standing_offer_queue::*;
 pub use transaction_queue::*;
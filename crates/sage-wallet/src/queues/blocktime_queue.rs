@@ -1,4 +1,4 @@
-use crate::{PeerState, SyncEvent, WalletError, WalletPeer};
+use crate::{PeerState, SyncEvent, SyncPhase, SyncProgress, WalletError, WalletPeer};
 
 use futures_util::{StreamExt, stream::FuturesUnordered};
 use sage_database::Database;
@@ -15,6 +15,7 @@ use tracing::{error, info};
 pub struct BlockTimeQueue {
     db: Database,
     state: Arc<Mutex<PeerState>>,
+    progress: SyncProgress,
     sync_sender: mpsc::Sender<SyncEvent>,
 }
 
@@ -22,11 +23,13 @@ impl BlockTimeQueue {
     pub fn new(
         db: Database,
         state: Arc<Mutex<PeerState>>,
+        progress: SyncProgress,
         sync_sender: mpsc::Sender<SyncEvent>,
     ) -> Self {
         Self {
             db,
             state,
+            progress,
             sync_sender,
         }
     }
@@ -63,14 +66,29 @@ impl BlockTimeQueue {
             }
         }
 
+        let mut fetched = 0;
+
         while let Some(result) = tasks.next().await {
             if let Err(error) = result {
                 error!("Error fetching and processing blockinfo: {error}");
+            } else {
+                fetched += 1;
             }
         }
 
         self.sync_sender.send(SyncEvent::CoinsUpdated).await.ok();
 
+        let remaining = self.db.unsynced_block_count().await?;
+
+        self.progress
+            .record(
+                SyncPhase::BlockTimes,
+                fetched,
+                Some(remaining),
+                &self.sync_sender,
+            )
+            .await;
+
         Ok(())
     }
 
@@ -8,20 +8,30 @@ use tokio::{
     time::{sleep, timeout},
 };
 
-use crate::{SyncEvent, WalletError};
+use crate::{SyncEvent, SyncPhase, SyncProgress, WalletError};
 
 #[derive(Debug)]
 pub struct CatQueue {
     db: Database,
-    testnet: bool,
+    dexie_api: String,
+    dexie_icons: Option<String>,
+    progress: SyncProgress,
     sync_sender: mpsc::Sender<SyncEvent>,
 }
 
 impl CatQueue {
-    pub fn new(db: Database, testnet: bool, sync_sender: mpsc::Sender<SyncEvent>) -> Self {
+    pub fn new(
+        db: Database,
+        dexie_api: String,
+        dexie_icons: Option<String>,
+        progress: SyncProgress,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
         Self {
             db,
-            testnet,
+            dexie_api,
+            dexie_icons,
+            progress,
             sync_sender,
         }
     }
@@ -34,12 +44,18 @@ impl CatQueue {
     }
 
     async fn process_batch(&self) -> Result<(), WalletError> {
-        let cats = timeout(Duration::from_secs(120), DexieCat::fetch_all(self.testnet)).await??;
+        let cats = timeout(
+            Duration::from_secs(120),
+            DexieCat::fetch_all(&self.dexie_api, self.dexie_icons.as_deref()),
+        )
+        .await??;
 
         if cats.is_empty() {
             return Ok(());
         }
 
+        let missing_before = self.missing_cat_info().await?;
+
         let mut tx = self.db.tx().await?;
 
         for cat in cats {
@@ -62,6 +78,29 @@ impl CatQueue {
 
         self.sync_sender.send(SyncEvent::CatInfo).await.ok();
 
+        // Progress is measured by the owned CATs which are still missing a name, since most
+        // of the CATs listed on Dexie aren't relevant to the wallet.
+        let missing = self.missing_cat_info().await?;
+
+        self.progress
+            .record(
+                SyncPhase::CatInfo,
+                missing_before.saturating_sub(missing),
+                Some(missing),
+                &self.sync_sender,
+            )
+            .await;
+
         Ok(())
     }
+
+    async fn missing_cat_info(&self) -> Result<u64, WalletError> {
+        Ok(self
+            .db
+            .owned_cats()
+            .await?
+            .iter()
+            .filter(|cat| cat.name.is_none())
+            .count() as u64)
+    }
 }
@@ -0,0 +1,267 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chia_wallet_sdk::prelude::*;
+use sage_config::ClawbackPolicy;
+use sage_database::AssetKind;
+use tokio::{
+    sync::{Mutex, mpsc},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{PeerState, SyncCommand, Transaction, Wallet, WalletError, insert_transaction};
+
+/// The maximum number of clawback coins to spend in a single automatic transaction.
+const MAX_COINS_PER_TRANSACTION: usize = 50;
+
+#[derive(Debug)]
+pub struct ClawbackQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    policy: ClawbackPolicy,
+    state: Arc<Mutex<PeerState>>,
+    command_sender: mpsc::Sender<SyncCommand>,
+}
+
+impl ClawbackQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        policy: ClawbackPolicy,
+        state: Arc<Mutex<PeerState>>,
+        command_sender: mpsc::Sender<SyncCommand>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            policy,
+            state,
+            command_sender,
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        if self.state.lock().await.peer_count() == 0 {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let mut finalize = Vec::new();
+        let mut reclaim = Vec::new();
+
+        for row in self.wallet.db.clawback_coins().await? {
+            if row.created_height.is_none() || row.mempool_item_hash.is_some() {
+                continue;
+            }
+
+            let coin_id = row.coin.coin_id();
+
+            if timestamp >= row.expiration_seconds {
+                // Only fungible assets need to be pushed through to the receiver.
+                if self.policy.finalize_incoming
+                    && row.is_incoming
+                    && row.asset.kind == AssetKind::Token
+                {
+                    finalize.push(coin_id);
+                }
+            } else if self.policy.reclaim_outgoing
+                && row.is_outgoing
+                && !row.is_incoming
+                && row.expiration_seconds - timestamp <= self.policy.reclaim_window_seconds
+            {
+                reclaim.push(coin_id);
+            }
+        }
+
+        finalize.truncate(MAX_COINS_PER_TRANSACTION);
+        reclaim.truncate(MAX_COINS_PER_TRANSACTION);
+
+        if !finalize.is_empty() {
+            info!("Automatically finalizing {} clawback coins", finalize.len());
+
+            match self
+                .wallet
+                .finalize_clawback(finalize, self.policy.fee)
+                .await
+            {
+                Ok(coin_spends) => self.submit(coin_spends).await?,
+                Err(error) => warn!("Failed to finalize clawback coins: {error}"),
+            }
+        }
+
+        if !reclaim.is_empty() {
+            info!(
+                "Automatically clawing back {} coins before they expire",
+                reclaim.len()
+            );
+
+            match self.wallet.combine(reclaim, self.policy.fee).await {
+                Ok(coin_spends) => self.submit(coin_spends).await?,
+                Err(error) => warn!("Failed to claw back coins: {error}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn submit(&self, coin_spends: Vec<CoinSpend>) -> Result<(), WalletError> {
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(());
+        };
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            &peer,
+            self.wallet.genesis_challenge,
+            spend_bundle.name(),
+            Transaction::from_coin_spends(spend_bundle.coin_spends)?,
+            spend_bundle.aggregated_signature,
+        )
+        .await?;
+
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                fingerprint: self.wallet.fingerprint,
+                coin_ids: subscriptions,
+            })
+            .await
+            .ok();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::TestWallet;
+
+    use super::*;
+
+    fn queue(test: &TestWallet, policy: ClawbackPolicy) -> ClawbackQueue {
+        ClawbackQueue::new(
+            test.wallet.clone(),
+            test.master_sk.clone(),
+            policy,
+            test.state.clone(),
+            test.sender.clone(),
+        )
+    }
+
+    #[test(tokio::test)]
+    async fn test_finalize_incoming_clawback() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let timestamp = alice.new_block_with_current_time().await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                vec![(bob.puzzle_hash, 1000)],
+                0,
+                vec![],
+                Some(timestamp + 5),
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        let outgoing = alice.wallet.db.clawback_coins().await?;
+        assert_eq!(outgoing.len(), 1);
+        assert!(outgoing[0].is_outgoing);
+        assert!(!outgoing[0].is_incoming);
+
+        let incoming = bob.wallet.db.clawback_coins().await?;
+        assert_eq!(incoming.len(), 1);
+        assert!(!incoming[0].is_outgoing);
+        assert!(incoming[0].is_incoming);
+
+        let mut queue = queue(&bob, ClawbackPolicy::default());
+
+        // Nothing can be finalized before the clawback expires.
+        queue.process_batch().await?;
+        assert_eq!(bob.wallet.db.clawback_coins().await?.len(), 1);
+
+        sleep(Duration::from_secs(6)).await;
+        bob.new_block_with_current_time().await?;
+
+        queue.process_batch().await?;
+        bob.wait_for_coins().await;
+
+        assert_eq!(bob.wallet.db.clawback_coins().await?.len(), 0);
+        assert_eq!(bob.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_reclaim_outgoing_clawback() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let timestamp = alice.new_block_with_current_time().await?;
+
+        let coin_spends = alice
+            .wallet
+            .send_xch(
+                vec![(bob.puzzle_hash, 1000)],
+                0,
+                vec![],
+                Some(timestamp + 300),
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 0);
+
+        // The clawback expires after the reclaim window, so it's left alone.
+        let mut queue = queue(
+            &alice,
+            ClawbackPolicy {
+                finalize_incoming: false,
+                reclaim_outgoing: true,
+                reclaim_window_seconds: 60,
+                fee: 0,
+            },
+        );
+        queue.process_batch().await?;
+        assert_eq!(alice.wallet.db.clawback_coins().await?.len(), 1);
+
+        queue.policy.reclaim_window_seconds = 3600;
+        queue.process_batch().await?;
+        alice.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.clawback_coins().await?.len(), 0);
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,462 @@
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chia_wallet_sdk::prelude::*;
+use itertools::Itertools;
+use sage_config::{CoinManagement, CoinPolicy};
+use tokio::{
+    sync::{Mutex, mpsc},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{
+    CoinManagementAction, PeerState, SyncCommand, SyncEvent, Transaction, Wallet, WalletError,
+    insert_transaction,
+};
+
+/// The maximum number of coins to spend or create in a single automatic transaction.
+const MAX_COINS_PER_TRANSACTION: usize = 100;
+
+/// The window over which the daily fee budget is enforced.
+const FEE_BUDGET_WINDOW: Duration = Duration::from_secs(60 * 60 * 24);
+
+#[derive(Debug)]
+struct Plan {
+    action: CoinManagementAction,
+    coin_ids: Vec<Bytes32>,
+    output_amount: u64,
+    output_count: usize,
+}
+
+#[derive(Debug)]
+pub struct CoinManagementQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    config: CoinManagement,
+    state: Arc<Mutex<PeerState>>,
+    command_sender: mpsc::Sender<SyncCommand>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+    pending: HashSet<Bytes32>,
+}
+
+impl CoinManagementQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        config: CoinManagement,
+        state: Arc<Mutex<PeerState>>,
+        command_sender: mpsc::Sender<SyncCommand>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            config,
+            state,
+            command_sender,
+            sync_sender,
+            pending: HashSet::new(),
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        if self.state.lock().await.peer_count() == 0 {
+            return Ok(());
+        }
+
+        // Coin counts aren't accurate until our previous transactions have confirmed,
+        // so we wait for them rather than acting on the same coins twice.
+        let mempool_items: HashSet<Bytes32> = self
+            .wallet
+            .db
+            .mempool_items()
+            .await?
+            .into_iter()
+            .map(|item| item.hash)
+            .collect();
+
+        self.pending.retain(|hash| mempool_items.contains(hash));
+
+        if !self.pending.is_empty() {
+            return Ok(());
+        }
+
+        for policy in self.config.policies.clone() {
+            let Some(plan) = self.plan(policy).await? else {
+                continue;
+            };
+
+            if !self.within_budget().await? {
+                warn!(
+                    "Skipping coin management for {:?}, since the daily fee budget has been exhausted",
+                    policy.asset_id
+                );
+                return Ok(());
+            }
+
+            let action = plan.action;
+            let coin_count = plan.coin_ids.len();
+
+            let result = match plan.action {
+                CoinManagementAction::Split => {
+                    self.wallet
+                        .split_by_amount(
+                            plan.coin_ids,
+                            plan.output_amount,
+                            plan.output_count,
+                            self.config.fee,
+                        )
+                        .await
+                }
+                CoinManagementAction::Combine => {
+                    self.wallet.combine(plan.coin_ids, self.config.fee).await
+                }
+            };
+
+            let coin_spends = match result {
+                Ok(coin_spends) => coin_spends,
+                Err(error) => {
+                    warn!(
+                        "Failed to apply coin management policy for {:?}: {error}",
+                        policy.asset_id
+                    );
+                    continue;
+                }
+            };
+
+            info!(
+                "Applying coin management policy for {:?} with action {action:?} on {coin_count} coins",
+                policy.asset_id
+            );
+
+            let Some(transaction_id) = self.submit(coin_spends).await? else {
+                return Ok(());
+            };
+
+            self.pending.insert(transaction_id);
+
+            let paid_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+            self.wallet
+                .db
+                .insert_coin_management_fee(transaction_id, self.config.fee, paid_timestamp)
+                .await?;
+
+            self.sync_sender
+                .send(SyncEvent::CoinsManaged {
+                    asset_id: policy.asset_id,
+                    action,
+                    coin_count,
+                    fee: self.config.fee,
+                    transaction_id,
+                })
+                .await
+                .ok();
+
+            // Policies for other assets may rely on the same XCH coins for fees,
+            // so only a single transaction is submitted at a time.
+            return Ok(());
+        }
+
+        Ok(())
+    }
+
+    async fn plan(&self, policy: CoinPolicy) -> Result<Option<Plan>, WalletError> {
+        let coins = if let Some(asset_id) = policy.asset_id {
+            self.wallet
+                .db
+                .selectable_cat_coins(asset_id)
+                .await?
+                .into_iter()
+                .map(|cat| cat.coin)
+                .collect_vec()
+        } else {
+            self.wallet.db.selectable_xch_coins().await?
+        };
+
+        if let Some(split) = policy.split
+            && split.coin_amount > 0
+        {
+            // Coins within half of the target amount in either direction are close enough.
+            let lower = split.coin_amount / 2;
+            let upper = split.coin_amount.saturating_add(split.coin_amount / 2);
+
+            let count = coins
+                .iter()
+                .filter(|coin| coin.amount >= lower && coin.amount <= upper)
+                .count();
+
+            if count < split.min_coins as usize {
+                let needed = (split.min_coins as usize - count).min(MAX_COINS_PER_TRANSACTION);
+
+                // The fee is paid out of the selected coins if they are XCH.
+                let fee = if policy.asset_id.is_none() {
+                    self.config.fee
+                } else {
+                    0
+                };
+
+                let target = split
+                    .coin_amount
+                    .saturating_mul(needed as u64)
+                    .saturating_add(fee);
+
+                let mut selected = Vec::new();
+                let mut selected_amount = 0;
+
+                for coin in coins
+                    .iter()
+                    .filter(|coin| coin.amount > upper)
+                    .sorted_by_key(|coin| std::cmp::Reverse(coin.amount))
+                {
+                    if selected_amount >= target {
+                        break;
+                    }
+
+                    selected.push(coin.coin_id());
+                    selected_amount += coin.amount;
+                }
+
+                let output_count = (selected_amount.saturating_sub(fee) / split.coin_amount)
+                    .min(needed as u64) as usize;
+
+                if output_count > 0 {
+                    return Ok(Some(Plan {
+                        action: CoinManagementAction::Split,
+                        coin_ids: selected,
+                        output_amount: split.coin_amount,
+                        output_count,
+                    }));
+                }
+            }
+        }
+
+        if let Some(combine) = policy.combine
+            && coins.len() > combine.coin_count_threshold as usize
+        {
+            let selected = coins
+                .iter()
+                .filter(|coin| coin.amount <= combine.max_coin_amount)
+                .sorted_by_key(|coin| coin.amount)
+                .take(MAX_COINS_PER_TRANSACTION)
+                .map(Coin::coin_id)
+                .collect_vec();
+
+            if selected.len() > 1 {
+                return Ok(Some(Plan {
+                    action: CoinManagementAction::Combine,
+                    coin_ids: selected,
+                    output_amount: 0,
+                    output_count: 1,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn within_budget(&self) -> Result<bool, WalletError> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let spent = self
+            .wallet
+            .db
+            .coin_management_fees_since(now.saturating_sub(FEE_BUDGET_WINDOW.as_secs()))
+            .await?
+            .into_iter()
+            .fold(0, u64::saturating_add);
+
+        Ok(spent
+            .checked_add(self.config.fee)
+            .is_some_and(|total| total <= self.config.daily_fee_budget))
+    }
+
+    async fn submit(&self, coin_spends: Vec<CoinSpend>) -> Result<Option<Bytes32>, WalletError> {
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(None);
+        };
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let transaction_id = spend_bundle.name();
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            &peer,
+            self.wallet.genesis_challenge,
+            transaction_id,
+            Transaction::from_coin_spends(spend_bundle.coin_spends)?,
+            spend_bundle.aggregated_signature,
+        )
+        .await?;
+
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                fingerprint: self.wallet.fingerprint,
+                coin_ids: subscriptions,
+            })
+            .await
+            .ok();
+
+        Ok(Some(transaction_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sage_config::{CombinePolicy, SplitPolicy};
+    use test_log::test;
+
+    use crate::TestWallet;
+
+    use super::*;
+
+    fn queue(
+        test: &TestWallet,
+        config: CoinManagement,
+    ) -> (CoinManagementQueue, mpsc::Receiver<SyncEvent>) {
+        let (sync_sender, sync_receiver) = mpsc::channel(10);
+
+        let queue = CoinManagementQueue::new(
+            test.wallet.clone(),
+            test.master_sk.clone(),
+            config,
+            test.state.clone(),
+            test.sender.clone(),
+            sync_sender,
+        );
+
+        (queue, sync_receiver)
+    }
+
+    async fn xch_amounts(test: &TestWallet) -> anyhow::Result<Vec<u64>> {
+        Ok(test
+            .wallet
+            .db
+            .selectable_xch_coins()
+            .await?
+            .into_iter()
+            .map(|coin| coin.amount)
+            .sorted()
+            .collect())
+    }
+
+    #[test(tokio::test)]
+    async fn test_coin_management_split_and_combine() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let (mut queue, mut events) = queue(
+            &test,
+            CoinManagement {
+                fee: 0,
+                daily_fee_budget: 0,
+                policies: vec![CoinPolicy {
+                    asset_id: None,
+                    split: Some(SplitPolicy {
+                        min_coins: 3,
+                        coin_amount: 100,
+                    }),
+                    combine: None,
+                }],
+            },
+        );
+
+        queue.process_batch().await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(xch_amounts(&test).await?, [100, 100, 100, 700]);
+        assert!(matches!(
+            events.try_recv()?,
+            SyncEvent::CoinsManaged {
+                asset_id: None,
+                action: CoinManagementAction::Split,
+                coin_count: 1,
+                fee: 0,
+                ..
+            }
+        ));
+
+        // The policy is already satisfied, so nothing else happens.
+        queue.process_batch().await?;
+        assert!(events.try_recv().is_err());
+
+        queue.config.policies[0] = CoinPolicy {
+            asset_id: None,
+            split: None,
+            combine: Some(CombinePolicy {
+                max_coin_amount: 100,
+                coin_count_threshold: 3,
+            }),
+        };
+
+        queue.process_batch().await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(xch_amounts(&test).await?, [300, 700]);
+        assert!(matches!(
+            events.try_recv()?,
+            SyncEvent::CoinsManaged {
+                action: CoinManagementAction::Combine,
+                coin_count: 3,
+                ..
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_coin_management_fee_budget() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let config = |min_coins| CoinManagement {
+            fee: 50,
+            daily_fee_budget: 50,
+            policies: vec![CoinPolicy {
+                asset_id: None,
+                split: Some(SplitPolicy {
+                    min_coins,
+                    coin_amount: 100,
+                }),
+                combine: None,
+            }],
+        };
+
+        let (mut reloaded, mut reloaded_events) = queue(&test, config(4));
+        let (mut queue, mut events) = queue(&test, config(2));
+
+        queue.process_batch().await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(xch_amounts(&test).await?, [100, 100, 750]);
+        assert!(events.try_recv().is_ok());
+
+        // The budget has been used up, so the policy can no longer be enforced,
+        // even by a new queue after the wallet is reloaded.
+        reloaded.process_batch().await?;
+
+        assert_eq!(xch_amounts(&test).await?, [100, 100, 750]);
+        assert!(reloaded_events.try_recv().is_err());
+
+        Ok(())
+    }
+}
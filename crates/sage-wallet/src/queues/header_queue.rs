@@ -0,0 +1,872 @@
+use std::{future::Future, net::IpAddr, sync::Arc, time::Duration};
+
+use chia_wallet_sdk::{chia::protocol::HeaderBlock, prelude::*};
+use sage_database::{Database, ValidatedBlock};
+use tokio::{
+    sync::{Mutex, mpsc},
+    time::sleep,
+};
+use tracing::{debug, info, warn};
+
+use crate::{PeerState, SyncCommand, SyncEvent, WalletError};
+
+/// The number of headers requested from a peer at a time. This is also how far past the
+/// validated peak headers are fetched in each batch, including when switching to a fork.
+const HEADER_BATCH_SIZE: u32 = 32;
+
+/// Forks which branch off further than this below the validated peak aren't followed, which
+/// limits how much of the coin state can be rolled back and how many headers are fetched.
+const MAX_FORK_DEPTH: u32 = 256;
+
+/// The number of other peers asked to confirm a fork before switching to it.
+const MAX_WITNESSES: usize = 3;
+
+/// The parts of a header block which link it to the rest of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainHeader {
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub prev_header_hash: Bytes32,
+    pub weight: u128,
+}
+
+impl From<&HeaderBlock> for ChainHeader {
+    fn from(header: &HeaderBlock) -> Self {
+        Self {
+            height: header.height(),
+            header_hash: header.header_hash(),
+            prev_header_hash: header.prev_header_hash(),
+            weight: header.weight(),
+        }
+    }
+}
+
+/// The peak claimed by a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimedPeak {
+    pub ip: IpAddr,
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub weight: u128,
+}
+
+/// The peers which headers are fetched from.
+pub trait HeaderPeers {
+    /// The heaviest peak claimed by a peer, which is the chain that headers are validated
+    /// against.
+    fn heaviest_peak(&self) -> impl Future<Output = Option<ClaimedPeak>> + Send;
+
+    /// The other peers in order of preference, which are asked to confirm a fork.
+    fn witnesses(&self, ip: IpAddr) -> impl Future<Output = Vec<IpAddr>> + Send;
+
+    fn block_headers(
+        &self,
+        ip: IpAddr,
+        start_height: u32,
+        end_height: u32,
+    ) -> impl Future<Output = Result<Vec<ChainHeader>, WalletError>> + Send;
+
+    fn ban(&self, ip: IpAddr, message: &str) -> impl Future<Output = ()> + Send;
+
+    fn record_disagreement(&self, ip: IpAddr) -> impl Future<Output = ()> + Send;
+}
+
+impl HeaderPeers for Arc<Mutex<PeerState>> {
+    async fn heaviest_peak(&self) -> Option<ClaimedPeak> {
+        self.lock()
+            .await
+            .heaviest_peer()
+            .map(|(peer, height, header_hash, weight)| ClaimedPeak {
+                ip: peer.socket_addr().ip(),
+                height,
+                header_hash,
+                weight,
+            })
+    }
+
+    async fn witnesses(&self, ip: IpAddr) -> Vec<IpAddr> {
+        self.lock()
+            .await
+            .witnesses(ip)
+            .iter()
+            .map(|peer| peer.socket_addr().ip())
+            .collect()
+    }
+
+    async fn block_headers(
+        &self,
+        ip: IpAddr,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<ChainHeader>, WalletError> {
+        let Some(peer) = self.lock().await.peer(ip).map(|info| info.peer.clone()) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(peer
+            .block_headers(start_height, end_height)
+            .await?
+            .iter()
+            .map(ChainHeader::from)
+            .collect())
+    }
+
+    async fn ban(&self, ip: IpAddr, message: &str) {
+        self.lock()
+            .await
+            .ban(ip, Duration::from_secs(60 * 60), message);
+    }
+
+    async fn record_disagreement(&self, ip: IpAddr) {
+        self.lock().await.record_disagreement(ip);
+    }
+}
+
+/// Follows the heaviest header chain claimed by the connected peers. Each header has to link
+/// to its parent by header hash and add weight. If the chain being followed is replaced by a
+/// heavier fork which another peer confirms, the coin state above the fork point is rolled
+/// back. While headers are being validated, this is the only place reorgs are handled.
+#[derive(Debug)]
+pub struct HeaderQueue<P = Arc<Mutex<PeerState>>> {
+    db: Database,
+    fingerprint: u32,
+    peers: P,
+    sync_sender: mpsc::Sender<SyncEvent>,
+    command_sender: mpsc::Sender<SyncCommand>,
+}
+
+impl<P> HeaderQueue<P>
+where
+    P: HeaderPeers + Send + Sync,
+{
+    pub fn new(
+        db: Database,
+        fingerprint: u32,
+        peers: P,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        command_sender: mpsc::Sender<SyncCommand>,
+    ) -> Self {
+        Self {
+            db,
+            fingerprint,
+            peers,
+            sync_sender,
+            command_sender,
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        let Some(peak) = self.peers.heaviest_peak().await else {
+            return Ok(());
+        };
+
+        let Some(validated) = self.db.validated_peak().await? else {
+            return self.anchor(peak).await;
+        };
+
+        if peak.weight <= validated.weight {
+            return Ok(());
+        }
+
+        let ip = peak.ip;
+
+        let Some(fork_height) = self
+            .find_fork(ip, validated.height.min(peak.height))
+            .await?
+        else {
+            warn!(
+                "Header chain from peer {ip} doesn't connect to the validated chain within {MAX_FORK_DEPTH} blocks"
+            );
+            self.peers.record_disagreement(ip).await;
+            return Ok(());
+        };
+
+        let Some(fork_point) = self.db.validated_block(fork_height).await? else {
+            return Ok(());
+        };
+
+        let end_height = peak.height.min(validated.height + HEADER_BATCH_SIZE);
+
+        let Some(headers) = self
+            .fetch_linked_headers(ip, fork_point, end_height)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if fork_height == validated.height {
+            let mut tx = self.db.tx().await?;
+
+            for header in &headers {
+                tx.insert_validated_block(
+                    header.height,
+                    header.header_hash,
+                    header.prev_header_hash,
+                    header.weight,
+                )
+                .await?;
+            }
+
+            tx.commit().await?;
+
+            if let Some(header) = headers.last() {
+                info!("Validated headers up to height {}", header.height);
+            }
+
+            return Ok(());
+        }
+
+        let Some(tip) = headers
+            .last()
+            .copied()
+            .filter(|header| header.weight > validated.weight)
+        else {
+            warn!(
+                "Fork from peer {ip} at height {fork_height} isn't heavier than the validated chain"
+            );
+            self.peers.record_disagreement(ip).await;
+            return Ok(());
+        };
+
+        // Both the headers and their weight come from the peer, so another peer has to have
+        // the same chain before anything is rolled back.
+        if !self.is_confirmed(ip, tip).await {
+            warn!(
+                "Fork from peer {ip} at height {fork_height} hasn't been confirmed by another peer"
+            );
+            return Ok(());
+        }
+
+        let mut tx = self.db.tx().await?;
+
+        let coin_ids = tx.rollback_to_height(fork_height).await?;
+
+        for header in &headers {
+            tx.insert_validated_block(
+                header.height,
+                header.header_hash,
+                header.prev_header_hash,
+                header.weight,
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        warn!(
+            "Reorg detected at height {fork_height}, rolled back {} coins",
+            coin_ids.len()
+        );
+
+        self.sync_sender
+            .send(SyncEvent::Reorg { fork_height })
+            .await
+            .ok();
+
+        if !coin_ids.is_empty() {
+            self.command_sender
+                .send(SyncCommand::ResyncCoins {
+                    fingerprint: self.fingerprint,
+                    coin_ids,
+                })
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    /// There is no trusted checkpoint to start from, so the chain is followed from the peak
+    /// that was current when header tracking was enabled.
+    async fn anchor(&self, peak: ClaimedPeak) -> Result<(), WalletError> {
+        let Some(header) = self
+            .peers
+            .block_headers(peak.ip, peak.height, peak.height)
+            .await?
+            .pop()
+        else {
+            return Ok(());
+        };
+
+        // The peak may have moved on since it was reported.
+        if header.header_hash != peak.header_hash {
+            return Ok(());
+        }
+
+        let mut tx = self.db.tx().await?;
+
+        tx.insert_validated_block(
+            header.height,
+            header.header_hash,
+            header.prev_header_hash,
+            header.weight,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        info!("Started validating headers from height {}", header.height);
+
+        Ok(())
+    }
+
+    /// Finds the highest validated height at or below the given height which the peer agrees
+    /// with, if there is one within [`MAX_FORK_DEPTH`] blocks.
+    async fn find_fork(&self, ip: IpAddr, from_height: u32) -> Result<Option<u32>, WalletError> {
+        let Some(lowest_height) = self.db.lowest_validated_height().await? else {
+            return Ok(None);
+        };
+
+        let lowest_height = lowest_height.max(from_height.saturating_sub(MAX_FORK_DEPTH));
+        let mut end_height = from_height;
+
+        while end_height >= lowest_height {
+            let start_height = end_height
+                .saturating_sub(HEADER_BATCH_SIZE - 1)
+                .max(lowest_height);
+
+            let headers = self
+                .peers
+                .block_headers(ip, start_height, end_height)
+                .await?;
+
+            for header in headers.iter().rev() {
+                if self
+                    .db
+                    .validated_block(header.height)
+                    .await?
+                    .is_some_and(|block| block.header_hash == header.header_hash)
+                {
+                    return Ok(Some(header.height));
+                }
+            }
+
+            if start_height == 0 {
+                break;
+            }
+
+            end_height = start_height - 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Fetches the headers after the given block up to the end height, and checks that they
+    /// form a chain. The peer is banned if they don't.
+    async fn fetch_linked_headers(
+        &self,
+        ip: IpAddr,
+        parent: ValidatedBlock,
+        end_height: u32,
+    ) -> Result<Option<Vec<ChainHeader>>, WalletError> {
+        let mut headers = Vec::new();
+        let mut prev = parent;
+
+        while prev.height < end_height {
+            let start_height = prev.height + 1;
+            let batch_end = end_height.min(start_height + HEADER_BATCH_SIZE - 1);
+
+            let batch = self
+                .peers
+                .block_headers(ip, start_height, batch_end)
+                .await?;
+
+            if batch.is_empty() {
+                break;
+            }
+
+            for header in batch {
+                if header.height != prev.height + 1
+                    || header.prev_header_hash != prev.header_hash
+                    || header.weight <= prev.weight
+                {
+                    self.peers
+                        .ban(ip, "sent headers which don't form a chain")
+                        .await;
+                    return Ok(None);
+                }
+
+                prev = ValidatedBlock {
+                    height: header.height,
+                    header_hash: header.header_hash,
+                    weight: header.weight,
+                };
+
+                headers.push(header);
+            }
+        }
+
+        Ok(Some(headers))
+    }
+
+    /// Whether another peer has the same header at the tip of a fork.
+    async fn is_confirmed(&self, ip: IpAddr, tip: ChainHeader) -> bool {
+        for witness in self
+            .peers
+            .witnesses(ip)
+            .await
+            .into_iter()
+            .take(MAX_WITNESSES)
+        {
+            match self
+                .peers
+                .block_headers(witness, tip.height, tip.height)
+                .await
+            {
+                Ok(headers) => {
+                    if headers.first() == Some(&tip) {
+                        return true;
+                    }
+                }
+                Err(error) => {
+                    debug!(
+                        "Failed to fetch header {} from peer {witness}: {error}",
+                        tip.height
+                    );
+                }
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, net::Ipv4Addr};
+
+    use chia_wallet_sdk::test::Simulator;
+    use test_log::test;
+
+    use crate::{SyncOptions, TestWallet, default_test_options, new_peak_message};
+
+    use super::*;
+
+    const PEER_A: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    const PEER_B: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+    /// The simulator doesn't serve headers, so each peer's chain is built from the simulator's
+    /// header hashes instead.
+    #[derive(Debug, Default)]
+    struct FakePeers {
+        chains: HashMap<IpAddr, Vec<ChainHeader>>,
+        banned: Vec<IpAddr>,
+        disagreements: Vec<IpAddr>,
+    }
+
+    impl HeaderPeers for Arc<Mutex<FakePeers>> {
+        async fn heaviest_peak(&self) -> Option<ClaimedPeak> {
+            self.lock()
+                .await
+                .chains
+                .iter()
+                .filter_map(|(ip, chain)| Some((*ip, *chain.last()?)))
+                .max_by_key(|(ip, header)| (header.weight, *ip))
+                .map(|(ip, header)| ClaimedPeak {
+                    ip,
+                    height: header.height,
+                    header_hash: header.header_hash,
+                    weight: header.weight,
+                })
+        }
+
+        async fn witnesses(&self, ip: IpAddr) -> Vec<IpAddr> {
+            self.lock()
+                .await
+                .chains
+                .keys()
+                .copied()
+                .filter(|witness| *witness != ip)
+                .collect()
+        }
+
+        async fn block_headers(
+            &self,
+            ip: IpAddr,
+            start_height: u32,
+            end_height: u32,
+        ) -> Result<Vec<ChainHeader>, WalletError> {
+            Ok(self
+                .lock()
+                .await
+                .chains
+                .get(&ip)
+                .map(|chain| {
+                    chain
+                        .iter()
+                        .filter(|header| (start_height..=end_height).contains(&header.height))
+                        .copied()
+                        .collect()
+                })
+                .unwrap_or_default())
+        }
+
+        async fn ban(&self, ip: IpAddr, _message: &str) {
+            let mut peers = self.lock().await;
+            peers.chains.remove(&ip);
+            peers.banned.push(ip);
+        }
+
+        async fn record_disagreement(&self, ip: IpAddr) {
+            self.lock().await.disagreements.push(ip);
+        }
+    }
+
+    fn sim_chain(sim: &Simulator) -> Vec<ChainHeader> {
+        (0..=sim.height())
+            .map(|height| ChainHeader {
+                height,
+                header_hash: sim.header_hash_of(height).expect("missing header hash"),
+                prev_header_hash: height
+                    .checked_sub(1)
+                    .and_then(|height| sim.header_hash_of(height))
+                    .unwrap_or_default(),
+                weight: u128::from(height + 1) * 1000,
+            })
+            .collect()
+    }
+
+    async fn header_wallet() -> anyhow::Result<TestWallet> {
+        TestWallet::new_with_options(
+            1000,
+            SyncOptions {
+                header_validation: true,
+                ..default_test_options()
+            },
+        )
+        .await
+    }
+
+    fn header_queue(
+        test: &TestWallet,
+        peers: &Arc<Mutex<FakePeers>>,
+    ) -> (
+        HeaderQueue<Arc<Mutex<FakePeers>>>,
+        mpsc::Receiver<SyncEvent>,
+    ) {
+        let (sync_sender, sync_receiver) = mpsc::channel(10);
+
+        let queue = HeaderQueue::new(
+            test.wallet.db.clone(),
+            test.wallet.fingerprint,
+            peers.clone(),
+            sync_sender,
+            test.sender.clone(),
+        );
+
+        (queue, sync_receiver)
+    }
+
+    async fn set_chain(peers: &Arc<Mutex<FakePeers>>, ip: IpAddr, chain: Vec<ChainHeader>) {
+        peers.lock().await.chains.insert(ip, chain);
+    }
+
+    async fn validated_height(test: &TestWallet) -> anyhow::Result<Option<u32>> {
+        Ok(test
+            .wallet
+            .db
+            .validated_peak()
+            .await?
+            .map(|block| block.height))
+    }
+
+    #[test(tokio::test)]
+    async fn test_header_queue_anchors_and_extends() -> anyhow::Result<()> {
+        let test = header_wallet().await?;
+        let peers = Arc::new(Mutex::new(FakePeers::default()));
+        let (mut queue, _events) = header_queue(&test, &peers);
+
+        let chain = sim_chain(&test.sim.lock().await);
+        let anchor = *chain.last().expect("no headers");
+        set_chain(&peers, PEER_A, chain).await;
+
+        queue.process_batch().await?;
+
+        let validated = test
+            .wallet
+            .db
+            .validated_peak()
+            .await?
+            .expect("not anchored");
+        assert_eq!(validated.height, anchor.height);
+        assert_eq!(validated.header_hash, anchor.header_hash);
+
+        // The chain is only extended by one batch of headers at a time.
+        let chain = {
+            let mut sim = test.sim.lock().await;
+            for _ in 0..HEADER_BATCH_SIZE + 5 {
+                sim.create_block();
+            }
+            sim_chain(&sim)
+        };
+        let peak = *chain.last().expect("no headers");
+        set_chain(&peers, PEER_A, chain).await;
+
+        queue.process_batch().await?;
+        assert_eq!(
+            validated_height(&test).await?,
+            Some(anchor.height + HEADER_BATCH_SIZE)
+        );
+
+        queue.process_batch().await?;
+        let validated = test
+            .wallet
+            .db
+            .validated_peak()
+            .await?
+            .expect("not anchored");
+        assert_eq!(validated.height, peak.height);
+        assert_eq!(validated.header_hash, peak.header_hash);
+
+        assert!(peers.lock().await.banned.is_empty());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_header_queue_bans_unlinked_headers() -> anyhow::Result<()> {
+        let test = header_wallet().await?;
+        let peers = Arc::new(Mutex::new(FakePeers::default()));
+        let (mut queue, _events) = header_queue(&test, &peers);
+
+        set_chain(&peers, PEER_A, sim_chain(&test.sim.lock().await)).await;
+        queue.process_batch().await?;
+        let anchor_height = validated_height(&test).await?;
+
+        let mut chain = {
+            let mut sim = test.sim.lock().await;
+            sim.create_block();
+            sim.create_block();
+            sim_chain(&sim)
+        };
+        chain.last_mut().expect("no headers").prev_header_hash = Bytes32::new([1; 32]);
+        set_chain(&peers, PEER_A, chain).await;
+
+        queue.process_batch().await?;
+
+        assert_eq!(peers.lock().await.banned, vec![PEER_A]);
+        assert_eq!(validated_height(&test).await?, anchor_height);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_header_queue_bans_weight_that_does_not_increase() -> anyhow::Result<()> {
+        let test = header_wallet().await?;
+        let peers = Arc::new(Mutex::new(FakePeers::default()));
+        let (mut queue, _events) = header_queue(&test, &peers);
+
+        set_chain(&peers, PEER_A, sim_chain(&test.sim.lock().await)).await;
+        queue.process_batch().await?;
+        let anchor_height = validated_height(&test).await?;
+
+        let mut chain = {
+            let mut sim = test.sim.lock().await;
+            sim.create_block();
+            sim.create_block();
+            sim_chain(&sim)
+        };
+        let parent_weight = chain[chain.len() - 2].weight;
+        chain.last_mut().expect("no headers").weight = parent_weight;
+        set_chain(&peers, PEER_A, chain).await;
+
+        queue.process_batch().await?;
+
+        assert_eq!(peers.lock().await.banned, vec![PEER_A]);
+        assert_eq!(validated_height(&test).await?, anchor_height);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_header_queue_rolls_back_to_confirmed_fork() -> anyhow::Result<()> {
+        let mut test = header_wallet().await?;
+        let peers = Arc::new(Mutex::new(FakePeers::default()));
+        let (mut queue, mut events) = header_queue(&test, &peers);
+
+        let snapshot = {
+            let mut sim = test.sim.lock().await;
+            sim.create_block();
+            sim.create_block();
+            sim.clone()
+        };
+        let fork_height = snapshot.height();
+
+        set_chain(&peers, PEER_A, sim_chain(&snapshot)).await;
+        set_chain(&peers, PEER_B, sim_chain(&snapshot)).await;
+        queue.process_batch().await?;
+
+        // The simulator records coins as created at the height before the block they're in, so
+        // the spend goes in the block after the next one for its coins to be above the fork.
+        test.sim.lock().await.create_block();
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(test.puzzle_hash, 1000)], 0, vec![], None)
+            .await?;
+
+        let spend_bundle = test
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &test.agg_sig,
+                test.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        test.push_bundle(spend_bundle.clone()).await?;
+        test.wait_for_coins().await;
+
+        let coin = test.wallet.db.selectable_xch_coins().await?[0];
+        assert_eq!(
+            coin_created_height(&test, coin.coin_id()).await?,
+            Some(fork_height + 1)
+        );
+
+        let chain = sim_chain(&test.sim.lock().await);
+        set_chain(&peers, PEER_A, chain.clone()).await;
+        set_chain(&peers, PEER_B, chain).await;
+        queue.process_batch().await?;
+        assert_eq!(validated_height(&test).await?, Some(fork_height + 2));
+
+        // Replace the chain with a heavier fork, which includes the same spend in a later block
+        // than the original chain did.
+        let fork = {
+            let mut sim = test.sim.lock().await;
+            *sim = snapshot;
+            sim.new_coin(Bytes32::default(), 1);
+            sim.create_block();
+            sim.create_block();
+            sim.new_transaction(spend_bundle)?;
+            sim_chain(&sim)
+        };
+        test.wait_for_coins().await;
+
+        // A single peer can't cause a rollback until another peer has the same chain.
+        set_chain(&peers, PEER_A, fork.clone()).await;
+        queue.process_batch().await?;
+
+        assert!(events.try_recv().is_err());
+        assert_eq!(validated_height(&test).await?, Some(fork_height + 2));
+
+        set_chain(&peers, PEER_B, fork.clone()).await;
+        queue.process_batch().await?;
+
+        assert!(matches!(
+            events.try_recv(),
+            Ok(SyncEvent::Reorg { fork_height: height }) if height == fork_height
+        ));
+
+        let validated = test
+            .wallet
+            .db
+            .validated_peak()
+            .await?
+            .expect("not anchored");
+        let tip = fork.last().expect("no headers");
+        assert_eq!(validated.height, tip.height);
+        assert_eq!(validated.header_hash, tip.header_hash);
+
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.xch_balance().await?, 1000);
+        assert_eq!(
+            coin_created_height(&test, coin.coin_id()).await?,
+            Some(fork_height + 2)
+        );
+
+        let peers = peers.lock().await;
+        assert!(peers.banned.is_empty());
+        assert!(peers.disagreements.is_empty());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_new_peak_does_not_roll_back_validated_chain() -> anyhow::Result<()> {
+        let mut test = header_wallet().await?;
+        let peers = Arc::new(Mutex::new(FakePeers::default()));
+        let (mut queue, mut events) = header_queue(&test, &peers);
+
+        let chain = {
+            let mut sim = test.sim.lock().await;
+            sim.create_block();
+            sim.create_block();
+            sim_chain(&sim)
+        };
+        let peak = *chain.last().expect("no headers");
+        set_chain(&peers, PEER_A, chain.clone()).await;
+        set_chain(&peers, PEER_B, chain).await;
+        queue.process_batch().await?;
+
+        // Without header validation, a peak at the same height with a different header hash
+        // would roll the wallet back.
+        for (header_hash, fork_point) in [
+            (peak.header_hash, peak.height - 1),
+            (Bytes32::new([2; 32]), peak.height - 2),
+        ] {
+            test.sender
+                .send(SyncCommand::HandleMessage {
+                    ip: test.peer.socket_addr().ip(),
+                    message: new_peak_message(header_hash, peak.height, fork_point)?,
+                })
+                .await?;
+        }
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(test.puzzle_hash, 1000)], 0, vec![], None)
+            .await?;
+        test.transact(coin_spends).await?;
+
+        test.consume_until(|event| {
+            assert!(!matches!(event, SyncEvent::Reorg { .. }));
+            matches!(event, SyncEvent::CoinsUpdated)
+        })
+        .await;
+
+        let validated = test
+            .wallet
+            .db
+            .validated_block(peak.height)
+            .await?
+            .expect("not validated");
+        assert_eq!(validated.header_hash, peak.header_hash);
+
+        // The header queue still follows the chain as it's extended.
+        let chain = sim_chain(&test.sim.lock().await);
+        set_chain(&peers, PEER_A, chain.clone()).await;
+        set_chain(&peers, PEER_B, chain).await;
+        queue.process_batch().await?;
+
+        assert!(events.try_recv().is_err());
+        assert_eq!(
+            validated_height(&test).await?,
+            Some(test.sim.lock().await.height())
+        );
+        assert_eq!(test.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+
+    async fn coin_created_height(
+        test: &TestWallet,
+        coin_id: Bytes32,
+    ) -> anyhow::Result<Option<u32>> {
+        Ok(test
+            .wallet
+            .db
+            .coins_by_ids(&[coin_id.to_string()])
+            .await?
+            .first()
+            .and_then(|row| row.created_height))
+    }
+}
@@ -0,0 +1,494 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use chia_wallet_sdk::{
+    chia::{
+        consensus::{
+            spendbundle_conditions::get_conditions_from_spendbundle, validation_error::ErrorCode,
+        },
+        puzzle_types::nft::NftMetadata,
+    },
+    prelude::*,
+};
+use indexmap::IndexMap;
+use sage_database::{MintJob, MintJobItemStatus};
+use tokio::{
+    sync::{Mutex, mpsc},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{
+    PeerState, SyncCommand, SyncEvent, Transaction, Wallet, WalletError, WalletNftMint,
+    insert_transaction,
+};
+
+#[derive(Debug)]
+pub struct MintJobQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    state: Arc<Mutex<PeerState>>,
+    command_sender: mpsc::Sender<SyncCommand>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+}
+
+impl MintJobQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        state: Arc<Mutex<PeerState>>,
+        command_sender: mpsc::Sender<SyncCommand>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            state,
+            command_sender,
+            sync_sender,
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        if self.state.lock().await.peer_count() == 0 {
+            return Ok(());
+        }
+
+        let mempool_items: HashSet<Bytes32> = self
+            .wallet
+            .db
+            .mempool_items()
+            .await?
+            .into_iter()
+            .map(|item| item.hash)
+            .collect();
+
+        for mint_job in self.wallet.db.mint_jobs().await? {
+            if !mint_job.is_active {
+                continue;
+            }
+
+            // Each batch spends the DID, so the next one can't be built until the previous one is resolved.
+            if mint_job.submitted_count > 0 {
+                self.resolve_submitted(mint_job.id, &mempool_items).await?;
+                continue;
+            }
+
+            if mint_job.is_complete() {
+                info!(
+                    "Deactivating mint job {}, since all of its NFTs have been minted",
+                    mint_job.id
+                );
+
+                self.wallet
+                    .db
+                    .set_mint_job_active(mint_job.id, false)
+                    .await?;
+
+                self.sync_sender
+                    .send(SyncEvent::MintJobUpdated {
+                        mint_job_id: mint_job.id,
+                    })
+                    .await
+                    .ok();
+
+                continue;
+            }
+
+            match self.submit_batch(mint_job).await {
+                Ok(Some(transaction_id)) => {
+                    info!(
+                        "Submitted transaction {transaction_id} for mint job {}",
+                        mint_job.id
+                    );
+
+                    self.sync_sender
+                        .send(SyncEvent::MintJobUpdated {
+                            mint_job_id: mint_job.id,
+                        })
+                        .await
+                        .ok();
+
+                    // Batches for other mint jobs may rely on the same XCH coins for fees,
+                    // so only a single transaction is submitted at a time.
+                    return Ok(());
+                }
+                Ok(None) => return Ok(()),
+                Err(error) => {
+                    warn!(
+                        "Failed to submit the next batch of mint job {}: {error}",
+                        mint_job.id
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn resolve_submitted(
+        &self,
+        mint_job_id: u32,
+        mempool_items: &HashSet<Bytes32>,
+    ) -> Result<(), WalletError> {
+        let mut transactions = IndexMap::new();
+
+        for item in self
+            .wallet
+            .db
+            .mint_job_items(mint_job_id, Some(MintJobItemStatus::Submitted), None)
+            .await?
+        {
+            if let (Some(transaction_id), Some(launcher_id)) =
+                (item.transaction_id, item.launcher_id)
+            {
+                transactions.entry(transaction_id).or_insert(launcher_id);
+            }
+        }
+
+        for (transaction_id, launcher_id) in transactions {
+            if mempool_items.contains(&transaction_id) {
+                continue;
+            }
+
+            let Some(peer) = self.state.lock().await.acquire_peer() else {
+                return Ok(());
+            };
+
+            // The mempool item is removed once the transaction either confirms or fails,
+            // and the launcher only exists on-chain if it confirmed.
+            if peer
+                .fetch_optional_coin(launcher_id, self.wallet.genesis_challenge)
+                .await?
+                .is_some()
+            {
+                self.wallet
+                    .db
+                    .complete_mint_job_items(transaction_id)
+                    .await?;
+            } else {
+                warn!(
+                    "Transaction {transaction_id} for mint job {mint_job_id} failed, so its NFTs will be minted again"
+                );
+
+                self.wallet.db.reset_mint_job_items(transaction_id).await?;
+            }
+
+            self.sync_sender
+                .send(SyncEvent::MintJobUpdated { mint_job_id })
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn submit_batch(&self, mint_job: MintJob) -> Result<Option<Bytes32>, WalletError> {
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(None);
+        };
+
+        let height = self
+            .wallet
+            .db
+            .latest_peak()
+            .await?
+            .map_or(0, |(height, _)| height);
+
+        let mut batch_size = mint_job.batch_size;
+
+        // The batch size is a maximum, since large metadata can make a batch too expensive
+        // for the mempool to accept. In that case, fewer NFTs are minted at a time.
+        let (items, coin_spends, nfts) = loop {
+            let items = self
+                .wallet
+                .db
+                .mint_job_items(
+                    mint_job.id,
+                    Some(MintJobItemStatus::Pending),
+                    Some(batch_size),
+                )
+                .await?;
+
+            let mut allocator = Allocator::new();
+            let mut mints = Vec::with_capacity(items.len());
+
+            for item in &items {
+                let metadata = item.metadata.to_clvm(&mut allocator)?;
+
+                mints.push(WalletNftMint {
+                    metadata: NftMetadata::from_clvm(&allocator, metadata)?,
+                    p2_puzzle_hash: item.p2_puzzle_hash,
+                    royalty_puzzle_hash: item.royalty_puzzle_hash,
+                    royalty_basis_points: item.royalty_basis_points,
+                });
+            }
+
+            let (coin_spends, nfts) = self
+                .wallet
+                .bulk_mint_nfts(mint_job.fee, mint_job.did_id, mints)
+                .await?;
+
+            if batch_size == 1 || !exceeds_max_cost(&coin_spends, height) {
+                break (items, coin_spends, nfts);
+            }
+
+            batch_size /= 2;
+
+            info!(
+                "Batch of mint job {} exceeds the maximum cost, so it's reduced to {batch_size} NFTs",
+                mint_job.id
+            );
+        };
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        let transaction_id = spend_bundle.name();
+
+        // The rows are marked as submitted first, so that if we crash before the transaction
+        // makes it into the mempool, they are reset rather than minted a second time.
+        let mut tx = self.wallet.db.tx().await?;
+
+        for (item, nft) in items.iter().zip(&nfts) {
+            tx.submit_mint_job_item(
+                mint_job.id,
+                item.row_index,
+                transaction_id,
+                nft.info.launcher_id,
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        let subscriptions = insert_transaction(
+            &self.wallet.db,
+            &peer,
+            self.wallet.genesis_challenge,
+            transaction_id,
+            Transaction::from_coin_spends(spend_bundle.coin_spends)?,
+            spend_bundle.aggregated_signature,
+        )
+        .await?;
+
+        self.command_sender
+            .send(SyncCommand::SubscribeCoins {
+                fingerprint: self.wallet.fingerprint,
+                coin_ids: subscriptions,
+            })
+            .await
+            .ok();
+
+        Ok(Some(transaction_id))
+    }
+}
+
+/// The maximum cost of a transaction which the mempool accepts, which is half of the
+/// maximum cost of a block.
+pub const MAX_SPEND_BUNDLE_COST: u64 = 5_500_000_000;
+
+/// Whether the transaction costs more than the mempool accepts. The signature isn't validated,
+/// so this can be checked before signing.
+fn exceeds_max_cost(coin_spends: &[CoinSpend], height: u32) -> bool {
+    let mut allocator = Allocator::new();
+
+    matches!(
+        get_conditions_from_spendbundle(
+            &mut allocator,
+            &SpendBundle::new(coin_spends.to_vec(), Signature::default()),
+            MAX_SPEND_BUNDLE_COST,
+            height,
+            &MAINNET_CONSTANTS,
+        ),
+        Err(error) if error.1 == ErrorCode::CostExceeded
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::TestWallet;
+
+    use super::*;
+
+    fn queue(test: &TestWallet) -> (MintJobQueue, mpsc::Receiver<SyncEvent>) {
+        let (sync_sender, sync_receiver) = mpsc::channel(10);
+
+        let queue = MintJobQueue::new(
+            test.wallet.clone(),
+            test.master_sk.clone(),
+            test.state.clone(),
+            test.sender.clone(),
+            sync_sender,
+        );
+
+        (queue, sync_receiver)
+    }
+
+    fn mints(count: u64) -> Vec<WalletNftMint> {
+        (1..=count)
+            .map(|edition_number| WalletNftMint {
+                metadata: NftMetadata {
+                    edition_number,
+                    edition_total: count,
+                    ..Default::default()
+                },
+                p2_puzzle_hash: None,
+                royalty_puzzle_hash: Some(Bytes32::default()),
+                royalty_basis_points: 300,
+            })
+            .collect()
+    }
+
+    #[test(tokio::test)]
+    async fn test_mint_job_batches() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(4).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mint_job_id = test
+            .wallet
+            .create_mint_job(did.info.launcher_id, 0, 2, mints(3))
+            .await?;
+
+        let (mut queue, mut events) = queue(&test);
+
+        for minted_count in [2, 3] {
+            queue.process_batch().await?;
+            test.wait_for_coins().await;
+
+            assert!(matches!(
+                events.try_recv()?,
+                SyncEvent::MintJobUpdated { .. }
+            ));
+
+            let mint_job = test.wallet.db.mint_job(mint_job_id).await?.expect("job");
+            assert_eq!(
+                mint_job.submitted_count,
+                minted_count - mint_job.minted_count
+            );
+
+            queue.process_batch().await?;
+
+            let mint_job = test.wallet.db.mint_job(mint_job_id).await?.expect("job");
+            assert_eq!(mint_job.submitted_count, 0);
+            assert_eq!(mint_job.minted_count, minted_count);
+        }
+
+        queue.process_batch().await?;
+
+        let mint_job = test.wallet.db.mint_job(mint_job_id).await?.expect("job");
+        assert!(mint_job.is_complete());
+        assert!(!mint_job.is_active);
+
+        let items = test
+            .wallet
+            .db
+            .mint_job_items(mint_job_id, None, None)
+            .await?;
+
+        assert_eq!(items.len(), 3);
+
+        for item in items {
+            let launcher_id = item.launcher_id.expect("launcher id");
+            assert_eq!(item.status, MintJobItemStatus::Minted);
+            assert!(test.wallet.db.nft(launcher_id).await?.is_some());
+        }
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_exceeds_max_cost() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(2).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let (coin_spends, _) = test
+            .wallet
+            .bulk_mint_nfts(0, did.info.launcher_id, mints(1))
+            .await?;
+        assert!(!exceeds_max_cost(&coin_spends, 0));
+
+        // Every byte of the transaction costs 12,000, so this is too large on its own.
+        let mut mints = mints(1);
+        mints[0].metadata.data_uris = vec!["a".repeat(500_000)];
+
+        let (coin_spends, _) = test
+            .wallet
+            .bulk_mint_nfts(0, did.info.launcher_id, mints)
+            .await?;
+        assert!(exceeds_max_cost(&coin_spends, 0));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_mint_job_resets_failed_batch() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(3).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mint_job_id = test
+            .wallet
+            .create_mint_job(did.info.launcher_id, 0, 10, mints(2))
+            .await?;
+
+        // Simulate a crash after the rows were marked as submitted, but before the transaction was sent.
+        let mut tx = test.wallet.db.tx().await?;
+        for row_index in 0..2 {
+            tx.submit_mint_job_item(
+                mint_job_id,
+                row_index,
+                Bytes32::new([1; 32]),
+                Bytes32::new([2; 32]),
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        let (mut queue, _events) = queue(&test);
+
+        queue.process_batch().await?;
+
+        let items = test
+            .wallet
+            .db
+            .mint_job_items(mint_job_id, Some(MintJobItemStatus::Pending), None)
+            .await?;
+
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|item| item.launcher_id.is_none()));
+
+        // The rows are picked up again on the next run.
+        queue.process_batch().await?;
+        test.wait_for_coins().await;
+        queue.process_batch().await?;
+
+        let mint_job = test.wallet.db.mint_job(mint_job_id).await?.expect("job");
+        assert_eq!(mint_job.minted_count, 2);
+
+        Ok(())
+    }
+}
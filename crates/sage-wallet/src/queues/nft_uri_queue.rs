@@ -10,20 +10,26 @@ use tokio::{
 };
 use tracing::{debug, info, warn};
 
-use crate::{SyncEvent, WalletError, compute_nft_info};
-use chia_wallet_sdk::types::TESTNET11_CONSTANTS;
+use crate::{SyncEvent, SyncPhase, SyncProgress, WalletError, compute_nft_info};
 use sage_config::Network;
 #[derive(Debug)]
 pub struct NftUriQueue {
     db: Database,
+    progress: SyncProgress,
     sync_sender: mpsc::Sender<SyncEvent>,
     network: Network,
 }
 
 impl NftUriQueue {
-    pub fn new(db: Database, sync_sender: mpsc::Sender<SyncEvent>, network: Network) -> Self {
+    pub fn new(
+        db: Database,
+        progress: SyncProgress,
+        sync_sender: mpsc::Sender<SyncEvent>,
+        network: Network,
+    ) -> Self {
         Self {
             db,
+            progress,
             sync_sender,
             network,
         }
@@ -45,13 +51,16 @@ impl NftUriQueue {
 
         info!("Processing batch of {} NFT URIs", batch.len());
 
+        let checked = batch.len() as u64;
+
         let mut futures = FuturesUnordered::new();
-        let testnet = self.network.genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let services = self.network.services();
+        let mintgarden_url = services.mintgarden_assets.as_deref();
         for item in batch {
             futures.push(async move {
                 let result = timeout(
                     Duration::from_secs(15),
-                    fetch_uri(item.uri.clone(), testnet),
+                    fetch_uri(item.uri.clone(), mintgarden_url),
                 )
                 .await;
                 (item, result)
@@ -134,6 +143,21 @@ impl NftUriQueue {
 
         self.sync_sender.send(SyncEvent::NftData).await.ok();
 
+        let remaining = self
+            .db
+            .total_files()
+            .await?
+            .saturating_sub(self.db.checked_files().await?);
+
+        self.progress
+            .record(
+                SyncPhase::NftUris,
+                checked,
+                Some(remaining),
+                &self.sync_sender,
+            )
+            .await;
+
         Ok(())
     }
 }
@@ -10,16 +10,18 @@ use tokio::{
 use tracing::{debug, info, warn};
 
 use crate::{
-    ChildKind, PeerState, PuzzleContext, SyncCommand, SyncEvent, WalletError, WalletPeer,
-    database::insert_puzzle, validate_wallet_coin,
+    ChildKind, PeerState, PuzzleContext, SyncCommand, SyncEvent, SyncPhase, SyncProgress,
+    WalletError, WalletPeer, database::insert_puzzle, validate_wallet_coin,
 };
 
 #[derive(Debug)]
 pub struct PuzzleQueue {
     db: Database,
+    fingerprint: u32,
     genesis_challenge: Bytes32,
     batch_size_per_peer: usize,
     state: Arc<Mutex<PeerState>>,
+    progress: SyncProgress,
     sync_sender: mpsc::Sender<SyncEvent>,
     command_sender: mpsc::Sender<SyncCommand>,
 }
@@ -27,17 +29,21 @@ pub struct PuzzleQueue {
 impl PuzzleQueue {
     pub fn new(
         db: Database,
+        fingerprint: u32,
         genesis_challenge: Bytes32,
         batch_size_per_peer: usize,
         state: Arc<Mutex<PeerState>>,
+        progress: SyncProgress,
         sync_sender: mpsc::Sender<SyncEvent>,
         command_sender: mpsc::Sender<SyncCommand>,
     ) -> Self {
         Self {
             db,
+            fingerprint,
             genesis_challenge,
             batch_size_per_peer,
             state,
+            progress,
             sync_sender,
             command_sender,
         }
@@ -98,10 +104,13 @@ impl PuzzleQueue {
 
         let mut subscriptions = Vec::new();
         let mut send_events = false;
+        let mut looked_up = 0;
 
         while let Some((addr, root, synced_coins)) = futures.next().await {
             match synced_coins {
                 Ok(synced_coins) => {
+                    looked_up += 1;
+
                     let mut tx = self.db.tx().await?;
 
                     if root.is_children_unsynced {
@@ -238,6 +247,7 @@ impl PuzzleQueue {
         if send_events {
             self.command_sender
                 .send(SyncCommand::SubscribeCoins {
+                    fingerprint: self.fingerprint,
                     coin_ids: subscriptions,
                 })
                 .await
@@ -248,6 +258,22 @@ impl PuzzleQueue {
                 .await
                 .ok();
         }
+
+        let remaining = self
+            .db
+            .total_coin_count()
+            .await?
+            .saturating_sub(self.db.synced_coin_count().await?);
+
+        self.progress
+            .record(
+                SyncPhase::PuzzleLookups,
+                looked_up,
+                Some(remaining.into()),
+                &self.sync_sender,
+            )
+            .await;
+
         Ok(())
     }
 }
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use chia_wallet_sdk::prelude::*;
+use sage_assets::{
+    FileReputationSource, HttpReputationSource, ReputationError, ReputationList,
+    ReputationSource as _,
+};
+use sage_config::ReputationSource;
+use sage_database::Database;
+use tokio::{
+    sync::mpsc,
+    time::{sleep, timeout},
+};
+use tracing::{info, warn};
+
+use crate::{SyncEvent, WalletError};
+
+/// Keeps the collection reputations in the database in sync with the configured allowlists
+/// and blocklists. The lists are replaced as a whole, so entries which have been removed from
+/// a source stop applying the next time it's fetched.
+#[derive(Debug)]
+pub struct ReputationQueue {
+    db: Database,
+    sources: Vec<ReputationSource>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+}
+
+impl ReputationQueue {
+    pub fn new(
+        db: Database,
+        sources: Vec<ReputationSource>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            db,
+            sources,
+            sync_sender,
+        }
+    }
+
+    pub async fn start(self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&self) -> Result<(), WalletError> {
+        let mut lists = Vec::new();
+
+        for source in &self.sources {
+            match timeout(Duration::from_secs(30), fetch_list(source)).await {
+                Ok(Ok(list)) => lists.push(list),
+                Ok(Err(error)) => {
+                    // Applying a partial set of lists could unhide NFTs which are still blocked.
+                    warn!("Failed to fetch reputation list from {source:?}: {error}");
+                    return Ok(());
+                }
+                Err(_) => {
+                    warn!("Timed out fetching reputation list from {source:?}");
+                    return Ok(());
+                }
+            }
+        }
+
+        let mut tx = self.db.tx().await?;
+
+        tx.clear_collection_reputations().await?;
+
+        for list in &lists {
+            for (entry, is_blocked) in list
+                .allow
+                .iter()
+                .map(|entry| (entry, false))
+                .chain(list.block.iter().map(|entry| (entry, true)))
+            {
+                let Some(hash) = parse_entry(entry) else {
+                    warn!("Skipping invalid reputation list entry {entry}");
+                    continue;
+                };
+
+                tx.insert_collection_reputation(hash, is_blocked).await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        info!(
+            "Updated collection reputations from {} sources",
+            self.sources.len()
+        );
+
+        self.sync_sender.send(SyncEvent::NftData).await.ok();
+
+        Ok(())
+    }
+}
+
+async fn fetch_list(source: &ReputationSource) -> Result<ReputationList, ReputationError> {
+    match source {
+        ReputationSource::File { path } => FileReputationSource::new(path).fetch_list().await,
+        ReputationSource::Http { url } => HttpReputationSource::new(url).fetch_list().await,
+    }
+}
+
+/// Entries are either collection ids or minter DIDs, both of which are stored by their hash.
+fn parse_entry(entry: &str) -> Option<Bytes32> {
+    let address = Address::decode(entry).ok()?;
+
+    if address.prefix != "col" && address.prefix != "did:chia:" {
+        return None;
+    }
+
+    Some(address.puzzle_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_wallet_sdk::chia::puzzle_types::nft::NftMetadata;
+    use sage_database::{NftRow, NftSortMode};
+    use test_log::test;
+
+    use crate::{TestWallet, WalletNftMint};
+
+    use super::*;
+
+    async fn owned_nfts(
+        db: &Database,
+        include_hidden: bool,
+        verified: Option<bool>,
+    ) -> anyhow::Result<Vec<NftRow>> {
+        let (nfts, _) = db
+            .owned_nfts(
+                None,
+                None,
+                NftSortMode::Recent,
+                include_hidden,
+                verified,
+                100,
+                0,
+            )
+            .await?;
+
+        Ok(nfts)
+    }
+
+    #[test(tokio::test)]
+    async fn test_reputation_blocklist() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(2).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let (coin_spends, _nfts) = alice
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![WalletNftMint {
+                    metadata: NftMetadata::default(),
+                    p2_puzzle_hash: None,
+                    royalty_puzzle_hash: None,
+                    royalty_basis_points: 0,
+                }],
+            )
+            .await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let db = alice.wallet.db.clone();
+
+        let nfts = owned_nfts(&db, false, None).await?;
+        assert_eq!(nfts.len(), 1);
+        assert_eq!(nfts[0].nft_info.minter_hash, Some(did.info.launcher_id));
+        assert_eq!(owned_nfts(&db, false, Some(true)).await?.len(), 0);
+
+        let did_id = Address::new(did.info.launcher_id, "did:chia:".to_string()).encode()?;
+        let path = std::env::temp_dir().join(format!("reputation-{}.json", std::process::id()));
+
+        let (sync_sender, _sync_receiver) = mpsc::channel(10);
+        let queue = ReputationQueue::new(
+            db.clone(),
+            vec![ReputationSource::File {
+                path: path.to_string_lossy().to_string(),
+            }],
+            sync_sender,
+        );
+
+        // The allowlisted DID marks the NFT as verified.
+        std::fs::write(&path, format!(r#"{{ "allow": ["{did_id}", "invalid"] }}"#))?;
+        queue.process_batch().await?;
+
+        assert_eq!(owned_nfts(&db, false, Some(true)).await?.len(), 1);
+        assert_eq!(owned_nfts(&db, false, Some(false)).await?.len(), 0);
+
+        // Blocking takes precedence, and hides the NFT unless hidden NFTs are included.
+        std::fs::write(
+            &path,
+            format!(r#"{{ "allow": ["{did_id}"], "block": ["{did_id}"] }}"#),
+        )?;
+        queue.process_batch().await?;
+
+        assert_eq!(owned_nfts(&db, false, None).await?.len(), 0);
+        let nfts = owned_nfts(&db, true, None).await?;
+        assert_eq!(nfts.len(), 1);
+        assert!(!nfts[0].asset.is_visible);
+        assert_eq!(owned_nfts(&db, true, Some(true)).await?.len(), 0);
+
+        // A source which can't be read leaves the previous lists in place.
+        std::fs::remove_file(&path)?;
+        queue.process_batch().await?;
+
+        assert_eq!(owned_nfts(&db, false, None).await?.len(), 0);
+
+        Ok(())
+    }
+}
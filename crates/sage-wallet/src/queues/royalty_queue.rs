@@ -0,0 +1,374 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use chia_wallet_sdk::{
+    chia::puzzle_types::{
+        cat::{CatArgs, CatSolution},
+        offer::SettlementPaymentsSolution,
+    },
+    prelude::*,
+    puzzles::SETTLEMENT_PAYMENT_HASH,
+    types::puzzles::{RevocationArgs, RevocationSolution},
+};
+use sage_database::{Database, RoyaltyCandidate};
+use tokio::{
+    sync::{Mutex, mpsc},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{PeerState, SyncEvent, WalletError, WalletPeer};
+
+/// The number of times checking a coin can fail before it's given up on, so that coins which
+/// can't be checked don't get retried forever.
+const MAX_ROYALTY_CHECK_FAILURES: u32 = 5;
+
+/// Checks received coins for royalty payments from offer settlements.
+/// Royalties are paid with the launcher id of the NFT as the nonce, whereas
+/// regular offer payments use a hash of the offered coin ids instead.
+#[derive(Debug)]
+pub struct RoyaltyQueue {
+    db: Database,
+    genesis_challenge: Bytes32,
+    state: Arc<Mutex<PeerState>>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+}
+
+impl RoyaltyQueue {
+    pub fn new(
+        db: Database,
+        genesis_challenge: Bytes32,
+        state: Arc<Mutex<PeerState>>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            db,
+            genesis_challenge,
+            state,
+            sync_sender,
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        let Some(peer) = self.state.lock().await.acquire_peer() else {
+            return Ok(());
+        };
+
+        let candidates = self.db.unchecked_royalty_coins(100).await?;
+
+        if candidates.is_empty() {
+            return Ok(());
+        }
+
+        let parent_coin_ids: HashSet<Bytes32> = candidates
+            .iter()
+            .map(|candidate| candidate.coin.parent_coin_info)
+            .collect();
+
+        let parents: HashMap<Bytes32, CoinState> = match peer
+            .fetch_coins(
+                parent_coin_ids.into_iter().collect(),
+                self.genesis_challenge,
+            )
+            .await
+        {
+            Ok(coin_states) => coin_states
+                .into_iter()
+                .map(|coin_state| (coin_state.coin.coin_id(), coin_state))
+                .collect(),
+            Err(error) => {
+                warn!("Failed to fetch royalty payment parents: {error}");
+                return Ok(());
+            }
+        };
+
+        let mut checked = Vec::new();
+        let mut failed = Vec::new();
+        let mut payments = Vec::new();
+
+        for candidate in candidates {
+            let coin_id = candidate.coin.coin_id();
+
+            // Only coins created by a settlement payment can be royalties, so most coins can be
+            // ruled out without fetching the parent spend.
+            let Some(parent) = parents.get(&candidate.coin.parent_coin_info) else {
+                checked.push(coin_id);
+                continue;
+            };
+
+            if parent.coin.puzzle_hash
+                != wrapped_puzzle_hash(&candidate, SETTLEMENT_PAYMENT_HASH.into())
+            {
+                checked.push(coin_id);
+                continue;
+            }
+
+            let Some(spent_height) = parent.spent_height else {
+                checked.push(coin_id);
+                continue;
+            };
+
+            match self
+                .find_royalty(&peer, &candidate, parent.coin, spent_height)
+                .await
+            {
+                Ok(launcher_id) => {
+                    checked.push(coin_id);
+
+                    if let Some(launcher_id) = launcher_id {
+                        payments.push((coin_id, launcher_id));
+                    }
+                }
+                Err(error) => {
+                    warn!("Failed to check coin {coin_id} for a royalty payment: {error}");
+                    failed.push(coin_id);
+                }
+            }
+        }
+
+        let mut tx = self.db.tx().await?;
+
+        for &(coin_id, launcher_id) in &payments {
+            info!("Received royalty payment {coin_id} for NFT {launcher_id}");
+            tx.insert_royalty_payment(coin_id, launcher_id).await?;
+        }
+
+        for coin_id in checked {
+            tx.set_royalty_checked(coin_id).await?;
+        }
+
+        for coin_id in failed {
+            tx.record_royalty_check_failure(coin_id, MAX_ROYALTY_CHECK_FAILURES)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        if !payments.is_empty() {
+            self.sync_sender.send(SyncEvent::CoinsUpdated).await.ok();
+        }
+
+        Ok(())
+    }
+
+    async fn find_royalty(
+        &self,
+        peer: &WalletPeer,
+        candidate: &RoyaltyCandidate,
+        parent: Coin,
+        spent_height: u32,
+    ) -> Result<Option<Bytes32>, WalletError> {
+        let (puzzle_reveal, solution) = peer
+            .fetch_puzzle_solution(parent.coin_id(), spent_height)
+            .await?;
+
+        let coin_spend = CoinSpend::new(parent, puzzle_reveal, solution);
+
+        let Some((nonce, payment_puzzle_hash)) = settlement_nonce(candidate, &coin_spend)? else {
+            return Ok(None);
+        };
+
+        // The nonce alone can be chosen by anyone, so the payment is only a royalty if it was
+        // sent to the royalty address of the NFT with that launcher id.
+        let Some(royalty_puzzle_hash) = self.nft_royalty_puzzle_hash(peer, nonce).await? else {
+            return Ok(None);
+        };
+
+        if payment_puzzle_hash != royalty_puzzle_hash {
+            return Ok(None);
+        }
+
+        Ok(Some(nonce))
+    }
+
+    /// Looks up the royalty puzzle hash of an NFT from its eve spend, since it can't change
+    /// after the NFT is minted. An NFT that was never spent can't have been sold either.
+    async fn nft_royalty_puzzle_hash(
+        &self,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+    ) -> Result<Option<Bytes32>, WalletError> {
+        let Some(child) = peer.try_fetch_singleton_child(launcher_id).await? else {
+            return Ok(None);
+        };
+
+        let Some(spent_height) = child.spent_height else {
+            return Ok(None);
+        };
+
+        let (puzzle_reveal, _solution) = peer
+            .fetch_puzzle_solution(child.coin.coin_id(), spent_height)
+            .await?;
+
+        let mut allocator = Allocator::new();
+        let puzzle_reveal = puzzle_reveal.to_clvm(&mut allocator)?;
+        let puzzle = Puzzle::parse(&allocator, puzzle_reveal);
+
+        let Some((nft_info, _p2_puzzle)) = NftInfo::parse(&allocator, puzzle).ok().flatten() else {
+            return Ok(None);
+        };
+
+        if nft_info.launcher_id != launcher_id {
+            return Ok(None);
+        }
+
+        Ok(Some(nft_info.royalty_puzzle_hash))
+    }
+}
+
+/// Wraps an inner puzzle hash in the same layers as the candidate coin's asset.
+fn wrapped_puzzle_hash(candidate: &RoyaltyCandidate, inner_puzzle_hash: Bytes32) -> Bytes32 {
+    if candidate.asset_id == Bytes32::default() {
+        return inner_puzzle_hash;
+    }
+
+    let inner_puzzle_hash = if let Some(hidden_puzzle_hash) = candidate.hidden_puzzle_hash {
+        RevocationArgs::new(hidden_puzzle_hash, inner_puzzle_hash).curry_tree_hash()
+    } else {
+        TreeHash::new(inner_puzzle_hash.to_bytes())
+    };
+
+    CatArgs::curry_tree_hash(candidate.asset_id, inner_puzzle_hash).into()
+}
+
+/// Finds the nonce and inner puzzle hash of the notarized payment in a settlement spend that
+/// created the candidate coin.
+fn settlement_nonce(
+    candidate: &RoyaltyCandidate,
+    coin_spend: &CoinSpend,
+) -> Result<Option<(Bytes32, Bytes32)>, WalletError> {
+    let mut allocator = Allocator::new();
+
+    let mut solution = coin_spend.solution.to_clvm(&mut allocator)?;
+
+    if candidate.asset_id != Bytes32::default() {
+        solution = CatSolution::<NodePtr>::from_clvm(&allocator, solution)?.inner_puzzle_solution;
+
+        if candidate.hidden_puzzle_hash.is_some() {
+            solution =
+                RevocationSolution::<NodePtr, NodePtr>::from_clvm(&allocator, solution)?.solution;
+        }
+    }
+
+    let solution = SettlementPaymentsSolution::from_clvm(&allocator, solution)?;
+
+    Ok(solution
+        .notarized_payments
+        .into_iter()
+        .find_map(|notarized_payment| {
+            notarized_payment
+                .payments
+                .iter()
+                .find(|payment| {
+                    payment.amount == candidate.coin.amount
+                        && wrapped_puzzle_hash(candidate, payment.puzzle_hash)
+                            == candidate.coin.puzzle_hash
+                })
+                .map(|payment| (notarized_payment.nonce, payment.puzzle_hash))
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_wallet_sdk::chia::puzzle_types::nft::NftMetadata;
+    use test_log::test;
+
+    use crate::{Offered, Requested, TestWallet, WalletNftMint};
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_royalty_payment() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(2).await?;
+        let mut bob = alice.next(1030).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let (coin_spends, mut nfts) = alice
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![WalletNftMint {
+                    metadata: NftMetadata::default(),
+                    p2_puzzle_hash: None,
+                    royalty_puzzle_hash: Some(alice.puzzle_hash),
+                    royalty_basis_points: 300,
+                }],
+            )
+            .await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let nft = nfts.remove(0);
+
+        let offer = alice
+            .wallet
+            .make_offer(
+                Offered {
+                    nfts: vec![nft.info.launcher_id],
+                    ..Default::default()
+                },
+                Requested {
+                    xch: 1000,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        alice.wait_for_coins().await;
+        bob.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1030);
+
+        let (sync_sender, _sync_receiver) = mpsc::channel(10);
+        let mut queue = RoyaltyQueue::new(
+            alice.wallet.db.clone(),
+            alice.wallet.genesis_challenge,
+            alice.state.clone(),
+            sync_sender,
+        );
+
+        queue.process_batch().await?;
+
+        // The payment for the NFT itself also came from the settlement, but isn't a royalty.
+        let payments = alice.wallet.db.royalty_payments().await?;
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].launcher_id, nft.info.launcher_id);
+        assert_eq!(payments[0].coin.amount, 30);
+        assert!(
+            alice
+                .wallet
+                .db
+                .unchecked_royalty_coins(100)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,462 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chia_wallet_sdk::{
+    driver::{Offer, encode_offer},
+    prelude::*,
+};
+use indexmap::indexmap;
+use sage_database::{OfferRow, OfferStatus, StandingOffer};
+use tokio::{
+    sync::{Mutex, mpsc},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+use crate::{
+    Offered, PeerState, Requested, RequestedCat, SyncEvent, Wallet, WalletError, sort_offer,
+};
+
+#[derive(Debug)]
+pub struct StandingOfferQueue {
+    wallet: Arc<Wallet>,
+    master_sk: SecretKey,
+    state: Arc<Mutex<PeerState>>,
+    sync_sender: mpsc::Sender<SyncEvent>,
+}
+
+impl StandingOfferQueue {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        master_sk: SecretKey,
+        state: Arc<Mutex<PeerState>>,
+        sync_sender: mpsc::Sender<SyncEvent>,
+    ) -> Self {
+        Self {
+            wallet,
+            master_sk,
+            state,
+            sync_sender,
+        }
+    }
+
+    pub async fn start(mut self, delay: Duration) -> Result<(), WalletError> {
+        loop {
+            self.process_batch().await?;
+            sleep(delay).await;
+        }
+    }
+
+    async fn process_batch(&mut self) -> Result<(), WalletError> {
+        if self.state.lock().await.peer_count() == 0 {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        for mut standing_offer in self.wallet.db.standing_offers().await? {
+            if !standing_offer.is_active {
+                continue;
+            }
+
+            if let Some(offer_id) = standing_offer.current_offer_id {
+                // An offer which has been deleted can no longer be tracked.
+                let status = self
+                    .wallet
+                    .db
+                    .offer(offer_id)
+                    .await?
+                    .map_or(OfferStatus::Cancelled, |offer| offer.status);
+
+                if matches!(status, OfferStatus::Pending | OfferStatus::Active) {
+                    continue;
+                }
+
+                self.wallet
+                    .db
+                    .finish_standing_offer_history(offer_id, status, timestamp)
+                    .await?;
+
+                // If the offer was cancelled by hand, we shouldn't create another one.
+                if status == OfferStatus::Cancelled {
+                    info!(
+                        "Deactivating standing offer {}, since its offer was cancelled",
+                        standing_offer.id
+                    );
+
+                    self.wallet
+                        .db
+                        .set_standing_offer_active(standing_offer.id, false)
+                        .await?;
+
+                    continue;
+                }
+
+                if status == OfferStatus::Completed {
+                    standing_offer.fill_count += 1;
+                }
+            }
+
+            if !standing_offer.has_capacity() {
+                info!(
+                    "Deactivating standing offer {}, since it has reached its limits",
+                    standing_offer.id
+                );
+
+                self.wallet
+                    .db
+                    .set_standing_offer_active(standing_offer.id, false)
+                    .await?;
+
+                continue;
+            }
+
+            match self.create_offer(standing_offer, timestamp).await {
+                Ok(offer_id) => {
+                    self.sync_sender
+                        .send(SyncEvent::OfferUpdated {
+                            offer_id,
+                            status: OfferStatus::Active,
+                        })
+                        .await
+                        .ok();
+                }
+                Err(error) => {
+                    warn!(
+                        "Failed to refresh standing offer {}: {error}",
+                        standing_offer.id
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn create_offer(
+        &self,
+        standing_offer: StandingOffer,
+        timestamp: u64,
+    ) -> Result<Bytes32, WalletError> {
+        let mut offered = Offered {
+            fee: standing_offer.fee,
+            ..Default::default()
+        };
+
+        if standing_offer.offered_asset_id == Bytes32::default() {
+            offered.xch = standing_offer.offered_amount;
+        } else {
+            offered.cats = indexmap! {
+                standing_offer.offered_asset_id => standing_offer.offered_amount,
+            };
+        }
+
+        let mut requested = Requested::default();
+
+        if standing_offer.requested_asset_id == Bytes32::default() {
+            requested.xch = standing_offer.requested_amount;
+        } else {
+            let hidden_puzzle_hash = self
+                .wallet
+                .fetch_offer_cat_hidden_puzzle_hash(standing_offer.requested_asset_id)
+                .await?;
+
+            requested.cats = indexmap! {
+                standing_offer.requested_asset_id => RequestedCat {
+                    amount: standing_offer.requested_amount,
+                    hidden_puzzle_hash,
+                },
+            };
+        }
+
+        let expiration_timestamp = standing_offer
+            .expiration_seconds
+            .map(|seconds| timestamp + seconds);
+
+        let unsigned = self
+            .wallet
+            .make_offer(offered, requested, expiration_timestamp)
+            .await?;
+
+        let spend_bundle = self
+            .wallet
+            .sign_transaction(
+                unsigned,
+                &self.wallet.agg_sig_constants,
+                self.master_sk.clone(),
+                true,
+            )
+            .await?;
+
+        let spend_bundle = sort_offer(spend_bundle);
+        let offer_id = spend_bundle.name();
+        let encoded_offer = encode_offer(&spend_bundle)?;
+
+        let mut allocator = Allocator::new();
+        let offer = Offer::from_spend_bundle(&mut allocator, &spend_bundle)?;
+
+        let mut tx = self.wallet.db.tx().await?;
+
+        tx.insert_offer(OfferRow {
+            offer_id,
+            encoded_offer,
+            expiration_height: None,
+            expiration_timestamp,
+            fee: standing_offer.fee,
+            status: OfferStatus::Active,
+            inserted_timestamp: timestamp,
+        })
+        .await?;
+
+        for coin_spend in offer.cancellable_coin_spends()? {
+            tx.insert_offered_coin(offer_id, coin_spend.coin.coin_id())
+                .await?;
+        }
+
+        tx.insert_offer_asset(
+            offer_id,
+            standing_offer.offered_asset_id,
+            standing_offer.offered_amount,
+            0,
+            false,
+        )
+        .await?;
+
+        tx.insert_offer_asset(
+            offer_id,
+            standing_offer.requested_asset_id,
+            standing_offer.requested_amount,
+            0,
+            true,
+        )
+        .await?;
+
+        tx.insert_standing_offer_history(standing_offer.id, offer_id, timestamp)
+            .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "Created offer {offer_id} for standing offer {}",
+            standing_offer.id
+        );
+
+        Ok(offer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_wallet_sdk::driver::decode_offer;
+    use sage_database::{Asset, AssetKind, NewStandingOffer};
+    use test_log::test;
+    use tokio::time::timeout;
+
+    use crate::TestWallet;
+
+    use super::*;
+
+    async fn wait_for_status(
+        test: &TestWallet,
+        offer_id: Bytes32,
+        status: OfferStatus,
+    ) -> anyhow::Result<()> {
+        timeout(Duration::from_secs(10), async {
+            loop {
+                if test
+                    .wallet
+                    .db
+                    .offer(offer_id)
+                    .await?
+                    .is_some_and(|offer| offer.status == status)
+                {
+                    return anyhow::Ok(());
+                }
+
+                sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await?
+    }
+
+    #[test(tokio::test)]
+    async fn test_standing_offer_refills() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(1000).await?;
+
+        let (coin_spends, asset_id) = bob.wallet.issue_cat(1000, 0, None).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        alice
+            .wallet
+            .db
+            .insert_asset(Asset {
+                hash: asset_id,
+                name: None,
+                ticker: None,
+                precision: 3,
+                icon_url: None,
+                description: None,
+                is_sensitive_content: false,
+                is_visible: true,
+                hidden_puzzle_hash: None,
+                kind: AssetKind::Token,
+            })
+            .await?;
+
+        let standing_offer_id = alice
+            .wallet
+            .db
+            .insert_standing_offer(NewStandingOffer {
+                offered_asset_id: Bytes32::default(),
+                offered_amount: 400,
+                requested_asset_id: asset_id,
+                requested_amount: 100,
+                fee: 0,
+                expiration_seconds: None,
+                max_fills: Some(2),
+                max_spend: None,
+            })
+            .await?;
+
+        let (sync_sender, mut events) = mpsc::channel(10);
+
+        let mut queue = StandingOfferQueue::new(
+            alice.wallet.clone(),
+            alice.master_sk.clone(),
+            alice.state.clone(),
+            sync_sender,
+        );
+
+        for fill in 1..=2 {
+            queue.process_batch().await?;
+
+            let Some(SyncEvent::OfferUpdated { offer_id, .. }) = events.try_recv().ok() else {
+                panic!("expected an offer to be created");
+            };
+
+            // The offer stays in place until it has been taken.
+            queue.process_batch().await?;
+            assert!(events.try_recv().is_err());
+
+            let row = alice.wallet.db.offer(offer_id).await?.expect("offer");
+            let offer = bob
+                .wallet
+                .take_offer(decode_offer(&row.encoded_offer)?, 0)
+                .await?;
+            let spend_bundle = bob
+                .wallet
+                .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+                .await?;
+            bob.push_bundle(spend_bundle).await?;
+
+            bob.wait_for_coins().await;
+            alice.wait_for_puzzles().await;
+            wait_for_status(&alice, offer_id, OfferStatus::Completed).await?;
+
+            queue.process_batch().await?;
+
+            let standing_offer = alice
+                .wallet
+                .db
+                .standing_offer(standing_offer_id)
+                .await?
+                .expect("standing offer");
+
+            assert_eq!(standing_offer.fill_count, fill);
+        }
+
+        // The fill cap has been reached, so no more offers are created.
+        assert!(events.try_recv().is_err());
+
+        let standing_offer = alice
+            .wallet
+            .db
+            .standing_offer(standing_offer_id)
+            .await?
+            .expect("standing offer");
+
+        assert!(!standing_offer.is_active);
+        assert_eq!(standing_offer.current_offer_id, None);
+        assert_eq!(alice.wallet.db.xch_balance().await?, 200);
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 200);
+        assert_eq!(
+            alice
+                .wallet
+                .db
+                .standing_offer_history(Some(standing_offer_id))
+                .await?
+                .len(),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_standing_offer_spend_cap_includes_fee() -> anyhow::Result<()> {
+        let alice = TestWallet::new(1000).await?;
+        let asset_id = Bytes32::new([1; 32]);
+
+        alice
+            .wallet
+            .db
+            .insert_asset(Asset {
+                hash: asset_id,
+                name: None,
+                ticker: None,
+                precision: 3,
+                icon_url: None,
+                description: None,
+                is_sensitive_content: false,
+                is_visible: true,
+                hidden_puzzle_hash: None,
+                kind: AssetKind::Token,
+            })
+            .await?;
+
+        // The offered amount alone fits within the cap, but not once the fee is added.
+        let standing_offer_id = alice
+            .wallet
+            .db
+            .insert_standing_offer(NewStandingOffer {
+                offered_asset_id: Bytes32::default(),
+                offered_amount: 400,
+                requested_asset_id: asset_id,
+                requested_amount: 100,
+                fee: 100,
+                expiration_seconds: None,
+                max_fills: None,
+                max_spend: Some(450),
+            })
+            .await?;
+
+        let (sync_sender, mut events) = mpsc::channel(10);
+
+        let mut queue = StandingOfferQueue::new(
+            alice.wallet.clone(),
+            alice.master_sk.clone(),
+            alice.state.clone(),
+            sync_sender,
+        );
+
+        queue.process_batch().await?;
+        assert!(events.try_recv().is_err());
+
+        let standing_offer = alice
+            .wallet
+            .db
+            .standing_offer(standing_offer_id)
+            .await?
+            .expect("standing offer");
+
+        assert!(!standing_offer.is_active);
+        assert_eq!(standing_offer.current_offer_id, None);
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
 use chia_wallet_sdk::prelude::*;
 use sage_database::Database;
@@ -14,6 +20,7 @@ use crate::{PeerState, Status, SyncEvent, WalletError, submit_to_peers};
 pub struct TransactionQueue {
     db: Database,
     state: Arc<Mutex<PeerState>>,
+    retries: Arc<AtomicU64>,
     sync_sender: mpsc::Sender<SyncEvent>,
 }
 
@@ -21,11 +28,13 @@ impl TransactionQueue {
     pub fn new(
         db: Database,
         state: Arc<Mutex<PeerState>>,
+        retries: Arc<AtomicU64>,
         sync_sender: mpsc::Sender<SyncEvent>,
     ) -> Self {
         Self {
             db,
             state,
+            retries,
             sync_sender,
         }
     }
@@ -110,6 +119,8 @@ impl TransactionQueue {
                 }
                 Status::Unknown => {
                     warn!("Transaction inclusion in mempool unknown, retrying later");
+
+                    self.retries.fetch_add(1, Ordering::Relaxed);
                 }
             }
         }
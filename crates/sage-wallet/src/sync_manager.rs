@@ -5,6 +5,7 @@ use std::{
     time::Duration,
 };
 
+use address_book::now;
 use chia_traits::Streamable;
 use chia_wallet_sdk::{
     chia::protocol::{CoinStateUpdate, Message, NewPeakWallet, ProtocolMessageTypes},
@@ -12,52 +13,61 @@ use chia_wallet_sdk::{
     prelude::*,
 };
 use futures_lite::future::poll_once;
+use indexmap::IndexMap;
 use itertools::Itertools;
-use sage_config::Network;
+use loaded_wallet::{InitialWalletSync, LoadedWallet};
+use reorg::handle_new_peak;
+use rpc_fallback::{FullNodeRpcClient, RpcSyncedHeights, full_node_client};
+use sage_config::{Network, ReputationSource};
 use tokio::{
     sync::{Mutex, mpsc},
     task::JoinHandle,
     time::{sleep, timeout},
 };
 use tracing::{debug, info, warn};
+use verify::verify_coin_states;
 use wallet_sync::{add_new_subscriptions, incremental_sync, sync_wallet};
 
-use crate::{
-    BlockTimeQueue, CatQueue, NftUriQueue, OfferQueue, PuzzleQueue, TransactionQueue, Wallet,
-    WalletError,
-};
+use crate::{Wallet, WalletError, WalletPeer};
 
+mod address_book;
+mod connect;
 mod dns;
+mod loaded_wallet;
 mod options;
 mod peer_discovery;
+mod peer_score;
 mod peer_state;
+mod reorg;
+mod rpc_fallback;
 mod sync_command;
 mod sync_event;
+mod sync_progress;
+mod verify;
 mod wallet_sync;
 
+pub use address_book::*;
 pub use options::*;
+pub use peer_score::*;
 pub use peer_state::*;
 pub use sync_command::*;
 pub use sync_event::*;
+pub use sync_progress::*;
 
 pub struct SyncManager {
     options: SyncOptions,
     state: Arc<Mutex<PeerState>>,
-    wallet: Option<Arc<Wallet>>,
+    wallets: IndexMap<u32, LoadedWallet>,
+    reputation_sources: Vec<ReputationSource>,
     network: Network,
     connector: Connector,
-    event_sender: mpsc::Sender<SyncEvent>,
+    full_node_client: Option<Arc<FullNodeRpcClient>>,
+    rpc_fallback_task: Option<JoinHandle<()>>,
+    verify_task: Option<JoinHandle<()>>,
+    rpc_synced_heights: RpcSyncedHeights,
+    event_sender: mpsc::Sender<WalletEvent>,
     command_sender: mpsc::Sender<SyncCommand>,
     command_receiver: mpsc::Receiver<SyncCommand>,
-    initial_wallet_sync: InitialWalletSync,
-    puzzle_lookup_task: Option<JoinHandle<Result<(), WalletError>>>,
-    cat_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
-    nft_uri_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
-    transaction_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
-    offer_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
-    blocktime_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
-    pending_coin_subscriptions: Vec<Bytes32>,
-    pending_puzzle_subscriptions: Vec<Bytes32>,
 }
 
 impl fmt::Debug for SyncManager {
@@ -66,78 +76,40 @@ impl fmt::Debug for SyncManager {
     }
 }
 
-#[derive(Default)]
-enum InitialWalletSync {
-    #[default]
-    Idle,
-    Syncing {
-        ip: IpAddr,
-        task: JoinHandle<Result<(), WalletError>>,
-    },
-    Subscribed(IpAddr),
-}
-
-impl Drop for SyncManager {
-    fn drop(&mut self) {
-        if let InitialWalletSync::Syncing { task, .. } = &mut self.initial_wallet_sync {
-            task.abort();
-        }
-        if let Some(task) = &mut self.puzzle_lookup_task {
-            task.abort();
-        }
-        if let Some(task) = &mut self.cat_queue_task {
-            task.abort();
-        }
-        if let Some(task) = &mut self.nft_uri_queue_task {
-            task.abort();
-        }
-        if let Some(task) = &mut self.transaction_queue_task {
-            task.abort();
-        }
-        if let Some(task) = &mut self.offer_queue_task {
-            task.abort();
-        }
-        if let Some(task) = &mut self.blocktime_queue_task {
-            task.abort();
-        }
-    }
-}
-
 impl SyncManager {
     pub fn new(
         options: SyncOptions,
         state: Arc<Mutex<PeerState>>,
-        wallet: Option<Arc<Wallet>>,
         network: Network,
         connector: Connector,
-    ) -> (Self, mpsc::Sender<SyncCommand>, mpsc::Receiver<SyncEvent>) {
+    ) -> (Self, mpsc::Sender<SyncCommand>, mpsc::Receiver<WalletEvent>) {
         let (command_sender, command_receiver) = mpsc::channel(100);
         let (event_sender, event_receiver) = mpsc::channel(100);
 
+        let full_node_client = full_node_client(&network, options.proxy);
+
         let manager = Self {
             options,
             state,
-            wallet,
+            wallets: IndexMap::new(),
+            reputation_sources: Vec::new(),
+            full_node_client,
+            rpc_fallback_task: None,
+            verify_task: None,
+            rpc_synced_heights: RpcSyncedHeights::default(),
             network,
             connector,
             event_sender,
             command_sender: command_sender.clone(),
             command_receiver,
-            initial_wallet_sync: InitialWalletSync::Idle,
-            puzzle_lookup_task: None,
-            cat_queue_task: None,
-            nft_uri_queue_task: None,
-            transaction_queue_task: None,
-            offer_queue_task: None,
-            blocktime_queue_task: None,
-            pending_coin_subscriptions: Vec::new(),
-            pending_puzzle_subscriptions: Vec::new(),
         };
 
         (manager, command_sender, event_receiver)
     }
 
     pub async fn sync(mut self) {
+        self.update_trusted_peers().await;
+
         loop {
             self.process_commands().await;
             self.update().await;
@@ -149,11 +121,63 @@ impl SyncManager {
     async fn process_commands(&mut self) {
         while let Ok(command) = self.command_receiver.try_recv() {
             match command {
-                SyncCommand::SwitchWallet { wallet, delta_sync } => {
-                    self.clear_subscriptions().await;
-                    self.abort_wallet_tasks();
-                    self.wallet = wallet;
-                    self.options.delta_sync = delta_sync;
+                SyncCommand::LoadWallet {
+                    wallet,
+                    delta_sync,
+                    master_sk,
+                    clawback_policy,
+                    coin_management,
+                } => {
+                    let fingerprint = wallet.fingerprint;
+
+                    // Reloading the same wallet only changes its config, so it keeps syncing.
+                    if let Some(loaded) = self.wallets.get_mut(&fingerprint)
+                        && Arc::ptr_eq(&loaded.wallet, &wallet)
+                    {
+                        loaded.update_config(
+                            delta_sync,
+                            master_sk,
+                            clawback_policy,
+                            coin_management,
+                        );
+                        continue;
+                    }
+
+                    self.unload_wallet(fingerprint).await;
+
+                    if !self.options.header_validation {
+                        clear_validated_blocks(&wallet).await;
+                    }
+
+                    self.wallets.insert(
+                        fingerprint,
+                        LoadedWallet::new(
+                            wallet,
+                            delta_sync,
+                            master_sk,
+                            clawback_policy,
+                            coin_management,
+                            self.event_sender.clone(),
+                        ),
+                    );
+                }
+                SyncCommand::UnloadWallet { fingerprint } => {
+                    self.unload_wallet(fingerprint).await;
+                }
+                SyncCommand::ConfigureWallet {
+                    fingerprint,
+                    master_sk,
+                    clawback_policy,
+                    coin_management,
+                } => {
+                    if let Some(loaded) = self.wallets.get_mut(&fingerprint) {
+                        loaded.update_config(
+                            loaded.delta_sync,
+                            master_sk,
+                            clawback_policy,
+                            coin_management,
+                        );
+                    }
                 }
                 SyncCommand::SwitchNetwork(network) => {
                     if self.network.network_id() != network.network_id()
@@ -161,9 +185,19 @@ impl SyncManager {
                         || self.network.default_port != network.default_port
                     {
                         self.state.lock().await.reset();
-                        self.abort_wallet_tasks();
-                        self.network = network;
+                        for loaded in self.wallets.values_mut() {
+                            loaded.abort_tasks();
+                        }
+
+                        if let Some(task) = self.verify_task.take() {
+                            task.abort();
+                        }
                     }
+
+                    self.abort_rpc_fallback(true);
+                    self.full_node_client = full_node_client(&network, self.options.proxy);
+                    self.network = network;
+                    self.update_trusted_peers().await;
                 }
                 SyncCommand::HandleMessage { ip, message } => {
                     if let Err(error) = self.handle_message(ip, message).await {
@@ -175,22 +209,69 @@ impl SyncManager {
                         );
                     }
                 }
+                SyncCommand::VerifiedCoinStates { ip, update } => {
+                    if let Err(error) = self.apply_coin_state_update(ip, update).await {
+                        debug!("Failed to handle coin state update from {ip}: {error}");
+                        self.state.lock().await.ban(
+                            ip,
+                            Duration::from_secs(300),
+                            "failed to handle message",
+                        );
+                    }
+                }
+                SyncCommand::DisputedCoinStates(ip) => {
+                    // We don't know which peer is in the wrong, so start over with
+                    // whichever peer is now preferred rather than banning this one.
+                    for loaded in self.wallets.values_mut() {
+                        if loaded.initial_wallet_sync.ip() == Some(ip) {
+                            loaded.reset_sync();
+                            loaded.event_sender.send(SyncEvent::Stop).await.ok();
+                        }
+                    }
+                }
                 SyncCommand::ConnectPeer { ip, user_managed } => {
-                    self.connect_batch(
-                        &[SocketAddr::new(ip, self.network.default_port)],
-                        true,
-                        user_managed,
-                    )
-                    .await;
+                    // Only trusted peers are synced from, unless the user asks for others.
+                    if !user_managed && !self.network.trusted_peers.is_empty() {
+                        continue;
+                    }
+
+                    let addrs = [SocketAddr::new(ip, self.network.default_port)];
+                    let source = if user_managed {
+                        PeerSource::User
+                    } else {
+                        PeerSource::PeerExchange
+                    };
+
+                    self.record_seen(&addrs, source, now()).await;
+                    self.connect_batch(&addrs, true, user_managed).await;
                 }
                 SyncCommand::AddPeer { peer, receiver } => {
-                    self.try_add_peer(peer, receiver, true, false).await;
+                    self.try_add_peer(WalletPeer::new(peer), receiver, true, false)
+                        .await;
+                }
+                SyncCommand::SubscribeCoins {
+                    fingerprint,
+                    coin_ids,
+                } => {
+                    if let Some(loaded) = self.wallets.get_mut(&fingerprint) {
+                        loaded.pending_coin_subscriptions.extend(coin_ids);
+                    }
                 }
-                SyncCommand::SubscribeCoins { coin_ids } => {
-                    self.pending_coin_subscriptions.extend(coin_ids);
+                SyncCommand::SubscribePuzzles {
+                    fingerprint,
+                    puzzle_hashes,
+                } => {
+                    if let Some(loaded) = self.wallets.get_mut(&fingerprint) {
+                        loaded.pending_puzzle_subscriptions.extend(puzzle_hashes);
+                    }
                 }
-                SyncCommand::SubscribePuzzles { puzzle_hashes } => {
-                    self.pending_puzzle_subscriptions.extend(puzzle_hashes);
+                SyncCommand::ResyncCoins {
+                    fingerprint,
+                    coin_ids,
+                } => {
+                    if let Some(loaded) = self.wallets.get_mut(&fingerprint) {
+                        loaded.pending_coin_resyncs.extend(coin_ids);
+                    }
                 }
                 SyncCommand::ConnectionClosed(ip) => {
                     self.state.lock().await.remove_peer(ip);
@@ -202,140 +283,180 @@ impl SyncManager {
                 SyncCommand::SetDiscoverPeers(discover_peers) => {
                     self.options.discover_peers = discover_peers;
                 }
+                SyncCommand::SetParanoidSync(paranoid_sync) => {
+                    self.options.paranoid_sync = paranoid_sync;
+                }
+                SyncCommand::SetProxy(proxy) => {
+                    if self.options.proxy != proxy {
+                        self.options.proxy = proxy;
+
+                        self.abort_rpc_fallback(false);
+                        self.full_node_client = full_node_client(&self.network, proxy);
+
+                        // Existing connections bypass the new setting, so reconnect.
+                        self.state.lock().await.reset();
+                        for loaded in self.wallets.values_mut() {
+                            loaded.abort_tasks();
+                        }
+                    }
+                }
+                SyncCommand::SetDnsDiscovery(dns_discovery) => {
+                    self.options.dns_discovery = dns_discovery;
+                }
+                SyncCommand::SetHeaderValidation(header_validation) => {
+                    self.options.header_validation = header_validation;
+
+                    if !header_validation {
+                        for loaded in self.wallets.values_mut() {
+                            loaded.abort_header_queue();
+                            clear_validated_blocks(&loaded.wallet).await;
+                        }
+                    }
+                }
+                SyncCommand::SetReputationSources(sources) => {
+                    self.reputation_sources = sources;
+
+                    for loaded in self.wallets.values_mut() {
+                        loaded.abort_reputation_queue();
+                    }
+                }
             }
         }
     }
 
     async fn subscribe(&mut self) {
-        if self.pending_coin_subscriptions.is_empty()
-            && self.pending_puzzle_subscriptions.is_empty()
-        {
-            return;
+        for loaded in self.wallets.values_mut() {
+            if loaded.pending_coin_subscriptions.is_empty()
+                && loaded.pending_puzzle_subscriptions.is_empty()
+                && loaded.pending_coin_resyncs.is_empty()
+            {
+                continue;
+            }
+
+            let InitialWalletSync::Subscribed(ip) = loaded.initial_wallet_sync else {
+                continue;
+            };
+
+            let Some(peer) = self
+                .state
+                .lock()
+                .await
+                .peer(ip)
+                .map(|info| info.peer.clone())
+            else {
+                continue;
+            };
+
+            match add_new_subscriptions(
+                &loaded.wallet,
+                &peer,
+                &self.state,
+                self.options.paranoid_sync,
+                loaded.pending_coin_subscriptions.clone(),
+                loaded.pending_puzzle_subscriptions.clone(),
+                loaded.pending_coin_resyncs.clone(),
+                loaded.event_sender.clone(),
+                self.command_sender.clone(),
+            )
+            .await
+            {
+                Ok(()) => {
+                    loaded.pending_coin_subscriptions.clear();
+                    loaded.pending_puzzle_subscriptions.clear();
+                    loaded.pending_coin_resyncs.clear();
+                }
+                Err(WalletError::UnverifiedCoinStates) => {
+                    // A full sync includes the pending subscriptions anyway.
+                    warn!("Failed to verify new subscriptions, resyncing wallet");
+                    loaded.pending_coin_subscriptions.clear();
+                    loaded.pending_puzzle_subscriptions.clear();
+                    loaded.pending_coin_resyncs.clear();
+                    loaded.reset_sync();
+                    loaded.event_sender.send(SyncEvent::Stop).await.ok();
+                }
+                Err(error) => {
+                    warn!("Failed to add new subscriptions: {error}");
+                    self.state.lock().await.ban(
+                        ip,
+                        Duration::from_secs(300),
+                        "failed to add new subscriptions",
+                    );
+                }
+            }
         }
+    }
 
-        let InitialWalletSync::Subscribed(ip) = self.initial_wallet_sync else {
+    async fn unload_wallet(&mut self, fingerprint: u32) {
+        let Some(mut loaded) = self.wallets.shift_remove(&fingerprint) else {
             return;
         };
 
-        let Some(peer) = self
-            .state
-            .lock()
-            .await
-            .peer(ip)
-            .map(|info| info.peer.clone())
-        else {
+        let Some(ip) = loaded.initial_wallet_sync.ip() else {
             return;
         };
 
-        let Some(wallet) = self.wallet.as_ref() else {
-            return;
-        };
+        loaded.abort_tasks();
 
-        if let Err(error) = add_new_subscriptions(
-            wallet,
-            &peer,
-            self.pending_coin_subscriptions.clone(),
-            self.pending_puzzle_subscriptions.clone(),
-            self.event_sender.clone(),
-            self.command_sender.clone(),
-        )
-        .await
-        {
-            warn!("Failed to add new subscriptions: {error}");
-            self.state.lock().await.ban(
-                ip,
-                Duration::from_secs(300),
-                "failed to add new subscriptions",
-            );
-        } else {
-            self.pending_coin_subscriptions.clear();
-            self.pending_puzzle_subscriptions.clear();
-        }
-    }
+        let peer = self
+            .state
+            .lock()
+            .await
+            .peer(ip)
+            .map(|info| info.peer.clone());
 
-    fn abort_wallet_tasks(&mut self) {
-        if let InitialWalletSync::Syncing { task, .. } =
-            std::mem::take(&mut self.initial_wallet_sync)
+        // Other wallets sharing the peer keep their own subscriptions, so they don't need to
+        // sync again.
+        if let Some(peer) = peer
+            && let Err(error) = loaded.unsubscribe(&peer).await
         {
-            task.abort();
-        }
-        if let Some(task) = self.puzzle_lookup_task.take() {
-            task.abort();
-        }
-        if let Some(task) = &mut self.cat_queue_task.take() {
-            task.abort();
-        }
-        if let Some(task) = &mut self.nft_uri_queue_task.take() {
-            task.abort();
-        }
-        if let Some(task) = &mut self.transaction_queue_task.take() {
-            task.abort();
-        }
-        if let Some(task) = &mut self.offer_queue_task.take() {
-            task.abort();
-        }
-        if let Some(task) = &mut self.blocktime_queue_task.take() {
-            task.abort();
+            debug!("Failed to remove subscriptions of wallet {fingerprint} from {ip}: {error}");
         }
     }
 
-    async fn handle_message(&self, ip: IpAddr, message: Message) -> Result<(), WalletError> {
+    async fn handle_message(&mut self, ip: IpAddr, message: Message) -> Result<(), WalletError> {
         match message.msg_type {
             ProtocolMessageTypes::NewPeakWallet => {
                 let message =
                     NewPeakWallet::from_bytes(&message.data).map_err(ClientError::from)?;
-                self.state
-                    .lock()
-                    .await
-                    .update_peak(ip, message.height, message.header_hash);
-            }
-            ProtocolMessageTypes::CoinStateUpdate => {
-                let message =
-                    CoinStateUpdate::from_bytes(&message.data).map_err(ClientError::from)?;
-                if let Some(wallet) = self.wallet.as_ref() {
-                    let unspent_count = message
-                        .items
-                        .iter()
-                        .filter(|item| item.spent_height.is_none())
-                        .count();
-
-                    let spent_coin_ids = message
-                        .items
-                        .iter()
-                        .filter_map(|item| {
-                            if item.spent_height.is_some() {
-                                Some(item.coin.coin_id())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect_vec();
-
-                    let spent_count = spent_coin_ids.len();
-
-                    if !spent_coin_ids.is_empty()
-                        && let InitialWalletSync::Subscribed(ip) = self.initial_wallet_sync
-                        && let Some(info) = self.state.lock().await.peer(ip)
-                    {
-                        // TODO: Handle cases
-                        info.peer.unsubscribe_coins(spent_coin_ids).await.ok();
+
+                let (peer, peer_previous) = {
+                    let mut state = self.state.lock().await;
+                    let peer = state.peer(ip).map(|info| info.peer.clone());
+                    let peer_previous = state.peak_of(ip);
+                    state.update_peak(ip, message.height, message.header_hash, message.weight);
+                    (peer, peer_previous)
+                };
+
+                for loaded in self.wallets.values() {
+                    let InitialWalletSync::Subscribed(subscribed_ip) = loaded.initial_wallet_sync
+                    else {
+                        continue;
+                    };
+
+                    if subscribed_ip != ip {
+                        continue;
                     }
 
-                    incremental_sync(
-                        wallet,
-                        message.items,
-                        true,
-                        &self.event_sender,
+                    handle_new_peak(
+                        &loaded.wallet,
+                        peer.as_ref(),
+                        peer_previous,
+                        &message,
+                        self.options.header_validation,
+                        &loaded.event_sender,
                         &self.command_sender,
                     )
                     .await?;
+                }
+            }
+            ProtocolMessageTypes::CoinStateUpdate => {
+                let message =
+                    CoinStateUpdate::from_bytes(&message.data).map_err(ClientError::from)?;
 
-                    info!(
-                        "Received {} unspent coins, {} spent coins, and synced to peak {} with header hash {}",
-                        unspent_count, spent_count, message.height, message.peak_hash
-                    );
+                if self.options.paranoid_sync {
+                    self.verify_coin_state_update(ip, message).await;
                 } else {
-                    debug!("Received coin state update but no database to update");
+                    self.apply_coin_state_update(ip, message).await?;
                 }
             }
             _ => {
@@ -346,280 +467,399 @@ impl SyncManager {
         Ok(())
     }
 
-    async fn update(&mut self) {
-        let peer_count = self.state.lock().await.peer_count();
+    /// Checks a coin state update against other peers in the background, so that other commands
+    /// aren't held up while the witnesses respond. Each check waits for the previous one, so that
+    /// updates are still applied in the order they were received.
+    async fn verify_coin_state_update(&mut self, ip: IpAddr, message: CoinStateUpdate) {
+        if !self
+            .wallets
+            .values()
+            .any(|loaded| loaded.initial_wallet_sync.ip() == Some(ip))
+        {
+            debug!("Received coin state update but no wallet is subscribed to {ip}");
+            return;
+        }
 
-        if peer_count < self.options.target_peers && self.options.discover_peers {
-            if peer_count > 0 {
-                if !self.peer_discovery().await && !self.dns_discovery().await {
-                    self.introducer_discovery().await;
-                }
-            } else if !self.dns_discovery().await {
-                self.introducer_discovery().await;
+        let Some(peer) = self
+            .state
+            .lock()
+            .await
+            .peer(ip)
+            .map(|info| info.peer.clone())
+        else {
+            return;
+        };
+
+        let state = self.state.clone();
+        let genesis_challenge = self.network.genesis_challenge;
+        let command_sender = self.command_sender.clone();
+        let previous = self.verify_task.take();
+
+        self.verify_task = Some(tokio::spawn(async move {
+            if let Some(previous) = previous {
+                previous.await.ok();
             }
-        }
 
-        self.update_tasks().await;
-        self.poll_tasks().await;
+            let command =
+                match verify_coin_states(&state, &peer, &message.items, genesis_challenge).await {
+                    Ok(()) => SyncCommand::VerifiedCoinStates {
+                        ip,
+                        update: message,
+                    },
+                    Err(_) => SyncCommand::DisputedCoinStates(ip),
+                };
+
+            command_sender.send(command).await.ok();
+        }));
     }
 
-    async fn update_tasks(&mut self) {
-        let state = self.state.lock().await;
+    async fn apply_coin_state_update(
+        &self,
+        ip: IpAddr,
+        message: CoinStateUpdate,
+    ) -> Result<(), WalletError> {
+        let wallets = self
+            .wallets
+            .values()
+            .filter(|loaded| loaded.initial_wallet_sync.ip() == Some(ip))
+            .collect_vec();
+
+        if wallets.is_empty() {
+            debug!("Received coin state update but no wallet is subscribed to {ip}");
+            return Ok(());
+        }
 
-        match &mut self.initial_wallet_sync {
-            sync @ InitialWalletSync::Idle => {
-                if let Some(wallet) = self.wallet.clone()
-                    && let Some(peer) = state.acquire_peer()
-                {
-                    let ip = peer.socket_addr().ip();
-                    let task = tokio::spawn(sync_wallet(
-                        wallet.clone(),
-                        peer,
-                        self.state.clone(),
-                        self.event_sender.clone(),
-                        self.command_sender.clone(),
-                        self.options.delta_sync,
-                    ));
-                    *sync = InitialWalletSync::Syncing { ip, task };
-                    self.event_sender.send(SyncEvent::Start(ip)).await.ok();
+        let peer = self
+            .state
+            .lock()
+            .await
+            .peer(ip)
+            .map(|info| info.peer.clone());
+
+        let unspent_count = message
+            .items
+            .iter()
+            .filter(|item| item.spent_height.is_none())
+            .count();
+
+        let spent_coin_ids = message
+            .items
+            .iter()
+            .filter_map(|item| {
+                if item.spent_height.is_some() {
+                    Some(item.coin.coin_id())
+                } else {
+                    None
                 }
-            }
-            InitialWalletSync::Syncing { ip, task }
-                if !state.is_connected(*ip) || self.wallet.is_none() =>
-            {
-                task.abort();
-                self.initial_wallet_sync = InitialWalletSync::Idle;
-                self.event_sender.send(SyncEvent::Stop).await.ok();
-            }
-            InitialWalletSync::Subscribed(ip)
-                if !state.is_connected(*ip) || self.wallet.is_none() =>
-            {
-                self.initial_wallet_sync = InitialWalletSync::Idle;
-                self.event_sender.send(SyncEvent::Stop).await.ok();
-            }
-            _ => {}
+            })
+            .collect_vec();
+
+        let spent_count = spent_coin_ids.len();
+
+        if !spent_coin_ids.is_empty()
+            && wallets.iter().any(|loaded| {
+                matches!(loaded.initial_wallet_sync, InitialWalletSync::Subscribed(_))
+            })
+            && let Some(info) = self.state.lock().await.peer(ip)
+        {
+            // TODO: Handle cases
+            info.peer.unsubscribe_coins(spent_coin_ids).await.ok();
         }
 
-        if let Some(wallet) = self.wallet.clone() {
-            if self.puzzle_lookup_task.is_none() {
-                let task = tokio::spawn(
-                    PuzzleQueue::new(
-                        wallet.db.clone(),
-                        wallet.genesis_challenge,
-                        self.options.puzzle_batch_size_per_peer,
-                        self.state.clone(),
-                        self.event_sender.clone(),
-                        self.command_sender.clone(),
-                    )
-                    .start(self.options.timeouts.puzzle_delay),
-                );
-                self.puzzle_lookup_task = Some(task);
+        for (loaded, coin_states) in route_coin_states(
+            &wallets,
+            peer.as_ref(),
+            self.network.genesis_challenge,
+            message.items,
+        )
+        .await?
+        {
+            if coin_states.is_empty() {
+                continue;
             }
 
-            if self.cat_queue_task.is_none() && !self.options.testing {
-                let mainnet = self.network.genesis_challenge == MAINNET_CONSTANTS.genesis_challenge;
-                let testnet =
-                    self.network.genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+            incremental_sync(
+                &loaded.wallet,
+                coin_states,
+                true,
+                &loaded.event_sender,
+                &self.command_sender,
+            )
+            .await?;
+        }
 
-                if mainnet || testnet {
-                    let task = tokio::spawn(
-                        CatQueue::new(wallet.db.clone(), testnet, self.event_sender.clone())
-                            .start(self.options.timeouts.cat_delay),
-                    );
-                    self.cat_queue_task = Some(task);
-                }
-            }
+        info!(
+            "Received {} unspent coins, {} spent coins, and synced to peak {} with header hash {}",
+            unspent_count, spent_count, message.height, message.peak_hash
+        );
 
-            if self.nft_uri_queue_task.is_none() && !self.options.testing {
-                let task = tokio::spawn(
-                    NftUriQueue::new(
-                        wallet.db.clone(),
-                        self.event_sender.clone(),
-                        self.network.clone(),
-                    )
-                    .start(self.options.timeouts.nft_uri_delay),
-                );
-                self.nft_uri_queue_task = Some(task);
-            }
+        Ok(())
+    }
 
-            if self.transaction_queue_task.is_none() {
-                let task = tokio::spawn(
-                    TransactionQueue::new(
-                        wallet.db.clone(),
-                        self.state.clone(),
-                        self.event_sender.clone(),
-                    )
-                    .start(self.options.timeouts.transaction_delay),
-                );
-                self.transaction_queue_task = Some(task);
-            }
+    async fn update(&mut self) {
+        let peer_count = self.state.lock().await.peer_count();
 
-            if self.offer_queue_task.is_none() {
-                let task = tokio::spawn(
-                    OfferQueue::new(
-                        wallet.db.clone(),
-                        wallet.genesis_challenge,
-                        self.state.clone(),
-                        self.event_sender.clone(),
-                    )
-                    .start(self.options.timeouts.offer_delay),
-                );
-                self.offer_queue_task = Some(task);
-            }
+        if !self.network.trusted_peers.is_empty() {
+            self.connect_trusted_peers().await;
+        } else if peer_count < self.options.target_peers && self.options.discover_peers {
+            let discovered = peer_count > 0 && self.peer_discovery().await;
 
-            if self.blocktime_queue_task.is_none() && !self.options.testing {
-                let task = tokio::spawn(
-                    BlockTimeQueue::new(
-                        wallet.db.clone(),
-                        self.state.clone(),
-                        self.event_sender.clone(),
-                    )
-                    .start(self.options.timeouts.blocktime_delay),
-                );
-                self.blocktime_queue_task = Some(task);
+            if !discovered
+                && !self.address_book_discovery().await
+                && self.options.dns_discovery
+                && !self.dns_discovery().await
+            {
+                self.introducer_discovery().await;
             }
+        }
+
+        if self.state.lock().await.peer_count() == 0 {
+            self.rpc_fallback();
+        }
+
+        self.update_tasks().await;
+        self.poll_tasks().await;
+    }
+
+    /// The options for the current network, which can be relaxed if its peers are trusted.
+    pub(super) fn effective_options(&self) -> SyncOptions {
+        if self.network.trusted_peers.is_empty() {
+            self.options
         } else {
-            self.puzzle_lookup_task = None;
-            self.cat_queue_task = None;
-            self.nft_uri_queue_task = None;
-            self.transaction_queue_task = None;
-            self.offer_queue_task = None;
-            self.blocktime_queue_task = None;
+            self.options.trusted()
         }
     }
 
-    async fn poll_tasks(&mut self) {
-        if let InitialWalletSync::Syncing { ip, task } = &mut self.initial_wallet_sync
-            && let Ok(Some(result)) = timeout(Duration::from_secs(1), poll_once(task)).await
-        {
-            match result {
-                Ok(Ok(())) => {
-                    self.initial_wallet_sync = InitialWalletSync::Subscribed(*ip);
-                    self.event_sender.send(SyncEvent::Subscribed).await.ok();
-                }
-                Ok(Err(error)) => {
-                    warn!("Initial wallet sync failed: {error}");
-                    self.state.lock().await.ban(
-                        *ip,
-                        Duration::from_secs(300),
-                        "wallet sync failed",
-                    );
-                    self.initial_wallet_sync = InitialWalletSync::Idle;
-                    self.event_sender.send(SyncEvent::Stop).await.ok();
-                }
-                Err(_timeout) => {
-                    warn!("Initial wallet sync timed out");
-                    self.state.lock().await.ban(
-                        *ip,
-                        Duration::from_secs(300),
-                        "wallet sync timed out",
-                    );
-                    self.initial_wallet_sync = InitialWalletSync::Idle;
-                    self.event_sender.send(SyncEvent::Stop).await.ok();
-                }
-            }
+    async fn update_trusted_peers(&self) {
+        let trusted_peers = self
+            .network
+            .trusted_peer_addrs()
+            .into_iter()
+            .map(|addr| addr.ip())
+            .collect();
+
+        self.state.lock().await.set_trusted_peers(trusted_peers);
+    }
+
+    async fn connect_trusted_peers(&mut self) {
+        let state = self.state.lock().await;
+
+        let addrs: Vec<SocketAddr> = self
+            .network
+            .trusted_peer_addrs()
+            .into_iter()
+            .filter(|addr| !state.is_connected(addr.ip()))
+            .collect();
+
+        drop(state);
+
+        if !addrs.is_empty() {
+            self.record_seen(&addrs, PeerSource::User, now()).await;
+            self.connect_batch(&addrs, true, true).await;
         }
+    }
 
-        if let Some(task) = &mut self.puzzle_lookup_task {
-            match poll_once(task).await {
-                Some(Err(error)) => {
-                    warn!("Puzzle lookup queue failed with panic: {error}");
-                    self.puzzle_lookup_task = None;
+    async fn update_tasks(&mut self) {
+        let options = self.effective_options();
+        let state = self.state.lock().await;
+
+        for loaded in self.wallets.values_mut() {
+            match &mut loaded.initial_wallet_sync {
+                // Paranoid sync needs another peer to check the coin states against.
+                sync @ InitialWalletSync::Idle
+                    if !self.options.paranoid_sync || state.peer_count() > 1 =>
+                {
+                    if let Some(peer) = state.acquire_peer() {
+                        let ip = peer.socket_addr().ip();
+                        let task = tokio::spawn(sync_wallet(
+                            loaded.wallet.clone(),
+                            peer,
+                            self.state.clone(),
+                            loaded.event_sender.clone(),
+                            self.command_sender.clone(),
+                            loaded.delta_sync,
+                            self.options.paranoid_sync,
+                        ));
+                        *sync = InitialWalletSync::Syncing { ip, task };
+                        loaded.event_sender.send(SyncEvent::Start(ip)).await.ok();
+                    }
                 }
-                Some(Ok(Err(error))) => {
-                    warn!("Puzzle lookup queue failed with error: {error}");
-                    self.puzzle_lookup_task = None;
+                InitialWalletSync::Syncing { ip, task } if !state.is_connected(*ip) => {
+                    task.abort();
+                    loaded.initial_wallet_sync = InitialWalletSync::Idle;
+                    loaded.event_sender.send(SyncEvent::Stop).await.ok();
                 }
-                Some(Ok(Ok(()))) => {
-                    self.puzzle_lookup_task = None;
+                InitialWalletSync::Subscribed(ip) if !state.is_connected(*ip) => {
+                    loaded.initial_wallet_sync = InitialWalletSync::Idle;
+                    loaded.event_sender.send(SyncEvent::Stop).await.ok();
                 }
-                None => {}
+                _ => {}
             }
+
+            loaded.spawn_tasks(
+                &options,
+                &self.network,
+                &self.state,
+                &self.command_sender,
+                &self.reputation_sources,
+            );
         }
+    }
 
-        if let Some(task) = &mut self.cat_queue_task {
-            match poll_once(task).await {
-                Some(Err(error)) => {
-                    warn!("CAT lookup queue failed with panic: {error}");
-                    self.cat_queue_task = None;
-                }
-                Some(Ok(Err(error))) => {
-                    warn!("CAT lookup queue failed with error: {error}");
-                    self.cat_queue_task = None;
-                }
-                Some(Ok(Ok(()))) => {
-                    self.cat_queue_task = None;
+    async fn poll_tasks(&mut self) {
+        for loaded in self.wallets.values_mut() {
+            if let InitialWalletSync::Syncing { ip, task } = &mut loaded.initial_wallet_sync
+                && let Ok(Some(result)) = timeout(Duration::from_secs(1), poll_once(task)).await
+            {
+                match result {
+                    Ok(Ok(())) => {
+                        loaded.initial_wallet_sync = InitialWalletSync::Subscribed(*ip);
+                        loaded.event_sender.send(SyncEvent::Subscribed).await.ok();
+                    }
+                    Ok(Err(WalletError::UnverifiedCoinStates)) => {
+                        warn!("Initial wallet sync couldn't be verified, retrying");
+                        loaded.initial_wallet_sync = InitialWalletSync::Idle;
+                        loaded.event_sender.send(SyncEvent::Stop).await.ok();
+                    }
+                    Ok(Err(error)) => {
+                        warn!("Initial wallet sync failed: {error}");
+                        self.state.lock().await.ban(
+                            *ip,
+                            Duration::from_secs(300),
+                            "wallet sync failed",
+                        );
+                        loaded.initial_wallet_sync = InitialWalletSync::Idle;
+                        loaded.event_sender.send(SyncEvent::Stop).await.ok();
+                    }
+                    Err(_timeout) => {
+                        warn!("Initial wallet sync timed out");
+                        self.state.lock().await.ban(
+                            *ip,
+                            Duration::from_secs(300),
+                            "wallet sync timed out",
+                        );
+                        loaded.initial_wallet_sync = InitialWalletSync::Idle;
+                        loaded.event_sender.send(SyncEvent::Stop).await.ok();
+                    }
                 }
-                None => {}
             }
+
+            loaded.poll_tasks().await;
         }
+    }
+}
 
-        if let Some(task) = &mut self.nft_uri_queue_task {
-            match poll_once(task).await {
-                Some(Err(error)) => {
-                    warn!("NFT URI queue failed with panic: {error}");
-                    self.nft_uri_queue_task = None;
-                }
-                Some(Ok(Err(error))) => {
-                    warn!("NFT URI queue failed with error: {error}");
-                    self.nft_uri_queue_task = None;
-                }
-                Some(Ok(Ok(()))) => {
-                    self.nft_uri_queue_task = None;
-                }
-                None => {}
+/// Peers send a single update for every subscription on the connection, so when wallets
+/// share a peer the coin states are split between them. Incoming assets which have only been
+/// hinted are routed by the hint in their parent's spend, and anything else is dropped.
+async fn route_coin_states<'a>(
+    wallets: &[&'a LoadedWallet],
+    peer: Option<&WalletPeer>,
+    genesis_challenge: Bytes32,
+    coin_states: Vec<CoinState>,
+) -> Result<Vec<(&'a LoadedWallet, Vec<CoinState>)>, WalletError> {
+    if let [loaded] = wallets {
+        return Ok(vec![(*loaded, coin_states)]);
+    }
+
+    let mut routes = wallets
+        .iter()
+        .map(|&loaded| (loaded, Vec::new()))
+        .collect_vec();
+
+    for coin_state in coin_states {
+        let mut matched = false;
+
+        for (loaded, routed) in &mut routes {
+            if loaded.is_relevant(&coin_state).await? {
+                routed.push(coin_state);
+                matched = true;
             }
         }
 
-        if let Some(task) = &mut self.transaction_queue_task {
-            match poll_once(task).await {
-                Some(Err(error)) => {
-                    warn!("Transaction queue failed with panic: {error}");
-                    self.transaction_queue_task = None;
-                }
-                Some(Ok(Err(error))) => {
-                    warn!("Transaction queue failed with error: {error}");
-                    self.transaction_queue_task = None;
-                }
-                Some(Ok(Ok(()))) => {
-                    self.transaction_queue_task = None;
-                }
-                None => {}
-            }
+        if matched {
+            continue;
         }
 
-        if let Some(task) = &mut self.offer_queue_task {
-            match poll_once(task).await {
-                Some(Err(error)) => {
-                    warn!("Offer queue failed with panic: {error}");
-                    self.offer_queue_task = None;
-                }
-                Some(Ok(Err(error))) => {
-                    warn!("Offer queue failed with error: {error}");
-                    self.offer_queue_task = None;
-                }
-                Some(Ok(Ok(()))) => {
-                    self.offer_queue_task = None;
-                }
-                None => {}
+        let hint = match peer {
+            Some(peer) => coin_hint(peer, &coin_state, genesis_challenge).await,
+            None => Ok(None),
+        };
+
+        let hint = match hint {
+            Ok(Some(hint)) => hint,
+            Ok(None) => {
+                debug!(
+                    "Dropping coin state {} which doesn't belong to any wallet",
+                    coin_state.coin.coin_id()
+                );
+                continue;
             }
-        }
+            Err(error) => {
+                debug!(
+                    "Failed to look up the hint of coin {}: {error}",
+                    coin_state.coin.coin_id()
+                );
+                continue;
+            }
+        };
 
-        if let Some(task) = &mut self.blocktime_queue_task {
-            match poll_once(task).await {
-                Some(Err(error)) => {
-                    warn!("Blocktime queue failed with panic: {error}");
-                    self.blocktime_queue_task = None;
-                }
-                Some(Ok(Err(error))) => {
-                    warn!("Blocktime queue failed with error: {error}");
-                    self.blocktime_queue_task = None;
-                }
-                Some(Ok(Ok(()))) => {
-                    self.blocktime_queue_task = None;
-                }
-                None => {}
+        for (loaded, routed) in &mut routes {
+            if loaded.wallet.db.is_p2_puzzle_hash(hint).await? {
+                routed.push(coin_state);
             }
         }
     }
+
+    Ok(routes)
+}
+
+/// The hint memo of the coin's creation, read from the spend of its parent.
+async fn coin_hint(
+    peer: &WalletPeer,
+    coin_state: &CoinState,
+    genesis_challenge: Bytes32,
+) -> Result<Option<Bytes32>, WalletError> {
+    let Some(parent) = peer
+        .fetch_optional_coin_spend(coin_state.coin.parent_coin_info, genesis_challenge)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let mut allocator = Allocator::new();
+    let puzzle = parent.puzzle_reveal.to_clvm(&mut allocator)?;
+    let solution = parent.solution.to_clvm(&mut allocator)?;
+    let output = run_puzzle(&mut allocator, puzzle, solution)?;
+    let conditions = Vec::<Condition>::from_clvm(&allocator, output)?;
+
+    let Some(CreateCoin {
+        memos: Memos::Some(memos),
+        ..
+    }) = conditions
+        .into_iter()
+        .filter_map(Condition::into_create_coin)
+        .find(|cc| {
+            cc.puzzle_hash == coin_state.coin.puzzle_hash && cc.amount == coin_state.coin.amount
+        })
+    else {
+        return Ok(None);
+    };
+
+    let Ok((hint, _)) = <(Bytes32, NodePtr)>::from_clvm(&allocator, memos) else {
+        return Ok(None);
+    };
+
+    Ok(Some(hint))
+}
+
+/// Validated blocks would keep peaks from peers from being recorded, and would be a stale
+/// starting point for the header chain if validation is turned back on later.
+async fn clear_validated_blocks(wallet: &Wallet) {
+    if let Err(error) = wallet.db.clear_validated_blocks().await {
+        warn!(
+            "Failed to clear validated blocks of wallet {}: {error}",
+            wallet.fingerprint
+        );
+    }
 }
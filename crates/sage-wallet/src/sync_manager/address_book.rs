@@ -0,0 +1,294 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Peers which have failed to connect this many times in a row aren't reconnected to from the
+/// address book, although they can still be found again through discovery.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Peers which keep failing are forgotten entirely, unless they were added by the user.
+const EVICTION_FAILURES: u32 = 10;
+
+/// Peers which haven't been advertised or connected to for this long are forgotten.
+const STALE_AFTER_SECONDS: u64 = 60 * 60 * 24 * 30;
+
+/// The most peers to remember, since every peer exchange can advertise new ones.
+const MAX_ENTRIES: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerSource {
+    Dns,
+    Introducer,
+    PeerExchange,
+    User,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressBookEntry {
+    pub ip: IpAddr,
+    pub port: u16,
+    pub source: PeerSource,
+    /// When the peer was last advertised or connected to, in seconds since the epoch.
+    pub last_seen: u64,
+    pub last_success: Option<u64>,
+    /// Failed connection attempts since the last successful handshake.
+    pub failures: u32,
+    pub peak_height: Option<u32>,
+}
+
+impl AddressBookEntry {
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.ip, self.port)
+    }
+
+    fn is_known_good(&self) -> bool {
+        self.last_success.is_some() && self.failures < MAX_CONSECUTIVE_FAILURES
+    }
+
+    fn is_evictable(&self, now: u64) -> bool {
+        self.source != PeerSource::User
+            && (self.failures >= EVICTION_FAILURES
+                || self.last_seen.saturating_add(STALE_AFTER_SECONDS) < now)
+    }
+}
+
+/// Every peer that has been heard about, along with how reliable it has been, so that
+/// peers which have worked before can be reconnected to without looking up new ones.
+#[derive(Debug, Default, Clone)]
+pub struct AddressBook {
+    entries: HashMap<IpAddr, AddressBookEntry>,
+}
+
+impl AddressBook {
+    pub fn from_entries(entries: Vec<AddressBookEntry>) -> Self {
+        let mut book = Self {
+            entries: entries.into_iter().map(|entry| (entry.ip, entry)).collect(),
+        };
+        book.evict(now());
+        book
+    }
+
+    pub fn entries(&self) -> Vec<AddressBookEntry> {
+        let mut entries: Vec<_> = self.entries.values().copied().collect();
+        entries.sort_by_key(|entry| entry.ip);
+        entries
+    }
+
+    pub fn entry(&self, ip: IpAddr) -> Option<&AddressBookEntry> {
+        self.entries.get(&ip)
+    }
+
+    /// Records that a peer has been advertised. The source is only kept from the first time
+    /// it was heard about, unless the user adds it themselves.
+    pub fn record_seen(&mut self, socket_addr: SocketAddr, source: PeerSource, timestamp: u64) {
+        let entry = self
+            .entries
+            .entry(socket_addr.ip())
+            .or_insert(AddressBookEntry {
+                ip: socket_addr.ip(),
+                port: socket_addr.port(),
+                source,
+                last_seen: timestamp,
+                last_success: None,
+                failures: 0,
+                peak_height: None,
+            });
+
+        entry.port = socket_addr.port();
+        entry.last_seen = entry.last_seen.max(timestamp);
+
+        if source == PeerSource::User {
+            entry.source = source;
+        }
+
+        self.evict(timestamp);
+    }
+
+    pub fn record_success(&mut self, ip: IpAddr, peak_height: u32) {
+        let now = now();
+
+        if let Some(entry) = self.entries.get_mut(&ip) {
+            entry.last_seen = now;
+            entry.last_success = Some(now);
+            entry.failures = 0;
+            entry.peak_height = Some(peak_height);
+        }
+    }
+
+    pub fn record_failure(&mut self, ip: IpAddr) {
+        if let Some(entry) = self.entries.get_mut(&ip) {
+            entry.failures = entry.failures.saturating_add(1);
+
+            if entry.source != PeerSource::User && entry.failures >= EVICTION_FAILURES {
+                self.entries.remove(&ip);
+            }
+        }
+    }
+
+    pub fn record_peak(&mut self, ip: IpAddr, peak_height: u32) {
+        if let Some(entry) = self.entries.get_mut(&ip) {
+            entry.last_seen = now();
+            entry.peak_height = Some(peak_height);
+        }
+    }
+
+    /// Peers which have completed a handshake before and haven't failed repeatedly since,
+    /// most recently successful first.
+    pub fn known_good(&self) -> Vec<SocketAddr> {
+        let mut entries: Vec<_> = self
+            .entries
+            .values()
+            .filter(|entry| entry.is_known_good())
+            .collect();
+
+        entries.sort_by_key(|entry| (Reverse(entry.last_success), entry.failures));
+        entries
+            .into_iter()
+            .map(|entry| entry.socket_addr())
+            .collect()
+    }
+
+    /// Forgets stale and failing peers, and then the least useful ones until the address book
+    /// fits within its cap. Peers added by the user are always kept.
+    fn evict(&mut self, now: u64) {
+        self.entries.retain(|_, entry| !entry.is_evictable(now));
+
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+
+        if excess == 0 {
+            return;
+        }
+
+        let mut candidates: Vec<_> = self
+            .entries
+            .values()
+            .filter(|entry| entry.source != PeerSource::User)
+            .map(|entry| (entry.is_known_good(), entry.last_seen, entry.ip))
+            .collect();
+
+        candidates.sort_unstable();
+
+        for (_, _, ip) in candidates.into_iter().take(excess) {
+            self.entries.remove(&ip);
+        }
+    }
+}
+
+/// A clock set before the epoch falls back to 0, rather than panicking while the address book
+/// is being updated or saved.
+pub(super) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(last_octet: u8) -> SocketAddr {
+        SocketAddr::from(([10, 0, 0, last_octet], 8444))
+    }
+
+    #[test]
+    fn test_known_good_order() {
+        let mut book = AddressBook::default();
+
+        for i in 1..=3 {
+            book.record_seen(addr(i), PeerSource::Dns, 100);
+        }
+
+        book.record_success(addr(1).ip(), 10);
+        book.entries.get_mut(&addr(1).ip()).unwrap().last_success = Some(200);
+        book.record_success(addr(2).ip(), 10);
+        book.entries.get_mut(&addr(2).ip()).unwrap().last_success = Some(300);
+
+        assert_eq!(book.known_good(), vec![addr(2), addr(1)]);
+    }
+
+    #[test]
+    fn test_failures_exclude_peer() {
+        let mut book = AddressBook::default();
+
+        book.record_seen(addr(1), PeerSource::PeerExchange, 100);
+        book.record_success(addr(1).ip(), 10);
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            book.record_failure(addr(1).ip());
+        }
+
+        assert!(book.known_good().is_empty());
+
+        book.record_success(addr(1).ip(), 11);
+
+        assert_eq!(book.known_good(), vec![addr(1)]);
+        assert_eq!(book.entry(addr(1).ip()).unwrap().peak_height, Some(11));
+    }
+
+    #[test]
+    fn test_source_is_kept() {
+        let mut book = AddressBook::default();
+
+        book.record_seen(addr(1), PeerSource::Dns, 100);
+        book.record_seen(addr(1), PeerSource::PeerExchange, 50);
+
+        let entry = book.entry(addr(1).ip()).unwrap();
+        assert_eq!(entry.source, PeerSource::Dns);
+        assert_eq!(entry.last_seen, 100);
+
+        book.record_seen(addr(1), PeerSource::User, 150);
+
+        let entry = book.entry(addr(1).ip()).unwrap();
+        assert_eq!(entry.source, PeerSource::User);
+        assert_eq!(entry.last_seen, 150);
+    }
+
+    #[test]
+    fn test_evict_stale_and_failing() {
+        let mut book = AddressBook::default();
+
+        book.record_seen(addr(1), PeerSource::Dns, 100);
+        book.record_seen(addr(2), PeerSource::User, 100);
+        book.record_seen(addr(3), PeerSource::Dns, 100 + STALE_AFTER_SECONDS + 1);
+
+        assert!(book.entry(addr(1).ip()).is_none());
+        assert!(book.entry(addr(2).ip()).is_some());
+
+        for _ in 1..EVICTION_FAILURES {
+            book.record_failure(addr(3).ip());
+        }
+
+        assert!(book.entry(addr(3).ip()).is_some());
+
+        book.record_failure(addr(3).ip());
+
+        assert!(book.entry(addr(3).ip()).is_none());
+    }
+
+    #[test]
+    fn test_evict_over_capacity() {
+        let mut book = AddressBook::default();
+
+        for i in 0..=MAX_ENTRIES as u32 {
+            let ip = IpAddr::from((i + 1).to_be_bytes());
+            book.record_seen(
+                SocketAddr::new(ip, 8444),
+                PeerSource::PeerExchange,
+                100 + u64::from(i),
+            );
+        }
+
+        assert_eq!(book.entries.len(), MAX_ENTRIES);
+
+        // The peer which was seen the longest ago goes first.
+        assert!(book.entry(IpAddr::from(1u32.to_be_bytes())).is_none());
+        assert!(book.entry(IpAddr::from(2u32.to_be_bytes())).is_some());
+    }
+}
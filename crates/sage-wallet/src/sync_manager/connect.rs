@@ -0,0 +1,206 @@
+use std::{io, net::SocketAddr};
+
+use chia_traits::Streamable;
+use chia_wallet_sdk::{
+    chia::protocol::{Handshake, Message, NodeType, ProtocolMessageTypes},
+    client::{ClientError, Connector, connect_peer},
+    prelude::*,
+};
+use tokio::sync::mpsc;
+use tokio_socks::{IntoTargetAddr, tcp::Socks5Stream};
+
+use crate::WalletPeer;
+
+/// The handshake sent by the SDK's `connect_peer`, which can't be reused for proxied streams or
+/// introducers. These must be kept in sync with the SDK when it's upgraded.
+const PROTOCOL_VERSION: &str = "0.0.37";
+const SOFTWARE_VERSION: &str = "0.0.0";
+
+/// Connects to a full node, through the SOCKS5 proxy if there is one. The peer is identified by
+/// the address that was requested, since a proxied socket is connected to the proxy instead.
+pub async fn connect_full_node(
+    network_id: String,
+    connector: Connector,
+    socket_addr: SocketAddr,
+    proxy: Option<SocketAddr>,
+) -> Result<(WalletPeer, mpsc::Receiver<Message>), ClientError> {
+    let (peer, receiver) = match proxy {
+        Some(proxy) => {
+            let (peer, mut receiver) =
+                connect_through_proxy(proxy, socket_addr, &socket_addr.to_string(), connector)
+                    .await?;
+            handshake(&peer, &mut receiver, network_id, NodeType::FullNode).await?;
+            (peer, receiver)
+        }
+        None => connect_peer(network_id, connector, socket_addr, PeerOptions::default()).await?,
+    };
+
+    Ok((WalletPeer::with_socket_addr(peer, socket_addr), receiver))
+}
+
+/// Connects to an introducer by its host name. When a proxy is used, the host name is resolved
+/// by the proxy rather than locally.
+pub async fn connect_introducer(
+    network_id: String,
+    connector: Connector,
+    host: &str,
+    port: u16,
+    proxy: Option<SocketAddr>,
+) -> Result<(Peer, mpsc::Receiver<Message>), ClientError> {
+    let (peer, mut receiver) = match proxy {
+        Some(proxy) => {
+            connect_through_proxy(proxy, (host, port), &format!("{host}:{port}"), connector).await?
+        }
+        None => {
+            Peer::connect_full_uri(
+                &format!("wss://{host}:{port}/ws"),
+                connector,
+                PeerOptions::default(),
+            )
+            .await?
+        }
+    };
+
+    handshake(&peer, &mut receiver, network_id, NodeType::Introducer).await?;
+
+    Ok((peer, receiver))
+}
+
+async fn connect_through_proxy<'a>(
+    proxy: SocketAddr,
+    target: impl IntoTargetAddr<'a>,
+    authority: &str,
+    connector: Connector,
+) -> Result<(Peer, mpsc::Receiver<Message>), ClientError> {
+    let stream = Socks5Stream::connect(proxy, target)
+        .await
+        .map_err(io::Error::other)?
+        .into_inner();
+
+    let scheme = if matches!(connector, Connector::Plain) {
+        "ws"
+    } else {
+        "wss"
+    };
+
+    let (ws, _) = tokio_tungstenite::client_async_tls_with_config(
+        format!("{scheme}://{authority}/ws"),
+        stream,
+        None,
+        Some(connector),
+    )
+    .await?;
+
+    Peer::from_websocket(ws, PeerOptions::default())
+}
+
+async fn handshake(
+    peer: &Peer,
+    receiver: &mut mpsc::Receiver<Message>,
+    network_id: String,
+    node_type: NodeType,
+) -> Result<(), ClientError> {
+    peer.send(Handshake {
+        network_id: network_id.clone(),
+        protocol_version: PROTOCOL_VERSION.to_string(),
+        software_version: SOFTWARE_VERSION.to_string(),
+        server_port: 0,
+        node_type: NodeType::Wallet,
+        capabilities: vec![
+            (1, "1".to_string()),
+            (2, "1".to_string()),
+            (3, "1".to_string()),
+        ],
+    })
+    .await?;
+
+    let Some(message) = receiver.recv().await else {
+        return Err(ClientError::MissingHandshake);
+    };
+
+    if message.msg_type != ProtocolMessageTypes::Handshake {
+        return Err(ClientError::InvalidResponse(
+            vec![ProtocolMessageTypes::Handshake],
+            message.msg_type,
+        ));
+    }
+
+    let handshake = Handshake::from_bytes(&message.data)?;
+
+    if handshake.node_type != node_type {
+        return Err(ClientError::WrongNodeType(node_type, handshake.node_type));
+    }
+
+    if handshake.network_id != network_id {
+        return Err(ClientError::WrongNetwork(network_id, handshake.network_id));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_wallet_sdk::test::PeerSimulator;
+    use test_log::test;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt, copy_bidirectional},
+        net::{TcpListener, TcpStream},
+        sync::oneshot,
+    };
+
+    use super::*;
+
+    /// Accepts a single SOCKS5 connection without authentication to an IPv4 address, and reports
+    /// which address was requested.
+    async fn socks_stand_in() -> anyhow::Result<(SocketAddr, oneshot::Receiver<SocketAddr>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (sender, receiver) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await?;
+
+            let mut greeting = [0; 2];
+            client.read_exact(&mut greeting).await?;
+            let mut methods = vec![0; greeting[1] as usize];
+            client.read_exact(&mut methods).await?;
+            client.write_all(&[5, 0]).await?;
+
+            let mut request = [0; 10];
+            client.read_exact(&mut request).await?;
+            assert_eq!(request[..4], [5, 1, 0, 1]);
+
+            let target = SocketAddr::from((
+                [request[4], request[5], request[6], request[7]],
+                u16::from_be_bytes([request[8], request[9]]),
+            ));
+            let mut server = TcpStream::connect(target).await?;
+            client.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await?;
+            sender.send(target).ok();
+
+            copy_bidirectional(&mut client, &mut server).await?;
+
+            anyhow::Ok(())
+        });
+
+        Ok((addr, receiver))
+    }
+
+    #[test(tokio::test)]
+    async fn test_connect_through_proxy() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let sim_addr = sim.connect_raw().await?.0.socket_addr();
+        let (proxy, requested) = socks_stand_in().await?;
+
+        let (peer, mut receiver) =
+            connect_through_proxy(proxy, sim_addr, &sim_addr.to_string(), Connector::Plain).await?;
+
+        assert_eq!(requested.await?, sim_addr);
+        assert_eq!(peer.socket_addr(), proxy);
+
+        let message = receiver.recv().await.expect("missing initial peak");
+        assert_eq!(message.msg_type, ProtocolMessageTypes::NewPeakWallet);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,674 @@
+use std::{net::IpAddr, sync::Arc};
+
+use chia_wallet_sdk::prelude::*;
+use futures_lite::future::poll_once;
+use sage_config::{ClawbackPolicy, CoinManagement, Network, ReputationSource};
+use tokio::{
+    sync::{Mutex, mpsc},
+    task::JoinHandle,
+};
+use tracing::warn;
+
+use crate::{
+    BlockTimeQueue, CatQueue, ClawbackQueue, CoinManagementQueue, HeaderQueue, MintJobQueue,
+    NftUriQueue, OfferQueue, PuzzleQueue, ReputationQueue, RoyaltyQueue, StandingOfferQueue,
+    SyncCommand, TransactionQueue, Wallet, WalletError, WalletPeer,
+};
+
+use super::{PeerState, SyncEvent, SyncOptions, WalletEvent};
+
+#[derive(Default)]
+pub(super) enum InitialWalletSync {
+    #[default]
+    Idle,
+    Syncing {
+        ip: IpAddr,
+        task: JoinHandle<Result<(), WalletError>>,
+    },
+    Subscribed(IpAddr),
+}
+
+impl InitialWalletSync {
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Self::Idle => None,
+            Self::Syncing { ip, .. } | Self::Subscribed(ip) => Some(*ip),
+        }
+    }
+}
+
+/// The sync state and background queues of a single wallet managed by the sync manager.
+pub(super) struct LoadedWallet {
+    pub wallet: Arc<Wallet>,
+    pub delta_sync: bool,
+    pub master_sk: Option<SecretKey>,
+    pub clawback_policy: Option<ClawbackPolicy>,
+    pub coin_management: Option<CoinManagement>,
+    pub event_sender: mpsc::Sender<SyncEvent>,
+    pub initial_wallet_sync: InitialWalletSync,
+    pub pending_coin_subscriptions: Vec<Bytes32>,
+    pub pending_puzzle_subscriptions: Vec<Bytes32>,
+    pub pending_coin_resyncs: Vec<Bytes32>,
+    event_task: JoinHandle<()>,
+    puzzle_lookup_task: Option<JoinHandle<Result<(), WalletError>>>,
+    cat_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    nft_uri_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    transaction_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    offer_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    blocktime_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    clawback_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    coin_management_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    standing_offer_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    mint_job_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    royalty_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    reputation_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+    header_queue_task: Option<JoinHandle<Result<(), WalletError>>>,
+}
+
+impl Drop for LoadedWallet {
+    fn drop(&mut self) {
+        self.abort_tasks();
+        self.event_task.abort();
+    }
+}
+
+impl LoadedWallet {
+    pub fn new(
+        wallet: Arc<Wallet>,
+        delta_sync: bool,
+        master_sk: Option<SecretKey>,
+        clawback_policy: Option<ClawbackPolicy>,
+        coin_management: Option<CoinManagement>,
+        wallet_event_sender: mpsc::Sender<WalletEvent>,
+    ) -> Self {
+        // The queues only know about their own wallet, so their events are tagged with the
+        // fingerprint on the way out.
+        let (event_sender, mut event_receiver) = mpsc::channel(100);
+        let fingerprint = wallet.fingerprint;
+
+        let event_task = tokio::spawn(async move {
+            while let Some(event) = event_receiver.recv().await {
+                if wallet_event_sender
+                    .send(WalletEvent { fingerprint, event })
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            wallet,
+            delta_sync,
+            master_sk,
+            clawback_policy,
+            coin_management,
+            event_sender,
+            initial_wallet_sync: InitialWalletSync::Idle,
+            pending_coin_subscriptions: Vec::new(),
+            pending_puzzle_subscriptions: Vec::new(),
+            pending_coin_resyncs: Vec::new(),
+            event_task,
+            puzzle_lookup_task: None,
+            cat_queue_task: None,
+            nft_uri_queue_task: None,
+            transaction_queue_task: None,
+            offer_queue_task: None,
+            blocktime_queue_task: None,
+            clawback_queue_task: None,
+            coin_management_queue_task: None,
+            standing_offer_queue_task: None,
+            mint_job_queue_task: None,
+            royalty_queue_task: None,
+            reputation_queue_task: None,
+            header_queue_task: None,
+        }
+    }
+
+    /// Applies a new config to the wallet without resyncing it. The queues which depend on the
+    /// config or on the signing key are restarted with the new values on the next update.
+    pub fn update_config(
+        &mut self,
+        delta_sync: bool,
+        master_sk: Option<SecretKey>,
+        clawback_policy: Option<ClawbackPolicy>,
+        coin_management: Option<CoinManagement>,
+    ) {
+        self.delta_sync = delta_sync;
+
+        if self.master_sk.is_some() == master_sk.is_some()
+            && self.clawback_policy == clawback_policy
+            && self.coin_management == coin_management
+        {
+            return;
+        }
+
+        self.master_sk = master_sk;
+        self.clawback_policy = clawback_policy;
+        self.coin_management = coin_management;
+
+        for task in [
+            self.clawback_queue_task.take(),
+            self.coin_management_queue_task.take(),
+            self.standing_offer_queue_task.take(),
+            self.mint_job_queue_task.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            task.abort();
+        }
+    }
+
+    /// Whether a coin state from a peer shared with other wallets belongs to this wallet.
+    pub async fn is_relevant(&self, coin_state: &CoinState) -> Result<bool, WalletError> {
+        let db = &self.wallet.db;
+
+        Ok(db.is_p2_puzzle_hash(coin_state.coin.puzzle_hash).await?
+            || db.is_known_coin(coin_state.coin.coin_id()).await?
+            || db.is_known_coin(coin_state.coin.parent_coin_info).await?)
+    }
+
+    /// Removes this wallet's subscriptions from the peer, leaving those of other wallets alone.
+    pub async fn unsubscribe(&self, peer: &WalletPeer) -> Result<(), WalletError> {
+        let db = &self.wallet.db;
+
+        for batch in db.custody_p2_puzzle_hashes().await?.chunks(1000) {
+            peer.unsubscribe_puzzles(batch.to_vec()).await?;
+        }
+
+        for batch in db.subscription_coin_ids().await?.chunks(1000) {
+            peer.unsubscribe_coins(batch.to_vec()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Goes back to idle, so that the initial sync is started again on the next update.
+    pub fn reset_sync(&mut self) {
+        if let InitialWalletSync::Syncing { task, .. } =
+            std::mem::take(&mut self.initial_wallet_sync)
+        {
+            task.abort();
+        }
+    }
+
+    pub fn abort_tasks(&mut self) {
+        self.reset_sync();
+        if let Some(task) = self.puzzle_lookup_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.cat_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.nft_uri_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.transaction_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.offer_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.blocktime_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.clawback_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.coin_management_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.standing_offer_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.mint_job_queue_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.royalty_queue_task.take() {
+            task.abort();
+        }
+        self.abort_reputation_queue();
+        self.abort_header_queue();
+    }
+
+    /// The queue is restarted with the current sources on the next update.
+    pub fn abort_reputation_queue(&mut self) {
+        if let Some(task) = self.reputation_queue_task.take() {
+            task.abort();
+        }
+    }
+
+    pub fn abort_header_queue(&mut self) {
+        if let Some(task) = self.header_queue_task.take() {
+            task.abort();
+        }
+    }
+
+    pub fn spawn_tasks(
+        &mut self,
+        options: &SyncOptions,
+        network: &Network,
+        state: &Arc<Mutex<PeerState>>,
+        command_sender: &mpsc::Sender<SyncCommand>,
+        reputation_sources: &[ReputationSource],
+    ) {
+        let wallet = &self.wallet;
+
+        if self.puzzle_lookup_task.is_none() {
+            let task = tokio::spawn(
+                PuzzleQueue::new(
+                    wallet.db.clone(),
+                    wallet.fingerprint,
+                    wallet.genesis_challenge,
+                    options.puzzle_batch_size_per_peer,
+                    state.clone(),
+                    wallet.progress.clone(),
+                    self.event_sender.clone(),
+                    command_sender.clone(),
+                )
+                .start(options.timeouts.puzzle_delay),
+            );
+            self.puzzle_lookup_task = Some(task);
+        }
+
+        if self.cat_queue_task.is_none() && !options.testing {
+            let services = network.services();
+
+            if let Some(dexie_api) = services.dexie_api {
+                let task = tokio::spawn(
+                    CatQueue::new(
+                        wallet.db.clone(),
+                        dexie_api,
+                        services.dexie_icons,
+                        wallet.progress.clone(),
+                        self.event_sender.clone(),
+                    )
+                    .start(options.timeouts.cat_delay),
+                );
+                self.cat_queue_task = Some(task);
+            }
+        }
+
+        if self.nft_uri_queue_task.is_none() && !options.testing {
+            let task = tokio::spawn(
+                NftUriQueue::new(
+                    wallet.db.clone(),
+                    wallet.progress.clone(),
+                    self.event_sender.clone(),
+                    network.clone(),
+                )
+                .start(options.timeouts.nft_uri_delay),
+            );
+            self.nft_uri_queue_task = Some(task);
+        }
+
+        if self.transaction_queue_task.is_none() {
+            let task = tokio::spawn(
+                TransactionQueue::new(
+                    wallet.db.clone(),
+                    state.clone(),
+                    wallet.transaction_retries.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.transaction_delay),
+            );
+            self.transaction_queue_task = Some(task);
+        }
+
+        if self.offer_queue_task.is_none() {
+            let task = tokio::spawn(
+                OfferQueue::new(
+                    wallet.db.clone(),
+                    wallet.genesis_challenge,
+                    state.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.offer_delay),
+            );
+            self.offer_queue_task = Some(task);
+        }
+
+        if self.blocktime_queue_task.is_none() && !options.testing {
+            let task = tokio::spawn(
+                BlockTimeQueue::new(
+                    wallet.db.clone(),
+                    state.clone(),
+                    wallet.progress.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.blocktime_delay),
+            );
+            self.blocktime_queue_task = Some(task);
+        }
+
+        if self.clawback_queue_task.is_none()
+            && let Some(master_sk) = self.master_sk.clone()
+            && let Some(policy) = self.clawback_policy
+        {
+            let task = tokio::spawn(
+                ClawbackQueue::new(
+                    wallet.clone(),
+                    master_sk,
+                    policy,
+                    state.clone(),
+                    command_sender.clone(),
+                )
+                .start(options.timeouts.clawback_delay),
+            );
+            self.clawback_queue_task = Some(task);
+        }
+
+        if self.coin_management_queue_task.is_none()
+            && let Some(master_sk) = self.master_sk.clone()
+            && let Some(config) = self.coin_management.clone()
+        {
+            let task = tokio::spawn(
+                CoinManagementQueue::new(
+                    wallet.clone(),
+                    master_sk,
+                    config,
+                    state.clone(),
+                    command_sender.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.coin_management_delay),
+            );
+            self.coin_management_queue_task = Some(task);
+        }
+
+        if self.standing_offer_queue_task.is_none()
+            && let Some(master_sk) = self.master_sk.clone()
+        {
+            let task = tokio::spawn(
+                StandingOfferQueue::new(
+                    wallet.clone(),
+                    master_sk,
+                    state.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.standing_offer_delay),
+            );
+            self.standing_offer_queue_task = Some(task);
+        }
+
+        if self.mint_job_queue_task.is_none()
+            && let Some(master_sk) = self.master_sk.clone()
+        {
+            let task = tokio::spawn(
+                MintJobQueue::new(
+                    wallet.clone(),
+                    master_sk,
+                    state.clone(),
+                    command_sender.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.mint_job_delay),
+            );
+            self.mint_job_queue_task = Some(task);
+        }
+
+        if self.royalty_queue_task.is_none() {
+            let task = tokio::spawn(
+                RoyaltyQueue::new(
+                    wallet.db.clone(),
+                    wallet.genesis_challenge,
+                    state.clone(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.royalty_delay),
+            );
+            self.royalty_queue_task = Some(task);
+        }
+
+        if self.reputation_queue_task.is_none() {
+            let task = tokio::spawn(
+                ReputationQueue::new(
+                    wallet.db.clone(),
+                    reputation_sources.to_vec(),
+                    self.event_sender.clone(),
+                )
+                .start(options.timeouts.reputation_delay),
+            );
+            self.reputation_queue_task = Some(task);
+        }
+
+        if self.header_queue_task.is_none() && options.header_validation && !options.testing {
+            let task = tokio::spawn(
+                HeaderQueue::new(
+                    wallet.db.clone(),
+                    wallet.fingerprint,
+                    state.clone(),
+                    self.event_sender.clone(),
+                    command_sender.clone(),
+                )
+                .start(options.timeouts.header_delay),
+            );
+            self.header_queue_task = Some(task);
+        }
+    }
+
+    pub async fn poll_tasks(&mut self) {
+        if let Some(task) = &mut self.puzzle_lookup_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Puzzle lookup queue failed with panic: {error}");
+                    self.puzzle_lookup_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Puzzle lookup queue failed with error: {error}");
+                    self.puzzle_lookup_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.puzzle_lookup_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.cat_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("CAT lookup queue failed with panic: {error}");
+                    self.cat_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("CAT lookup queue failed with error: {error}");
+                    self.cat_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.cat_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.nft_uri_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("NFT URI queue failed with panic: {error}");
+                    self.nft_uri_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("NFT URI queue failed with error: {error}");
+                    self.nft_uri_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.nft_uri_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.transaction_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Transaction queue failed with panic: {error}");
+                    self.transaction_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Transaction queue failed with error: {error}");
+                    self.transaction_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.transaction_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.offer_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Offer queue failed with panic: {error}");
+                    self.offer_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Offer queue failed with error: {error}");
+                    self.offer_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.offer_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.blocktime_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Blocktime queue failed with panic: {error}");
+                    self.blocktime_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Blocktime queue failed with error: {error}");
+                    self.blocktime_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.blocktime_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.clawback_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Clawback queue failed with panic: {error}");
+                    self.clawback_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Clawback queue failed with error: {error}");
+                    self.clawback_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.clawback_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.coin_management_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Coin management queue failed with panic: {error}");
+                    self.coin_management_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Coin management queue failed with error: {error}");
+                    self.coin_management_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.coin_management_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.standing_offer_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Standing offer queue failed with panic: {error}");
+                    self.standing_offer_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Standing offer queue failed with error: {error}");
+                    self.standing_offer_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.standing_offer_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.mint_job_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Mint job queue failed with panic: {error}");
+                    self.mint_job_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Mint job queue failed with error: {error}");
+                    self.mint_job_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.mint_job_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.royalty_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Royalty queue failed with panic: {error}");
+                    self.royalty_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Royalty queue failed with error: {error}");
+                    self.royalty_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.royalty_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.reputation_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Reputation queue failed with panic: {error}");
+                    self.reputation_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Reputation queue failed with error: {error}");
+                    self.reputation_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.reputation_queue_task = None;
+                }
+                None => {}
+            }
+        }
+
+        if let Some(task) = &mut self.header_queue_task {
+            match poll_once(task).await {
+                Some(Err(error)) => {
+                    warn!("Header queue failed with panic: {error}");
+                    self.header_queue_task = None;
+                }
+                Some(Ok(Err(error))) => {
+                    warn!("Header queue failed with error: {error}");
+                    self.header_queue_task = None;
+                }
+                Some(Ok(Ok(()))) => {
+                    self.header_queue_task = None;
+                }
+                None => {}
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{net::SocketAddr, time::Duration};
 
 #[derive(Debug, Clone, Copy)]
 pub struct SyncOptions {
@@ -7,12 +7,37 @@ pub struct SyncOptions {
     pub dns_batch_size: usize,
     pub connection_batch_size: usize,
     pub max_peer_age_seconds: u64,
-    pub delta_sync: bool,
     pub puzzle_batch_size_per_peer: usize,
+    /// Whether coin states should be checked against a second peer before they are committed.
+    pub paranoid_sync: bool,
+    /// Whether the header chain should be followed, so that reorgs can be detected.
+    pub header_validation: bool,
+    /// A SOCKS5 proxy which all peer connections are made through.
+    pub proxy: Option<SocketAddr>,
+    /// Whether DNS and peer introducers can be used to find peers. If disabled, only peers
+    /// which have been added manually or learned from connected peers are used.
+    pub dns_discovery: bool,
     pub timeouts: Timeouts,
     pub testing: bool,
 }
 
+impl SyncOptions {
+    /// Trusted peers are our own full nodes, which aren't rate limited as strictly and are
+    /// expected to respond quickly.
+    #[must_use]
+    pub fn trusted(self) -> Self {
+        Self {
+            puzzle_batch_size_per_peer: self.puzzle_batch_size_per_peer * 4,
+            timeouts: Timeouts {
+                connection: self.timeouts.connection.min(Duration::from_secs(1)),
+                initial_peak: self.timeouts.initial_peak.min(Duration::from_secs(1)),
+                ..self.timeouts
+            },
+            ..self
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Timeouts {
     pub sync_delay: Duration,
@@ -22,10 +47,18 @@ pub struct Timeouts {
     pub transaction_delay: Duration,
     pub offer_delay: Duration,
     pub blocktime_delay: Duration,
+    pub clawback_delay: Duration,
+    pub coin_management_delay: Duration,
+    pub standing_offer_delay: Duration,
+    pub mint_job_delay: Duration,
+    pub royalty_delay: Duration,
+    pub reputation_delay: Duration,
+    pub header_delay: Duration,
     pub connection: Duration,
     pub initial_peak: Duration,
     pub dns: Duration,
     pub introducer: Duration,
+    pub rpc_fallback: Duration,
 }
 
 impl Default for Timeouts {
@@ -38,10 +71,18 @@ impl Default for Timeouts {
             transaction_delay: Duration::from_secs(1),
             offer_delay: Duration::from_secs(5),
             blocktime_delay: Duration::from_secs(1),
+            clawback_delay: Duration::from_secs(30),
+            coin_management_delay: Duration::from_secs(60),
+            standing_offer_delay: Duration::from_secs(10),
+            mint_job_delay: Duration::from_secs(10),
+            royalty_delay: Duration::from_secs(5),
+            reputation_delay: Duration::from_secs(3600),
+            header_delay: Duration::from_secs(5),
             connection: Duration::from_secs(3),
             initial_peak: Duration::from_secs(2),
             dns: Duration::from_secs(3),
             introducer: Duration::from_secs(10),
+            rpc_fallback: Duration::from_secs(30),
         }
     }
 }
@@ -3,18 +3,13 @@ use std::{
     collections::HashMap,
     net::{IpAddr, SocketAddr},
     str::FromStr,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::Duration,
 };
 
 use chia_streamable_macro::Streamable;
 use chia_traits::Streamable;
-use chia_wallet_sdk::{
-    chia::protocol::{
-        ChiaProtocolMessage, Handshake, Message, NewPeakWallet, NodeType, ProtocolMessageTypes,
-        TimestampedPeerInfo,
-    },
-    client::{ClientError, connect_peer},
-    prelude::*,
+use chia_wallet_sdk::chia::protocol::{
+    ChiaProtocolMessage, Message, NewPeakWallet, ProtocolMessageTypes, TimestampedPeerInfo,
 };
 use futures_lite::StreamExt;
 use futures_util::stream::FuturesUnordered;
@@ -24,7 +19,12 @@ use tracing::{debug, info, warn};
 
 use crate::{SyncCommand, WalletError, WalletPeer};
 
-use super::{PeerInfo, SyncManager, dns::lookup_all};
+use super::{
+    PeerInfo, PeerSource, SyncManager,
+    address_book::now,
+    connect::{connect_full_node, connect_introducer},
+    dns::lookup_all,
+};
 
 #[derive(Streamable)]
 struct RequestPeersIntroducer {}
@@ -47,25 +47,13 @@ impl ChiaProtocolMessage for RespondPeersIntroducer {
 }
 
 impl SyncManager {
-    pub(super) async fn clear_subscriptions(&self) {
-        let mut futures = FuturesUnordered::new();
-
-        for peer in self.state.lock().await.peers() {
-            let ip = peer.socket_addr().ip();
-
-            let puzzle_peer = peer.clone();
-
-            futures.push(async move {
-                if let Err(error) = puzzle_peer.unsubscribe().await {
-                    debug!("Failed to clear subscriptions from {ip}: {error}");
-                }
-            });
+    pub(super) async fn dns_discovery(&mut self) -> bool {
+        // DNS lookups are made locally and would leak around the proxy, so the introducers are
+        // used instead, since their host names are resolved by the proxy.
+        if self.options.proxy.is_some() {
+            return false;
         }
 
-        while let Some(()) = futures.next().await {}
-    }
-
-    pub(super) async fn dns_discovery(&mut self) -> bool {
         let addrs = lookup_all(
             &self.network.dns_introducers(),
             self.network.default_port,
@@ -78,6 +66,8 @@ impl SyncManager {
             return false;
         }
 
+        self.record_seen(&addrs, PeerSource::Dns, now()).await;
+
         for addrs in addrs.chunks(self.options.connection_batch_size) {
             if self.connect_batch(addrs, false, false).await {
                 break;
@@ -87,6 +77,35 @@ impl SyncManager {
         true
     }
 
+    /// Reconnects to peers which have worked before, so that DNS only needs to be used when
+    /// none of them are reachable.
+    pub(super) async fn address_book_discovery(&mut self) -> bool {
+        let state = self.state.lock().await;
+
+        let addrs: Vec<SocketAddr> = state
+            .address_book()
+            .known_good()
+            .into_iter()
+            .filter(|addr| !state.is_connected(addr.ip()) && !state.is_banned(addr.ip()))
+            .collect();
+
+        drop(state);
+
+        if addrs.is_empty() {
+            return false;
+        }
+
+        info!("Reconnecting to {} known peers", addrs.len());
+
+        for addrs in addrs.chunks(self.options.connection_batch_size) {
+            if self.connect_batch(addrs, false, false).await {
+                return true;
+            }
+        }
+
+        false
+    }
+
     pub(super) async fn introducer_discovery(&mut self) {
         info!(
             "Looking up non-DNS introducers {}",
@@ -100,59 +119,14 @@ impl SyncManager {
             let port = self.network.default_port;
             let connector = self.connector.clone();
             let network_id = self.network.network_id();
+            let proxy = self.options.proxy;
 
             futures.push(async move {
                 let host_clone = host.clone();
 
                 let result = timeout(introducer_timeout, async move {
-                    let (peer, mut receiver) = Peer::connect_full_uri(
-                        &format!("wss://{host_clone}:{port}/ws"),
-                        connector,
-                        PeerOptions::default(),
-                    )
-                    .await?;
-
-                    peer.send(Handshake {
-                        network_id: network_id.clone(),
-                        protocol_version: "0.0.37".to_string(),
-                        software_version: "0.0.0".to_string(),
-                        server_port: 0,
-                        node_type: NodeType::Wallet,
-                        capabilities: vec![
-                            (1, "1".to_string()),
-                            (2, "1".to_string()),
-                            (3, "1".to_string()),
-                        ],
-                    })
-                    .await?;
-
-                    let Some(message) = receiver.recv().await else {
-                        return Err(ClientError::MissingHandshake)?;
-                    };
-
-                    if message.msg_type != ProtocolMessageTypes::Handshake {
-                        return Err(ClientError::InvalidResponse(
-                            vec![ProtocolMessageTypes::Handshake],
-                            message.msg_type,
-                        ))?;
-                    }
-
-                    let handshake =
-                        Handshake::from_bytes(&message.data).map_err(ClientError::from)?;
-
-                    if handshake.node_type != NodeType::Introducer {
-                        return Err(ClientError::WrongNodeType(
-                            NodeType::Introducer,
-                            handshake.node_type,
-                        ))?;
-                    }
-
-                    if handshake.network_id != network_id {
-                        return Err(ClientError::WrongNetwork(
-                            network_id.to_string(),
-                            handshake.network_id,
-                        ))?;
-                    }
+                    let (peer, _receiver) =
+                        connect_introducer(network_id, connector, &host_clone, port, proxy).await?;
 
                     let peer_list = peer
                         .request_infallible::<RespondPeersIntroducer, _>(RequestPeersIntroducer {})
@@ -170,7 +144,8 @@ impl SyncManager {
         while let Some((host, result)) = futures.next().await {
             match result {
                 Ok(Ok((ip, peer_list))) => {
-                    self.handle_peer_list(None, peer_list, ip, false).await;
+                    self.handle_peer_list(None, peer_list, ip, PeerSource::Introducer)
+                        .await;
                 }
                 Ok(Err(error)) => {
                     debug!("Failed to request peers from {host}: {error}");
@@ -200,17 +175,13 @@ impl SyncManager {
             });
         }
 
-        let start = SystemTime::now();
-        let since_the_epoch = start
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards");
-        let timestamp = since_the_epoch.as_secs();
+        let timestamp = now();
 
         while let Some((ip, result)) = futures.next().await {
             match result {
                 Ok(peer_list) => {
                     if self
-                        .handle_peer_list(Some(timestamp), peer_list, ip, true)
+                        .handle_peer_list(Some(timestamp), peer_list, ip, PeerSource::PeerExchange)
                         .await
                     {
                         return true;
@@ -235,8 +206,11 @@ impl SyncManager {
         timestamp: Option<u64>,
         mut peer_list: Vec<TimestampedPeerInfo>,
         ip: IpAddr,
-        ban: bool,
+        source: PeerSource,
     ) -> bool {
+        // Introducers are only asked once, so a bad response isn't worth banning them for.
+        let ban = source == PeerSource::PeerExchange;
+
         if let Some(timestamp) = timestamp {
             peer_list
                 .retain(|item| item.timestamp >= timestamp - self.options.max_peer_age_seconds);
@@ -266,7 +240,16 @@ impl SyncManager {
 
                 break;
             };
-            addrs.push(SocketAddr::new(new_ip, self.network.default_port));
+
+            let addr = SocketAddr::new(new_ip, self.network.default_port);
+
+            self.state
+                .lock()
+                .await
+                .address_book_mut()
+                .record_seen(addr, source, item.timestamp);
+
+            addrs.push(addr);
         }
 
         for addrs in addrs.chunks(self.options.connection_batch_size) {
@@ -295,12 +278,13 @@ impl SyncManager {
 
             let network_id = self.network.network_id();
             let connector = self.connector.clone();
-            let duration = self.options.timeouts.connection;
+            let duration = self.effective_options().timeouts.connection;
+            let proxy = self.options.proxy;
 
             futures.push(async move {
                 let result = timeout(
                     duration,
-                    connect_peer(network_id, connector, socket_addr, PeerOptions::default()),
+                    connect_full_node(network_id, connector, socket_addr, proxy),
                 )
                 .await;
                 (socket_addr, result)
@@ -324,8 +308,12 @@ impl SyncManager {
                 }
                 Ok(Err(error)) => {
                     debug!("Failed to connect to peer {socket_addr}: {error}");
+
+                    let mut state = self.state.lock().await;
+                    state.address_book_mut().record_failure(socket_addr.ip());
+
                     if !force {
-                        self.state.lock().await.ban(
+                        state.ban(
                             socket_addr.ip(),
                             Duration::from_secs(60 * 10),
                             "failed to connect",
@@ -334,8 +322,12 @@ impl SyncManager {
                 }
                 Err(_timeout) => {
                     debug!("Connection to peer {socket_addr} timed out");
+
+                    let mut state = self.state.lock().await;
+                    state.address_book_mut().record_failure(socket_addr.ip());
+
                     if !force {
-                        self.state.lock().await.ban(
+                        state.ban(
                             socket_addr.ip(),
                             Duration::from_secs(60 * 10),
                             "connection timed out",
@@ -348,19 +340,34 @@ impl SyncManager {
         self.check_peer_count().await
     }
 
+    pub(super) async fn record_seen(
+        &self,
+        addrs: &[SocketAddr],
+        source: PeerSource,
+        timestamp: u64,
+    ) {
+        let mut state = self.state.lock().await;
+
+        for &addr in addrs {
+            state
+                .address_book_mut()
+                .record_seen(addr, source, timestamp);
+        }
+    }
+
     async fn check_peer_count(&mut self) -> bool {
         self.state.lock().await.peer_count() >= self.options.target_peers
     }
 
     pub(crate) async fn try_add_peer(
         &mut self,
-        peer: Peer,
+        peer: WalletPeer,
         mut receiver: mpsc::Receiver<Message>,
         force: bool,
         user_managed: bool,
     ) -> bool {
-        let Ok(Some(message)) = timeout(self.options.timeouts.initial_peak, receiver.recv()).await
-        else {
+        let initial_peak = self.effective_options().timeouts.initial_peak;
+        let Ok(Some(message)) = timeout(initial_peak, receiver.recv()).await else {
             debug!(
                 "Timeout receiving NewPeakWallet message from peer {}",
                 peer.socket_addr()
@@ -441,8 +448,9 @@ impl SyncManager {
         }
 
         state.add_peer(PeerInfo {
-            peer: WalletPeer::new(peer),
+            peer,
             claimed_peak: message.height,
+            claimed_weight: message.weight,
             header_hash: message.header_hash,
             user_managed,
             receive_message_task: tokio::spawn(async move {
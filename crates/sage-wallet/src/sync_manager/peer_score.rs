@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+/// Latency assumed for peers which haven't responded to anything yet, so that new peers
+/// aren't preferred over peers that are known to be reasonably fast.
+const DEFAULT_LATENCY: Duration = Duration::from_millis(500);
+
+const LATENCY_PENALTY_PER_MS: i64 = 1;
+const FAILURE_PENALTY: i64 = 250;
+const DISAGREEMENT_PENALTY: i64 = 1000;
+const PEAK_LAG_PENALTY: i64 = 100;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PeerScore {
+    /// An exponentially weighted moving average of the response time.
+    pub latency: Option<Duration>,
+    /// Failed requests, which are forgiven one at a time as requests succeed.
+    pub failures: u32,
+    /// The number of times this peer has reported coin states that other peers disputed.
+    pub disagreements: u32,
+    /// How many blocks this peer's peak is behind the highest known peak.
+    pub peak_lag: u32,
+}
+
+impl PeerScore {
+    pub fn record_response(&mut self, latency: Duration) {
+        self.latency = Some(match self.latency {
+            Some(average) => (average * 3 + latency) / 4,
+            None => latency,
+        });
+        self.failures = self.failures.saturating_sub(1);
+    }
+
+    pub fn record_failure(&mut self) {
+        self.failures = self.failures.saturating_add(1);
+    }
+
+    pub fn record_disagreement(&mut self) {
+        self.disagreements = self.disagreements.saturating_add(1);
+    }
+
+    /// A higher value indicates a more reliable peer.
+    pub fn value(&self) -> i64 {
+        let latency = self.latency.unwrap_or(DEFAULT_LATENCY).as_millis();
+
+        -(i64::try_from(latency).unwrap_or(i64::MAX / 4) * LATENCY_PENALTY_PER_MS)
+            - i64::from(self.failures) * FAILURE_PENALTY
+            - i64::from(self.disagreements) * DISAGREEMENT_PENALTY
+            - i64::from(self.peak_lag) * PEAK_LAG_PENALTY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_average() {
+        let mut score = PeerScore::default();
+
+        score.record_response(Duration::from_millis(100));
+        assert_eq!(score.latency, Some(Duration::from_millis(100)));
+
+        score.record_response(Duration::from_millis(500));
+        assert_eq!(score.latency, Some(Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_failures_are_forgiven() {
+        let mut score = PeerScore::default();
+
+        score.record_failure();
+        score.record_failure();
+        score.record_response(Duration::from_millis(100));
+
+        assert_eq!(score.failures, 1);
+    }
+
+    #[test]
+    fn test_score_ordering() {
+        let fast = PeerScore {
+            latency: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+
+        let slow = PeerScore {
+            latency: Some(Duration::from_millis(900)),
+            ..Default::default()
+        };
+
+        let unknown = PeerScore::default();
+
+        let lagging = PeerScore {
+            peak_lag: 10,
+            ..fast
+        };
+
+        let disputed = PeerScore {
+            disagreements: 1,
+            ..fast
+        };
+
+        assert!(fast.value() > unknown.value());
+        assert!(unknown.value() > slow.value());
+        assert!(slow.value() > lagging.value());
+        assert!(slow.value() > disputed.value());
+    }
+}
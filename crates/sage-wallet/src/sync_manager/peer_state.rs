@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     net::IpAddr,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -11,15 +12,36 @@ use tracing::debug;
 
 use crate::WalletPeer;
 
+use super::{AddressBook, PeerScore};
+
+/// Peers which are further behind the highest peak than this are only used as a last resort.
+const MAX_ACQUIRE_PEAK_LAG: u32 = 3;
+
+/// Peers are banned once they have disagreed with other peers this many times.
+const MAX_DISAGREEMENTS: u32 = 3;
+
 #[derive(Debug)]
 pub struct PeerInfo {
     pub peer: WalletPeer,
     pub claimed_peak: u32,
+    pub claimed_weight: u128,
     pub header_hash: Bytes32,
     pub receive_message_task: JoinHandle<()>,
     pub user_managed: bool,
 }
 
+impl PeerInfo {
+    fn preference(&self) -> (bool, i64, u32) {
+        let score = self.peer.score();
+
+        (
+            score.peak_lag <= MAX_ACQUIRE_PEAK_LAG,
+            score.value(),
+            self.claimed_peak,
+        )
+    }
+}
+
 impl Drop for PeerInfo {
     fn drop(&mut self) {
         self.receive_message_task.abort();
@@ -30,6 +52,8 @@ impl Drop for PeerInfo {
 pub struct PeerState {
     peers: HashMap<IpAddr, PeerInfo>,
     banned_peers: HashMap<IpAddr, u64>,
+    trusted_peers: HashSet<IpAddr>,
+    address_book: AddressBook,
 }
 
 impl PeerState {
@@ -38,6 +62,30 @@ impl PeerState {
         self.banned_peers.clear();
     }
 
+    pub fn set_trusted_peers(&mut self, trusted_peers: HashSet<IpAddr>) {
+        for ip in &trusted_peers {
+            self.banned_peers.remove(ip);
+        }
+        self.trusted_peers = trusted_peers;
+    }
+
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted_peers.contains(&ip)
+    }
+
+    pub fn address_book(&self) -> &AddressBook {
+        &self.address_book
+    }
+
+    pub fn address_book_mut(&mut self) -> &mut AddressBook {
+        &mut self.address_book
+    }
+
+    /// Replaces the address book with the one saved for the current network.
+    pub fn set_address_book(&mut self, address_book: AddressBook) {
+        self.address_book = address_book;
+    }
+
     pub fn peak(&self) -> Option<(u32, Bytes32)> {
         self.peers
             .values()
@@ -72,6 +120,22 @@ impl PeerState {
             .collect()
     }
 
+    /// The peer with the heaviest claimed peak, which is the chain that headers are
+    /// validated against.
+    pub fn heaviest_peer(&self) -> Option<(WalletPeer, u32, Bytes32, u128)> {
+        self.peers
+            .values()
+            .max_by_key(|info| (info.claimed_weight, info.preference()))
+            .map(|info| {
+                (
+                    info.peer.clone(),
+                    info.claimed_peak,
+                    info.header_hash,
+                    info.claimed_weight,
+                )
+            })
+    }
+
     pub fn peers_with_heights(&self) -> Vec<(WalletPeer, u32)> {
         self.peers
             .values()
@@ -90,11 +154,47 @@ impl PeerState {
     pub fn acquire_peer(&self) -> Option<WalletPeer> {
         self.peers
             .values()
-            .max_by_key(|info| info.claimed_peak)
+            .max_by_key(|info| info.preference())
+            .map(|info| info.peer.clone())
+    }
+
+    /// Returns the other peers in order of preference, for cross-checking a peer's responses.
+    pub fn witnesses(&self, ip: IpAddr) -> Vec<WalletPeer> {
+        self.peers
+            .iter()
+            .filter(|(peer_ip, _)| **peer_ip != ip)
+            .map(|(_, info)| info)
+            .sorted_by_key(|info| Reverse(info.preference()))
             .map(|info| info.peer.clone())
+            .collect()
+    }
+
+    pub fn score(&self, ip: IpAddr) -> Option<PeerScore> {
+        self.peers.get(&ip).map(|info| info.peer.score())
+    }
+
+    pub fn record_disagreement(&mut self, ip: IpAddr) {
+        let Some(info) = self.peers.get(&ip) else {
+            return;
+        };
+
+        info.peer.update_score(PeerScore::record_disagreement);
+
+        if info.peer.score().disagreements >= MAX_DISAGREEMENTS && !info.user_managed {
+            self.ban(
+                ip,
+                Duration::from_secs(60 * 60),
+                "disagreed with other peers too many times",
+            );
+        }
     }
 
     pub fn ban(&mut self, ip: IpAddr, duration: Duration, message: &str) {
+        if self.is_trusted(ip) {
+            debug!("Not banning trusted peer {ip}: {message}");
+            return;
+        }
+
         debug!("Banning peer {ip} ({duration:?}): {message}");
 
         let start = SystemTime::now();
@@ -122,11 +222,26 @@ impl PeerState {
         })
     }
 
-    pub fn update_peak(&mut self, ip: IpAddr, height: u32, header_hash: Bytes32) {
+    pub fn update_peak(&mut self, ip: IpAddr, height: u32, header_hash: Bytes32, weight: u128) {
         if let Some(peer) = self.peers.get_mut(&ip) {
             peer.claimed_peak = height;
+            peer.claimed_weight = weight;
             peer.header_hash = header_hash;
         }
+
+        self.address_book.record_peak(ip, height);
+        self.update_peak_lag();
+    }
+
+    fn update_peak_lag(&mut self) {
+        let Some(max_peak) = self.peers.values().map(|info| info.claimed_peak).max() else {
+            return;
+        };
+
+        for info in self.peers.values() {
+            info.peer
+                .update_score(|score| score.peak_lag = max_peak - info.claimed_peak);
+        }
     }
 
     pub fn peer(&self, ip: IpAddr) -> Option<&PeerInfo> {
@@ -138,7 +253,10 @@ impl PeerState {
     }
 
     pub(super) fn add_peer(&mut self, state: PeerInfo) {
-        self.peers.insert(state.peer.socket_addr().ip(), state);
+        let ip = state.peer.socket_addr().ip();
+        self.address_book.record_success(ip, state.claimed_peak);
+        self.peers.insert(ip, state);
+        self.update_peak_lag();
     }
 
     pub fn banned_peers(&mut self) -> &HashMap<IpAddr, u64> {
@@ -0,0 +1,353 @@
+use chia_wallet_sdk::{chia::protocol::NewPeakWallet, prelude::*};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::{SyncCommand, Wallet, WalletError, WalletPeer};
+
+use super::SyncEvent;
+
+/// How a new peak from a peer relates to the peak the wallet has synced to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeakChange {
+    /// The peak is the same as ours or builds on it.
+    Extends,
+    /// The peer is behind us, such as when it's lagging or still syncing, so it says nothing
+    /// about whether our chain is still valid.
+    Behind,
+    /// Blocks we've seen were orphaned, so everything above this height needs to be rolled back.
+    Reorg(u32),
+    /// The peer is ahead of us, but its fork point isn't relative to our peak, so its header at
+    /// our height needs to be compared with ours.
+    Unknown,
+}
+
+/// Records a new peak from the peer the wallet is subscribed to. If the peer's chain no longer
+/// includes the block at our peak, the chain has been reorganized, so everything above the fork
+/// point is rolled back and the affected coins are requested again.
+///
+/// While headers are being validated, the header queue is the only thing which rolls back, so
+/// the peak is just recorded unless it conflicts with a block that has already been validated.
+pub async fn handle_new_peak(
+    wallet: &Wallet,
+    peer: Option<&WalletPeer>,
+    peer_previous: Option<(u32, Bytes32)>,
+    peak: &NewPeakWallet,
+    header_validation: bool,
+    sync_sender: &mpsc::Sender<SyncEvent>,
+    command_sender: &mpsc::Sender<SyncCommand>,
+) -> Result<(), WalletError> {
+    if header_validation {
+        if wallet
+            .db
+            .validated_block(peak.height)
+            .await?
+            .is_some_and(|block| block.header_hash != peak.header_hash)
+        {
+            debug!(
+                "Ignoring peak {} which conflicts with the validated chain",
+                peak.height
+            );
+            return Ok(());
+        }
+
+        wallet
+            .db
+            .insert_block(peak.height, peak.header_hash, None, true)
+            .await?;
+
+        return Ok(());
+    }
+
+    let previous = wallet.db.latest_peak().await?;
+
+    let fork_height = match peak_change(previous, peer_previous, peak) {
+        PeakChange::Extends => None,
+        PeakChange::Behind => {
+            debug!(
+                "Ignoring peak {} which is behind the wallet's peak",
+                peak.height
+            );
+            return Ok(());
+        }
+        PeakChange::Reorg(fork_height) => Some(fork_height),
+        PeakChange::Unknown => {
+            let (Some(peer), Some((previous_height, previous_header_hash))) = (peer, previous)
+            else {
+                return Ok(());
+            };
+
+            if peer.header_hash(previous_height).await? == previous_header_hash {
+                None
+            } else {
+                Some(
+                    peak.fork_point_with_previous_peak
+                        .min(previous_height.saturating_sub(1)),
+                )
+            }
+        }
+    };
+
+    if let Some(fork_height) = fork_height {
+        let mut tx = wallet.db.tx().await?;
+        let coin_ids = tx.rollback_to_height(fork_height).await?;
+        tx.commit().await?;
+
+        warn!(
+            "Reorg detected at height {fork_height}, rolled back {} coins",
+            coin_ids.len()
+        );
+
+        sync_sender
+            .send(SyncEvent::Reorg { fork_height })
+            .await
+            .ok();
+
+        if !coin_ids.is_empty() {
+            command_sender
+                .send(SyncCommand::ResyncCoins {
+                    fingerprint: wallet.fingerprint,
+                    coin_ids,
+                })
+                .await
+                .ok();
+        }
+    }
+
+    wallet
+        .db
+        .insert_block(peak.height, peak.header_hash, None, true)
+        .await?;
+
+    Ok(())
+}
+
+/// Only a peer which is at least as far along as we are can show that our peak was orphaned.
+/// Its fork point is relative to the last peak it sent, so it can only be relied on if that
+/// peak was the same as ours.
+fn peak_change(
+    previous: Option<(u32, Bytes32)>,
+    peer_previous: Option<(u32, Bytes32)>,
+    peak: &NewPeakWallet,
+) -> PeakChange {
+    let Some((previous_height, previous_header_hash)) = previous else {
+        return PeakChange::Extends;
+    };
+
+    if peak.height < previous_height {
+        return PeakChange::Behind;
+    }
+
+    if peak.height == previous_height {
+        return if peak.header_hash == previous_header_hash {
+            PeakChange::Extends
+        } else {
+            PeakChange::Reorg(
+                peak.fork_point_with_previous_peak
+                    .min(previous_height.saturating_sub(1)),
+            )
+        };
+    }
+
+    if peer_previous != previous {
+        return PeakChange::Unknown;
+    }
+
+    if peak.fork_point_with_previous_peak >= previous_height {
+        PeakChange::Extends
+    } else {
+        PeakChange::Reorg(peak.fork_point_with_previous_peak)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{TestWallet, new_peak_message};
+
+    use super::*;
+
+    fn peak(header_hash: Bytes32, height: u32, fork_point: u32) -> NewPeakWallet {
+        NewPeakWallet::new(header_hash, height, 0, fork_point)
+    }
+
+    #[test]
+    fn test_peak_change() {
+        let previous = Some((10, Bytes32::new([1; 32])));
+
+        assert_eq!(
+            peak_change(None, None, &peak(Bytes32::new([2; 32]), 11, 10)),
+            PeakChange::Extends
+        );
+        assert_eq!(
+            peak_change(previous, previous, &peak(Bytes32::new([1; 32]), 10, 10)),
+            PeakChange::Extends
+        );
+        assert_eq!(
+            peak_change(previous, previous, &peak(Bytes32::new([2; 32]), 11, 10)),
+            PeakChange::Extends
+        );
+        assert_eq!(
+            peak_change(previous, previous, &peak(Bytes32::new([2; 32]), 10, 10)),
+            PeakChange::Reorg(9)
+        );
+        assert_eq!(
+            peak_change(previous, previous, &peak(Bytes32::new([2; 32]), 12, 7)),
+            PeakChange::Reorg(7)
+        );
+    }
+
+    #[test]
+    fn test_peak_change_lagging_peer() {
+        let previous = Some((10, Bytes32::new([1; 32])));
+        let lagging = Some((5, Bytes32::new([3; 32])));
+
+        // A peer which is behind us can't orphan our peak, whatever its fork point is.
+        assert_eq!(
+            peak_change(previous, lagging, &peak(Bytes32::new([2; 32]), 9, 8)),
+            PeakChange::Behind
+        );
+        assert_eq!(
+            peak_change(previous, None, &peak(Bytes32::new([2; 32]), 6, 0)),
+            PeakChange::Behind
+        );
+
+        // Once it catches up past us, its fork point is relative to its own previous peak, so
+        // its header at our height has to be checked instead.
+        assert_eq!(
+            peak_change(previous, lagging, &peak(Bytes32::new([2; 32]), 12, 5)),
+            PeakChange::Unknown
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_lagging_peer_does_not_roll_back() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        {
+            let mut sim = test.sim.lock().await;
+            sim.create_block();
+            sim.create_block();
+        }
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(test.puzzle_hash, 1000)], 0, vec![], None)
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let (height, _) = test.wallet.db.latest_peak().await?.expect("no peak");
+
+        test.sender
+            .send(SyncCommand::HandleMessage {
+                ip: test.peer.socket_addr().ip(),
+                message: new_peak_message(Bytes32::new([2; 32]), height - 1, 0)?,
+            })
+            .await?;
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(test.puzzle_hash, 1000)], 0, vec![], None)
+            .await?;
+        test.transact(coin_spends).await?;
+
+        test.consume_until(|event| {
+            assert!(!matches!(event, SyncEvent::Reorg { .. }));
+            matches!(event, SyncEvent::CoinsUpdated)
+        })
+        .await;
+
+        let (new_height, _) = test.wallet.db.latest_peak().await?.expect("no peak");
+        assert!(new_height > height);
+        assert_eq!(test.wallet.db.xch_balance().await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_reorg_resyncs_coins() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        {
+            let mut sim = test.sim.lock().await;
+            sim.create_block();
+            sim.create_block();
+        }
+
+        let snapshot = test.sim.lock().await.clone();
+
+        let coin_spends = test
+            .wallet
+            .send_xch(vec![(test.puzzle_hash, 1000)], 0, vec![], None)
+            .await?;
+
+        let spend_bundle = test
+            .wallet
+            .sign_transaction(
+                SpendBundle::new(coin_spends, Signature::default()),
+                &test.agg_sig,
+                test.master_sk.clone(),
+                false,
+            )
+            .await?;
+
+        test.push_bundle(spend_bundle.clone()).await?;
+        test.wait_for_coins().await;
+
+        let coin = test.wallet.db.selectable_xch_coins().await?[0];
+        assert_eq!(coin_created_height(&test, coin.coin_id()).await?, Some(2));
+
+        // Replace the chain with a fork after height 1, which includes the same spend in a
+        // later block than the original chain did.
+        // The peer announces the peak we synced to first, so that the fork point of the next
+        // peak is relative to ours.
+        let (height, header_hash) = test.wallet.db.latest_peak().await?.expect("no peak");
+
+        test.sender
+            .send(SyncCommand::HandleMessage {
+                ip: test.peer.socket_addr().ip(),
+                message: new_peak_message(header_hash, height, height - 1)?,
+            })
+            .await?;
+
+        let message = {
+            let mut sim = test.sim.lock().await;
+            *sim = snapshot;
+            sim.new_coin(Bytes32::default(), 1);
+            sim.create_block();
+            sim.new_transaction(spend_bundle)?;
+
+            new_peak_message(sim.header_hash(), sim.height(), 1)?
+        };
+
+        test.sender
+            .send(SyncCommand::HandleMessage {
+                ip: test.peer.socket_addr().ip(),
+                message,
+            })
+            .await?;
+
+        test.consume_until(|event| matches!(event, SyncEvent::Reorg { fork_height: 1 }))
+            .await;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.xch_balance().await?, 1000);
+        assert_eq!(coin_created_height(&test, coin.coin_id()).await?, Some(3));
+
+        Ok(())
+    }
+
+    async fn coin_created_height(
+        test: &TestWallet,
+        coin_id: Bytes32,
+    ) -> anyhow::Result<Option<u32>> {
+        Ok(test
+            .wallet
+            .db
+            .coins_by_ids(&[coin_id.to_string()])
+            .await?
+            .first()
+            .and_then(|row| row.created_height))
+    }
+}
@@ -0,0 +1,402 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::SocketAddr,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chia_wallet_sdk::{
+    coinset::{ChiaRpcClient, CoinRecord, GetCoinRecordsResponse},
+    prelude::*,
+};
+use reqwest::{Client, Identity, Proxy};
+use sage_config::Network;
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::{sync::mpsc, time::sleep};
+use tracing::{info, warn};
+
+use crate::{SyncCommand, Wallet, WalletError};
+
+use super::{SyncEvent, SyncManager, wallet_sync::incremental_sync};
+
+/// A client for the full node's RPC. This is the same as the SDK's `FullNodeClient`,
+/// except that requests go through the SOCKS5 proxy if one is set.
+#[derive(Debug)]
+pub struct FullNodeRpcClient {
+    base_url: String,
+    client: Client,
+}
+
+impl FullNodeRpcClient {
+    pub fn new(
+        base_url: String,
+        cert_bytes: &[u8],
+        key_bytes: &[u8],
+        proxy: Option<SocketAddr>,
+    ) -> reqwest::Result<Self> {
+        let mut builder = Client::builder()
+            .danger_accept_invalid_certs(true)
+            .identity(Identity::from_pem(&[key_bytes, cert_bytes].concat())?);
+
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(Proxy::all(format!("socks5h://{proxy}"))?);
+        }
+
+        Ok(Self {
+            base_url,
+            client: builder.build()?,
+        })
+    }
+}
+
+impl ChiaRpcClient for FullNodeRpcClient {
+    type Error = reqwest::Error;
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    async fn make_post_request<R, B>(&self, endpoint: &str, body: B) -> Result<R, Self::Error>
+    where
+        B: Serialize + Send,
+        R: DeserializeOwned + Send,
+    {
+        let url = format!("{}/{}", self.base_url(), endpoint);
+        let response = self.client.post(&url).json(&body).send().await?;
+        response.json::<R>().await
+    }
+}
+
+/// Creates a client for the network's full node RPC, if one is configured.
+pub fn full_node_client(
+    network: &Network,
+    proxy: Option<SocketAddr>,
+) -> Option<Arc<FullNodeRpcClient>> {
+    let rpc = network.full_node_rpc.as_ref()?;
+
+    let result = fs::read(&rpc.cert_path).and_then(|cert| Ok((cert, fs::read(&rpc.key_path)?)));
+
+    let (cert, key) = match result {
+        Ok(files) => files,
+        Err(error) => {
+            warn!("Failed to read full node RPC certificate: {error}");
+            return None;
+        }
+    };
+
+    match FullNodeRpcClient::new(
+        rpc.url.trim_end_matches('/').to_string(),
+        &cert,
+        &key,
+        proxy,
+    ) {
+        Ok(client) => Some(Arc::new(client)),
+        Err(error) => {
+            warn!("Failed to create full node RPC client: {error}");
+            None
+        }
+    }
+}
+
+/// The height each wallet was last synced to through the full node's RPC, by fingerprint.
+pub(super) type RpcSyncedHeights = Arc<Mutex<HashMap<u32, u32>>>;
+
+impl SyncManager {
+    /// Keeps the loaded wallets up to date through the full node's RPC while no peer can be
+    /// reached over the wallet protocol. After the first time, only coins created since the
+    /// last synced height are requested, along with the current state of our unspent coins.
+    pub(super) fn rpc_fallback(&mut self) {
+        let Some(client) = self.full_node_client.clone() else {
+            return;
+        };
+
+        if self
+            .rpc_fallback_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+        {
+            return;
+        }
+
+        let wallets: Vec<_> = self
+            .wallets
+            .values()
+            .map(|loaded| (loaded.wallet.clone(), loaded.event_sender.clone()))
+            .collect();
+
+        let synced_heights = self.rpc_synced_heights.clone();
+        let command_sender = self.command_sender.clone();
+        let delay = self.options.timeouts.rpc_fallback;
+
+        self.rpc_fallback_task = Some(tokio::spawn(async move {
+            for (wallet, sync_sender) in wallets {
+                let start_height = synced_heights
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .get(&wallet.fingerprint)
+                    .copied();
+
+                match sync_from_rpc(
+                    &wallet,
+                    client.as_ref(),
+                    start_height,
+                    &sync_sender,
+                    &command_sender,
+                )
+                .await
+                {
+                    Ok(Some(height)) => {
+                        synced_heights
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .insert(wallet.fingerprint, height);
+                    }
+                    Ok(None) => {}
+                    Err(error) => {
+                        warn!(
+                            "Failed to sync wallet {} from full node RPC: {error}",
+                            wallet.fingerprint
+                        );
+                    }
+                }
+            }
+
+            sleep(delay).await;
+        }));
+    }
+
+    /// Stops the fallback, and forgets how far each wallet was synced if `reset` is set,
+    /// since the heights don't carry over to a different network or node.
+    pub(super) fn abort_rpc_fallback(&mut self, reset: bool) {
+        if let Some(task) = self.rpc_fallback_task.take() {
+            task.abort();
+        }
+
+        if reset {
+            self.rpc_synced_heights
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+    }
+}
+
+/// Reads the coin records for the wallet's puzzle hashes, hints and unspent coins, and
+/// applies them the same way as coin state updates from a peer. Puzzle hashes and hints are
+/// only looked up from `start_height` onwards, if it's set.
+///
+/// The highest height seen in any of the records is returned as the start height for the next
+/// sync. Anything confirmed later can't be below it, since the chain had already reached it.
+pub async fn sync_from_rpc(
+    wallet: &Wallet,
+    client: &impl ChiaRpcClient<Error = reqwest::Error>,
+    start_height: Option<u32>,
+    sync_sender: &mpsc::Sender<SyncEvent>,
+    command_sender: &mpsc::Sender<SyncCommand>,
+) -> Result<Option<u32>, WalletError> {
+    let mut coin_states = Vec::new();
+
+    for batch in wallet.db.custody_p2_puzzle_hashes().await?.chunks(1000) {
+        coin_states.extend(coin_records(
+            client
+                .get_coin_records_by_puzzle_hashes(batch.to_vec(), start_height, None, Some(true))
+                .await?,
+        )?);
+
+        coin_states.extend(coin_records(
+            client
+                .get_coin_records_by_hints(batch.to_vec(), start_height, None, Some(true))
+                .await?,
+        )?);
+    }
+
+    // Coins created before the start height can still have been spent since.
+    for batch in wallet.db.unspent_coin_ids().await?.chunks(1000) {
+        coin_states.extend(coin_records(
+            client
+                .get_coin_records_by_names(batch.to_vec(), None, None, Some(true))
+                .await?,
+        )?);
+    }
+
+    info!(
+        "Received {} coin records from full node RPC",
+        coin_states.len()
+    );
+
+    let synced_height = coin_states
+        .iter()
+        .flat_map(|coin_state| [coin_state.created_height, coin_state.spent_height])
+        .flatten()
+        .chain(start_height)
+        .max();
+
+    incremental_sync(wallet, coin_states, true, sync_sender, command_sender).await?;
+
+    Ok(synced_height)
+}
+
+fn coin_records(response: GetCoinRecordsResponse) -> Result<Vec<CoinState>, WalletError> {
+    if !response.success {
+        return Err(WalletError::Rpc(response.error.unwrap_or_default()));
+    }
+
+    Ok(response
+        .coin_records
+        .unwrap_or_default()
+        .into_iter()
+        .map(coin_state)
+        .collect())
+}
+
+fn coin_state(record: CoinRecord) -> CoinState {
+    CoinState::new(
+        record.coin,
+        record.spent.then_some(record.spent_block_index),
+        Some(record.confirmed_block_index),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use axum::{Json, Router, extract::State, routing::post};
+    use chia_wallet_sdk::coinset::CoinsetClient;
+    use serde_json::{Value, json};
+    use test_log::test;
+    use tokio::net::TcpListener;
+
+    use crate::TestWallet;
+
+    use super::*;
+
+    /// The requests received by the mock node, in order.
+    type Requests = Arc<Mutex<Vec<(&'static str, Value)>>>;
+
+    /// Serves a full node RPC which returns the given coin records by puzzle hash,
+    /// or an error for every request if `error` is set.
+    async fn mock_node(
+        records: Vec<Value>,
+        error: Option<&'static str>,
+    ) -> anyhow::Result<(String, Requests)> {
+        let requests = Requests::default();
+
+        let coin_records = move |endpoint: &'static str, records: Vec<Value>| {
+            move |State(requests): State<Requests>, Json(body): Json<Value>| {
+                let records = records.clone();
+
+                async move {
+                    requests.lock().unwrap().push((endpoint, body));
+
+                    Json(match error {
+                        Some(error) => json!({ "error": error, "success": false }),
+                        None => json!({ "coin_records": records, "success": true }),
+                    })
+                }
+            }
+        };
+
+        let router = Router::new()
+            .route(
+                "/get_coin_records_by_puzzle_hashes",
+                post(coin_records("get_coin_records_by_puzzle_hashes", records)),
+            )
+            .route(
+                "/get_coin_records_by_hints",
+                post(coin_records("get_coin_records_by_hints", Vec::new())),
+            )
+            .route(
+                "/get_coin_records_by_names",
+                post(coin_records("get_coin_records_by_names", Vec::new())),
+            )
+            .with_state(requests.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Ok((format!("http://{addr}"), requests))
+    }
+
+    #[test(tokio::test)]
+    async fn test_sync_from_rpc() -> anyhow::Result<()> {
+        let test = TestWallet::new(0).await?;
+
+        let coin = Coin::new(Bytes32::new([1; 32]), test.puzzle_hash, 1000);
+        let spent_coin = Coin::new(Bytes32::new([2; 32]), test.puzzle_hash, 500);
+
+        let (url, requests) = mock_node(
+            vec![
+                coin_record(coin, 5, None),
+                coin_record(spent_coin, 3, Some(4)),
+            ],
+            None,
+        )
+        .await?;
+        let client = CoinsetClient::new(url);
+
+        let (sync_sender, _sync_receiver) = mpsc::channel(10);
+
+        let height = sync_from_rpc(&test.wallet, &client, None, &sync_sender, &test.sender).await?;
+
+        assert_eq!(height, Some(5));
+        assert_eq!(test.wallet.db.xch_balance().await?, 1000);
+
+        // The next sync only asks for new coins, but still checks whether ours were spent.
+        requests.lock().unwrap().clear();
+
+        sync_from_rpc(&test.wallet, &client, height, &sync_sender, &test.sender).await?;
+
+        let requests = requests.lock().unwrap().clone();
+
+        assert!(
+            requests
+                .iter()
+                .filter(|(endpoint, _)| *endpoint != "get_coin_records_by_names")
+                .all(|(_, body)| body["start_height"] == 5)
+        );
+
+        assert!(requests.iter().any(|(endpoint, body)| {
+            *endpoint == "get_coin_records_by_names"
+                && body["names"]
+                    .as_array()
+                    .is_some_and(|names| names.contains(&json!(format!("0x{}", coin.coin_id()))))
+        }));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_sync_from_rpc_error() -> anyhow::Result<()> {
+        let test = TestWallet::new(0).await?;
+
+        let (url, _requests) = mock_node(Vec::new(), Some("too many puzzle hashes")).await?;
+        let client = CoinsetClient::new(url);
+
+        let (sync_sender, _sync_receiver) = mpsc::channel(10);
+
+        assert!(matches!(
+            sync_from_rpc(&test.wallet, &client, None, &sync_sender, &test.sender).await,
+            Err(WalletError::Rpc(error)) if error == "too many puzzle hashes"
+        ));
+
+        Ok(())
+    }
+
+    fn coin_record(coin: Coin, created_height: u32, spent_height: Option<u32>) -> Value {
+        json!({
+            "coin": {
+                "parent_coin_info": format!("0x{}", coin.parent_coin_info),
+                "puzzle_hash": format!("0x{}", coin.puzzle_hash),
+                "amount": coin.amount,
+            },
+            "coinbase": false,
+            "confirmed_block_index": created_height,
+            "spent": spent_height.is_some(),
+            "spent_block_index": spent_height.unwrap_or_default(),
+            "timestamp": 0,
+        })
+    }
+}
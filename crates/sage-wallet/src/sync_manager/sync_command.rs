@@ -1,22 +1,51 @@
-use std::{net::IpAddr, sync::Arc};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
-use chia_wallet_sdk::{chia::protocol::Message, prelude::*};
-use sage_config::Network;
+use chia_wallet_sdk::{
+    chia::protocol::{CoinStateUpdate, Message},
+    prelude::*,
+};
+use sage_config::{ClawbackPolicy, CoinManagement, Network, ReputationSource};
 use tokio::sync::mpsc;
 
 use crate::Wallet;
 
 #[derive(Debug)]
 pub enum SyncCommand {
-    SwitchWallet {
-        wallet: Option<Arc<Wallet>>,
+    /// Starts syncing the wallet alongside any others which are already loaded.
+    /// Loading a wallet with the same fingerprint again replaces it.
+    LoadWallet {
+        wallet: Arc<Wallet>,
         delta_sync: bool,
+        master_sk: Option<SecretKey>,
+        clawback_policy: Option<ClawbackPolicy>,
+        coin_management: Option<CoinManagement>,
+    },
+    UnloadWallet {
+        fingerprint: u32,
+    },
+    /// Restarts the background tasks of a loaded wallet with a new signing key and policies,
+    /// without resyncing it. Wallets which aren't loaded are left alone.
+    ConfigureWallet {
+        fingerprint: u32,
+        master_sk: Option<SecretKey>,
+        clawback_policy: Option<ClawbackPolicy>,
+        coin_management: Option<CoinManagement>,
     },
     SwitchNetwork(Network),
     HandleMessage {
         ip: IpAddr,
         message: Message,
     },
+    /// A coin state update which other peers agreed with, so it can be applied in paranoid sync.
+    VerifiedCoinStates {
+        ip: IpAddr,
+        update: CoinStateUpdate,
+    },
+    /// A coin state update which other peers disputed, or which couldn't be checked.
+    DisputedCoinStates(IpAddr),
     ConnectPeer {
         ip: IpAddr,
         user_managed: bool,
@@ -26,12 +55,24 @@ pub enum SyncCommand {
         receiver: mpsc::Receiver<Message>,
     },
     SubscribeCoins {
+        fingerprint: u32,
         coin_ids: Vec<Bytes32>,
     },
     SubscribePuzzles {
+        fingerprint: u32,
         puzzle_hashes: Vec<Bytes32>,
     },
+    /// Requests the current state of coins again, after it was rolled back by a reorg.
+    ResyncCoins {
+        fingerprint: u32,
+        coin_ids: Vec<Bytes32>,
+    },
     ConnectionClosed(IpAddr),
     SetTargetPeers(usize),
     SetDiscoverPeers(bool),
+    SetParanoidSync(bool),
+    SetHeaderValidation(bool),
+    SetProxy(Option<SocketAddr>),
+    SetDnsDiscovery(bool),
+    SetReputationSources(Vec<ReputationSource>),
 }
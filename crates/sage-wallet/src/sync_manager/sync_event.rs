@@ -1,6 +1,15 @@
 use chia_wallet_sdk::prelude::*;
 use sage_database::OfferStatus;
-use std::net::IpAddr;
+use std::{net::IpAddr, time::Duration};
+
+use super::SyncPhase;
+
+/// An event emitted while syncing one of the loaded wallets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletEvent {
+    pub fingerprint: u32,
+    pub event: SyncEvent,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SyncEvent {
@@ -26,4 +35,29 @@ pub enum SyncEvent {
     CatInfo,
     DidInfo,
     NftData,
+    CoinsManaged {
+        asset_id: Option<Bytes32>,
+        action: CoinManagementAction,
+        coin_count: usize,
+        fee: u64,
+        transaction_id: Bytes32,
+    },
+    MintJobUpdated {
+        mint_job_id: u32,
+    },
+    Reorg {
+        fork_height: u32,
+    },
+    Progress {
+        phase: SyncPhase,
+        completed: u64,
+        remaining: u64,
+        eta: Option<Duration>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinManagementAction {
+    Split,
+    Combine,
 }
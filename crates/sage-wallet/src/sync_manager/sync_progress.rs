@@ -0,0 +1,294 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc;
+
+use super::SyncEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SyncPhase {
+    /// Puzzle hashes subscribed to, including newly derived ones.
+    PuzzleSubscriptions,
+    /// Coin ids subscribed to, such as those of offered or rolled back coins.
+    CoinSubscriptions,
+    /// Coins whose puzzles or children still need to be looked up by the puzzle queue.
+    PuzzleLookups,
+    /// NFT data, metadata and license files which haven't been downloaded yet.
+    NftUris,
+    /// Owned CATs whose name and icon haven't been found on Dexie yet.
+    CatInfo,
+    /// Block heights which still need a timestamp.
+    BlockTimes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhaseProgress {
+    pub phase: SyncPhase,
+    /// Items completed since the phase last started with new work.
+    pub completed: u64,
+    pub remaining: u64,
+    /// Items completed per second since the phase started.
+    pub rate: f64,
+    pub eta: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PhaseState {
+    completed: u64,
+    remaining: u64,
+    started_at: Instant,
+    updated_at: Instant,
+}
+
+impl PhaseState {
+    fn new(now: Instant) -> Self {
+        Self {
+            completed: 0,
+            remaining: 0,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Once a phase has caught up, the counters are reset when more work arrives, so that
+    /// the rate isn't diluted by the time spent idle.
+    fn restart_if_idle(&mut self, now: Instant) {
+        if self.remaining == 0 {
+            *self = Self::new(now);
+        }
+    }
+
+    fn progress(&self, phase: SyncPhase) -> PhaseProgress {
+        let elapsed = self
+            .updated_at
+            .duration_since(self.started_at)
+            .as_secs_f64();
+
+        #[allow(clippy::cast_precision_loss)]
+        let rate = if elapsed > 0.0 {
+            self.completed as f64 / elapsed
+        } else {
+            0.0
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let eta = if self.remaining == 0 {
+            Some(Duration::ZERO)
+        } else if rate > 0.0 {
+            Duration::try_from_secs_f64(self.remaining as f64 / rate).ok()
+        } else {
+            None
+        };
+
+        PhaseProgress {
+            phase,
+            completed: self.completed,
+            remaining: self.remaining,
+            rate,
+            eta,
+        }
+    }
+}
+
+/// Tracks how far along each phase of the sync is for a wallet, which is shared between the
+/// sync manager and the queues that do the work.
+#[derive(Debug, Default, Clone)]
+pub struct SyncProgress {
+    phases: Arc<Mutex<HashMap<SyncPhase, PhaseState>>>,
+}
+
+impl SyncProgress {
+    /// Adds work which will be completed in batches, such as subscriptions.
+    pub fn add(&self, phase: SyncPhase, items: u64) {
+        self.add_at(phase, items, Instant::now());
+    }
+
+    /// Starts the phase over with the full amount of outstanding work, such as at the beginning
+    /// of a sync, so that work left over from an interrupted sync isn't counted twice.
+    pub fn start(&self, phase: SyncPhase, items: u64) {
+        self.start_at(phase, items, Instant::now());
+    }
+
+    /// Records completed items. If the backlog is known, it replaces the remaining count,
+    /// otherwise the completed items are subtracted from it.
+    pub async fn record(
+        &self,
+        phase: SyncPhase,
+        completed: u64,
+        remaining: Option<u64>,
+        sync_sender: &mpsc::Sender<SyncEvent>,
+    ) {
+        let progress = self.record_at(phase, completed, remaining, Instant::now());
+
+        sync_sender
+            .send(SyncEvent::Progress {
+                phase,
+                completed: progress.completed,
+                remaining: progress.remaining,
+                eta: progress.eta,
+            })
+            .await
+            .ok();
+    }
+
+    /// The progress of every phase which has been started, in a stable order.
+    pub fn phases(&self) -> Vec<PhaseProgress> {
+        let phases = self.phases.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut progress: Vec<_> = phases
+            .iter()
+            .map(|(&phase, state)| state.progress(phase))
+            .collect();
+
+        progress.sort_by_key(|progress| progress.phase);
+        progress
+    }
+
+    fn add_at(&self, phase: SyncPhase, items: u64, now: Instant) {
+        let mut phases = self.phases.lock().unwrap_or_else(PoisonError::into_inner);
+        let state = phases.entry(phase).or_insert_with(|| PhaseState::new(now));
+
+        state.restart_if_idle(now);
+        state.remaining = state.remaining.saturating_add(items);
+        state.updated_at = now;
+    }
+
+    fn start_at(&self, phase: SyncPhase, items: u64, now: Instant) {
+        let mut phases = self.phases.lock().unwrap_or_else(PoisonError::into_inner);
+
+        phases.insert(
+            phase,
+            PhaseState {
+                remaining: items,
+                ..PhaseState::new(now)
+            },
+        );
+    }
+
+    fn record_at(
+        &self,
+        phase: SyncPhase,
+        completed: u64,
+        remaining: Option<u64>,
+        now: Instant,
+    ) -> PhaseProgress {
+        let mut phases = self.phases.lock().unwrap_or_else(PoisonError::into_inner);
+        let state = phases.entry(phase).or_insert_with(|| PhaseState::new(now));
+
+        if remaining.is_some_and(|remaining| remaining > 0) {
+            state.restart_if_idle(now);
+        }
+
+        state.completed = state.completed.saturating_add(completed);
+        state.remaining = remaining.unwrap_or_else(|| state.remaining.saturating_sub(completed));
+        state.updated_at = now;
+
+        state.progress(phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_and_eta() {
+        let progress = SyncProgress::default();
+        let start = Instant::now();
+
+        progress.add_at(SyncPhase::PuzzleSubscriptions, 3000, start);
+        progress.record_at(
+            SyncPhase::PuzzleSubscriptions,
+            1000,
+            None,
+            start + Duration::from_secs(10),
+        );
+
+        let phase = progress.phases()[0];
+        assert_eq!(phase.completed, 1000);
+        assert_eq!(phase.remaining, 2000);
+        assert!((phase.rate - 100.0).abs() < f64::EPSILON);
+        assert_eq!(phase.eta, Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_eta_out_of_range() {
+        let progress = SyncProgress::default();
+        let start = Instant::now();
+
+        // At this rate, the remaining items would take longer than a `Duration` can hold.
+        progress.start_at(SyncPhase::PuzzleLookups, u64::MAX, start);
+        progress.record_at(
+            SyncPhase::PuzzleLookups,
+            1,
+            Some(u64::MAX),
+            start + Duration::from_secs(1_000_000),
+        );
+
+        let phase = progress.phases()[0];
+        assert_eq!(phase.eta, None);
+    }
+
+    #[test]
+    fn test_restart_after_idle() {
+        let progress = SyncProgress::default();
+        let start = Instant::now();
+
+        progress.record_at(SyncPhase::PuzzleLookups, 0, Some(50), start);
+        progress.record_at(
+            SyncPhase::PuzzleLookups,
+            50,
+            Some(0),
+            start + Duration::from_secs(5),
+        );
+
+        let phase = progress.phases()[0];
+        assert_eq!(phase.remaining, 0);
+        assert_eq!(phase.eta, Some(Duration::ZERO));
+
+        progress.record_at(
+            SyncPhase::PuzzleLookups,
+            0,
+            Some(10),
+            start + Duration::from_secs(60),
+        );
+        progress.record_at(
+            SyncPhase::PuzzleLookups,
+            5,
+            Some(5),
+            start + Duration::from_secs(61),
+        );
+
+        let phase = progress.phases()[0];
+        assert_eq!(phase.completed, 5);
+        assert_eq!(phase.eta, Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_start_replaces_interrupted_sync() {
+        let progress = SyncProgress::default();
+        let start = Instant::now();
+
+        progress.start_at(SyncPhase::CoinSubscriptions, 100, start);
+        progress.record_at(
+            SyncPhase::CoinSubscriptions,
+            40,
+            None,
+            start + Duration::from_secs(4),
+        );
+
+        // The sync was interrupted and started again with everything outstanding.
+        progress.start_at(
+            SyncPhase::CoinSubscriptions,
+            100,
+            start + Duration::from_secs(5),
+        );
+
+        let phase = progress.phases()[0];
+        assert_eq!(phase.completed, 0);
+        assert_eq!(phase.remaining, 100);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use chia_wallet_sdk::prelude::*;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::{WalletError, WalletPeer};
+
+use super::PeerState;
+
+/// Witnesses are asked about this many coins at a time.
+const VERIFY_BATCH_SIZE: usize = 1000;
+
+/// Coin states are rejected once this many witnesses have disputed them.
+const MAX_DISSENTERS: usize = 2;
+
+/// Checks coin states received from a peer against other connected peers before they are
+/// committed to the database. The coin states are accepted once more witnesses agree with
+/// them than disagree, and whichever side is outvoted has a disagreement recorded against it.
+///
+/// Peers can be at slightly different peaks, so only the part of each coin state up to the
+/// lower of the two peaks is compared. Anything newer than that can't be verified.
+pub async fn verify_coin_states(
+    state: &Mutex<PeerState>,
+    peer: &WalletPeer,
+    coin_states: &[CoinState],
+    genesis_challenge: Bytes32,
+) -> Result<(), WalletError> {
+    if coin_states.is_empty() {
+        return Ok(());
+    }
+
+    let ip = peer.socket_addr().ip();
+    let witnesses = state.lock().await.witnesses(ip);
+
+    let mut agreements = 0;
+    let mut dissenters = Vec::new();
+
+    for witness in witnesses {
+        if agreements > dissenters.len() || dissenters.len() >= MAX_DISSENTERS {
+            break;
+        }
+
+        let witness_ip = witness.socket_addr().ip();
+
+        match compare_with_witness(state, peer, &witness, coin_states, genesis_challenge).await {
+            Ok(true) => agreements += 1,
+            Ok(false) => {
+                info!("Peer {witness_ip} disagrees with coin states received from {ip}");
+                dissenters.push(witness_ip);
+            }
+            Err(error) => {
+                warn!("Failed to verify coin states against peer {witness_ip}: {error}");
+            }
+        }
+    }
+
+    if agreements > dissenters.len() {
+        let mut state = state.lock().await;
+
+        for dissenter in dissenters {
+            state.record_disagreement(dissenter);
+        }
+
+        return Ok(());
+    }
+
+    if agreements == 0 && dissenters.is_empty() {
+        warn!("No other peers were available to verify coin states from {ip}");
+    } else {
+        warn!("Coin states from {ip} were disputed by other peers");
+        state.lock().await.record_disagreement(ip);
+    }
+
+    Err(WalletError::UnverifiedCoinStates)
+}
+
+async fn compare_with_witness(
+    state: &Mutex<PeerState>,
+    peer: &WalletPeer,
+    witness: &WalletPeer,
+    coin_states: &[CoinState],
+    genesis_challenge: Bytes32,
+) -> Result<bool, WalletError> {
+    let witness_ip = witness.socket_addr().ip();
+
+    let height = {
+        let state = state.lock().await;
+        let peer_peak = state.peak_of(peer.socket_addr().ip());
+        let witness_peak = state.peak_of(witness_ip);
+        peer_peak
+            .map_or(u32::MAX, |(height, _)| height)
+            .min(witness_peak.map_or(u32::MAX, |(height, _)| height))
+    };
+
+    for batch in coin_states.chunks(VERIFY_BATCH_SIZE) {
+        let coin_ids = batch.iter().map(|cs| cs.coin.coin_id()).collect();
+
+        let witness_states: HashMap<Bytes32, CoinState> = witness
+            .fetch_coins(coin_ids, genesis_challenge)
+            .await?
+            .into_iter()
+            .map(|cs| (cs.coin.coin_id(), cs))
+            .collect();
+
+        for coin_state in batch {
+            if !coin_states_agree(
+                coin_state,
+                witness_states.get(&coin_state.coin.coin_id()),
+                height,
+            ) {
+                return Ok(false);
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+fn coin_states_agree(
+    coin_state: &CoinState,
+    witness_state: Option<&CoinState>,
+    height: u32,
+) -> bool {
+    let at_height = |coin_state: Option<&CoinState>| {
+        coin_state.map_or((None, None), |cs| {
+            (
+                cs.created_height.filter(|created| *created <= height),
+                cs.spent_height.filter(|spent| *spent <= height),
+            )
+        })
+    };
+
+    at_height(Some(coin_state)) == at_height(witness_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin_state(created_height: Option<u32>, spent_height: Option<u32>) -> CoinState {
+        CoinState::new(
+            Coin::new(Bytes32::default(), Bytes32::default(), 1),
+            spent_height,
+            created_height,
+        )
+    }
+
+    #[test]
+    fn test_matching_coin_states() {
+        let cs = coin_state(Some(10), Some(20));
+        assert!(coin_states_agree(&cs, Some(&cs), 30));
+    }
+
+    #[test]
+    fn test_newer_changes_are_ignored() {
+        let peer = coin_state(Some(10), Some(25));
+        let witness = coin_state(Some(10), None);
+        assert!(coin_states_agree(&peer, Some(&witness), 20));
+        assert!(!coin_states_agree(&peer, Some(&witness), 25));
+
+        let peer = coin_state(Some(25), None);
+        assert!(coin_states_agree(&peer, None, 20));
+        assert!(!coin_states_agree(&peer, None, 25));
+    }
+
+    #[test]
+    fn test_conflicting_coin_states() {
+        let peer = coin_state(Some(10), Some(20));
+        let witness = coin_state(Some(10), Some(15));
+        assert!(!coin_states_agree(&peer, Some(&witness), 30));
+
+        let peer = coin_state(Some(10), None);
+        let witness = coin_state(Some(12), None);
+        assert!(!coin_states_agree(&peer, Some(&witness), 30));
+    }
+}
@@ -10,7 +10,7 @@ use tracing::{info, warn};
 
 use crate::{SyncCommand, Wallet, WalletError, WalletPeer};
 
-use super::{PeerState, SyncEvent};
+use super::{PeerState, SyncEvent, SyncPhase, verify::verify_coin_states};
 
 pub async fn sync_wallet(
     wallet: Arc<Wallet>,
@@ -19,6 +19,7 @@ pub async fn sync_wallet(
     sync_sender: mpsc::Sender<SyncEvent>,
     command_sender: mpsc::Sender<SyncCommand>,
     delta_sync: bool,
+    paranoid_sync: bool,
 ) -> Result<(), WalletError> {
     info!("Starting sync against peer {}", peer.socket_addr());
 
@@ -36,9 +37,19 @@ pub async fn sync_wallet(
 
     let coin_ids = wallet.db.subscription_coin_ids().await?;
 
+    wallet
+        .progress
+        .start(SyncPhase::CoinSubscriptions, coin_ids.len() as u64);
+    wallet.progress.start(
+        SyncPhase::PuzzleSubscriptions,
+        p2_puzzle_hashes.len() as u64,
+    );
+
     sync_coin_ids(
         &wallet,
         &peer,
+        &state,
+        paranoid_sync,
         start_height,
         start_header_hash,
         coin_ids,
@@ -52,6 +63,8 @@ pub async fn sync_wallet(
         sync_puzzle_hashes(
             &wallet,
             &peer,
+            &state,
+            paranoid_sync,
             start_height,
             start_header_hash,
             batch,
@@ -78,10 +91,16 @@ pub async fn sync_wallet(
             .await
             .ok();
 
+        wallet
+            .progress
+            .add(SyncPhase::PuzzleSubscriptions, derivations.len() as u64);
+
         for batch in derivations.chunks(1000) {
             sync_puzzle_hashes(
                 &wallet,
                 &peer,
+                &state,
+                paranoid_sync,
                 None,
                 wallet.genesis_challenge,
                 batch,
@@ -115,6 +134,8 @@ pub async fn sync_wallet(
 async fn sync_coin_ids(
     wallet: &Wallet,
     peer: &WalletPeer,
+    state: &Mutex<PeerState>,
+    paranoid_sync: bool,
     start_height: Option<u32>,
     start_header_hash: Bytes32,
     coin_ids: Vec<Bytes32>,
@@ -139,20 +160,37 @@ async fn sync_coin_ids(
 
         info!("Received {} coin states", coin_states.len());
 
+        if paranoid_sync {
+            verify_coin_states(state, peer, &coin_states, wallet.genesis_challenge).await?;
+        }
+
         if coin_states
             .iter()
             .any(|cs| cs.spent_height.is_some() || !only_send_event_if_spent)
         {
             incremental_sync(wallet, coin_states, true, &sync_sender, &command_sender).await?;
         }
+
+        wallet
+            .progress
+            .record(
+                SyncPhase::CoinSubscriptions,
+                coin_ids.len() as u64,
+                None,
+                &sync_sender,
+            )
+            .await;
     }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn sync_puzzle_hashes(
     wallet: &Wallet,
     peer: &WalletPeer,
+    state: &Mutex<PeerState>,
+    paranoid_sync: bool,
     start_height: Option<u32>,
     start_header_hash: Bytes32,
     puzzle_hashes: &[Bytes32],
@@ -186,6 +224,10 @@ async fn sync_puzzle_hashes(
 
         info!("Received {} coin states", data.coin_states.len());
 
+        if paranoid_sync {
+            verify_coin_states(state, peer, &data.coin_states, wallet.genesis_challenge).await?;
+        }
+
         if !data.coin_states.is_empty() {
             incremental_sync(
                 wallet,
@@ -205,6 +247,16 @@ async fn sync_puzzle_hashes(
         }
     }
 
+    wallet
+        .progress
+        .record(
+            SyncPhase::PuzzleSubscriptions,
+            puzzle_hashes.len() as u64,
+            None,
+            &sync_sender,
+        )
+        .await;
+
     Ok(())
 }
 
@@ -280,6 +332,7 @@ pub async fn incremental_sync(
 
         command_sender
             .send(SyncCommand::SubscribePuzzles {
+                fingerprint: wallet.fingerprint,
                 puzzle_hashes: new_derivations,
             })
             .await
@@ -311,18 +364,50 @@ async fn auto_insert_unhardened_derivations(
     Ok(derivations)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn add_new_subscriptions(
     wallet: &Wallet,
     peer: &WalletPeer,
+    state: &Mutex<PeerState>,
+    paranoid_sync: bool,
     coin_ids: Vec<Bytes32>,
     puzzle_hashes: Vec<Bytes32>,
+    resync_coin_ids: Vec<Bytes32>,
     sync_sender: mpsc::Sender<SyncEvent>,
     command_sender: mpsc::Sender<SyncCommand>,
 ) -> Result<(), WalletError> {
+    wallet.progress.add(
+        SyncPhase::CoinSubscriptions,
+        (resync_coin_ids.len() + coin_ids.len()) as u64,
+    );
+    wallet
+        .progress
+        .add(SyncPhase::PuzzleSubscriptions, puzzle_hashes.len() as u64);
+
+    // Coins which were rolled back may have been deleted, so they need to be inserted again
+    // even if they are unspent.
+    for batch in resync_coin_ids.chunks(1000) {
+        sync_coin_ids(
+            wallet,
+            peer,
+            state,
+            paranoid_sync,
+            None,
+            wallet.genesis_challenge,
+            batch.to_vec(),
+            sync_sender.clone(),
+            command_sender.clone(),
+            false,
+        )
+        .await?;
+    }
+
     for batch in coin_ids.chunks(1000) {
         sync_coin_ids(
             wallet,
             peer,
+            state,
+            paranoid_sync,
             None,
             wallet.genesis_challenge,
             batch.to_vec(),
@@ -337,6 +422,8 @@ pub async fn add_new_subscriptions(
         sync_puzzle_hashes(
             wallet,
             peer,
+            state,
+            paranoid_sync,
             None,
             wallet.genesis_challenge,
             batch,
@@ -3,12 +3,14 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use chia_traits::Streamable;
 use chia_wallet_sdk::{
     chia::{
         bls::{
             DerivableKey, master_to_wallet_hardened, master_to_wallet_hardened_intermediate,
             master_to_wallet_unhardened_intermediate,
         },
+        protocol::{Message, NewPeakWallet, ProtocolMessageTypes},
         puzzle_types::{DeriveSynthetic, standard::StandardArgs},
     },
     client::Connector,
@@ -29,7 +31,7 @@ use tracing::debug;
 
 use crate::{
     PeerState, SyncCommand, SyncEvent, SyncManager, SyncOptions, Timeouts, Transaction, Wallet,
-    insert_transaction,
+    WalletEvent, WalletPeer, insert_transaction,
 };
 
 static INDEX: Mutex<u32> = Mutex::const_new(0);
@@ -44,7 +46,7 @@ pub struct TestWallet {
     pub puzzle_hash: Bytes32,
     pub hardened_puzzle_hash: Bytes32,
     pub sender: Sender<SyncCommand>,
-    pub events: Receiver<SyncEvent>,
+    pub events: Receiver<WalletEvent>,
     pub index: u32,
     pub state: Arc<Mutex<PeerState>>,
     pub options: SyncOptions,
@@ -62,7 +64,7 @@ impl TestWallet {
 
     pub async fn new_with_options(balance: u64, options: SyncOptions) -> anyhow::Result<Self> {
         let sim = PeerSimulator::new().await?;
-        Self::with_sim(Arc::new(sim), balance, 0, options).await
+        Self::with_sim(Arc::new(sim), balance, 0, &options).await
     }
 
     pub async fn next_with_options(
@@ -70,14 +72,14 @@ impl TestWallet {
         balance: u64,
         options: SyncOptions,
     ) -> anyhow::Result<Self> {
-        Self::with_sim(self.sim.clone(), balance, self.index + 1, options).await
+        Self::with_sim(self.sim.clone(), balance, self.index + 1, &options).await
     }
 
     async fn with_sim(
         sim: Arc<PeerSimulator>,
         balance: u64,
         key_index: u32,
-        options: SyncOptions,
+        options: &SyncOptions,
     ) -> anyhow::Result<Self> {
         let db_index = {
             let mut lock = INDEX.lock().await;
@@ -143,19 +145,24 @@ impl TestWallet {
             None,
         ));
 
-        let (mut sync_manager, sender, events) = SyncManager::new(
-            options,
-            state.clone(),
-            Some(wallet.clone()),
-            TESTNET11.clone(),
-            Connector::Plain,
-        );
+        let (mut sync_manager, sender, events) =
+            SyncManager::new(*options, state.clone(), TESTNET11.clone(), Connector::Plain);
+
+        sender
+            .send(SyncCommand::LoadWallet {
+                wallet: wallet.clone(),
+                delta_sync: true,
+                master_sk: None,
+                clawback_policy: None,
+                coin_management: None,
+            })
+            .await?;
 
         let (peer, receiver) = sim.connect_raw().await?;
 
         assert!(
             sync_manager
-                .try_add_peer(peer.clone(), receiver, true, false)
+                .try_add_peer(WalletPeer::new(peer.clone()), receiver, true, false)
                 .await
         );
 
@@ -173,7 +180,7 @@ impl TestWallet {
             events,
             index: key_index,
             state,
-            options,
+            options: *options,
         };
 
         test.consume_until(|event| matches!(event, SyncEvent::Subscribed))
@@ -184,7 +191,7 @@ impl TestWallet {
     }
 
     pub async fn resync(&mut self) -> anyhow::Result<()> {
-        *self = Self::with_sim(self.sim.clone(), 0, self.index, self.options).await?;
+        *self = Self::with_sim(self.sim.clone(), 0, self.index, &self.options).await?;
         Ok(())
     }
 
@@ -219,6 +226,7 @@ impl TestWallet {
 
         self.sender
             .send(SyncCommand::SubscribeCoins {
+                fingerprint: self.wallet.fingerprint,
                 coin_ids: subscriptions,
             })
             .await?;
@@ -235,7 +243,7 @@ impl TestWallet {
 
             debug!("Consuming event for wallet {}: {next:?}", self.index);
 
-            if f(next) {
+            if f(next.event) {
                 return;
             }
         }
@@ -260,6 +268,21 @@ impl TestWallet {
     }
 }
 
+/// A peak announcement from a peer, with no weight since the simulator doesn't have any.
+pub fn new_peak_message(
+    header_hash: Bytes32,
+    height: u32,
+    fork_point: u32,
+) -> anyhow::Result<Message> {
+    Ok(Message {
+        msg_type: ProtocolMessageTypes::NewPeakWallet,
+        id: None,
+        data: NewPeakWallet::new(header_hash, height, 0, fork_point)
+            .to_bytes()?
+            .into(),
+    })
+}
+
 pub fn default_test_options() -> SyncOptions {
     SyncOptions {
         target_peers: 0,
@@ -267,8 +290,11 @@ pub fn default_test_options() -> SyncOptions {
         dns_batch_size: 0,
         connection_batch_size: 0,
         max_peer_age_seconds: 0,
-        delta_sync: true,
         puzzle_batch_size_per_peer: 5,
+        paranoid_sync: false,
+        header_validation: false,
+        proxy: None,
+        dns_discovery: true,
         timeouts: Timeouts {
             sync_delay: Duration::from_millis(100),
             nft_uri_delay: Duration::from_millis(100),
@@ -276,6 +302,11 @@ pub fn default_test_options() -> SyncOptions {
             puzzle_delay: Duration::from_millis(100),
             transaction_delay: Duration::from_millis(100),
             offer_delay: Duration::from_millis(100),
+            clawback_delay: Duration::from_millis(100),
+            coin_management_delay: Duration::from_millis(100),
+            standing_offer_delay: Duration::from_millis(100),
+            mint_job_delay: Duration::from_millis(100),
+            royalty_delay: Duration::from_millis(100),
             ..Default::default()
         },
         testing: true,
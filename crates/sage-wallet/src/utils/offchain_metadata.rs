@@ -57,6 +57,7 @@ pub fn compute_nft_info(did_id: Option<Bytes32>, blob: &[u8]) -> ComputedNftInfo
                 }
             }),
             is_visible: true,
+            is_verified: false,
         })
     } else {
         None
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    sync::{Arc, atomic::AtomicU64},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -22,16 +23,20 @@ mod multi_send;
 mod nfts;
 mod offer;
 mod options;
+mod revocable_cats;
 mod signing;
 mod xch;
 
+pub use cats::*;
+pub use dids::*;
 pub use memos::*;
 pub use multi_send::*;
 pub use nfts::*;
 pub use offer::*;
 pub use options::*;
+pub use revocable_cats::*;
 
-use crate::WalletError;
+use crate::{SyncProgress, WalletError};
 
 #[derive(Debug)]
 pub struct Wallet {
@@ -41,6 +46,10 @@ pub struct Wallet {
     pub genesis_challenge: Bytes32,
     pub agg_sig_constants: AggSigConstants,
     pub change_p2_puzzle_hash: Option<Bytes32>,
+    pub progress: SyncProgress,
+    /// How many times the transaction queue has had to retry submitting a transaction,
+    /// because no peer could say whether it made it into the mempool.
+    pub transaction_retries: Arc<AtomicU64>,
 }
 
 impl Wallet {
@@ -59,6 +68,8 @@ impl Wallet {
             genesis_challenge,
             agg_sig_constants,
             change_p2_puzzle_hash,
+            progress: SyncProgress::default(),
+            transaction_retries: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -191,16 +202,45 @@ impl Wallet {
         spends: &mut Spends,
         actions: &[Action],
     ) -> Result<(), WalletError> {
-        let mut deltas = Deltas::from_actions(actions);
+        self.select_spends_excluding(ctx, spends, actions, &HashSet::new())
+            .await
+    }
+
+    /// Selects the coins needed for the actions, without using any of the excluded coins.
+    pub async fn select_spends_excluding(
+        &self,
+        ctx: &mut SpendContext,
+        spends: &mut Spends,
+        actions: &[Action],
+        excluded_coin_ids: &HashSet<Bytes32>,
+    ) -> Result<(), WalletError> {
+        self.select_spends_for_deltas(
+            ctx,
+            spends,
+            Deltas::from_actions(actions),
+            excluded_coin_ids,
+        )
+        .await
+    }
 
+    /// Selects the coins needed for the deltas, which can be marked as needed beyond what the
+    /// actions require, without using any of the excluded coins.
+    pub async fn select_spends_for_deltas(
+        &self,
+        ctx: &mut SpendContext,
+        spends: &mut Spends,
+        mut deltas: Deltas,
+        excluded_coin_ids: &HashSet<Bytes32>,
+    ) -> Result<(), WalletError> {
         deltas.update(Id::Xch).input += spends.xch.selected_amount();
 
         for (&id, cat) in &spends.cats {
             deltas.update(id).input += cat.selected_amount();
         }
 
-        let selected_coin_ids: HashSet<Bytes32> =
+        let mut selected_coin_ids: HashSet<Bytes32> =
             spends.non_settlement_coin_ids().into_iter().collect();
+        selected_coin_ids.extend(excluded_coin_ids);
 
         for &id in deltas.ids() {
             let delta = deltas.get(&id).copied().unwrap_or_default();
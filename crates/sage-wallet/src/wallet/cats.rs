@@ -1,29 +1,184 @@
-use chia_wallet_sdk::{chia::puzzle_types::cat::EverythingWithSignatureTailArgs, prelude::*};
+use chia_wallet_sdk::{
+    chia::{
+        bls::DerivableKey,
+        puzzle_types::{
+            DeriveSynthetic,
+            cat::{EverythingWithSignatureTailArgs, GenesisByCoinIdTailArgs},
+        },
+    },
+    prelude::*,
+};
 
 use crate::{WalletError, wallet::memos::Hint};
 
 use super::{Wallet, memos::calculate_memos};
 
+/// The TAIL that a new CAT is issued with.
+#[derive(Debug, Clone)]
+pub enum WalletCatTail {
+    /// Genesis by coin id, so the supply can never change after issuance.
+    SingleIssuance,
+    /// Everything with signature, so the supply can be changed by the owner of the key.
+    MultiIssuance(PublicKey),
+    /// A compiled TAIL and solution supplied by the caller.
+    Custom { puzzle: Program, solution: Program },
+}
+
 impl Wallet {
+    /// The key used for multi-issuance CATs, which is the synthetic key of the first derivation.
+    pub fn cat_issuance_key(&self) -> PublicKey {
+        self.intermediate_pk.derive_unhardened(0).derive_synthetic()
+    }
+
     pub async fn issue_cat(
         &self,
         amount: u64,
         fee: u64,
         multi_issuance_key: Option<PublicKey>,
     ) -> Result<(Vec<CoinSpend>, Bytes32), WalletError> {
+        let tail = if let Some(public_key) = multi_issuance_key {
+            WalletCatTail::MultiIssuance(public_key)
+        } else {
+            WalletCatTail::SingleIssuance
+        };
+
+        let (coin_spends, cat, _tail_reveal) =
+            self.issue_cat_with_tail(amount, fee, tail, None).await?;
+
+        Ok((coin_spends, cat.info.asset_id))
+    }
+
+    /// Issues a new CAT, returning the issued coin along with the serialized TAIL.
+    /// If a hidden puzzle hash is given, the CAT is revocable by whoever can spend that puzzle.
+    pub async fn issue_cat_with_tail(
+        &self,
+        amount: u64,
+        fee: u64,
+        tail: WalletCatTail,
+        hidden_puzzle_hash: Option<Bytes32>,
+    ) -> Result<(Vec<CoinSpend>, Cat, Program), WalletError> {
         let mut ctx = SpendContext::new();
 
-        let issue_cat = if let Some(public_key) = multi_issuance_key {
-            let tail = ctx.curry(EverythingWithSignatureTailArgs::new(public_key))?;
-            let tail_spend = Spend::new(tail, NodePtr::NIL);
-            Action::issue_cat(tail_spend, None, amount)
+        let tail_spend = match tail {
+            WalletCatTail::SingleIssuance => None,
+            WalletCatTail::MultiIssuance(public_key) => {
+                let puzzle = ctx.curry(EverythingWithSignatureTailArgs::new(public_key))?;
+                Some(Spend::new(puzzle, NodePtr::NIL))
+            }
+            WalletCatTail::Custom { puzzle, solution } => {
+                let puzzle = ctx.alloc(&puzzle)?;
+                let solution = ctx.alloc(&solution)?;
+                Some(Spend::new(puzzle, solution))
+            }
+        };
+
+        let issue_cat = if let Some(tail_spend) = tail_spend {
+            Action::issue_cat(tail_spend, hidden_puzzle_hash, amount)
         } else {
-            Action::single_issue_cat(None, amount)
+            Action::single_issue_cat(hidden_puzzle_hash, amount)
         };
+
         let actions = vec![Action::fee(fee), issue_cat];
         let outputs = self.spend(&mut ctx, vec![], &actions).await?;
+        let cat = outputs.cats[&Id::New(1)][0];
+        let asset_id = cat.info.asset_id;
+
+        let tail_puzzle = if let Some(tail_spend) = tail_spend {
+            tail_spend.puzzle
+        } else {
+            // The genesis coin is whichever spent coin the asset id was derived from.
+            let genesis_coin_id = ctx
+                .iter()
+                .map(|coin_spend| coin_spend.coin.coin_id())
+                .find(|&coin_id| {
+                    Bytes32::from(GenesisByCoinIdTailArgs::curry_tree_hash(coin_id)) == asset_id
+                })
+                .ok_or(WalletError::TailMismatch(asset_id))?;
+
+            ctx.curry(GenesisByCoinIdTailArgs::new(genesis_coin_id))?
+        };
+
+        let tail_reveal = ctx.serialize(&tail_puzzle)?;
+
+        Ok((ctx.take(), cat, tail_reveal))
+    }
+
+    /// Issues more of an existing CAT by running its TAIL with a positive supply change.
+    pub async fn reissue_cat(
+        &self,
+        asset_id: Bytes32,
+        hidden_puzzle_hash: Option<Bytes32>,
+        tail_puzzle: Program,
+        tail_solution: Program,
+        amount: u64,
+        fee: u64,
+    ) -> Result<(Vec<CoinSpend>, Cat), WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let tail_spend =
+            Self::existing_tail_spend(&mut ctx, asset_id, &tail_puzzle, &tail_solution)?;
+
+        let actions = vec![
+            Action::fee(fee),
+            Action::issue_cat(tail_spend, hidden_puzzle_hash, amount),
+        ];
+        let outputs = self.spend(&mut ctx, vec![], &actions).await?;
 
-        Ok((ctx.take(), outputs.cats[&Id::New(1)][0].info.asset_id))
+        Ok((ctx.take(), outputs.cats[&Id::New(1)][0]))
+    }
+
+    /// Melts the given amount of an existing CAT back into XCH by running its TAIL, returning
+    /// one of the coins it creates so that its confirmation can be tracked.
+    pub async fn melt_cat(
+        &self,
+        asset_id: Bytes32,
+        tail_puzzle: Program,
+        tail_solution: Program,
+        amount: u64,
+        fee: u64,
+    ) -> Result<(Vec<CoinSpend>, Coin), WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let tail_spend =
+            Self::existing_tail_spend(&mut ctx, asset_id, &tail_puzzle, &tail_solution)?;
+
+        let actions = vec![
+            Action::fee(fee),
+            Action::run_tail(Id::Existing(asset_id), tail_spend, Delta::new(0, amount)),
+        ];
+        let outputs = self.spend(&mut ctx, vec![], &actions).await?;
+
+        let coin = outputs
+            .xch
+            .first()
+            .copied()
+            .or_else(|| {
+                outputs
+                    .cats
+                    .get(&Id::Existing(asset_id))
+                    .and_then(|cats| cats.first())
+                    .map(|cat| cat.coin)
+            })
+            .ok_or(WalletError::EmptyMelt(asset_id))?;
+
+        Ok((ctx.take(), coin))
+    }
+
+    fn existing_tail_spend(
+        ctx: &mut SpendContext,
+        asset_id: Bytes32,
+        tail_puzzle: &Program,
+        tail_solution: &Program,
+    ) -> Result<Spend, WalletError> {
+        let puzzle = ctx.alloc(tail_puzzle)?;
+
+        if Bytes32::from(ctx.tree_hash(puzzle)) != asset_id {
+            return Err(WalletError::TailMismatch(asset_id));
+        }
+
+        let solution = ctx.alloc(tail_solution)?;
+
+        Ok(Spend::new(puzzle, solution))
     }
 
     /// Sends the given amount of CAT to the given puzzle hash.
@@ -82,10 +237,11 @@ impl Wallet {
 mod tests {
     use std::time::Duration;
 
+    use chia_wallet_sdk::{chia::puzzle_types::cat::EverythingWithSignatureTailArgs, prelude::*};
     use test_log::test;
     use tokio::time::sleep;
 
-    use crate::TestWallet;
+    use crate::{TestWallet, WalletCatTail, WalletError};
 
     #[test(tokio::test)]
     async fn test_send_cat() -> anyhow::Result<()> {
@@ -310,4 +466,125 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_reissue_and_melt_cat() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1500).await?;
+
+        let issuance_key = test.wallet.cat_issuance_key();
+        let (coin_spends, cat, tail_reveal) = test
+            .wallet
+            .issue_cat_with_tail(1000, 0, WalletCatTail::MultiIssuance(issuance_key), None)
+            .await?;
+        let asset_id = cat.info.asset_id;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.xch_balance().await?, 500);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 1000);
+
+        let (coin_spends, _cat) = test
+            .wallet
+            .reissue_cat(
+                asset_id,
+                None,
+                tail_reveal.clone(),
+                Program::default(),
+                250,
+                0,
+            )
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.xch_balance().await?, 250);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 1250);
+
+        let (coin_spends, coin) = test
+            .wallet
+            .melt_cat(asset_id, tail_reveal, Program::default(), 400, 0)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert!(test.wallet.db.is_known_coin(coin.coin_id()).await?);
+        assert_eq!(test.wallet.db.xch_balance().await?, 650);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 850);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_issue_cat_with_custom_tail() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let mut ctx = SpendContext::new();
+        let tail = ctx.curry(EverythingWithSignatureTailArgs::new(
+            test.wallet.cat_issuance_key(),
+        ))?;
+        let expected_asset_id: Bytes32 = ctx.tree_hash(tail).into();
+        let puzzle = ctx.serialize(&tail)?;
+
+        let (coin_spends, cat, tail_reveal) = test
+            .wallet
+            .issue_cat_with_tail(
+                1000,
+                0,
+                WalletCatTail::Custom {
+                    puzzle: puzzle.clone(),
+                    solution: Program::default(),
+                },
+                None,
+            )
+            .await?;
+        let asset_id = cat.info.asset_id;
+
+        assert_eq!(asset_id, expected_asset_id);
+        assert_eq!(tail_reveal, puzzle);
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.xch_balance().await?, 0);
+        assert_eq!(test.wallet.db.cat_balance(asset_id).await?, 1000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_reissue_cat_tail_mismatch() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1500).await?;
+
+        let (coin_spends, cat, tail_reveal) = test
+            .wallet
+            .issue_cat_with_tail(1000, 0, WalletCatTail::SingleIssuance, None)
+            .await?;
+        let asset_id = cat.info.asset_id;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mut ctx = SpendContext::new();
+        let tail = ctx.alloc(&tail_reveal)?;
+        assert_eq!(Bytes32::from(ctx.tree_hash(tail)), asset_id);
+
+        let result = test
+            .wallet
+            .reissue_cat(
+                Bytes32::default(),
+                None,
+                tail_reveal,
+                Program::default(),
+                250,
+                0,
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::TailMismatch(_))));
+
+        Ok(())
+    }
 }
@@ -90,12 +90,92 @@ impl Wallet {
 
         Ok(ctx.take())
     }
+
+    /// Splits the selected coins into `output_count` coins of exactly `amount` each,
+    /// with the remainder being sent back as change.
+    pub async fn split_by_amount(
+        &self,
+        selected_coin_ids: Vec<Bytes32>,
+        amount: u64,
+        output_count: usize,
+        fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let (coin_spends, _outputs) = self
+            .split_outputs_by_amount(selected_coin_ids, amount, output_count, fee)
+            .await?;
+
+        Ok(coin_spends)
+    }
+
+    /// The same as [`Wallet::split_by_amount`], but also returns the coins it creates.
+    pub(crate) async fn split_outputs_by_amount(
+        &self,
+        selected_coin_ids: Vec<Bytes32>,
+        amount: u64,
+        output_count: usize,
+        fee: u64,
+    ) -> Result<(Vec<CoinSpend>, Outputs), WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let mut spends = self
+            .prepare_spends_for_selection(&mut ctx, &selected_coin_ids)
+            .await?;
+
+        let asset_id = spends
+            .cats
+            .values()
+            .next()
+            .and_then(|cat| cat.items.first().map(|item| item.asset.info.asset_id));
+
+        let mut actions = vec![Action::fee(fee)];
+
+        let total = if let Some(asset_id) = asset_id {
+            self.select_spends(&mut ctx, &mut spends, &actions).await?;
+            spends.cats[&Id::Existing(asset_id)].selected_amount()
+        } else {
+            spends.xch.selected_amount().saturating_sub(fee)
+        };
+
+        if amount * output_count as u64 > total {
+            return Err(WalletError::InsufficientFunds);
+        }
+
+        let derivations_needed = output_count.div_ceil(selected_coin_ids.len()) as u32;
+
+        let puzzle_hashes = if let Some(change_p2_puzzle_hash) = self.change_p2_puzzle_hash {
+            [change_p2_puzzle_hash].repeat(derivations_needed as usize)
+        } else {
+            self.p2_puzzle_hashes(derivations_needed, false, true)
+                .await?
+        };
+
+        for index in 0..output_count {
+            let puzzle_hash = puzzle_hashes[index % puzzle_hashes.len()];
+
+            actions.push(Action::send(
+                asset_id.map_or(Id::Xch, Id::Existing),
+                puzzle_hash,
+                amount,
+                if asset_id.is_some() {
+                    ctx.hint(puzzle_hash)?
+                } else {
+                    Memos::None
+                },
+            ));
+        }
+
+        let deltas = spends.apply(&mut ctx, &actions)?;
+        let outputs = self.complete_spends(&mut ctx, &deltas, spends).await?;
+
+        Ok((ctx.take(), outputs))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use itertools::Itertools;
     use test_log::test;
 
     use crate::TestWallet;
@@ -169,4 +249,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_split_by_amount() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let coins = test.wallet.db.selectable_xch_coins().await?;
+        let coin_spends = test
+            .wallet
+            .split_by_amount(coins.iter().map(Coin::coin_id).collect(), 100, 3, 0)
+            .await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let amounts = test
+            .wallet
+            .db
+            .selectable_xch_coins()
+            .await?
+            .into_iter()
+            .map(|coin| coin.amount)
+            .sorted()
+            .collect_vec();
+
+        assert_eq!(amounts, [100, 100, 100, 700]);
+
+        let coins = test.wallet.db.selectable_xch_coins().await?;
+        assert!(matches!(
+            test.wallet
+                .split_by_amount(coins.iter().map(Coin::coin_id).collect(), 500, 3, 0)
+                .await,
+            Err(WalletError::InsufficientFunds)
+        ));
+
+        Ok(())
+    }
 }
@@ -1,13 +1,40 @@
-use chia_wallet_sdk::prelude::*;
-use sage_database::{SerializePrimitive, SerializedDid};
-
 use crate::{
-    WalletError,
+    WalletError, WalletPeer,
     wallet::memos::{Hint, calculate_memos},
 };
+use chia_wallet_sdk::{
+    chia::puzzle_types::{
+        LineageProof, Proof,
+        singleton::{SingletonArgs, SingletonSolution},
+        standard::StandardArgs,
+    },
+    clvm_traits::clvm_list,
+    prelude::*,
+};
+use sage_database::{P2Puzzle, SerializePrimitive, SerializedDid};
 
 use super::Wallet;
 
+/// What the recovery DIDs attest to when a DID is recovered into this wallet.
+#[derive(Debug, Clone, Copy)]
+pub struct WalletDidRecoveryRequest {
+    /// The id of the DID coin being recovered.
+    pub coin_id: Bytes32,
+    /// The inner puzzle hash of the DID once it has been recovered.
+    pub new_inner_puzzle_hash: Bytes32,
+    pub public_key: PublicKey,
+}
+
+/// An approval of a recovery by a DID in the recovery list, which created a message coin for it.
+#[derive(Debug, Clone, Copy)]
+pub struct WalletDidAttestation {
+    pub attestor_did_id: Bytes32,
+    /// The parent of the attestor's DID coin that created the message coin.
+    pub parent_coin_id: Bytes32,
+    pub inner_puzzle_hash: Bytes32,
+    pub amount: u64,
+}
+
 impl Wallet {
     pub async fn create_did(
         &self,
@@ -103,13 +130,359 @@ impl Wallet {
 
         Ok(ctx.take())
     }
+
+    /// Replaces the DID's metadata with a list of key value pairs.
+    pub async fn update_did_profile(
+        &self,
+        did_id: Bytes32,
+        metadata: Vec<(String, String)>,
+        fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let metadata = ctx.alloc_hashed(&metadata)?;
+
+        self.spend(
+            &mut ctx,
+            vec![],
+            &[
+                Action::fee(fee),
+                Action::update_did(Id::Existing(did_id), None, None, Some(metadata)),
+            ],
+        )
+        .await?;
+
+        Ok(ctx.take())
+    }
+
+    pub async fn set_did_recovery_list(
+        &self,
+        did_id: Bytes32,
+        recovery_did_ids: Vec<Bytes32>,
+        num_verifications_required: u64,
+        fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        self.spend(
+            &mut ctx,
+            vec![],
+            &[
+                Action::fee(fee),
+                Action::update_did(
+                    Id::Existing(did_id),
+                    Some(Some(recovery_did_ids.tree_hash().into())),
+                    Some(num_verifications_required),
+                    None,
+                ),
+            ],
+        )
+        .await?;
+
+        Ok(ctx.take())
+    }
+
+    /// Looks up a DID that isn't owned by this wallet, so that it can be recovered into it.
+    pub async fn did_recovery_request(
+        &self,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+    ) -> Result<WalletDidRecoveryRequest, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let (did, _) = self
+            .fetch_recoverable_did(&mut ctx, peer, launcher_id)
+            .await?;
+
+        let p2_puzzle_hash = self.change_p2_puzzle_hash().await?;
+
+        let P2Puzzle::PublicKey(public_key) = self.db.p2_puzzle(p2_puzzle_hash).await? else {
+            return Err(WalletError::UnknownPublicKey);
+        };
+
+        Ok(did_recovery_request(&did, public_key))
+    }
+
+    /// Approves a recovery from one of our DIDs, by creating the message coin that the recovery spend consumes.
+    pub async fn attest_did_recovery(
+        &self,
+        did_id: Bytes32,
+        request: WalletDidRecoveryRequest,
+        fee: u64,
+    ) -> Result<(Vec<CoinSpend>, WalletDidAttestation), WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let message_puzzle = did_recovery_message_puzzle(&mut ctx, request)?;
+        let message_puzzle_hash = ctx.tree_hash(message_puzzle);
+
+        let actions = [
+            Action::fee(fee),
+            Action::update_did(Id::Existing(did_id), None, None, None),
+        ];
+
+        let mut spends = self.prepare_spends(&mut ctx, vec![], &actions).await?;
+        let deltas = spends.apply(&mut ctx, &actions)?;
+
+        let did_spend = spends
+            .dids
+            .get_mut(&Id::Existing(did_id))
+            .and_then(|did| did.lineage.first_mut())
+            .ok_or(WalletError::MissingDid(did_id))?;
+
+        let SpendKind::Conditions(spend) = &mut did_spend.kind else {
+            return Err(WalletError::MissingDid(did_id));
+        };
+
+        spend.add_conditions(Conditions::new().create_coin(
+            message_puzzle_hash.into(),
+            0,
+            Memos::None,
+        ));
+
+        let attestation = WalletDidAttestation {
+            attestor_did_id: did_id,
+            parent_coin_id: did_spend.asset.coin.parent_coin_info,
+            inner_puzzle_hash: did_spend.asset.info.inner_puzzle_hash().into(),
+            amount: did_spend.asset.coin.amount,
+        };
+
+        self.complete_spends(&mut ctx, &deltas, spends).await?;
+
+        Ok((ctx.take(), attestation))
+    }
+
+    /// Recovers a DID into the standard puzzle of the public key from the recovery request.
+    /// Every DID in the recovery list must have attested, since the DID puzzle can't skip over ones that haven't.
+    pub async fn recover_did(
+        &self,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+        public_key: PublicKey,
+        recovery_did_ids: Vec<Bytes32>,
+        attestations: Vec<WalletDidAttestation>,
+        fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let (did, p2_puzzle) = self
+            .fetch_recoverable_did(&mut ctx, peer, launcher_id)
+            .await?;
+
+        if did.info.recovery_list_hash != Some(recovery_did_ids.tree_hash().into()) {
+            return Err(WalletError::RecoveryListMismatch(launcher_id));
+        }
+
+        let request = did_recovery_request(&did, public_key);
+        let mut coin_proofs = Vec::with_capacity(recovery_did_ids.len());
+
+        for &attestor_did_id in &recovery_did_ids {
+            let attestation = attestations
+                .iter()
+                .find(|attestation| attestation.attestor_did_id == attestor_did_id)
+                .ok_or(WalletError::MissingDidAttestation(attestor_did_id))?;
+
+            let attestor_coin_id = Coin::new(
+                attestation.parent_coin_id,
+                SingletonArgs::curry_tree_hash(
+                    attestor_did_id,
+                    attestation.inner_puzzle_hash.into(),
+                )
+                .into(),
+                attestation.amount,
+            )
+            .coin_id();
+
+            let message_puzzle = did_recovery_message_puzzle(&mut ctx, request)?;
+            let message_coin = Coin::new(attestor_coin_id, ctx.tree_hash(message_puzzle).into(), 0);
+
+            ctx.spend(message_coin, Spend::new(message_puzzle, NodePtr::NIL))?;
+
+            coin_proofs.push(clvm_list!(
+                attestation.parent_coin_id,
+                attestation.inner_puzzle_hash,
+                attestation.amount
+            ));
+        }
+
+        let puzzle = did.info.into_layers(p2_puzzle).construct_puzzle(&mut ctx)?;
+
+        let solution = ctx.alloc(&SingletonSolution {
+            lineage_proof: did.proof,
+            amount: did.coin.amount,
+            inner_solution: clvm_list!(
+                0,
+                did.coin.amount,
+                request.new_inner_puzzle_hash,
+                coin_proofs,
+                public_key,
+                recovery_did_ids,
+                request.coin_id
+            ),
+        })?;
+
+        ctx.spend(did.coin, Spend::new(puzzle, solution))?;
+
+        // The recovered coin is hinted with its inner puzzle hash rather than its p2 puzzle hash,
+        // so it's spent again right away in order to be found when syncing.
+        let new_info = DidInfo {
+            p2_puzzle_hash: StandardArgs::curry_tree_hash(public_key).into(),
+            ..did.info
+        };
+
+        let recovered = Did::new(
+            Coin::new(
+                request.coin_id,
+                SingletonArgs::curry_tree_hash(launcher_id, new_info.inner_puzzle_hash()).into(),
+                did.coin.amount,
+            ),
+            Proof::Lineage(LineageProof {
+                parent_parent_coin_info: did.coin.parent_coin_info,
+                parent_inner_puzzle_hash: did.info.inner_puzzle_hash().into(),
+                parent_amount: did.coin.amount,
+            }),
+            new_info,
+        );
+
+        // The recovered DID isn't in the database yet, so only the fee is used to select coins.
+        let mut spends = self
+            .prepare_spends(&mut ctx, vec![], &[Action::fee(fee)])
+            .await?;
+        spends.add(recovered);
+
+        let actions = [
+            Action::fee(fee),
+            Action::update_did(Id::Existing(launcher_id), None, None, None),
+        ];
+
+        let deltas = spends.apply(&mut ctx, &actions)?;
+        self.complete_spends(&mut ctx, &deltas, spends).await?;
+
+        Ok(ctx.take())
+    }
+
+    /// Fetches the p2 puzzle hash of a DID that may not be owned by this wallet.
+    pub async fn fetch_did_p2_puzzle_hash(
+        &self,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+    ) -> Result<Bytes32, WalletError> {
+        let mut ctx = SpendContext::new();
+
+        let (did, _) = self.fetch_latest_did(&mut ctx, peer, launcher_id).await?;
+
+        Ok(did.info.p2_puzzle_hash)
+    }
+
+    /// Fetches the latest coin of a DID and its p2 puzzle, which is needed to recover it.
+    async fn fetch_recoverable_did(
+        &self,
+        ctx: &mut SpendContext,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+    ) -> Result<(Did, NodePtr), WalletError> {
+        let (did, parent_puzzle) = self.fetch_latest_did(ctx, peer, launcher_id).await?;
+
+        if did.info.num_verifications_required == 0 || did.info.recovery_list_hash.is_none() {
+            return Err(WalletError::UnrecoverableDid(launcher_id));
+        }
+
+        // The p2 puzzle is only revealed once the coin is spent, so it has to be the same as the parent's.
+        let Some((_, p2_puzzle)) = DidInfo::parse(ctx, parent_puzzle)? else {
+            return Err(WalletError::MissingDid(launcher_id));
+        };
+
+        if p2_puzzle.curried_puzzle_hash() != did.info.p2_puzzle_hash.into() {
+            return Err(WalletError::UnrecoverableDid(launcher_id));
+        }
+
+        Ok((did, p2_puzzle.ptr()))
+    }
+
+    /// Walks the lineage of a DID to its latest coin, which is returned along with the puzzle of its parent.
+    async fn fetch_latest_did(
+        &self,
+        ctx: &mut SpendContext,
+        peer: &WalletPeer,
+        launcher_id: Bytes32,
+    ) -> Result<(Did, Puzzle), WalletError> {
+        let mut current_id = launcher_id;
+        let mut parent = None;
+
+        let child = loop {
+            let Some(child) = peer.try_fetch_singleton_child(current_id).await? else {
+                return Err(WalletError::MissingDid(launcher_id));
+            };
+
+            if child.spent_height.is_some() {
+                parent = Some(child);
+                current_id = child.coin.coin_id();
+                continue;
+            }
+
+            break child;
+        };
+
+        // Eve DIDs can't be parsed without the launcher solution.
+        let Some(parent) = parent else {
+            return Err(WalletError::MissingDid(launcher_id));
+        };
+
+        let (parent_puzzle_reveal, parent_solution) = peer
+            .fetch_puzzle_solution(
+                parent.coin.coin_id(),
+                parent.spent_height.ok_or(WalletError::PeerMisbehaved)?,
+            )
+            .await?;
+
+        let parent_puzzle = ctx.alloc(&parent_puzzle_reveal)?;
+        let parent_puzzle = Puzzle::parse(ctx, parent_puzzle);
+        let parent_solution = ctx.alloc(&parent_solution)?;
+
+        let Some(did) =
+            Did::parse_child(ctx, parent.coin, parent_puzzle, parent_solution, child.coin)?
+        else {
+            return Err(WalletError::MissingDid(launcher_id));
+        };
+
+        Ok((did, parent_puzzle))
+    }
+}
+
+fn did_recovery_request(did: &Did, public_key: PublicKey) -> WalletDidRecoveryRequest {
+    let new_info = DidInfo {
+        p2_puzzle_hash: StandardArgs::curry_tree_hash(public_key).into(),
+        ..did.info
+    };
+
+    WalletDidRecoveryRequest {
+        coin_id: did.coin.coin_id(),
+        new_inner_puzzle_hash: new_info.inner_puzzle_hash().into(),
+        public_key,
+    }
+}
+
+/// The puzzle of the message coin that a recovery DID creates, which must match the one in the DID puzzle.
+fn did_recovery_message_puzzle(
+    ctx: &mut SpendContext,
+    request: WalletDidRecoveryRequest,
+) -> Result<NodePtr, WalletError> {
+    let conditions = Conditions::new()
+        .create_coin_announcement(request.coin_id.to_vec().into())
+        .agg_sig_unsafe(
+            request.public_key,
+            request.new_inner_puzzle_hash.to_vec().into(),
+        );
+
+    Ok(ctx.alloc(&(1, conditions))?)
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
 
-    use crate::TestWallet;
+    use chia_wallet_sdk::prelude::*;
+
+    use crate::{TestWallet, WalletError, WalletPeer};
 
     use test_log::test;
     use tokio::time::sleep;
@@ -276,4 +649,108 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_update_did_profile() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let did_id = did.info.launcher_id;
+        let profile = vec![
+            ("name".to_string(), "Alice".to_string()),
+            ("links".to_string(), "https://example.com".to_string()),
+        ];
+
+        let coin_spends = test
+            .wallet
+            .update_did_profile(did_id, profile.clone(), 0)
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let did = test.wallet.db.did(did_id).await?.expect("did");
+
+        let mut allocator = Allocator::new();
+        let metadata = did.info.metadata.to_clvm(&mut allocator)?;
+
+        assert_eq!(
+            Vec::<(String, String)>::from_clvm(&allocator, metadata)?,
+            profile
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_recover_did() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1).await?;
+        let mut bob = alice.next(1).await?;
+        let mut carol = alice.next(0).await?;
+
+        let (coin_spends, did) = alice.wallet.create_did(0).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let did_id = did.info.launcher_id;
+
+        let (coin_spends, attestor) = bob.wallet.create_did(0).await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        let attestor_id = attestor.info.launcher_id;
+
+        let coin_spends = alice
+            .wallet
+            .set_did_recovery_list(did_id, vec![attestor_id], 1, 0)
+            .await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let peer = WalletPeer::new(carol.peer.clone());
+
+        let request = carol.wallet.did_recovery_request(&peer, did_id).await?;
+
+        let (coin_spends, attestation) = bob
+            .wallet
+            .attest_did_recovery(attestor_id, request, 0)
+            .await?;
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+
+        assert!(matches!(
+            carol
+                .wallet
+                .recover_did(
+                    &peer,
+                    did_id,
+                    request.public_key,
+                    vec![attestor_id],
+                    vec![],
+                    0,
+                )
+                .await,
+            Err(WalletError::MissingDidAttestation(..))
+        ));
+
+        let coin_spends = carol
+            .wallet
+            .recover_did(
+                &peer,
+                did_id,
+                request.public_key,
+                vec![attestor_id],
+                vec![attestation],
+                0,
+            )
+            .await?;
+        carol.transact(coin_spends).await?;
+        carol.wait_for_puzzles().await;
+
+        assert!(carol.wallet.db.spendable_did(did_id).await?.is_some());
+
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
+use std::collections::HashSet;
+
 use chia_wallet_sdk::{
     chia::puzzle_types::nft::NftMetadata,
     driver::{MetadataUpdate, TransferNftById},
     prelude::*,
     puzzles::NFT_METADATA_UPDATER_DEFAULT_HASH,
 };
-use sage_database::{SerializePrimitive, SerializedNft};
+use sage_database::{NewMintJob, NewMintJobItem, SerializePrimitive, SerializedNft};
 
 use crate::{
     WalletError,
@@ -21,6 +23,15 @@ pub struct WalletNftMint {
     pub royalty_basis_points: u16,
 }
 
+#[derive(Debug, Clone)]
+pub struct WalletNftUriUpdate {
+    pub nft_id: Bytes32,
+    pub update: MetadataUpdate,
+    /// The puzzle reveal of the NFT's metadata updater, if it's not the default one.
+    /// It's passed the same solution as the default metadata updater would be.
+    pub metadata_updater: Option<Program>,
+}
+
 impl Wallet {
     pub async fn bulk_mint_nfts(
         &self,
@@ -72,6 +83,51 @@ impl Wallet {
         ))
     }
 
+    /// Saves the NFTs as a mint job, which is minted from the DID in the background
+    /// by the mint job queue, at most `batch_size` NFTs per transaction.
+    pub async fn create_mint_job(
+        &self,
+        did_id: Bytes32,
+        fee: u64,
+        batch_size: u32,
+        mints: Vec<WalletNftMint>,
+    ) -> Result<u32, WalletError> {
+        if self.db.did(did_id).await?.is_none() {
+            return Err(WalletError::MissingDid(did_id));
+        }
+
+        let mut allocator = Allocator::new();
+        let mut tx = self.db.tx().await?;
+
+        let mint_job_id = tx
+            .insert_mint_job(NewMintJob {
+                did_id,
+                fee,
+                batch_size,
+            })
+            .await?;
+
+        for (row_index, mint) in mints.into_iter().enumerate() {
+            let metadata = mint.metadata.to_clvm(&mut allocator)?;
+
+            tx.insert_mint_job_item(
+                mint_job_id,
+                NewMintJobItem {
+                    row_index: row_index.try_into()?,
+                    metadata: Program::from_clvm(&allocator, metadata)?,
+                    p2_puzzle_hash: mint.p2_puzzle_hash,
+                    royalty_puzzle_hash: mint.royalty_puzzle_hash,
+                    royalty_basis_points: mint.royalty_basis_points,
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(mint_job_id)
+    }
+
     pub async fn transfer_nfts(
         &self,
         nft_ids: Vec<Bytes32>,
@@ -158,6 +214,40 @@ impl Wallet {
         Ok(ctx.take())
     }
 
+    /// Adds URIs to NFTs in a single transaction. The excluded coins won't be used to pay
+    /// the fee, so that batches created before any of them are submitted don't conflict.
+    pub async fn add_nft_uris(
+        &self,
+        updates: Vec<WalletNftUriUpdate>,
+        fee: u64,
+        excluded_coin_ids: &HashSet<Bytes32>,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let mut ctx = SpendContext::new();
+        let mut actions = vec![Action::fee(fee)];
+
+        for item in updates {
+            let mut spend = item.update.spend(&mut ctx)?;
+
+            if let Some(metadata_updater) = item.metadata_updater {
+                spend.puzzle = ctx.alloc(&metadata_updater)?;
+            }
+
+            actions.push(Action::update_nft(
+                Id::Existing(item.nft_id),
+                vec![spend],
+                None,
+            ));
+        }
+
+        let mut spends = self.prepare_spends_for_selection(&mut ctx, &[]).await?;
+        self.select_spends_excluding(&mut ctx, &mut spends, &actions, excluded_coin_ids)
+            .await?;
+        let deltas = spends.apply(&mut ctx, &actions)?;
+        self.complete_spends(&mut ctx, &deltas, spends).await?;
+
+        Ok(ctx.take())
+    }
+
     pub async fn assign_nfts(
         &self,
         nft_ids: Vec<Bytes32>,
@@ -185,7 +275,11 @@ impl Wallet {
 mod tests {
     use std::time::Duration;
 
-    use chia_wallet_sdk::driver::UriKind;
+    use chia_wallet_sdk::{
+        clvm_traits::{clvm_list, clvm_quote},
+        driver::UriKind,
+        types::puzzles::NftMetadataUpdater,
+    };
     use test_log::test;
     use tokio::time::sleep;
 
@@ -263,6 +357,151 @@ mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn test_add_nft_uris() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(3).await?;
+
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let mint = WalletNftMint {
+            metadata: NftMetadata::default(),
+            p2_puzzle_hash: None,
+            royalty_puzzle_hash: Some(Bytes32::default()),
+            royalty_basis_points: 300,
+        };
+
+        let (coin_spends, nfts) = test
+            .wallet
+            .bulk_mint_nfts(0, did.info.launcher_id, vec![mint.clone(), mint])
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let updates = nfts
+            .iter()
+            .enumerate()
+            .map(|(index, nft)| WalletNftUriUpdate {
+                nft_id: nft.info.launcher_id,
+                update: MetadataUpdate {
+                    kind: UriKind::Data,
+                    uri: format!("https://mirror.example/{index}.png"),
+                },
+                metadata_updater: None,
+            })
+            .collect();
+
+        let coin_spends = test
+            .wallet
+            .add_nft_uris(updates, 0, &HashSet::new())
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        for (index, nft) in nfts.iter().enumerate() {
+            let row = test
+                .wallet
+                .db
+                .wallet_nft(nft.info.launcher_id)
+                .await?
+                .expect("missing nft");
+
+            let mut allocator = Allocator::new();
+            let metadata = row.nft_info.metadata.to_clvm(&mut allocator)?;
+            let metadata = NftMetadata::from_clvm(&allocator, metadata)?;
+
+            assert_eq!(
+                metadata.data_uris,
+                [format!("https://mirror.example/{index}.png")]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_add_nft_uris_custom_updater() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(2).await?;
+
+        // This wraps the default metadata updater, so it takes the same solution but has a
+        // different puzzle hash.
+        let mut ctx = SpendContext::new();
+        let default_updater = ctx.alloc_mod::<NftMetadataUpdater>()?;
+        let metadata_updater = ctx.alloc(&clvm_list!(2, clvm_quote!(default_updater), 1))?;
+        let metadata_updater_puzzle_hash: Bytes32 = ctx.tree_hash(metadata_updater).into();
+        let metadata_updater = ctx.serialize(&metadata_updater)?;
+
+        assert_ne!(
+            metadata_updater_puzzle_hash,
+            NFT_METADATA_UPDATER_DEFAULT_HASH.into()
+        );
+
+        let metadata = ctx.alloc_hashed(&NftMetadata::default())?;
+
+        let outputs = test
+            .wallet
+            .spend(
+                &mut ctx,
+                vec![],
+                &[Action::mint_nft(
+                    metadata,
+                    metadata_updater_puzzle_hash,
+                    Bytes32::default(),
+                    300,
+                    1,
+                )],
+            )
+            .await?;
+        test.transact(ctx.take()).await?;
+        test.wait_for_coins().await;
+
+        let nft_id = outputs
+            .nfts
+            .into_values()
+            .next()
+            .expect("missing nft")
+            .info
+            .launcher_id;
+
+        let coin_spends = test
+            .wallet
+            .add_nft_uris(
+                vec![WalletNftUriUpdate {
+                    nft_id,
+                    update: MetadataUpdate {
+                        kind: UriKind::Data,
+                        uri: "https://mirror.example/0.png".to_string(),
+                    },
+                    metadata_updater: Some(metadata_updater),
+                }],
+                0,
+                &HashSet::new(),
+            )
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let row = test
+            .wallet
+            .db
+            .wallet_nft(nft_id)
+            .await?
+            .expect("missing nft");
+
+        let mut allocator = Allocator::new();
+        let metadata = row.nft_info.metadata.to_clvm(&mut allocator)?;
+        let metadata = NftMetadata::from_clvm(&allocator, metadata)?;
+
+        assert_eq!(metadata.data_uris, ["https://mirror.example/0.png"]);
+        assert_eq!(
+            row.nft_info.metadata_updater_puzzle_hash,
+            metadata_updater_puzzle_hash
+        );
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn test_transfer_nft_internal() -> anyhow::Result<()> {
         let mut test = TestWallet::new(2).await?;
@@ -2,10 +2,12 @@ mod aggregate_offer;
 mod cancel_offer;
 mod make_offer;
 mod offer_assets;
+mod offer_ladder;
 mod take_offer;
 
 pub use aggregate_offer::*;
 pub use make_offer::*;
+pub use offer_ladder::*;
 
 #[cfg(test)]
 mod tests {
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use chia_wallet_sdk::{
     chia::puzzle_types::offer::{NotarizedPayment, Payment},
     driver::{
@@ -22,6 +24,12 @@ pub struct Offered {
     pub fee: u64,
     pub p2_puzzle_hash: Option<Bytes32>,
     pub selected_coin_ids: Vec<Bytes32>,
+    /// Coins to spend which aren't in the database yet, since the transaction creating them
+    /// hasn't been submitted.
+    pub pending_xch: Vec<Coin>,
+    pub pending_cats: Vec<Cat>,
+    /// Coins which must not be selected, such as those already spent by another unsubmitted offer.
+    pub excluded_coin_ids: HashSet<Bytes32>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -135,7 +143,17 @@ impl Wallet {
         let mut spends = self
             .prepare_spends_for_selection(&mut ctx, &offered.selected_coin_ids)
             .await?;
-        self.select_spends(&mut ctx, &mut spends, &actions).await?;
+
+        for coin in offered.pending_xch {
+            spends.add(coin);
+        }
+
+        for cat in offered.pending_cats {
+            spends.add(cat);
+        }
+
+        self.select_spends_excluding(&mut ctx, &mut spends, &actions, &offered.excluded_coin_ids)
+            .await?;
 
         let nonce = Offer::nonce(spends.non_settlement_coin_ids());
 
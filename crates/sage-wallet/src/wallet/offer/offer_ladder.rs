@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use chia_wallet_sdk::prelude::*;
+use itertools::Itertools;
+
+use crate::{Offered, Wallet, WalletError};
+
+/// The coin which a single rung of an offer ladder is made from.
+#[derive(Debug, Clone, Copy)]
+pub enum LadderCoin {
+    /// A coin of the right amount which is already in the wallet.
+    Existing(Bytes32),
+    /// An XCH coin created by the split, which isn't in the wallet until it's submitted.
+    Xch(Coin),
+    /// A CAT coin created by the split, which isn't in the wallet until it's submitted.
+    Cat(Cat),
+}
+
+impl LadderCoin {
+    pub fn coin_id(&self) -> Bytes32 {
+        match self {
+            Self::Existing(coin_id) => *coin_id,
+            Self::Xch(coin) => coin.coin_id(),
+            Self::Cat(cat) => cat.coin.coin_id(),
+        }
+    }
+
+    /// Makes this the only coin that the offer spends for the rung.
+    pub fn select(self, offered: &mut Offered) {
+        match self {
+            Self::Existing(coin_id) => offered.selected_coin_ids.push(coin_id),
+            Self::Xch(coin) => offered.pending_xch.push(coin),
+            Self::Cat(cat) => offered.pending_cats.push(cat),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OfferLadderCoins {
+    /// One coin of the requested amount per rung, some of which may be created by the split.
+    pub coins: Vec<LadderCoin>,
+    /// The spends which split larger coins into the missing rung coins, if any are needed.
+    pub coin_spends: Vec<CoinSpend>,
+}
+
+impl Wallet {
+    /// Sets aside `count` coins of exactly `coin_amount` each, so that every offer in a ladder
+    /// can be made from its own input coin. Existing coins of the right amount are used first,
+    /// and the rest are split out of the largest available coins.
+    pub async fn offer_ladder_coins(
+        &self,
+        asset_id: Option<Bytes32>,
+        coin_amount: u64,
+        count: usize,
+        fee: u64,
+    ) -> Result<OfferLadderCoins, WalletError> {
+        let coins = if let Some(asset_id) = asset_id {
+            self.db
+                .selectable_cat_coins(asset_id)
+                .await?
+                .into_iter()
+                .map(|cat| cat.coin)
+                .collect_vec()
+        } else {
+            self.db.selectable_xch_coins().await?
+        };
+
+        let mut ladder_coins = coins
+            .iter()
+            .filter(|coin| coin.amount == coin_amount)
+            .take(count)
+            .map(|coin| LadderCoin::Existing(coin.coin_id()))
+            .collect_vec();
+
+        let needed = count - ladder_coins.len();
+
+        if needed == 0 {
+            return Ok(OfferLadderCoins {
+                coins: ladder_coins,
+                coin_spends: Vec::new(),
+            });
+        }
+
+        // The fee is paid out of the selected coins if they are XCH.
+        let target = coin_amount
+            .saturating_mul(needed as u64)
+            .saturating_add(if asset_id.is_none() { fee } else { 0 });
+
+        let reserved: HashSet<Bytes32> = ladder_coins.iter().map(LadderCoin::coin_id).collect();
+
+        let mut selected = Vec::new();
+        let mut selected_amount = 0;
+
+        for coin in coins
+            .iter()
+            .filter(|coin| !reserved.contains(&coin.coin_id()))
+            .sorted_by_key(|coin| std::cmp::Reverse(coin.amount))
+        {
+            if selected_amount >= target {
+                break;
+            }
+
+            selected.push(coin.coin_id());
+            selected_amount += coin.amount;
+        }
+
+        if selected_amount < target {
+            return Err(WalletError::InsufficientFunds);
+        }
+
+        let (coin_spends, outputs) = self
+            .split_outputs_by_amount(selected, coin_amount, needed, fee)
+            .await?;
+
+        // The change may happen to have the same amount, but it isn't needed.
+        if let Some(asset_id) = asset_id {
+            ladder_coins.extend(
+                outputs
+                    .cats
+                    .get(&Id::Existing(asset_id))
+                    .into_iter()
+                    .flatten()
+                    .filter(|cat| cat.coin.amount == coin_amount)
+                    .take(needed)
+                    .map(|&cat| LadderCoin::Cat(cat)),
+            );
+        } else {
+            ladder_coins.extend(
+                outputs
+                    .xch
+                    .into_iter()
+                    .filter(|coin| coin.amount == coin_amount)
+                    .take(needed)
+                    .map(LadderCoin::Xch),
+            );
+        }
+
+        Ok(OfferLadderCoins {
+            coins: ladder_coins,
+            coin_spends,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{Offered, Requested, TestWallet};
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_offer_ladder_coins() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let ladder = test.wallet.offer_ladder_coins(None, 100, 3, 50).await?;
+        assert_eq!(ladder.coins.len(), 3);
+
+        let ladder_coin_ids = ladder.coins.iter().map(LadderCoin::coin_id).collect_vec();
+
+        // Each offer is made from its own coin, before the split has even been submitted.
+        let mut offer_coin_ids = Vec::new();
+
+        for (index, &coin) in ladder.coins.iter().enumerate() {
+            let mut offered = Offered {
+                xch: 100,
+                ..Default::default()
+            };
+            coin.select(&mut offered);
+
+            let offer = test
+                .wallet
+                .make_offer(
+                    offered,
+                    Requested {
+                        xch: 200 + index as u64,
+                        ..Default::default()
+                    },
+                    None,
+                )
+                .await?;
+
+            let coin_ids = offer
+                .coin_spends
+                .iter()
+                .map(|coin_spend| coin_spend.coin.coin_id())
+                .filter(|coin_id| ladder_coin_ids.contains(coin_id))
+                .collect_vec();
+
+            assert_eq!(coin_ids, [coin.coin_id()]);
+            offer_coin_ids.extend(coin_ids);
+        }
+
+        assert!(offer_coin_ids.iter().all_unique());
+
+        test.transact(ladder.coin_spends).await?;
+        test.wait_for_coins().await;
+
+        assert_eq!(test.wallet.db.xch_balance().await?, 950);
+
+        // The coins now exist, so they are reused rather than split again.
+        let ladder = test.wallet.offer_ladder_coins(None, 100, 3, 0).await?;
+        assert_eq!(ladder.coins.len(), 3);
+        assert!(
+            ladder
+                .coins
+                .iter()
+                .all(|coin| matches!(coin, LadderCoin::Existing(_)))
+        );
+        assert!(ladder.coin_spends.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
+use std::collections::HashSet;
+
 use chia_wallet_sdk::{
     chia::puzzle_types::offer::{NotarizedPayment, Payment},
+    driver::TransferNftById,
     prelude::*,
     puzzles::SETTLEMENT_PAYMENT_HASH,
 };
-use sage_database::CoinKind;
+use sage_database::{CoinKind, DeserializePrimitive, NftOfferInfo};
 
 use crate::{
     WalletError,
@@ -19,6 +22,33 @@ pub struct WalletOptionMint {
     pub strike_type: OptionType,
 }
 
+/// The option type for an NFT underlying or strike. When an NFT strike is paid, the NFT
+/// is sent to the settlement puzzle without an owner, so its puzzle hash is calculated up front.
+pub fn nft_option_type(
+    launcher_id: Bytes32,
+    nft: &NftOfferInfo,
+) -> Result<OptionType, WalletError> {
+    let mut ctx = SpendContext::new();
+
+    let metadata = ctx.alloc_hashed(&nft.metadata)?;
+
+    let settlement_info = NftInfo::new(
+        launcher_id,
+        metadata,
+        nft.metadata_updater_puzzle_hash,
+        None,
+        nft.royalty_puzzle_hash,
+        nft.royalty_basis_points,
+        SETTLEMENT_PAYMENT_HASH.into(),
+    );
+
+    Ok(OptionType::Nft {
+        launcher_id,
+        settlement_puzzle_hash: settlement_info.puzzle_hash().into(),
+        amount: 1,
+    })
+}
+
 impl Wallet {
     pub async fn mint_option(
         &self,
@@ -34,13 +64,9 @@ impl Wallet {
             OptionType::Cat { asset_id, .. } | OptionType::RevocableCat { asset_id, .. } => {
                 Id::Existing(asset_id)
             }
-            OptionType::Nft { .. } => return Err(WalletError::NftOptionNotSupported),
+            OptionType::Nft { launcher_id, .. } => Id::Existing(launcher_id),
         };
 
-        if matches!(mint.strike_type, OptionType::Nft { .. }) {
-            return Err(WalletError::NftOptionNotSupported);
-        }
-
         let outputs = self
             .spend(
                 &mut ctx,
@@ -128,6 +154,7 @@ impl Wallet {
 
         let mut settlement_coins = Vec::new();
         let mut settlement_cats = Vec::new();
+        let mut settlement_nfts = Vec::new();
 
         for option_id in option_ids {
             let option = self
@@ -177,6 +204,15 @@ impl Wallet {
 
                     settlement_cats.push(children[0]);
                 }
+                CoinKind::Nft => {
+                    let Some(nft) = self.db.nft_coin(option.info.underlying_coin_id).await? else {
+                        return Err(WalletError::MissingNftCoin(option.info.underlying_coin_id));
+                    };
+
+                    let nft = nft.deserialize(&mut ctx)?;
+
+                    settlement_nfts.push(nft.spend(&mut ctx, underlying_spend)?);
+                }
                 kind => {
                     return Err(WalletError::UnsupportedUnderlyingCoinKind(kind));
                 }
@@ -215,13 +251,38 @@ impl Wallet {
                         ),
                     ));
                 }
-                OptionType::Nft { .. } => {
-                    return Err(WalletError::NftOptionNotSupported);
+                OptionType::Nft {
+                    launcher_id,
+                    amount,
+                    ..
+                } => {
+                    let hint = ctx.hint(underlying.creator_puzzle_hash)?;
+
+                    // The strike puzzle hash is calculated without an owner
+                    actions.push(Action::update_nft(
+                        Id::Existing(launcher_id),
+                        vec![],
+                        Some(TransferNftById::new(None, vec![])),
+                    ));
+
+                    actions.push(Action::settle(
+                        Id::Existing(launcher_id),
+                        NotarizedPayment::new(
+                            option.info.launcher_id,
+                            vec![Payment::new(underlying.creator_puzzle_hash, amount, hint)],
+                        ),
+                    ));
                 }
             }
         }
 
-        let mut spends = self.prepare_spends(&mut ctx, vec![], &actions).await?;
+        // The melted option amount is returned as change, which needs to come from an XCH coin
+        let mut deltas = Deltas::from_actions(&actions);
+        deltas.set_needed(Id::Xch);
+
+        let mut spends = self.prepare_spends_for_selection(&mut ctx, &[]).await?;
+        self.select_spends_for_deltas(&mut ctx, &mut spends, deltas, &HashSet::new())
+            .await?;
 
         for coin in settlement_coins {
             spends.add(coin);
@@ -231,6 +292,10 @@ impl Wallet {
             spends.add(cat);
         }
 
+        for nft in settlement_nfts {
+            spends.add(nft);
+        }
+
         let deltas = spends.apply(&mut ctx, &actions)?;
         self.complete_spends(&mut ctx, &deltas, spends).await?;
 
@@ -242,12 +307,39 @@ impl Wallet {
 mod tests {
     use std::time::Duration;
 
-    use crate::{TestWallet, WalletOptionMint};
+    use crate::{Offered, Requested, TestWallet, WalletNftMint, WalletOptionMint, WalletPeer};
 
-    use chia_wallet_sdk::driver::OptionType;
+    use chia_wallet_sdk::{chia::puzzle_types::nft::NftMetadata, prelude::*};
+    use sage_database::SerializedNft;
     use test_log::test;
     use tokio::time::sleep;
 
+    use super::nft_option_type;
+
+    async fn mint_nft(test: &mut TestWallet) -> anyhow::Result<SerializedNft> {
+        let (coin_spends, did) = test.wallet.create_did(0).await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let (coin_spends, mut nfts) = test
+            .wallet
+            .bulk_mint_nfts(
+                0,
+                did.info.launcher_id,
+                vec![WalletNftMint {
+                    metadata: NftMetadata::default(),
+                    p2_puzzle_hash: None,
+                    royalty_puzzle_hash: Some(Bytes32::default()),
+                    royalty_basis_points: 300,
+                }],
+            )
+            .await?;
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        Ok(nfts.remove(0))
+    }
+
     #[test(tokio::test)]
     async fn test_mint_option() -> anyhow::Result<()> {
         let mut test = TestWallet::new(1001).await?;
@@ -498,4 +590,308 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn test_reclaim_expired_option() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1001).await?;
+        let mut bob = alice.next(0).await?;
+
+        let (coin_spends, asset_id) = alice.wallet.issue_cat(1000, 0, None).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let timestamp = alice.new_block_with_current_time().await?;
+
+        let (coin_spends, option) = alice
+            .wallet
+            .mint_option(
+                WalletOptionMint {
+                    expiration_seconds: timestamp + 5,
+                    underlying_type: OptionType::Cat {
+                        asset_id,
+                        amount: 1000,
+                    },
+                    strike_type: OptionType::Xch { amount: 2000 },
+                },
+                0,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let option_id = option.info.launcher_id;
+
+        let coin_spends = alice
+            .wallet
+            .transfer_options(vec![option_id], bob.puzzle_hash, 0, None)
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        assert!(
+            alice
+                .wallet
+                .db
+                .expired_option_underlying_coin_ids()
+                .await?
+                .is_empty()
+        );
+
+        sleep(Duration::from_secs(6)).await;
+        alice.new_block_with_current_time().await?;
+
+        let coin_ids = alice.wallet.db.expired_option_underlying_coin_ids().await?;
+        assert_eq!(coin_ids, vec![option.info.underlying_coin_id]);
+
+        let coin_spends = alice.wallet.combine(coin_ids, 0).await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        assert!(
+            alice
+                .wallet
+                .db
+                .expired_option_underlying_coin_ids()
+                .await?
+                .is_empty()
+        );
+        assert_eq!(
+            alice.wallet.db.selectable_cat_balance(asset_id).await?,
+            1000
+        );
+
+        let chain = alice.wallet.db.option_chain().await?;
+        assert_eq!(chain.len(), 1);
+        assert!(chain[0].is_creator);
+        assert!(!chain[0].is_owned);
+        assert!(chain[0].underlying_spent_height.is_some());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_exercise_nft_underlying_option() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(3).await?;
+        let mut bob = alice.next(1999).await?; // 1 mojo is returned from melting the option
+
+        let nft = mint_nft(&mut alice).await?;
+        let nft_id = nft.info.launcher_id;
+
+        let nft_info = alice
+            .wallet
+            .db
+            .offer_nft_info(nft_id)
+            .await?
+            .expect("missing nft info");
+
+        let timestamp = alice.new_block_with_current_time().await?;
+
+        let (coin_spends, option) = alice
+            .wallet
+            .mint_option(
+                WalletOptionMint {
+                    expiration_seconds: timestamp + 1000,
+                    underlying_type: nft_option_type(nft_id, &nft_info)?,
+                    strike_type: OptionType::Xch { amount: 2000 },
+                },
+                0,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let option_id = option.info.launcher_id;
+
+        assert!(alice.wallet.db.spendable_nft(nft_id).await?.is_none());
+
+        let coin_spends = alice
+            .wallet
+            .transfer_options(vec![option_id], bob.puzzle_hash, 0, None)
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        bob.wait_for_puzzles().await;
+
+        assert!(bob.wallet.db.spendable_option(option_id).await?.is_some());
+
+        let coin_spends = bob.wallet.exercise_options(vec![option_id], 0).await?;
+
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+        alice.wait_for_coins().await;
+
+        assert!(bob.wallet.db.option(option_id).await?.is_none());
+        assert!(bob.wallet.db.spendable_nft(nft_id).await?.is_some());
+        assert_eq!(bob.wallet.db.selectable_xch_balance().await?, 0);
+        assert_eq!(alice.wallet.db.selectable_xch_balance().await?, 2000);
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_exercise_nft_strike_option() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1001).await?;
+        let mut bob = alice.next(3).await?;
+
+        let (coin_spends, asset_id) = alice.wallet.issue_cat(1000, 0, None).await?;
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let nft = mint_nft(&mut bob).await?;
+        let nft_id = nft.info.launcher_id;
+
+        // Alice has never seen the NFT, so its info has to be fetched from the network
+        let peer = WalletPeer::new(alice.peer.clone());
+
+        let nft_info = alice
+            .wallet
+            .fetch_offer_nft_info(Some(&peer), nft_id)
+            .await?
+            .expect("missing nft info");
+
+        let timestamp = alice.new_block_with_current_time().await?;
+
+        let (coin_spends, option) = alice
+            .wallet
+            .mint_option(
+                WalletOptionMint {
+                    expiration_seconds: timestamp + 1000,
+                    underlying_type: OptionType::Cat {
+                        asset_id,
+                        amount: 1000,
+                    },
+                    strike_type: nft_option_type(nft_id, &nft_info)?,
+                },
+                0,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let option_id = option.info.launcher_id;
+
+        let coin_spends = alice
+            .wallet
+            .transfer_options(vec![option_id], bob.puzzle_hash, 0, None)
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        bob.wait_for_puzzles().await;
+
+        let coin_spends = bob.wallet.exercise_options(vec![option_id], 0).await?;
+
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+        alice.wait_for_puzzles().await;
+
+        assert!(bob.wallet.db.option(option_id).await?.is_none());
+        assert!(bob.wallet.db.spendable_nft(nft_id).await?.is_none());
+        assert_eq!(bob.wallet.db.selectable_cat_balance(asset_id).await?, 1000);
+        assert_eq!(bob.wallet.db.selectable_xch_balance().await?, 2);
+        assert!(alice.wallet.db.spendable_nft(nft_id).await?.is_some());
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_offer_nft_underlying_option() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(3).await?;
+        let mut bob = alice.next(2999).await?; // 1 mojo is returned from melting the option
+
+        let nft = mint_nft(&mut alice).await?;
+        let nft_id = nft.info.launcher_id;
+
+        let nft_info = alice
+            .wallet
+            .db
+            .offer_nft_info(nft_id)
+            .await?
+            .expect("missing nft info");
+
+        let timestamp = alice.new_block_with_current_time().await?;
+
+        let (coin_spends, option) = alice
+            .wallet
+            .mint_option(
+                WalletOptionMint {
+                    expiration_seconds: timestamp + 1000,
+                    underlying_type: nft_option_type(nft_id, &nft_info)?,
+                    strike_type: OptionType::Xch { amount: 2000 },
+                },
+                0,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let option_id = option.info.launcher_id;
+
+        // Bob looks up the option before taking an offer for it
+        let peer = WalletPeer::new(bob.peer.clone());
+
+        let option_info = bob
+            .wallet
+            .fetch_offer_option_info(Some(&peer), option_id)
+            .await?
+            .expect("missing option info");
+
+        assert_eq!(
+            option_info.underlying_coin_hash,
+            option.info.underlying_coin_id
+        );
+
+        let offer = alice
+            .wallet
+            .make_offer(
+                Offered {
+                    options: vec![option_id],
+                    ..Default::default()
+                },
+                Requested {
+                    xch: 1000,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+        let offer = alice
+            .wallet
+            .sign_transaction(offer, &alice.agg_sig, alice.master_sk.clone(), true)
+            .await?;
+
+        let offer = bob.wallet.take_offer(offer, 0).await?;
+        let spend_bundle = bob
+            .wallet
+            .sign_transaction(offer, &bob.agg_sig, bob.master_sk.clone(), true)
+            .await?;
+        bob.push_bundle(spend_bundle).await?;
+
+        alice.wait_for_coins().await;
+        bob.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.xch_balance().await?, 1000);
+        assert!(bob.wallet.db.spendable_option(option_id).await?.is_some());
+
+        let coin_spends = bob.wallet.exercise_options(vec![option_id], 0).await?;
+
+        bob.transact(coin_spends).await?;
+        bob.wait_for_coins().await;
+        alice.wait_for_coins().await;
+
+        assert!(bob.wallet.db.spendable_nft(nft_id).await?.is_some());
+        assert_eq!(alice.wallet.db.xch_balance().await?, 3000);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,368 @@
+use std::collections::{HashMap, VecDeque};
+
+use chia_wallet_sdk::{
+    chia::puzzle_types::standard::StandardArgs, prelude::*, types::puzzles::RevocationArgs,
+};
+use futures_util::future::try_join_all;
+use indexmap::IndexMap;
+
+use crate::{WalletError, WalletPeer};
+
+use super::Wallet;
+
+/// The unspent coins of a CAT which are held by a single p2 puzzle hash.
+#[derive(Debug, Clone)]
+pub struct CatHolder {
+    pub p2_puzzle_hash: Bytes32,
+    pub amount: u64,
+    pub cats: Vec<Cat>,
+}
+
+/// A page of the holders of a CAT.
+#[derive(Debug, Clone)]
+pub struct CatHolderTrace {
+    pub holders: Vec<CatHolder>,
+    pub remaining_coin_ids: Vec<Bytes32>,
+}
+
+/// The number of coins which are looked up at once while tracing holders.
+const HOLDER_TRACE_BATCH_SIZE: usize = 25;
+
+impl Wallet {
+    /// The hidden puzzle hash for revocable CATs issued by this wallet.
+    /// It's the standard puzzle of the issuance key, so revocations can be signed like any other spend.
+    pub fn cat_revocation_puzzle_hash(&self) -> Bytes32 {
+        StandardArgs::curry_tree_hash(self.cat_issuance_key()).into()
+    }
+
+    /// Fetches an unspent CAT coin from the network, which doesn't need to belong to this wallet.
+    pub async fn fetch_cat(&self, peer: &WalletPeer, coin_id: Bytes32) -> Result<Cat, WalletError> {
+        let coin_state = peer.fetch_coin(coin_id, self.genesis_challenge).await?;
+
+        if coin_state.spent_height.is_some() {
+            return Err(WalletError::MissingCatCoin(coin_id));
+        }
+
+        let parent_spend = peer
+            .fetch_coin_spend(coin_state.coin.parent_coin_info, self.genesis_challenge)
+            .await?;
+
+        cat_children(&parent_spend)?
+            .into_iter()
+            .find(|cat| cat.coin == coin_state.coin)
+            .ok_or(WalletError::MissingCatCoin(coin_id))
+    }
+
+    /// Revokes CAT coins through the hidden puzzle, sending them back to this wallet.
+    /// The returned coins are wrapped in the same revocation layer, so they remain revocable.
+    pub async fn revoke_cats(
+        &self,
+        cats: Vec<Cat>,
+        fee: u64,
+    ) -> Result<Vec<CoinSpend>, WalletError> {
+        let revocation_puzzle_hash = self.cat_revocation_puzzle_hash();
+        let p2_puzzle_hash = self.change_p2_puzzle_hash().await?;
+        let revocable_puzzle_hash: Bytes32 =
+            RevocationArgs::new(revocation_puzzle_hash, p2_puzzle_hash)
+                .curry_tree_hash()
+                .into();
+
+        let mut ctx = SpendContext::new();
+
+        if fee > 0 {
+            self.spend(&mut ctx, vec![], &[Action::fee(fee)]).await?;
+        }
+
+        let p2 = StandardLayer::new(self.cat_issuance_key());
+        let mut cat_spends = IndexMap::<Bytes32, Vec<CatSpend>>::new();
+
+        for cat in cats {
+            if cat.info.hidden_puzzle_hash != Some(revocation_puzzle_hash) {
+                return Err(WalletError::UnrevocableCat(cat.coin.coin_id()));
+            }
+
+            let hint = ctx.hint(p2_puzzle_hash)?;
+            let spend = p2.spend_with_conditions(
+                &mut ctx,
+                Conditions::new().create_coin(revocable_puzzle_hash, cat.coin.amount, hint),
+            )?;
+
+            cat_spends
+                .entry(cat.info.asset_id)
+                .or_default()
+                .push(CatSpend::revoke(cat, spend));
+        }
+
+        for cat_spends in cat_spends.values() {
+            Cat::spend_all(&mut ctx, cat_spends)?;
+        }
+
+        Ok(ctx.take())
+    }
+
+    /// Traces the outstanding supply of a CAT from the given coins, grouped by holder.
+    /// At most `limit` coins are looked up, and the coins which weren't reached are returned
+    /// so that the trace can be continued from them.
+    pub async fn cat_holders(
+        &self,
+        peer: &WalletPeer,
+        asset_id: Bytes32,
+        coin_ids: Vec<Bytes32>,
+        limit: usize,
+    ) -> Result<CatHolderTrace, WalletError> {
+        let mut pending = VecDeque::new();
+
+        for batch in coin_ids.chunks(HOLDER_TRACE_BATCH_SIZE) {
+            // The issuance may not have been confirmed yet, in which case the coin is skipped.
+            let coin_states = peer
+                .fetch_coins(batch.to_vec(), self.genesis_challenge)
+                .await?;
+
+            let parent_spends = try_join_all(coin_states.iter().map(|coin_state| {
+                peer.fetch_coin_spend(coin_state.coin.parent_coin_info, self.genesis_challenge)
+            }))
+            .await?;
+
+            for (coin_state, parent_spend) in coin_states.iter().zip(parent_spends) {
+                pending.extend(
+                    cat_children(&parent_spend)?
+                        .into_iter()
+                        .filter(|cat| cat.coin == coin_state.coin && cat.info.asset_id == asset_id),
+                );
+            }
+        }
+
+        let mut holders = IndexMap::<Bytes32, CatHolder>::new();
+        let mut remaining = limit;
+
+        while !pending.is_empty() && remaining > 0 {
+            let count = pending.len().min(HOLDER_TRACE_BATCH_SIZE).min(remaining);
+            let batch: Vec<Cat> = pending.drain(..count).collect();
+            remaining -= count;
+
+            let spent_heights: HashMap<Bytes32, Option<u32>> = peer
+                .fetch_coins(
+                    batch.iter().map(|cat| cat.coin.coin_id()).collect(),
+                    self.genesis_challenge,
+                )
+                .await?
+                .into_iter()
+                .map(|coin_state| (coin_state.coin.coin_id(), coin_state.spent_height))
+                .collect();
+
+            let mut spent = Vec::new();
+
+            for cat in batch {
+                let coin_id = cat.coin.coin_id();
+
+                match spent_heights.get(&coin_id) {
+                    Some(Some(spent_height)) => spent.push((cat, *spent_height)),
+                    Some(None) => {
+                        let holder =
+                            holders
+                                .entry(cat.info.p2_puzzle_hash)
+                                .or_insert_with(|| CatHolder {
+                                    p2_puzzle_hash: cat.info.p2_puzzle_hash,
+                                    amount: 0,
+                                    cats: Vec::new(),
+                                });
+
+                        holder.amount = holder.amount.saturating_add(cat.coin.amount);
+                        holder.cats.push(cat);
+                    }
+                    None => return Err(WalletError::MissingCoin(coin_id)),
+                }
+            }
+
+            let solutions = try_join_all(spent.iter().map(|(cat, spent_height)| {
+                peer.fetch_puzzle_solution(cat.coin.coin_id(), *spent_height)
+            }))
+            .await?;
+
+            for ((cat, _), (puzzle_reveal, solution)) in spent.into_iter().zip(solutions) {
+                pending.extend(cat_children(&CoinSpend::new(
+                    cat.coin,
+                    puzzle_reveal,
+                    solution,
+                ))?);
+            }
+        }
+
+        Ok(CatHolderTrace {
+            holders: holders.into_values().collect(),
+            remaining_coin_ids: pending.iter().map(|cat| cat.coin.coin_id()).collect(),
+        })
+    }
+}
+
+fn cat_children(coin_spend: &CoinSpend) -> Result<Vec<Cat>, WalletError> {
+    let mut allocator = Allocator::new();
+
+    let puzzle = coin_spend.puzzle_reveal.to_clvm(&mut allocator)?;
+    let puzzle = Puzzle::parse(&allocator, puzzle);
+    let solution = coin_spend.solution.to_clvm(&mut allocator)?;
+
+    Ok(Cat::parse_children(&mut allocator, coin_spend.coin, puzzle, solution)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{TestWallet, WalletCatTail, WalletError, WalletPeer};
+
+    #[test(tokio::test)]
+    async fn test_revoke_cat() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let revocation_puzzle_hash = alice.wallet.cat_revocation_puzzle_hash();
+        let (coin_spends, cat, _tail_reveal) = alice
+            .wallet
+            .issue_cat_with_tail(
+                1000,
+                0,
+                WalletCatTail::SingleIssuance,
+                Some(revocation_puzzle_hash),
+            )
+            .await?;
+        let asset_id = cat.info.asset_id;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let coin_spends = alice
+            .wallet
+            .send_cat(
+                asset_id,
+                vec![(bob.puzzle_hash, 1000)],
+                0,
+                true,
+                vec![],
+                None,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 0);
+        assert_eq!(bob.wallet.db.cat_balance(asset_id).await?, 1000);
+
+        let bob_cats = bob.wallet.db.selectable_cat_coins(asset_id).await?;
+        assert_eq!(bob_cats.len(), 1);
+
+        let peer = WalletPeer::new(alice.peer.clone());
+        let cat = alice
+            .wallet
+            .fetch_cat(&peer, bob_cats[0].coin.coin_id())
+            .await?;
+        assert_eq!(cat.info.hidden_puzzle_hash, Some(revocation_puzzle_hash));
+        assert_eq!(cat.info.p2_puzzle_hash, bob.puzzle_hash);
+
+        let coin_spends = alice.wallet.revoke_cats(vec![cat], 0).await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_coins().await;
+
+        assert_eq!(alice.wallet.db.cat_balance(asset_id).await?, 1000);
+        assert_eq!(bob.wallet.db.cat_balance(asset_id).await?, 0);
+
+        let alice_cats = alice.wallet.db.selectable_cat_coins(asset_id).await?;
+        assert_eq!(alice_cats.len(), 1);
+        assert_eq!(
+            alice_cats[0].info.hidden_puzzle_hash,
+            Some(revocation_puzzle_hash)
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_revoke_unrevocable_cat() -> anyhow::Result<()> {
+        let mut test = TestWallet::new(1000).await?;
+
+        let (coin_spends, asset_id) = test.wallet.issue_cat(1000, 0, None).await?;
+
+        test.transact(coin_spends).await?;
+        test.wait_for_coins().await;
+
+        let cats = test.wallet.db.selectable_cat_coins(asset_id).await?;
+        let result = test.wallet.revoke_cats(cats, 0).await;
+
+        assert!(matches!(result, Err(WalletError::UnrevocableCat(_))));
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn test_cat_holders() -> anyhow::Result<()> {
+        let mut alice = TestWallet::new(1000).await?;
+        let mut bob = alice.next(0).await?;
+
+        let revocation_puzzle_hash = alice.wallet.cat_revocation_puzzle_hash();
+        let (coin_spends, cat, _tail_reveal) = alice
+            .wallet
+            .issue_cat_with_tail(
+                1000,
+                0,
+                WalletCatTail::SingleIssuance,
+                Some(revocation_puzzle_hash),
+            )
+            .await?;
+        let asset_id = cat.info.asset_id;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+
+        let coin_spends = alice
+            .wallet
+            .send_cat(
+                asset_id,
+                vec![(bob.puzzle_hash, 300)],
+                0,
+                true,
+                vec![],
+                None,
+            )
+            .await?;
+
+        alice.transact(coin_spends).await?;
+        alice.wait_for_coins().await;
+        bob.wait_for_puzzles().await;
+
+        let peer = WalletPeer::new(alice.peer.clone());
+
+        // The issued coin is spent, so it doesn't reach the holders within a limit of one.
+        let trace = alice
+            .wallet
+            .cat_holders(&peer, asset_id, vec![cat.coin.coin_id()], 1)
+            .await?;
+        assert!(trace.holders.is_empty());
+        assert_eq!(trace.remaining_coin_ids.len(), 2);
+
+        let trace = alice
+            .wallet
+            .cat_holders(&peer, asset_id, trace.remaining_coin_ids, 10)
+            .await?;
+        assert!(trace.remaining_coin_ids.is_empty());
+
+        let holders = trace.holders;
+        assert_eq!(holders.len(), 2);
+        assert_eq!(
+            holders.iter().map(|holder| holder.amount).sum::<u64>(),
+            1000
+        );
+
+        let bob_holder = holders
+            .iter()
+            .find(|holder| holder.p2_puzzle_hash == bob.puzzle_hash)
+            .expect("missing holder");
+        assert_eq!(bob_holder.amount, 300);
+        assert_eq!(bob_holder.cats.len(), 1);
+
+        Ok(())
+    }
+}
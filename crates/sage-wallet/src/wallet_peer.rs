@@ -1,29 +1,48 @@
-use std::{collections::HashMap, net::SocketAddr, time::Duration};
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use chia_wallet_sdk::{
     chia::protocol::{
-        CoinStateFilters, RejectStateReason, RequestBlockHeader, RespondBlockHeader, RespondPeers,
+        CoinStateFilters, HeaderBlock, RejectBlockHeaders, RejectStateReason, RequestBlockHeader,
+        RequestBlockHeaders, RespondBlockHeader, RespondBlockHeaders, RespondPeers,
         RespondPuzzleState, TransactionAck,
     },
+    client::ClientError,
     prelude::*,
 };
 use tokio::time::timeout;
 
-use crate::WalletError;
+use crate::{PeerScore, WalletError};
 
 #[derive(Debug, Clone)]
 pub struct WalletPeer {
     peer: Peer,
+    socket_addr: SocketAddr,
     pending_coin_states: HashMap<Bytes32, CoinState>,
     pending_coin_spends: HashMap<Bytes32, CoinSpend>,
+    score: Arc<Mutex<PeerScore>>,
 }
 
 impl WalletPeer {
     pub fn new(peer: Peer) -> Self {
+        let socket_addr = peer.socket_addr();
+        Self::with_socket_addr(peer, socket_addr)
+    }
+
+    /// Creates a peer with a different address than the one it's connected to, which is needed
+    /// when the connection is made through a proxy.
+    pub fn with_socket_addr(peer: Peer, socket_addr: SocketAddr) -> Self {
         Self {
             peer,
+            socket_addr,
             pending_coin_states: HashMap::new(),
             pending_coin_spends: HashMap::new(),
+            score: Arc::new(Mutex::new(PeerScore::default())),
         }
     }
 
@@ -35,13 +54,49 @@ impl WalletPeer {
     ) -> Self {
         Self {
             peer: self.peer.clone(),
+            socket_addr: self.socket_addr,
             pending_coin_states,
             pending_coin_spends,
+            score: self.score.clone(),
         }
     }
 
     pub fn socket_addr(&self) -> SocketAddr {
-        self.peer.socket_addr()
+        self.socket_addr
+    }
+
+    /// The score is shared between clones of the peer, so every request made to it counts.
+    pub fn score(&self) -> PeerScore {
+        *self.score.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn update_score(&self, update: impl FnOnce(&mut PeerScore)) {
+        update(&mut self.score.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+
+    /// Sends a request to the peer with a timeout, and records how long it took to respond or
+    /// that it failed to in the peer's score.
+    async fn request<T>(
+        &self,
+        duration: Duration,
+        request: impl Future<Output = Result<T, ClientError>>,
+    ) -> Result<T, WalletError> {
+        let start = Instant::now();
+
+        match timeout(duration, request).await {
+            Ok(Ok(response)) => {
+                self.update_score(|score| score.record_response(start.elapsed()));
+                Ok(response)
+            }
+            Ok(Err(error)) => {
+                self.update_score(PeerScore::record_failure);
+                Err(error.into())
+            }
+            Err(elapsed) => {
+                self.update_score(PeerScore::record_failure);
+                Err(elapsed.into())
+            }
+        }
     }
 
     pub async fn fetch_coin(
@@ -53,16 +108,18 @@ impl WalletPeer {
             return Ok(*coin_state);
         }
 
-        let Some(coin_state) = timeout(
-            Duration::from_secs(5),
-            self.peer
-                .request_coin_state(vec![coin_id], None, genesis_challenge, false),
-        )
-        .await??
-        .map_err(|_| WalletError::PeerMisbehaved)?
-        .coin_states
-        .into_iter()
-        .next() else {
+        let Some(coin_state) = self
+            .request(
+                Duration::from_secs(5),
+                self.peer
+                    .request_coin_state(vec![coin_id], None, genesis_challenge, false),
+            )
+            .await?
+            .map_err(|_| WalletError::PeerMisbehaved)?
+            .coin_states
+            .into_iter()
+            .next()
+        else {
             return Err(WalletError::MissingCoin(coin_id));
         };
 
@@ -78,16 +135,17 @@ impl WalletPeer {
             return Ok(Some(*coin_state));
         }
 
-        Ok(timeout(
-            Duration::from_secs(5),
-            self.peer
-                .request_coin_state(vec![coin_id], None, genesis_challenge, false),
-        )
-        .await??
-        .map_err(|_| WalletError::PeerMisbehaved)?
-        .coin_states
-        .into_iter()
-        .next())
+        Ok(self
+            .request(
+                Duration::from_secs(5),
+                self.peer
+                    .request_coin_state(vec![coin_id], None, genesis_challenge, false),
+            )
+            .await?
+            .map_err(|_| WalletError::PeerMisbehaved)?
+            .coin_states
+            .into_iter()
+            .next())
     }
 
     pub async fn fetch_coins(
@@ -106,14 +164,15 @@ impl WalletPeer {
             }
         }
 
-        let peer_coins = timeout(
-            Duration::from_secs(10),
-            self.peer
-                .request_coin_state(unknown_coin_ids, None, genesis_challenge, false),
-        )
-        .await??
-        .map_err(|_| WalletError::PeerMisbehaved)?
-        .coin_states;
+        let peer_coins = self
+            .request(
+                Duration::from_secs(10),
+                self.peer
+                    .request_coin_state(unknown_coin_ids, None, genesis_challenge, false),
+            )
+            .await?
+            .map_err(|_| WalletError::PeerMisbehaved)?
+            .coin_states;
 
         for coin_state in peer_coins {
             coin_states.insert(coin_state.coin.coin_id(), coin_state);
@@ -142,12 +201,13 @@ impl WalletPeer {
             ));
         }
 
-        let response = timeout(
-            Duration::from_secs(15),
-            self.peer.request_puzzle_and_solution(coin_id, spent_height),
-        )
-        .await??
-        .map_err(|_| WalletError::MissingSpend(coin_id))?;
+        let response = self
+            .request(
+                Duration::from_secs(15),
+                self.peer.request_puzzle_and_solution(coin_id, spent_height),
+            )
+            .await?
+            .map_err(|_| WalletError::MissingSpend(coin_id))?;
 
         Ok((response.puzzle, response.solution))
     }
@@ -202,37 +262,36 @@ impl WalletPeer {
             return Ok(Some(*child));
         }
 
-        Ok(
-            timeout(Duration::from_secs(5), self.peer.request_children(coin_id))
-                .await??
-                .coin_states
-                .into_iter()
-                .find(|child| child.coin.amount % 2 == 1),
-        )
+        Ok(self
+            .request(Duration::from_secs(5), self.peer.request_children(coin_id))
+            .await?
+            .coin_states
+            .into_iter()
+            .find(|child| child.coin.amount % 2 == 1))
     }
 
     pub async fn send_transaction(
         &self,
         spend_bundle: SpendBundle,
     ) -> Result<TransactionAck, WalletError> {
-        Ok(timeout(
+        self.request(
             Duration::from_secs(15),
             self.peer.send_transaction(spend_bundle),
         )
-        .await??)
+        .await
     }
 
     pub async fn unsubscribe(&self) -> Result<(), WalletError> {
-        timeout(
+        self.request(
             Duration::from_secs(10),
             self.peer.remove_puzzle_subscriptions(None),
         )
-        .await??;
-        timeout(
+        .await?;
+        self.request(
             Duration::from_secs(10),
             self.peer.remove_coin_subscriptions(None),
         )
-        .await??;
+        .await?;
 
         Ok(())
     }
@@ -249,16 +308,19 @@ impl WalletPeer {
         previous_height: Option<u32>,
         header_hash: Bytes32,
     ) -> Result<Vec<CoinState>, WalletError> {
-        let response = timeout(
-            Duration::from_secs(15),
-            self.peer
-                .request_coin_state(coin_ids, previous_height, header_hash, true),
-        )
-        .await??
-        .map_err(|error| match error.reason {
-            RejectStateReason::ExceededSubscriptionLimit => WalletError::SubscriptionLimitReached,
-            RejectStateReason::Reorg => WalletError::PeerMisbehaved,
-        })?;
+        let response = self
+            .request(
+                Duration::from_secs(15),
+                self.peer
+                    .request_coin_state(coin_ids, previous_height, header_hash, true),
+            )
+            .await?
+            .map_err(|error| match error.reason {
+                RejectStateReason::ExceededSubscriptionLimit => {
+                    WalletError::SubscriptionLimitReached
+                }
+                RejectStateReason::Reorg => WalletError::PeerMisbehaved,
+            })?;
 
         Ok(response.coin_states)
     }
@@ -270,7 +332,7 @@ impl WalletPeer {
         header_hash: Bytes32,
         filters: CoinStateFilters,
     ) -> Result<RespondPuzzleState, WalletError> {
-        timeout(
+        self.request(
             Duration::from_secs(45),
             self.peer.request_puzzle_state(
                 puzzle_hashes,
@@ -280,31 +342,45 @@ impl WalletPeer {
                 true,
             ),
         )
-        .await??
+        .await?
         .map_err(|error| match error.reason {
             RejectStateReason::ExceededSubscriptionLimit => WalletError::SubscriptionLimitReached,
             RejectStateReason::Reorg => WalletError::PeerMisbehaved,
         })
     }
 
+    pub async fn unsubscribe_puzzles(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+    ) -> Result<(), WalletError> {
+        self.request(
+            Duration::from_secs(10),
+            self.peer.remove_puzzle_subscriptions(Some(puzzle_hashes)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn unsubscribe_coins(&self, coin_ids: Vec<Bytes32>) -> Result<(), WalletError> {
-        timeout(
+        self.request(
             Duration::from_secs(10),
             self.peer.remove_coin_subscriptions(Some(coin_ids)),
         )
-        .await??;
+        .await?;
 
         Ok(())
     }
 
     pub async fn block_timestamp(&self, height: u32) -> Result<(Bytes32, u64), WalletError> {
-        let header_block = timeout(
-            Duration::from_secs(5),
-            self.peer
-                .request_infallible::<RespondBlockHeader, _>(RequestBlockHeader::new(height)),
-        )
-        .await??
-        .header_block;
+        let header_block = self
+            .request(
+                Duration::from_secs(5),
+                self.peer
+                    .request_infallible::<RespondBlockHeader, _>(RequestBlockHeader::new(height)),
+            )
+            .await?
+            .header_block;
 
         let timestamp = header_block
             .foliage_transaction_block
@@ -316,4 +392,35 @@ impl WalletPeer {
             timestamp.ok_or(WalletError::PeerMisbehaved)?,
         ))
     }
+
+    pub async fn header_hash(&self, height: u32) -> Result<Bytes32, WalletError> {
+        let header_block = self
+            .request(
+                Duration::from_secs(5),
+                self.peer
+                    .request_infallible::<RespondBlockHeader, _>(RequestBlockHeader::new(height)),
+            )
+            .await?
+            .header_block;
+
+        Ok(header_block.header_hash())
+    }
+
+    pub async fn block_headers(
+        &self,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<HeaderBlock>, WalletError> {
+        Ok(self
+            .request(
+                Duration::from_secs(15),
+                self.peer
+                    .request_fallible::<RespondBlockHeaders, RejectBlockHeaders, _>(
+                        RequestBlockHeaders::new(start_height, end_height, false),
+                    ),
+            )
+            .await?
+            .map_err(|_| WalletError::PeerMisbehaved)?
+            .header_blocks)
+    }
 }
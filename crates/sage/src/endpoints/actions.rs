@@ -25,9 +25,10 @@ impl Sage {
         let wallet = self.wallet()?;
 
         let asset_id = parse_asset_id(req.asset_id)?;
-        let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let services = self.network().services();
+        let dexie_api = services.dexie_api.ok_or(Error::DexieUnavailable)?;
 
-        let cat = DexieCat::fetch(asset_id, testnet).await?;
+        let cat = DexieCat::fetch(asset_id, &dexie_api, services.dexie_icons.as_deref()).await?;
 
         wallet
             .db
@@ -248,6 +249,7 @@ impl Sage {
 
         self.command_sender
             .send(SyncCommand::SubscribePuzzles {
+                fingerprint: wallet.fingerprint,
                 puzzle_hashes: derivations,
             })
             .await?;
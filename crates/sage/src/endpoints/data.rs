@@ -1,35 +1,51 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use crate::{
-    Error, Result, Sage, address_kind, parse_any_asset_id, parse_asset_id, parse_collection_id,
-    parse_did_id, parse_nft_id, parse_option_id,
+    Error, Result, RoyaltyIncome, Sage, address_kind, parse_any_asset_id, parse_asset_id,
+    parse_coin_ids, parse_collection_id, parse_did_id, parse_nft_id, parse_option_id,
+    royalty_period_start,
 };
 use base64::{Engine, prelude::BASE64_STANDARD};
 use chia_wallet_sdk::chia::puzzle_types::nft::NftMetadata;
 use chia_wallet_sdk::prelude::*;
 use chia_wallet_sdk::{driver::BURN_PUZZLE_HASH, utils::Address};
+use indexmap::IndexMap;
 use sage_api::{
-    Amount, CheckAddress, CheckAddressResponse, CoinFilterMode as ApiCoinFilterMode, CoinRecord,
-    CoinSortMode as ApiCoinSortMode, DerivationRecord, DidRecord, GetAllCats, GetAllCatsResponse,
-    GetAreCoinsSpendable, GetAreCoinsSpendableResponse, GetCats, GetCatsResponse, GetCoins,
-    GetCoinsByIds, GetCoinsByIdsResponse, GetCoinsResponse, GetDatabaseStats,
-    GetDatabaseStatsResponse, GetDerivations, GetDerivationsResponse, GetDids, GetDidsResponse,
-    GetMinterDidIds, GetMinterDidIdsResponse, GetNft, GetNftCollection, GetNftCollectionResponse,
-    GetNftCollections, GetNftCollectionsResponse, GetNftData, GetNftDataResponse, GetNftIcon,
-    GetNftIconResponse, GetNftResponse, GetNftThumbnail, GetNftThumbnailResponse, GetNfts,
-    GetNftsResponse, GetOption, GetOptionResponse, GetOptions, GetOptionsResponse,
-    GetPendingTransactions, GetPendingTransactionsResponse, GetSpendableCoinCount,
-    GetSpendableCoinCountResponse, GetSyncStatus, GetSyncStatusResponse, GetToken,
+    Amount, CatHolderRecord, CatIssuanceKind as ApiCatIssuanceKind, CheckAddress,
+    CheckAddressResponse, ClawbackRecord, CoinFilterMode as ApiCoinFilterMode, CoinRecord,
+    CoinSortMode as ApiCoinSortMode, CollectionRoyaltyIncomeRecord, DerivationRecord, DidRecord,
+    GetAllCats, GetAllCatsResponse, GetAreCoinsSpendable, GetAreCoinsSpendableResponse,
+    GetCatHolders, GetCatHoldersResponse, GetCats, GetCatsResponse, GetClawbacks,
+    GetClawbacksResponse, GetCoins, GetCoinsByIds, GetCoinsByIdsResponse, GetCoinsResponse,
+    GetDatabaseStats, GetDatabaseStatsResponse, GetDerivations, GetDerivationsResponse, GetDids,
+    GetDidsResponse, GetIssuedCats, GetIssuedCatsResponse, GetMinterDidIds,
+    GetMinterDidIdsResponse, GetNft, GetNftCollection, GetNftCollectionResponse, GetNftCollections,
+    GetNftCollectionsResponse, GetNftData, GetNftDataResponse, GetNftIcon, GetNftIconResponse,
+    GetNftResponse, GetNftThumbnail, GetNftThumbnailResponse, GetNfts, GetNftsResponse, GetOption,
+    GetOptionChain, GetOptionChainResponse, GetOptionResponse, GetOptions, GetOptionsResponse,
+    GetPendingTransactions, GetPendingTransactionsResponse, GetRoyaltyIncome,
+    GetRoyaltyIncomeResponse, GetSpendableCoinCount, GetSpendableCoinCountResponse,
+    GetSyncProgress, GetSyncProgressResponse, GetSyncStatus, GetSyncStatusResponse, GetToken,
     GetTokenResponse, GetTransaction, GetTransactionResponse, GetTransactions,
     GetTransactionsResponse, GetVersion, GetVersionResponse, IsAssetOwned, IsAssetOwnedResponse,
-    NftCollectionRecord, NftData, NftRecord, NftSortMode as ApiNftSortMode, NftSpecialUseType,
-    OptionRecord, OptionSortMode as ApiOptionSortMode, PendingTransactionRecord,
-    PerformDatabaseMaintenance, PerformDatabaseMaintenanceResponse, TokenRecord,
-    TransactionCoinRecord, TransactionRecord,
+    IssuedCatRecord, NftCollectionRecord, NftData, NftRecord, NftRoyaltyIncomeRecord,
+    NftSortMode as ApiNftSortMode, NftSpecialUseType, NftVerificationFilter, OptionChainExpiration,
+    OptionChainGroup, OptionChainRecord, OptionRecord, OptionSortMode as ApiOptionSortMode,
+    OptionStatus, PendingTransactionRecord, PerformDatabaseMaintenance,
+    PerformDatabaseMaintenanceResponse, RoyaltyIncomePeriodRecord, SyncPhase, SyncPhaseRecord,
+    TokenRecord, TransactionCoinRecord, TransactionRecord,
 };
 use sage_database::{
-    AssetFilter, CoinFilterMode, CoinSortMode, NftGroupSearch, NftRow, NftSortMode, OptionSortMode,
-    Transaction, TransactionCoin,
+    AssetFilter, CatIssuanceKind, CoinFilterMode, CoinSortMode, NftGroupSearch, NftRow,
+    NftSortMode, OptionSortMode, RoyaltyPayment, Transaction, TransactionCoin,
 };
 
+/// The number of coins looked up when tracing the holders of a CAT, unless otherwise specified.
+const DEFAULT_CAT_HOLDER_LIMIT: u32 = 500;
+
+/// Each coin takes up to two requests to the peer, so the trace is split into pages.
+const MAX_CAT_HOLDER_LIMIT: u32 = 5000;
+
 impl Sage {
     pub fn get_version(&self, _req: GetVersion) -> Result<GetVersionResponse> {
         Ok(GetVersionResponse {
@@ -117,6 +133,43 @@ impl Sage {
             checked_files: wallet.db.checked_files().await?.try_into().unwrap_or(0),
             total_files: wallet.db.total_files().await?.try_into().unwrap_or(0),
             database_size,
+            validated_height: wallet.db.validated_peak().await?.map(|block| block.height),
+        })
+    }
+
+    pub fn get_sync_progress(&self, _req: GetSyncProgress) -> Result<GetSyncProgressResponse> {
+        let wallet = self.wallet()?;
+
+        let phases: Vec<SyncPhaseRecord> = wallet
+            .progress
+            .phases()
+            .into_iter()
+            .map(|progress| SyncPhaseRecord {
+                phase: match progress.phase {
+                    sage_wallet::SyncPhase::PuzzleSubscriptions => SyncPhase::PuzzleSubscriptions,
+                    sage_wallet::SyncPhase::CoinSubscriptions => SyncPhase::CoinSubscriptions,
+                    sage_wallet::SyncPhase::PuzzleLookups => SyncPhase::PuzzleLookups,
+                    sage_wallet::SyncPhase::NftUris => SyncPhase::NftUris,
+                    sage_wallet::SyncPhase::CatInfo => SyncPhase::CatInfo,
+                    sage_wallet::SyncPhase::BlockTimes => SyncPhase::BlockTimes,
+                },
+                completed: progress.completed,
+                remaining: progress.remaining,
+                rate: progress.rate,
+                eta_seconds: progress.eta.map(|eta| eta.as_secs()),
+            })
+            .collect();
+
+        // Phases run concurrently, so the sync has caught up once the slowest one has.
+        let eta_seconds = phases
+            .iter()
+            .map(|phase| phase.eta_seconds)
+            .collect::<Option<Vec<_>>>()
+            .map(|etas| etas.into_iter().max().unwrap_or(0));
+
+        Ok(GetSyncProgressResponse {
+            phases,
+            eta_seconds,
         })
     }
 
@@ -318,6 +371,90 @@ impl Sage {
         Ok(GetCatsResponse { cats: records })
     }
 
+    pub async fn get_issued_cats(&self, _req: GetIssuedCats) -> Result<GetIssuedCatsResponse> {
+        let wallet = self.wallet()?;
+
+        let cats = wallet
+            .db
+            .issued_cats()
+            .await?
+            .into_iter()
+            .map(|cat| IssuedCatRecord {
+                asset_id: hex::encode(cat.asset_id),
+                name: cat.name,
+                ticker: cat.ticker,
+                tail: hex::encode(cat.tail_reveal),
+                issuance_kind: match cat.issuance_kind {
+                    CatIssuanceKind::SingleIssuance => ApiCatIssuanceKind::SingleIssuance,
+                    CatIssuanceKind::MultiIssuance => ApiCatIssuanceKind::MultiIssuance,
+                    CatIssuanceKind::Custom => ApiCatIssuanceKind::Custom,
+                },
+                total_issued: Amount::u64(cat.total_issued),
+                total_melted: Amount::u64(cat.total_melted),
+                created_timestamp: cat.created_timestamp,
+            })
+            .collect();
+
+        Ok(GetIssuedCatsResponse { cats })
+    }
+
+    pub async fn get_cat_holders(&self, req: GetCatHolders) -> Result<GetCatHoldersResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = parse_asset_id(req.asset_id)?;
+
+        if wallet.db.issued_cat(asset_id).await?.is_none() {
+            return Err(Error::NotIssuedCat(asset_id));
+        }
+
+        let limit = req.limit.unwrap_or(DEFAULT_CAT_HOLDER_LIMIT);
+
+        if limit == 0 || limit > MAX_CAT_HOLDER_LIMIT {
+            return Err(Error::InvalidLimit(limit));
+        }
+
+        let coin_ids = match req.coin_ids {
+            Some(coin_ids) => parse_coin_ids(coin_ids)?,
+            None => wallet.db.issued_cat_coin_ids(asset_id).await?,
+        };
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let trace = wallet
+            .cat_holders(&peer, asset_id, coin_ids, limit as usize)
+            .await?;
+
+        let holders = trace
+            .holders
+            .into_iter()
+            .map(|holder| {
+                Ok(CatHolderRecord {
+                    address: Address::new(holder.p2_puzzle_hash, self.network().prefix())
+                        .encode()?,
+                    amount: Amount::u64(holder.amount),
+                    coin_ids: holder
+                        .cats
+                        .into_iter()
+                        .map(|cat| hex::encode(cat.coin.coin_id()))
+                        .collect(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(GetCatHoldersResponse {
+            holders,
+            remaining_coin_ids: trace
+                .remaining_coin_ids
+                .into_iter()
+                .map(hex::encode)
+                .collect(),
+        })
+    }
+
     pub async fn get_token(&self, req: GetToken) -> Result<GetTokenResponse> {
         let wallet = self.wallet()?;
 
@@ -486,6 +623,76 @@ impl Sage {
         })
     }
 
+    pub async fn get_option_chain(&self, req: GetOptionChain) -> Result<GetOptionChainResponse> {
+        let wallet = self.wallet()?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch")
+            .as_secs();
+
+        let mut groups: Vec<OptionChainGroup> = Vec::new();
+        let mut group_key = None;
+
+        // Rows are ordered by underlying asset, strike asset, and expiration
+        for row in wallet.db.option_chain().await? {
+            if !req.include_hidden && !row.is_visible {
+                continue;
+            }
+
+            // The underlying can only be spent by exercising before expiration, or reclaiming after
+            let status = match row.underlying_spent_height {
+                None if timestamp < row.expiration_seconds => OptionStatus::Live,
+                None => OptionStatus::Expired,
+                Some(_) => match row.underlying_spent_timestamp {
+                    Some(spent_timestamp) if spent_timestamp < row.expiration_seconds => {
+                        OptionStatus::Exercised
+                    }
+                    Some(_) => OptionStatus::Reclaimed,
+                    None => OptionStatus::Unknown,
+                },
+            };
+
+            let record = OptionChainRecord {
+                launcher_id: Address::new(row.launcher_id, "option".to_string()).encode()?,
+                name: row.name,
+                underlying_amount: Amount::u64(row.underlying_amount),
+                strike_amount: Amount::u64(row.strike_amount),
+                status,
+                is_owned: row.is_owned,
+                is_creator: row.is_creator,
+            };
+
+            let key = (row.underlying_asset.hash, row.strike_asset.hash);
+
+            if group_key != Some(key) {
+                group_key = Some(key);
+
+                groups.push(OptionChainGroup {
+                    underlying_asset: self.encode_asset(row.underlying_asset)?,
+                    strike_asset: self.encode_asset(row.strike_asset)?,
+                    expirations: Vec::new(),
+                });
+            }
+
+            let group = groups.last_mut().expect("group was just inserted");
+
+            match group.expirations.last_mut() {
+                Some(expiration) if expiration.expiration_seconds == row.expiration_seconds => {
+                    expiration.options.push(record);
+                }
+                _ => {
+                    group.expirations.push(OptionChainExpiration {
+                        expiration_seconds: row.expiration_seconds,
+                        options: vec![record],
+                    });
+                }
+            }
+        }
+
+        Ok(GetOptionChainResponse { groups })
+    }
+
     pub async fn get_pending_transactions(
         &self,
         _req: GetPendingTransactions,
@@ -509,14 +716,53 @@ impl Sage {
         Ok(GetPendingTransactionsResponse { transactions })
     }
 
+    pub async fn get_clawbacks(&self, _req: GetClawbacks) -> Result<GetClawbacksResponse> {
+        let wallet = self.wallet()?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch")
+            .as_secs();
+
+        let mut outgoing = Vec::new();
+        let mut incoming = Vec::new();
+
+        for row in wallet.db.clawback_coins().await? {
+            let record = ClawbackRecord {
+                coin_id: hex::encode(row.coin.coin_id()),
+                asset: self.encode_asset(row.asset)?,
+                amount: Amount::u64(row.coin.amount),
+                sender_address: Address::new(row.sender_puzzle_hash, self.network().prefix())
+                    .encode()?,
+                receiver_address: Address::new(row.receiver_puzzle_hash, self.network().prefix())
+                    .encode()?,
+                expiration_timestamp: row.expiration_seconds,
+                seconds_remaining: row.expiration_seconds.saturating_sub(timestamp),
+                transaction_id: row.mempool_item_hash.map(hex::encode),
+                created_height: row.created_height,
+                created_timestamp: row.created_timestamp,
+            };
+
+            // A clawback sent to ourselves is both, but can only be spent once.
+            if row.is_incoming {
+                incoming.push(record);
+            } else if row.is_outgoing {
+                outgoing.push(record);
+            }
+        }
+
+        Ok(GetClawbacksResponse { outgoing, incoming })
+    }
+
     pub async fn get_transaction(&self, req: GetTransaction) -> Result<GetTransactionResponse> {
         let wallet = self.wallet()?;
 
         let transaction = wallet.db.transaction(req.height).await?;
 
-        let transaction = transaction
-            .map(|row| self.transaction_record(row))
-            .transpose()?;
+        let transaction = match transaction {
+            Some(row) => Some(self.transaction_record(row).await?),
+            None => None,
+        };
 
         Ok(GetTransactionResponse { transaction })
     }
@@ -532,7 +778,7 @@ impl Sage {
             .await?;
 
         for row in transaction_records {
-            let record = self.transaction_record(row)?;
+            let record = self.transaction_record(row).await?;
             transactions.push(record);
         }
 
@@ -542,6 +788,94 @@ impl Sage {
         })
     }
 
+    pub async fn get_royalty_income(
+        &self,
+        req: GetRoyaltyIncome,
+    ) -> Result<GetRoyaltyIncomeResponse> {
+        let wallet = self.wallet()?;
+
+        let mut total = RoyaltyIncome::default();
+        let mut collections = IndexMap::<Option<Bytes32>, (Option<String>, RoyaltyIncome)>::new();
+        let mut nfts = IndexMap::<Bytes32, (RoyaltyPayment, RoyaltyIncome)>::new();
+        let mut periods = IndexMap::<u64, RoyaltyIncome>::new();
+
+        for payment in wallet.db.royalty_payments().await? {
+            // Payments can't be placed in a time range until the block timestamp has been synced.
+            let is_in_range = match payment.created_timestamp {
+                Some(timestamp) => {
+                    req.start_timestamp.is_none_or(|start| timestamp >= start)
+                        && req.end_timestamp.is_none_or(|end| timestamp < end)
+                }
+                None => req.start_timestamp.is_none() && req.end_timestamp.is_none(),
+            };
+
+            if !is_in_range {
+                continue;
+            }
+
+            total.add(&payment);
+
+            collections
+                .entry(payment.collection_id)
+                .or_insert_with(|| (payment.collection_name.clone(), RoyaltyIncome::default()))
+                .1
+                .add(&payment);
+
+            if let Some(timestamp) = payment.created_timestamp {
+                periods
+                    .entry(royalty_period_start(timestamp, req.interval))
+                    .or_default()
+                    .add(&payment);
+            }
+
+            nfts.entry(payment.launcher_id)
+                .or_insert_with(|| (payment.clone(), RoyaltyIncome::default()))
+                .1
+                .add(&payment);
+        }
+
+        periods.sort_keys();
+
+        Ok(GetRoyaltyIncomeResponse {
+            total: self.royalty_income_records(total)?,
+            collections: collections
+                .into_iter()
+                .map(|(collection_id, (name, income))| {
+                    Ok(CollectionRoyaltyIncomeRecord {
+                        collection_id: collection_id
+                            .map(|id| Address::new(id, "col".to_string()).encode())
+                            .transpose()?,
+                        name,
+                        income: self.royalty_income_records(income)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            nfts: nfts
+                .into_values()
+                .map(|(payment, income)| {
+                    Ok(NftRoyaltyIncomeRecord {
+                        nft_id: Address::new(payment.launcher_id, "nft".to_string()).encode()?,
+                        name: payment.nft_name,
+                        collection_id: payment
+                            .collection_id
+                            .map(|id| Address::new(id, "col".to_string()).encode())
+                            .transpose()?,
+                        income: self.royalty_income_records(income)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            periods: periods
+                .into_iter()
+                .map(|(start_timestamp, income)| {
+                    Ok(RoyaltyIncomePeriodRecord {
+                        start_timestamp,
+                        income: self.royalty_income_records(income)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
     pub async fn get_nft_collections(
         &self,
         req: GetNftCollections,
@@ -563,6 +897,7 @@ impl Sage {
                     name: row.name,
                     icon: row.icon_url,
                     visible: row.is_visible,
+                    verified: row.is_verified,
                 })
             })
             .collect::<Result<Vec<_>>>()?;
@@ -596,6 +931,7 @@ impl Sage {
                 did_id: Address::new(collection.minter_hash, "did:chia:".to_string()).encode()?,
                 metadata_collection_id: collection.uuid,
                 visible: collection.is_visible,
+                verified: collection.is_verified,
                 name: collection.name,
                 icon: collection.icon_url,
             }
@@ -605,6 +941,7 @@ impl Sage {
                 did_id: "Miscellaneous".to_string(),
                 metadata_collection_id: "None".to_string(),
                 visible: true,
+                verified: false,
                 name: Some("Uncategorized".to_string()),
                 icon: None,
             }
@@ -657,6 +994,12 @@ impl Sage {
             ApiNftSortMode::Name => NftSortMode::Name,
         };
 
+        let verified = match req.verification {
+            NftVerificationFilter::All => None,
+            NftVerificationFilter::Verified => Some(true),
+            NftVerificationFilter::Unverified => Some(false),
+        };
+
         let (nfts, total) = wallet
             .db
             .owned_nfts(
@@ -664,6 +1007,7 @@ impl Sage {
                 group,
                 sort_mode,
                 req.include_hidden,
+                verified,
                 req.limit,
                 req.offset,
             )
@@ -863,7 +1207,19 @@ impl Sage {
         })
     }
 
-    fn transaction_coin(&self, transaction_coin: TransactionCoin) -> Result<TransactionCoinRecord> {
+    async fn transaction_coin(
+        &self,
+        transaction_coin: TransactionCoin,
+    ) -> Result<TransactionCoinRecord> {
+        let wallet = self.wallet()?;
+
+        let royalty_nft_id = wallet
+            .db
+            .royalty_payment_nft(transaction_coin.coin.coin_id())
+            .await?
+            .map(|launcher_id| Address::new(launcher_id, "nft".to_string()).encode())
+            .transpose()?;
+
         Ok(TransactionCoinRecord {
             coin_id: hex::encode(transaction_coin.coin.coin_id()),
             address: transaction_coin
@@ -875,18 +1231,19 @@ impl Sage {
             address_kind: address_kind(transaction_coin.p2_puzzle_hash),
             amount: Amount::u64(transaction_coin.coin.amount),
             asset: self.encode_asset(transaction_coin.asset)?,
+            royalty_nft_id,
         })
     }
 
-    fn transaction_record(&self, transaction: Transaction) -> Result<TransactionRecord> {
+    async fn transaction_record(&self, transaction: Transaction) -> Result<TransactionRecord> {
         let mut spent = Vec::new();
         let mut created = Vec::new();
 
         for coin in transaction.created {
-            created.push(self.transaction_coin(coin)?);
+            created.push(self.transaction_coin(coin).await?);
         }
         for coin in transaction.spent {
-            spent.push(self.transaction_coin(coin)?);
+            spent.push(self.transaction_coin(coin).await?);
         }
 
         Ok(TransactionRecord {
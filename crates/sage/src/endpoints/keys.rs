@@ -22,6 +22,7 @@ use sage_api::{
 };
 use sage_config::Wallet;
 use sage_database::{Database, Derivation};
+use sage_wallet::SyncCommand;
 use sqlx::query;
 
 use crate::{Error, Result, Sage};
@@ -49,6 +50,17 @@ impl Sage {
             self.switch_wallet().await?;
         }
 
+        // The wallet can't keep syncing in the background while its data is being deleted.
+        let background = self.background_wallets.remove(&req.fingerprint).is_some();
+
+        if background {
+            self.command_sender
+                .send(SyncCommand::UnloadWallet {
+                    fingerprint: req.fingerprint,
+                })
+                .await?;
+        }
+
         let pool = self.connect_to_database(req.fingerprint).await?;
 
         query!(
@@ -102,6 +114,8 @@ impl Sage {
             self.config.global.fingerprint = Some(req.fingerprint);
             self.save_config()?;
             self.switch_wallet().await?;
+        } else if background {
+            self.switch_wallet().await?;
         }
 
         Ok(ResyncResponse {})
@@ -278,6 +292,14 @@ impl Sage {
             self.config.global.fingerprint = None;
         }
 
+        if self.background_wallets.remove(&req.fingerprint).is_some() {
+            self.command_sender
+                .try_send(SyncCommand::UnloadWallet {
+                    fingerprint: req.fingerprint,
+                })
+                .ok();
+        }
+
         self.save_keychain()?;
         self.save_config()?;
 
@@ -345,6 +367,7 @@ impl Sage {
                 has_secrets: self.keychain.has_secret_key(fingerprint),
                 network_id,
                 emoji: wallet_config.emoji,
+                background_sync: wallet_config.background_sync,
             }),
         })
     }
@@ -379,6 +402,7 @@ impl Sage {
                 has_secrets: self.keychain.has_secret_key(wallet.fingerprint),
                 network_id: wallet.network.clone().unwrap_or_else(|| self.network_id()),
                 emoji: wallet.emoji.clone(),
+                background_sync: wallet.background_sync,
             });
         }
 
@@ -6,19 +6,30 @@ use chia_wallet_sdk::{
 use itertools::Itertools;
 use sage_api::{
     Amount, CancelOffer, CancelOfferResponse, CancelOffers, CancelOffersResponse, CombineOffers,
-    CombineOffersResponse, DeleteOffer, DeleteOfferResponse, GetOffer, GetOfferResponse, GetOffers,
-    GetOffersForAsset, GetOffersForAssetResponse, GetOffersResponse, ImportOffer,
-    ImportOfferResponse, MakeOffer, MakeOfferResponse, NftRoyalty, OfferAmount, OfferAsset,
-    OfferRecord, OfferRecordStatus, OfferSummary, OptionAssets, TakeOffer, TakeOfferResponse,
-    ViewOffer, ViewOfferResponse,
+    CombineOffersResponse, CreateStandingOffer, CreateStandingOfferResponse, DeleteOffer,
+    DeleteOfferResponse, DeleteStandingOffer, DeleteStandingOfferResponse, ExchangeOfferRecord,
+    FetchExchangeOffer, FetchExchangeOfferResponse, GetOffer, GetOfferResponse, GetOffers,
+    GetOffersForAsset, GetOffersForAssetResponse, GetOffersResponse, GetStandingOfferFills,
+    GetStandingOfferFillsResponse, GetStandingOffers, GetStandingOffersResponse, ImportOffer,
+    ImportOfferResponse, LadderOffer, MakeOffer, MakeOfferLadder, MakeOfferLadderResponse,
+    MakeOfferResponse, NftRoyalty, OfferAmount, OfferAsset, OfferRecord, OfferRecordStatus,
+    OfferSummary, OptionAssets, PostOffer, PostOfferResponse, SearchExchangeOffers,
+    SearchExchangeOffersResponse, SetStandingOfferActive, SetStandingOfferActiveResponse,
+    StandingOfferFillRecord, StandingOfferRecord, TakeOffer, TakeOfferResponse, ViewOffer,
+    ViewOfferResponse,
+};
+use sage_assets::{OfferExchange, fetch_uris_with_hash};
+use sage_database::{
+    AssetKind, NewStandingOffer, OfferRow, OfferStatus, OfferedAsset, StandingOffer,
 };
-use sage_assets::fetch_uris_with_hash;
-use sage_database::{AssetKind, OfferRow, OfferStatus, OfferedAsset};
 use sage_wallet::{
     Offered, Requested, RequestedCat, SyncCommand, Transaction, Wallet, WalletError,
     aggregate_offers, insert_transaction, sort_offer,
 };
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio::time::timeout;
 use tracing::debug;
 
@@ -28,6 +39,9 @@ use crate::{
     parse_offer_id, parse_option_id,
 };
 
+/// The maximum number of offers which can be created in a single ladder.
+const MAX_LADDER_RUNGS: usize = 100;
+
 #[derive(Debug, Clone)]
 struct AssetToOffer {
     offer_id: Bytes32,
@@ -175,7 +189,7 @@ impl Sage {
         let offer = wallet
             .sign_transaction(
                 unsigned,
-                &AggSigConstants::new(self.network().agg_sig_me()),
+                &self.network().agg_sig_constants(),
                 master_sk,
                 false,
             )
@@ -183,6 +197,18 @@ impl Sage {
 
         let encoded_offer = encode_offer(&offer)?;
 
+        // The offer is posted before it's imported, so that a failed post doesn't leave
+        // an offer behind which locks up coins without ever being seen by anyone.
+        let exchange_offer_id = if let Some(provider) = req.post_to {
+            Some(
+                self.offer_exchange(provider)?
+                    .post_offer(&encoded_offer)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
         if req.auto_import {
             self.import_offer(ImportOffer {
                 offer: encoded_offer.clone(),
@@ -193,6 +219,180 @@ impl Sage {
         Ok(MakeOfferResponse {
             offer: encoded_offer,
             offer_id: hex::encode(sort_offer(offer).name()),
+            exchange_offer_id,
+        })
+    }
+
+    pub async fn make_offer_ladder(&self, req: MakeOfferLadder) -> Result<MakeOfferLadderResponse> {
+        let wallet = self.wallet()?;
+
+        let amount_per_rung = parse_amount(req.amount_per_rung)?;
+        let min_price = parse_amount(req.min_price)?;
+        let max_price = parse_amount(req.max_price)?;
+        let price_step = parse_amount(req.price_step)?;
+        let fee = parse_amount(req.fee)?;
+
+        if amount_per_rung == 0 {
+            return Err(Error::InvalidAmount(amount_per_rung.to_string()));
+        }
+
+        if min_price == 0 || min_price > max_price {
+            return Err(Error::InvalidAmount(min_price.to_string()));
+        }
+
+        if price_step == 0 && min_price != max_price {
+            return Err(Error::InvalidAmount(price_step.to_string()));
+        }
+
+        let mut prices = vec![min_price];
+
+        while let Some(price) = prices
+            .last()
+            .and_then(|price| price.checked_add(price_step))
+            .filter(|price| price_step > 0 && *price <= max_price)
+        {
+            if prices.len() == MAX_LADDER_RUNGS {
+                return Err(Error::InvalidAmount(format!(
+                    "A ladder can have at most {MAX_LADDER_RUNGS} rungs"
+                )));
+            }
+
+            prices.push(price);
+        }
+
+        let offered_asset_id = req.offered_asset_id.map(parse_asset_id).transpose()?;
+        let requested_asset_id = req.requested_asset_id.map(parse_asset_id).transpose()?;
+
+        if offered_asset_id == requested_asset_id {
+            return Err(Error::InvalidAssetId(
+                offered_asset_id.map_or_else(|| "XCH".to_string(), hex::encode),
+            ));
+        }
+
+        let requested_hidden_puzzle_hash = if let Some(asset_id) = requested_asset_id {
+            wallet.fetch_offer_cat_hidden_puzzle_hash(asset_id).await?
+        } else {
+            None
+        };
+
+        // The fee budget is shared evenly between the offers and the split transaction,
+        // with any remainder going towards the split.
+        let offer_fee = fee / (prices.len() as u64 + 1);
+        let split_fee = fee - offer_fee * prices.len() as u64;
+
+        // Offers of XCH pay their fee out of the same coin.
+        let coin_amount = if offered_asset_id.is_none() {
+            amount_per_rung + offer_fee
+        } else {
+            amount_per_rung
+        };
+
+        let ladder = wallet
+            .offer_ladder_coins(offered_asset_id, coin_amount, prices.len(), split_fee)
+            .await?;
+
+        let (_mnemonic, Some(master_sk)) =
+            self.keychain.extract_secrets(wallet.fingerprint, b"")?
+        else {
+            return Err(Error::NoSigningKey);
+        };
+
+        // Every rung is built and signed before the split is submitted, so that a failure
+        // part of the way through doesn't leave behind split coins without any offers.
+        let mut signed_offers = Vec::with_capacity(prices.len());
+        let mut used_coin_ids: HashSet<Bytes32> = ladder
+            .coin_spends
+            .iter()
+            .map(|coin_spend| coin_spend.coin.coin_id())
+            .collect();
+
+        for (&coin, &price) in ladder.coins.iter().zip(&prices) {
+            let mut offered = Offered {
+                fee: offer_fee,
+                excluded_coin_ids: used_coin_ids.clone(),
+                ..Default::default()
+            };
+
+            coin.select(&mut offered);
+
+            if let Some(asset_id) = offered_asset_id {
+                offered.cats.insert(asset_id, amount_per_rung);
+            } else {
+                offered.xch = amount_per_rung;
+            }
+
+            let mut requested = Requested::default();
+
+            if let Some(asset_id) = requested_asset_id {
+                requested.cats.insert(
+                    asset_id,
+                    RequestedCat {
+                        amount: price,
+                        hidden_puzzle_hash: requested_hidden_puzzle_hash,
+                    },
+                );
+            } else {
+                requested.xch = price;
+            }
+
+            let unsigned = wallet
+                .make_offer(offered, requested, req.expires_at_second)
+                .await?;
+
+            // Nothing is submitted or imported yet, so the coins spent by the split and by earlier
+            // rungs have to be excluded by hand.
+            used_coin_ids.extend(
+                unsigned
+                    .coin_spends
+                    .iter()
+                    .map(|coin_spend| coin_spend.coin.coin_id()),
+            );
+
+            let offer = wallet
+                .sign_transaction(
+                    unsigned,
+                    &self.network().agg_sig_constants(),
+                    master_sk.clone(),
+                    false,
+                )
+                .await?;
+
+            signed_offers.push((encode_offer(&offer)?, price));
+        }
+
+        let split_transaction_id = if ladder.coin_spends.is_empty() {
+            None
+        } else {
+            let spend_bundle = self.sign(ladder.coin_spends, false).await?;
+            let transaction_id = spend_bundle.name();
+            self.submit(spend_bundle).await?;
+            Some(hex::encode(transaction_id))
+        };
+
+        let mut offers = Vec::with_capacity(signed_offers.len());
+
+        for (encoded_offer, price) in signed_offers {
+            let ImportOfferResponse { offer_id } = self
+                .import_offer(ImportOffer {
+                    offer: encoded_offer.clone(),
+                })
+                .await?;
+
+            offers.push(LadderOffer {
+                offer: encoded_offer,
+                offer_id,
+                price: Amount::u64(price),
+            });
+        }
+
+        Ok(MakeOfferLadderResponse {
+            offers,
+            coin_ids: ladder
+                .coins
+                .iter()
+                .map(|coin| hex::encode(coin.coin_id()))
+                .collect(),
+            split_transaction_id,
         })
     }
 
@@ -213,7 +413,7 @@ impl Sage {
         let spend_bundle = wallet
             .sign_transaction(
                 unsigned,
-                &AggSigConstants::new(self.network().agg_sig_me()),
+                &self.network().agg_sig_constants(),
                 master_sk,
                 true,
             )
@@ -244,6 +444,7 @@ impl Sage {
 
             self.command_sender
                 .send(SyncCommand::SubscribeCoins {
+                    fingerprint: wallet.fingerprint,
                     coin_ids: subscriptions,
                 })
                 .await?;
@@ -316,7 +517,7 @@ impl Sage {
             });
         }
 
-        let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let mintgarden_url = self.network().services().mintgarden_assets;
 
         for nft in offer.offered_coins().nfts.values() {
             let _info = if let Ok(metadata) = ctx.extract::<NftMetadata>(nft.info.metadata.ptr()) {
@@ -325,7 +526,11 @@ impl Sage {
                 if let Some(hash) = metadata.data_hash
                     && let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.data_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            metadata.data_uris.clone(),
+                            hash,
+                            mintgarden_url.as_deref(),
+                        ),
                     )
                     .await
                 {
@@ -335,7 +540,11 @@ impl Sage {
                 if let Some(hash) = metadata.metadata_hash
                     && let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.metadata_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            metadata.metadata_uris.clone(),
+                            hash,
+                            mintgarden_url.as_deref(),
+                        ),
                     )
                     .await
                 {
@@ -407,7 +616,11 @@ impl Sage {
                 if let Some(hash) = metadata.data_hash
                     && let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.data_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            metadata.data_uris.clone(),
+                            hash,
+                            mintgarden_url.as_deref(),
+                        ),
                     )
                     .await
                 {
@@ -417,7 +630,11 @@ impl Sage {
                 if let Some(hash) = metadata.metadata_hash
                     && let Ok(Some(data)) = timeout(
                         Duration::from_secs(10),
-                        fetch_uris_with_hash(metadata.metadata_uris.clone(), hash, testnet),
+                        fetch_uris_with_hash(
+                            metadata.metadata_uris.clone(),
+                            hash,
+                            mintgarden_url.as_deref(),
+                        ),
                     )
                     .await
                 {
@@ -737,4 +954,248 @@ impl Sage {
 
         self.transact(coin_spends, req.auto_submit).await
     }
+
+    pub async fn create_standing_offer(
+        &self,
+        req: CreateStandingOffer,
+    ) -> Result<CreateStandingOfferResponse> {
+        let wallet = self.wallet()?;
+
+        let offered_amount = parse_amount(req.offered_amount)?;
+        let requested_amount = parse_amount(req.requested_amount)?;
+        let fee = parse_amount(req.fee)?;
+        let max_spend = req.max_spend.map(parse_amount).transpose()?;
+
+        if offered_amount == 0 {
+            return Err(Error::InvalidAmount(offered_amount.to_string()));
+        }
+
+        if requested_amount == 0 {
+            return Err(Error::InvalidAmount(requested_amount.to_string()));
+        }
+
+        let offered_asset_id = self.standing_offer_asset_id(req.offered_asset_id).await?;
+        let requested_asset_id = self.standing_offer_asset_id(req.requested_asset_id).await?;
+
+        if offered_asset_id == requested_asset_id {
+            return Err(Error::InvalidAssetId(hex::encode(offered_asset_id)));
+        }
+
+        let standing_offer_id = wallet
+            .db
+            .insert_standing_offer(NewStandingOffer {
+                offered_asset_id,
+                offered_amount,
+                requested_asset_id,
+                requested_amount,
+                fee,
+                expiration_seconds: req.expiration_seconds,
+                max_fills: req.max_fills,
+                max_spend,
+            })
+            .await?;
+
+        self.configure_wallet(wallet.fingerprint).await?;
+
+        Ok(CreateStandingOfferResponse { standing_offer_id })
+    }
+
+    pub async fn get_standing_offers(
+        &self,
+        _req: GetStandingOffers,
+    ) -> Result<GetStandingOffersResponse> {
+        let wallet = self.wallet()?;
+
+        let mut standing_offers = Vec::new();
+
+        for standing_offer in wallet.db.standing_offers().await? {
+            standing_offers.push(self.standing_offer_record(&wallet, standing_offer).await?);
+        }
+
+        Ok(GetStandingOffersResponse { standing_offers })
+    }
+
+    pub async fn set_standing_offer_active(
+        &self,
+        req: SetStandingOfferActive,
+    ) -> Result<SetStandingOfferActiveResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet
+            .db
+            .standing_offer(req.standing_offer_id)
+            .await?
+            .is_none()
+        {
+            return Err(Error::MissingStandingOffer(req.standing_offer_id));
+        }
+
+        wallet
+            .db
+            .set_standing_offer_active(req.standing_offer_id, req.is_active)
+            .await?;
+
+        self.configure_wallet(wallet.fingerprint).await?;
+
+        Ok(SetStandingOfferActiveResponse {})
+    }
+
+    pub async fn delete_standing_offer(
+        &self,
+        req: DeleteStandingOffer,
+    ) -> Result<DeleteStandingOfferResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet
+            .db
+            .standing_offer(req.standing_offer_id)
+            .await?
+            .is_none()
+        {
+            return Err(Error::MissingStandingOffer(req.standing_offer_id));
+        }
+
+        wallet
+            .db
+            .delete_standing_offer(req.standing_offer_id)
+            .await?;
+
+        self.configure_wallet(wallet.fingerprint).await?;
+
+        Ok(DeleteStandingOfferResponse {})
+    }
+
+    pub async fn get_standing_offer_fills(
+        &self,
+        req: GetStandingOfferFills,
+    ) -> Result<GetStandingOfferFillsResponse> {
+        let wallet = self.wallet()?;
+
+        let standing_offers = wallet.db.standing_offers().await?;
+        let mut fills = Vec::new();
+
+        for history in wallet
+            .db
+            .standing_offer_history(req.standing_offer_id)
+            .await?
+        {
+            if history.status != OfferStatus::Completed {
+                continue;
+            }
+
+            let Some(standing_offer) = standing_offers
+                .iter()
+                .find(|standing_offer| standing_offer.id == history.standing_offer_id)
+            else {
+                continue;
+            };
+
+            fills.push(StandingOfferFillRecord {
+                standing_offer_id: history.standing_offer_id,
+                offer_id: hex::encode(history.offer_id),
+                offered_amount: Amount::u64(standing_offer.offered_amount),
+                requested_amount: Amount::u64(standing_offer.requested_amount),
+                creation_timestamp: history.created_timestamp,
+                fill_timestamp: history.finished_timestamp,
+            });
+        }
+
+        Ok(GetStandingOfferFillsResponse { fills })
+    }
+
+    async fn standing_offer_asset_id(&self, asset_id: Option<String>) -> Result<Bytes32> {
+        let Some(asset_id) = asset_id else {
+            return Ok(Bytes32::default());
+        };
+
+        let asset_id = parse_asset_id(asset_id)?;
+
+        self.cache_cat(asset_id, None).await?;
+
+        Ok(asset_id)
+    }
+
+    async fn standing_offer_record(
+        &self,
+        wallet: &Wallet,
+        standing_offer: StandingOffer,
+    ) -> Result<StandingOfferRecord> {
+        let Some(offered_asset) = wallet.db.asset(standing_offer.offered_asset_id).await? else {
+            return Err(Error::MissingCat(standing_offer.offered_asset_id));
+        };
+
+        let Some(requested_asset) = wallet.db.asset(standing_offer.requested_asset_id).await?
+        else {
+            return Err(Error::MissingCat(standing_offer.requested_asset_id));
+        };
+
+        Ok(StandingOfferRecord {
+            standing_offer_id: standing_offer.id,
+            offered_asset: self.encode_asset(offered_asset)?,
+            offered_amount: Amount::u64(standing_offer.offered_amount),
+            requested_asset: self.encode_asset(requested_asset)?,
+            requested_amount: Amount::u64(standing_offer.requested_amount),
+            fee: Amount::u64(standing_offer.fee),
+            expiration_seconds: standing_offer.expiration_seconds,
+            max_fills: standing_offer.max_fills,
+            max_spend: standing_offer.max_spend.map(Amount::u64),
+            is_active: standing_offer.is_active,
+            fill_count: standing_offer.fill_count,
+            total_spent: Amount::u64(standing_offer.total_spent()),
+            current_offer_id: standing_offer.current_offer_id.map(hex::encode),
+            creation_timestamp: standing_offer.created_timestamp,
+        })
+    }
+
+    pub async fn post_offer(&self, req: PostOffer) -> Result<PostOfferResponse> {
+        // Make sure the offer is valid before sending it anywhere.
+        decode_offer(&req.offer)?;
+
+        let id = self
+            .offer_exchange(req.exchange)?
+            .post_offer(&req.offer)
+            .await?;
+
+        Ok(PostOfferResponse { id })
+    }
+
+    pub async fn search_exchange_offers(
+        &self,
+        req: SearchExchangeOffers,
+    ) -> Result<SearchExchangeOffersResponse> {
+        let offered = req.offered_asset_id.map(parse_asset_id).transpose()?;
+        let requested = req.requested_asset_id.map(parse_asset_id).transpose()?;
+
+        let offers = self
+            .offer_exchange(req.exchange)?
+            .search_offers(offered, requested)
+            .await?;
+
+        Ok(SearchExchangeOffersResponse {
+            offers: offers
+                .into_iter()
+                .map(|offer| ExchangeOfferRecord {
+                    id: offer.id,
+                    offer: offer.offer,
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn fetch_exchange_offer(
+        &self,
+        req: FetchExchangeOffer,
+    ) -> Result<FetchExchangeOfferResponse> {
+        let offer = self
+            .offer_exchange(req.exchange)?
+            .fetch_offer(&req.id)
+            .await?;
+
+        Ok(FetchExchangeOfferResponse {
+            offer: ExchangeOfferRecord {
+                id: offer.id,
+                offer: offer.offer,
+            },
+        })
+    }
 }
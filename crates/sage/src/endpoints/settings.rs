@@ -1,18 +1,27 @@
-use std::time::Duration;
+use std::{net::SocketAddr, str::FromStr, time::Duration};
 
+use chia_wallet_sdk::prelude::*;
 use itertools::Itertools;
 use sage_api::{
-    AddPeer, AddPeerResponse, GetNetwork, GetNetworkResponse, GetNetworks, GetNetworksResponse,
-    GetPeers, GetPeersResponse, NetworkKind, PeerRecord, RemovePeer, RemovePeerResponse,
-    SetChangeAddress, SetChangeAddressResponse, SetDeltaSync, SetDeltaSyncOverride,
+    AddNetwork, AddNetworkResponse, AddPeer, AddPeerResponse, AddressBookRecord, Amount,
+    ClawbackPolicy, CoinManagement, CoinPolicy, CombinePolicy, GetClawbackPolicy,
+    GetClawbackPolicyResponse, GetCoinManagement, GetCoinManagementResponse, GetNetwork,
+    GetNetworkResponse, GetNetworks, GetNetworksResponse, GetPeerAddressBook,
+    GetPeerAddressBookResponse, GetPeers, GetPeersResponse, GetReputationSources,
+    GetReputationSourcesResponse, NetworkKind, PeerRecord, PeerSource, RemovePeer,
+    RemovePeerResponse, ReputationSource, SetBackgroundSync, SetBackgroundSyncResponse,
+    SetChangeAddress, SetChangeAddressResponse, SetClawbackPolicy, SetClawbackPolicyResponse,
+    SetCoinManagement, SetCoinManagementResponse, SetDeltaSync, SetDeltaSyncOverride,
     SetDeltaSyncOverrideResponse, SetDeltaSyncResponse, SetDiscoverPeers, SetDiscoverPeersResponse,
-    SetNetwork, SetNetworkOverride, SetNetworkOverrideResponse, SetNetworkResponse, SetTargetPeers,
-    SetTargetPeersResponse,
+    SetDnsDiscovery, SetDnsDiscoveryResponse, SetHeaderValidation, SetHeaderValidationResponse,
+    SetNetwork, SetNetworkOverride, SetNetworkOverrideResponse, SetNetworkResponse,
+    SetParanoidSync, SetParanoidSyncResponse, SetProxy, SetProxyResponse, SetReputationSources,
+    SetReputationSourcesResponse, SetTargetPeers, SetTargetPeersResponse, SplitPolicy,
 };
-use sage_config::{MAINNET, TESTNET11};
+use sage_config::{MAINNET, Network, TESTNET11};
 use sage_wallet::SyncCommand;
 
-use crate::{Error, Result, Sage};
+use crate::{Error, Result, Sage, parse_amount, parse_asset_id};
 
 impl Sage {
     pub async fn get_peers(&self, _req: GetPeers) -> Result<GetPeersResponse> {
@@ -36,6 +45,35 @@ impl Sage {
         })
     }
 
+    pub async fn get_peer_address_book(
+        &self,
+        _req: GetPeerAddressBook,
+    ) -> Result<GetPeerAddressBookResponse> {
+        let peer_state = self.peer_state.lock().await;
+
+        Ok(GetPeerAddressBookResponse {
+            peers: peer_state
+                .address_book()
+                .entries()
+                .into_iter()
+                .map(|entry| AddressBookRecord {
+                    ip_addr: entry.ip.to_string(),
+                    port: entry.port,
+                    source: match entry.source {
+                        sage_wallet::PeerSource::Dns => PeerSource::Dns,
+                        sage_wallet::PeerSource::Introducer => PeerSource::Introducer,
+                        sage_wallet::PeerSource::PeerExchange => PeerSource::PeerExchange,
+                        sage_wallet::PeerSource::User => PeerSource::User,
+                    },
+                    last_seen: entry.last_seen,
+                    last_success: entry.last_success,
+                    failures: entry.failures,
+                    peak_height: entry.peak_height,
+                })
+                .collect(),
+        })
+    }
+
     pub async fn remove_peer(&self, req: RemovePeer) -> Result<RemovePeerResponse> {
         let mut peer_state = self.peer_state.lock().await;
 
@@ -76,6 +114,66 @@ impl Sage {
         Ok(SetDiscoverPeersResponse {})
     }
 
+    pub async fn set_paranoid_sync(
+        &mut self,
+        req: SetParanoidSync,
+    ) -> Result<SetParanoidSyncResponse> {
+        if self.config.network.paranoid_sync != req.paranoid_sync {
+            self.config.network.paranoid_sync = req.paranoid_sync;
+            self.save_config()?;
+            self.command_sender
+                .send(SyncCommand::SetParanoidSync(req.paranoid_sync))
+                .await?;
+        }
+
+        Ok(SetParanoidSyncResponse {})
+    }
+
+    pub async fn set_header_validation(
+        &mut self,
+        req: SetHeaderValidation,
+    ) -> Result<SetHeaderValidationResponse> {
+        if self.config.network.header_validation != req.header_validation {
+            self.config.network.header_validation = req.header_validation;
+            self.save_config()?;
+            self.command_sender
+                .send(SyncCommand::SetHeaderValidation(req.header_validation))
+                .await?;
+        }
+
+        Ok(SetHeaderValidationResponse {})
+    }
+
+    pub async fn set_proxy(&mut self, req: SetProxy) -> Result<SetProxyResponse> {
+        let proxy = req.proxy.as_deref().map(SocketAddr::from_str).transpose()?;
+
+        if self.proxy()? != proxy {
+            sage_assets::set_proxy(proxy)?;
+            self.config.network.proxy = proxy.map(|proxy| proxy.to_string());
+            self.save_config()?;
+            self.command_sender
+                .send(SyncCommand::SetProxy(proxy))
+                .await?;
+        }
+
+        Ok(SetProxyResponse {})
+    }
+
+    pub async fn set_dns_discovery(
+        &mut self,
+        req: SetDnsDiscovery,
+    ) -> Result<SetDnsDiscoveryResponse> {
+        if self.config.network.dns_discovery != req.dns_discovery {
+            self.config.network.dns_discovery = req.dns_discovery;
+            self.save_config()?;
+            self.command_sender
+                .send(SyncCommand::SetDnsDiscovery(req.dns_discovery))
+                .await?;
+        }
+
+        Ok(SetDnsDiscoveryResponse {})
+    }
+
     pub async fn set_target_peers(
         &mut self,
         req: SetTargetPeers,
@@ -90,6 +188,13 @@ impl Sage {
     }
 
     pub async fn set_network(&mut self, req: SetNetwork) -> Result<SetNetworkResponse> {
+        if self.network_list.by_name(&req.name).is_none() {
+            return Err(Error::UnknownNetwork);
+        }
+
+        // The address book is stored per network, so it's saved before the network changes.
+        self.save_peers().await?;
+
         self.config.network.default_network.clone_from(&req.name);
         self.save_config()?;
         self.switch_wallet().await?;
@@ -101,6 +206,8 @@ impl Sage {
         &mut self,
         req: SetNetworkOverride,
     ) -> Result<SetNetworkOverrideResponse> {
+        self.save_peers().await?;
+
         let config = self
             .wallet_config
             .wallets
@@ -108,6 +215,12 @@ impl Sage {
             .find(|w| w.fingerprint == req.fingerprint)
             .ok_or(Error::UnknownFingerprint)?;
 
+        if let Some(name) = &req.name
+            && self.network_list.by_name(name).is_none()
+        {
+            return Err(Error::UnknownNetwork);
+        }
+
         config.network = req.name;
 
         self.save_config()?;
@@ -117,6 +230,15 @@ impl Sage {
         Ok(SetNetworkOverrideResponse {})
     }
 
+    pub fn add_network(&mut self, req: AddNetwork) -> Result<AddNetworkResponse> {
+        validate_network(&req.network, &self.network_list.networks)?;
+
+        self.network_list.networks.push(req.network);
+        self.save_config()?;
+
+        Ok(AddNetworkResponse {})
+    }
+
     pub fn get_networks(&mut self, _req: GetNetworks) -> Result<GetNetworksResponse> {
         Ok(self.network_list.clone())
     }
@@ -159,6 +281,24 @@ impl Sage {
         Ok(SetDeltaSyncOverrideResponse {})
     }
 
+    pub async fn set_background_sync(
+        &mut self,
+        req: SetBackgroundSync,
+    ) -> Result<SetBackgroundSyncResponse> {
+        let Some(wallet_config) = self
+            .wallet_config
+            .wallets
+            .iter_mut()
+            .find(|w| w.fingerprint == req.fingerprint)
+        else {
+            return Err(Error::UnknownFingerprint);
+        };
+        wallet_config.background_sync = req.background_sync;
+        self.save_config()?;
+        self.switch_wallet().await?;
+        Ok(SetBackgroundSyncResponse {})
+    }
+
     pub async fn set_change_address(
         &mut self,
         req: SetChangeAddress,
@@ -176,4 +316,373 @@ impl Sage {
         self.switch_wallet().await?;
         Ok(SetChangeAddressResponse {})
     }
+
+    pub fn get_clawback_policy(&self, req: GetClawbackPolicy) -> Result<GetClawbackPolicyResponse> {
+        let Some(wallet_config) = self
+            .wallet_config
+            .wallets
+            .iter()
+            .find(|w| w.fingerprint == req.fingerprint)
+        else {
+            return Err(Error::UnknownFingerprint);
+        };
+
+        Ok(GetClawbackPolicyResponse {
+            policy: wallet_config.clawback_policy.map(|policy| ClawbackPolicy {
+                finalize_incoming: policy.finalize_incoming,
+                reclaim_outgoing: policy.reclaim_outgoing,
+                reclaim_window_seconds: policy.reclaim_window_seconds,
+                fee: Amount::u64(policy.fee),
+            }),
+        })
+    }
+
+    pub async fn set_clawback_policy(
+        &mut self,
+        req: SetClawbackPolicy,
+    ) -> Result<SetClawbackPolicyResponse> {
+        let policy = req
+            .policy
+            .map(|policy| {
+                Ok::<_, Error>(sage_config::ClawbackPolicy {
+                    finalize_incoming: policy.finalize_incoming,
+                    reclaim_outgoing: policy.reclaim_outgoing,
+                    reclaim_window_seconds: policy.reclaim_window_seconds,
+                    fee: parse_amount(policy.fee)?,
+                })
+            })
+            .transpose()?;
+
+        let Some(wallet_config) = self
+            .wallet_config
+            .wallets
+            .iter_mut()
+            .find(|w| w.fingerprint == req.fingerprint)
+        else {
+            return Err(Error::UnknownFingerprint);
+        };
+        wallet_config.clawback_policy = policy;
+        self.save_config()?;
+        self.configure_wallet(req.fingerprint).await?;
+        Ok(SetClawbackPolicyResponse {})
+    }
+
+    pub fn get_coin_management(&self, req: GetCoinManagement) -> Result<GetCoinManagementResponse> {
+        let Some(wallet_config) = self
+            .wallet_config
+            .wallets
+            .iter()
+            .find(|w| w.fingerprint == req.fingerprint)
+        else {
+            return Err(Error::UnknownFingerprint);
+        };
+
+        Ok(GetCoinManagementResponse {
+            coin_management: wallet_config
+                .coin_management
+                .as_ref()
+                .map(|config| CoinManagement {
+                    fee: Amount::u64(config.fee),
+                    daily_fee_budget: Amount::u64(config.daily_fee_budget),
+                    policies: config
+                        .policies
+                        .iter()
+                        .map(|policy| CoinPolicy {
+                            asset_id: policy.asset_id.map(hex::encode),
+                            split: policy.split.map(|split| SplitPolicy {
+                                min_coins: split.min_coins,
+                                coin_amount: Amount::u64(split.coin_amount),
+                            }),
+                            combine: policy.combine.map(|combine| CombinePolicy {
+                                max_coin_amount: Amount::u64(combine.max_coin_amount),
+                                coin_count_threshold: combine.coin_count_threshold,
+                            }),
+                        })
+                        .collect(),
+                }),
+        })
+    }
+
+    pub async fn set_coin_management(
+        &mut self,
+        req: SetCoinManagement,
+    ) -> Result<SetCoinManagementResponse> {
+        let coin_management = req
+            .coin_management
+            .map(|config| {
+                Ok::<_, Error>(sage_config::CoinManagement {
+                    fee: parse_amount(config.fee)?,
+                    daily_fee_budget: parse_amount(config.daily_fee_budget)?,
+                    policies: config
+                        .policies
+                        .into_iter()
+                        .map(|policy| {
+                            Ok(sage_config::CoinPolicy {
+                                asset_id: policy.asset_id.map(parse_asset_id).transpose()?,
+                                split: policy
+                                    .split
+                                    .map(|split| {
+                                        Ok::<_, Error>(sage_config::SplitPolicy {
+                                            min_coins: split.min_coins,
+                                            coin_amount: parse_amount(split.coin_amount)?,
+                                        })
+                                    })
+                                    .transpose()?,
+                                combine: policy
+                                    .combine
+                                    .map(|combine| {
+                                        Ok::<_, Error>(sage_config::CombinePolicy {
+                                            max_coin_amount: parse_amount(combine.max_coin_amount)?,
+                                            coin_count_threshold: combine.coin_count_threshold,
+                                        })
+                                    })
+                                    .transpose()?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                })
+            })
+            .transpose()?;
+
+        let Some(wallet_config) = self
+            .wallet_config
+            .wallets
+            .iter_mut()
+            .find(|w| w.fingerprint == req.fingerprint)
+        else {
+            return Err(Error::UnknownFingerprint);
+        };
+        wallet_config.coin_management = coin_management;
+        self.save_config()?;
+        self.configure_wallet(req.fingerprint).await?;
+        Ok(SetCoinManagementResponse {})
+    }
+
+    pub fn get_reputation_sources(
+        &self,
+        _req: GetReputationSources,
+    ) -> Result<GetReputationSourcesResponse> {
+        Ok(GetReputationSourcesResponse {
+            sources: self
+                .config
+                .reputation
+                .sources
+                .iter()
+                .map(|source| match source {
+                    sage_config::ReputationSource::File { path } => {
+                        ReputationSource::File { path: path.clone() }
+                    }
+                    sage_config::ReputationSource::Http { url } => {
+                        ReputationSource::Http { url: url.clone() }
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn set_reputation_sources(
+        &mut self,
+        req: SetReputationSources,
+    ) -> Result<SetReputationSourcesResponse> {
+        self.config.reputation.sources = req
+            .sources
+            .into_iter()
+            .map(|source| match source {
+                ReputationSource::File { path } => sage_config::ReputationSource::File { path },
+                ReputationSource::Http { url } => sage_config::ReputationSource::Http { url },
+            })
+            .collect();
+        self.save_config()?;
+        self.command_sender
+            .send(SyncCommand::SetReputationSources(self.reputation_sources()))
+            .await?;
+
+        Ok(SetReputationSourcesResponse {})
+    }
+}
+
+/// Checks a new network on its own, and that it can't be mistaken for one of the existing ones.
+/// Wallet databases are named after the network id, and coins are only valid for a single
+/// genesis challenge, so neither can be shared.
+fn validate_network(network: &Network, existing: &[Network]) -> Result<()> {
+    if network.name.trim().is_empty() {
+        return Err(Error::InvalidNetwork("the name can't be empty".to_string()));
+    }
+
+    for other in existing {
+        if other.name == network.name {
+            return Err(Error::InvalidNetwork(format!(
+                "a network named {} already exists",
+                network.name
+            )));
+        }
+
+        if other.network_id() == network.network_id() {
+            return Err(Error::InvalidNetwork(format!(
+                "the network {} already has the id {}",
+                other.name,
+                network.network_id()
+            )));
+        }
+
+        if other.genesis_challenge == network.genesis_challenge {
+            return Err(Error::InvalidNetwork(format!(
+                "the network {} already has the genesis challenge {}",
+                other.name, network.genesis_challenge
+            )));
+        }
+    }
+
+    if network.ticker.trim().is_empty() {
+        return Err(Error::InvalidNetwork(
+            "the ticker can't be empty".to_string(),
+        ));
+    }
+
+    let prefix = network.prefix();
+
+    if prefix != prefix.to_lowercase()
+        || Address::new(Bytes32::default(), prefix.clone())
+            .encode()
+            .is_err()
+    {
+        return Err(Error::InvalidNetwork(format!(
+            "{prefix} isn't a valid address prefix"
+        )));
+    }
+
+    if 10u64.checked_pow(network.precision.into()).is_none() {
+        return Err(Error::InvalidNetwork(format!(
+            "a precision of {} is too large",
+            network.precision
+        )));
+    }
+
+    if network.default_port == 0 {
+        return Err(Error::InvalidNetwork(
+            "the default port can't be 0".to_string(),
+        ));
+    }
+
+    if let (Some(agg_sig_me), Some(agg_sig)) = (network.agg_sig_me, network.agg_sig)
+        && agg_sig_me != agg_sig.me
+    {
+        return Err(Error::InvalidNetwork(
+            "agg_sig_me doesn't match agg_sig.me".to_string(),
+        ));
+    }
+
+    if network.trusted_peer_addrs().len() != network.trusted_peers.len() {
+        return Err(Error::InvalidNetwork(
+            "every trusted peer must be an IP address, with an optional port".to_string(),
+        ));
+    }
+
+    let services = &network.services;
+
+    for url in [
+        &services.dexie_api,
+        &services.dexie_icons,
+        &services.mintgarden_assets,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(Error::InvalidNetwork(format!("{url} isn't an HTTP URL")));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{env, fs};
+
+    use sage_config::NetworkList;
+
+    use super::*;
+
+    fn custom_network() -> Network {
+        Network {
+            name: "custom".to_string(),
+            network_id: None,
+            ticker: "CUS".to_string(),
+            genesis_challenge: Bytes32::new([7; 32]),
+            ..NetworkList::default().networks[1].clone()
+        }
+    }
+
+    #[test]
+    fn test_validate_network() {
+        let existing = NetworkList::default().networks;
+
+        assert!(validate_network(&custom_network(), &existing).is_ok());
+
+        let empty_name = Network {
+            name: " ".to_string(),
+            ..custom_network()
+        };
+        assert!(validate_network(&empty_name, &existing).is_err());
+
+        let zero_port = Network {
+            default_port: 0,
+            ..custom_network()
+        };
+        assert!(validate_network(&zero_port, &existing).is_err());
+    }
+
+    #[test]
+    fn test_validate_network_duplicates() {
+        let existing = NetworkList::default().networks;
+        let mainnet = &existing[0];
+
+        let same_name = Network {
+            name: mainnet.name.clone(),
+            ..custom_network()
+        };
+        assert!(validate_network(&same_name, &existing).is_err());
+
+        let same_id = Network {
+            network_id: Some(mainnet.network_id()),
+            ..custom_network()
+        };
+        assert!(validate_network(&same_id, &existing).is_err());
+
+        let same_genesis = Network {
+            genesis_challenge: mainnet.genesis_challenge,
+            ..custom_network()
+        };
+        assert!(validate_network(&same_genesis, &existing).is_err());
+    }
+
+    #[test]
+    fn test_add_network() {
+        let path = env::temp_dir().join(format!("sage-add-network-{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+
+        let mut sage = Sage::new(&path, true);
+
+        sage.add_network(AddNetwork {
+            network: custom_network(),
+        })
+        .unwrap();
+
+        assert!(sage.network_list.by_name("custom").is_some());
+        assert!(
+            sage.add_network(AddNetwork {
+                network: custom_network(),
+            })
+            .is_err()
+        );
+
+        let saved: NetworkList =
+            toml::from_str(&fs::read_to_string(path.join("networks.toml")).unwrap()).unwrap();
+        assert!(saved.by_name("custom").is_some());
+
+        fs::remove_dir_all(path).unwrap();
+    }
 }
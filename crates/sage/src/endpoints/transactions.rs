@@ -1,31 +1,57 @@
-use std::time::Duration;
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use chia_wallet_sdk::{
     chia::puzzle_types::nft::NftMetadata,
     driver::{MetadataUpdate, UriKind},
     prelude::*,
+    puzzles::NFT_METADATA_UPDATER_DEFAULT_HASH,
 };
 use itertools::Itertools;
 use sage_api::{
-    AddNftUri, AssignNftsToDid, AutoCombineCat, AutoCombineCatResponse, AutoCombineXch,
-    AutoCombineXchResponse, BulkMintNfts, BulkMintNftsResponse, BulkSendCat, BulkSendXch, Combine,
-    CreateDid, ExerciseOptions, FinalizeClawback, IssueCat, MintOption, MintOptionResponse,
-    MultiSend, NftUriKind, NormalizeDids, OptionAsset, SendCat, SendXch, SignCoinSpends,
+    AddNftUri, AddNftUris, AddNftUrisResponse, Amount, AssignNftsToDid, AttestDidRecovery,
+    AttestDidRecoveryResponse, AutoCombineCat, AutoCombineCatResponse, AutoCombineXch,
+    AutoCombineXchResponse, BulkMintNfts, BulkMintNftsResponse, BulkSendCat, BulkSendXch,
+    ClawBackAllOutgoing, Combine, CreateDid, CreateMintJob, CreateMintJobResponse, DeleteMintJob,
+    DeleteMintJobResponse, DidAttestation, ExerciseOptions, FinalizeClawback,
+    GetDidRecoveryRequest, GetDidRecoveryRequestResponse, GetMintJobItems, GetMintJobItemsResponse,
+    GetMintJobs, GetMintJobsResponse, IssueCat, IssueCatWithTail, MeltCat, MintJobItemRecord,
+    MintJobItemStatus, MintJobRecord, MintOption, MintOptionResponse, MultiSend, NftMintWarning,
+    NftMintWarningKind, NftUriKind, NftUriSkip, NftUriUpdate, NormalizeDids, OptionAsset,
+    ReclaimExpiredOptions, RecoverDid, ReissueCat, RevokeCatCoins, SendCat, SendXch,
+    SetDidRecoveryList, SetMintJobActive, SetMintJobActiveResponse, SignCoinSpends,
     SignCoinSpendsResponse, Split, SubmitTransaction, SubmitTransactionResponse,
-    TransactionResponse, TransferDids, TransferNfts, TransferOptions, ViewCoinSpends,
-    ViewCoinSpendsResponse,
+    TransactionResponse, TransferDids, TransferNfts, TransferOptions, UpdateDidProfile,
+    ValidateNftMint, ValidateNftMintResponse, ViewCoinSpends, ViewCoinSpendsResponse,
+};
+use sage_assets::{Data, fetch_uri, fetch_uris_without_hash, validate_chip0007_metadata};
+use sage_database::{
+    Asset, AssetKind, CatIssuanceKind, IssuedCat, NftCoinInfo, NftGroupSearch, NftSortMode,
+};
+use sage_wallet::{
+    MultiSendPayment, WalletCatTail, WalletDidAttestation, WalletDidRecoveryRequest, WalletNftMint,
+    WalletNftUriUpdate, WalletOptionMint, nft_option_type,
 };
-use sage_assets::fetch_uris_without_hash;
-use sage_database::{Asset, AssetKind};
-use sage_wallet::{MultiSendPayment, WalletNftMint, WalletOptionMint};
 use tokio::time::timeout;
 
 use crate::{
     ConfirmationInfo, Error, Result, Sage, json_bundle, json_spend, parse_amount, parse_asset_id,
-    parse_coin_ids, parse_did_id, parse_hash, parse_memos, parse_nft_id, parse_option_id,
+    parse_coin_id, parse_coin_ids, parse_collection_id, parse_did_id, parse_hash, parse_memos,
+    parse_mint_manifest, parse_nft_id, parse_option_id, parse_program, parse_public_key,
     rust_bundle, rust_spend,
 };
 
+/// The number of NFTs minted in each transaction of a mint job, unless otherwise specified.
+/// Minting an NFT from a DID costs around 110 million, so this leaves plenty of room under
+/// [`sage_wallet::MAX_SPEND_BUNDLE_COST`] for metadata. Batches which exceed it anyway are made smaller
+/// when they're submitted.
+const DEFAULT_MINT_BATCH_SIZE: u32 = 25;
+
+/// The number of NFTs updated in each transaction when adding URIs to a collection.
+const DEFAULT_NFT_UPDATE_BATCH_SIZE: u32 = 25;
+
 impl Sage {
     pub async fn send_xch(&self, req: SendXch) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
@@ -158,27 +184,213 @@ impl Sage {
         let amount = parse_amount(req.amount)?;
         let fee = parse_amount(req.fee)?;
 
-        let (coin_spends, asset_id) = wallet.issue_cat(amount, fee, None).await?;
+        let (tail, issuance_kind) = if req.multi_issuance {
+            (
+                WalletCatTail::MultiIssuance(wallet.cat_issuance_key()),
+                CatIssuanceKind::MultiIssuance,
+            )
+        } else {
+            (
+                WalletCatTail::SingleIssuance,
+                CatIssuanceKind::SingleIssuance,
+            )
+        };
+
+        self.issue_cat_with(
+            req.name,
+            req.ticker,
+            amount,
+            fee,
+            tail,
+            issuance_kind,
+            req.revocable,
+            req.auto_submit,
+        )
+        .await
+    }
+
+    pub async fn issue_cat_with_tail(&self, req: IssueCatWithTail) -> Result<TransactionResponse> {
+        let amount = parse_amount(req.amount)?;
+        let fee = parse_amount(req.fee)?;
+        let puzzle = parse_program(req.tail)?;
+        let solution = req
+            .tail_solution
+            .map(parse_program)
+            .transpose()?
+            .unwrap_or_default();
+
+        self.issue_cat_with(
+            req.name,
+            req.ticker,
+            amount,
+            fee,
+            WalletCatTail::Custom { puzzle, solution },
+            CatIssuanceKind::Custom,
+            req.revocable,
+            req.auto_submit,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn issue_cat_with(
+        &self,
+        name: String,
+        ticker: String,
+        amount: u64,
+        fee: u64,
+        tail: WalletCatTail,
+        issuance_kind: CatIssuanceKind,
+        revocable: bool,
+        auto_submit: bool,
+    ) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+
+        let hidden_puzzle_hash = revocable.then(|| wallet.cat_revocation_puzzle_hash());
+
+        let (coin_spends, cat, tail_reveal) = wallet
+            .issue_cat_with_tail(amount, fee, tail, hidden_puzzle_hash)
+            .await?;
+        let asset_id = cat.info.asset_id;
         let mut tx = wallet.db.tx().await?;
 
         tx.insert_asset(Asset {
             hash: asset_id,
-            name: Some(req.name),
-            ticker: Some(req.ticker),
+            name: Some(name),
+            ticker: Some(ticker),
             precision: 3,
             icon_url: None,
             description: None,
             is_sensitive_content: false,
             is_visible: true,
-            hidden_puzzle_hash: None,
+            hidden_puzzle_hash,
             kind: AssetKind::Token,
         })
         .await?;
         tx.commit().await?;
 
+        let response = self.transact(coin_spends, auto_submit).await?;
+
+        // The issuance is only recorded once the transaction was created and submitted,
+        // so that a failed submission doesn't leave coins to trace that will never exist.
+        let mut tx = wallet.db.tx().await?;
+        tx.insert_issued_cat(asset_id, tail_reveal, issuance_kind)
+            .await?;
+        tx.insert_issued_cat_coin(asset_id, cat.coin.coin_id())
+            .await?;
+        tx.insert_cat_supply_change(asset_id, cat.coin.coin_id(), amount, 0)
+            .await?;
+        tx.commit().await?;
+
+        Ok(response)
+    }
+
+    pub async fn reissue_cat(&self, req: ReissueCat) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = parse_asset_id(req.asset_id)?;
+        let amount = parse_amount(req.amount)?;
+        let fee = parse_amount(req.fee)?;
+        let solution = req
+            .tail_solution
+            .map(parse_program)
+            .transpose()?
+            .unwrap_or_default();
+
+        let issued_cat = self.supply_changeable_cat(asset_id).await?;
+        let hidden_puzzle_hash = wallet
+            .db
+            .asset(asset_id)
+            .await?
+            .and_then(|asset| asset.hidden_puzzle_hash);
+
+        let (coin_spends, cat) = wallet
+            .reissue_cat(
+                asset_id,
+                hidden_puzzle_hash,
+                issued_cat.tail_reveal,
+                solution,
+                amount,
+                fee,
+            )
+            .await?;
+
+        let response = self.transact(coin_spends, req.auto_submit).await?;
+
+        let mut tx = wallet.db.tx().await?;
+        tx.insert_issued_cat_coin(asset_id, cat.coin.coin_id())
+            .await?;
+        tx.insert_cat_supply_change(asset_id, cat.coin.coin_id(), amount, 0)
+            .await?;
+        tx.commit().await?;
+
+        Ok(response)
+    }
+
+    pub async fn melt_cat(&self, req: MeltCat) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let asset_id = parse_asset_id(req.asset_id)?;
+        let amount = parse_amount(req.amount)?;
+        let fee = parse_amount(req.fee)?;
+        let solution = req
+            .tail_solution
+            .map(parse_program)
+            .transpose()?
+            .unwrap_or_default();
+
+        let issued_cat = self.supply_changeable_cat(asset_id).await?;
+
+        let (coin_spends, coin) = wallet
+            .melt_cat(asset_id, issued_cat.tail_reveal, solution, amount, fee)
+            .await?;
+
+        let response = self.transact(coin_spends, req.auto_submit).await?;
+
+        let mut tx = wallet.db.tx().await?;
+        tx.insert_cat_supply_change(asset_id, coin.coin_id(), 0, amount)
+            .await?;
+        tx.commit().await?;
+
+        Ok(response)
+    }
+
+    pub async fn revoke_cat_coins(&self, req: RevokeCatCoins) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let coin_ids = parse_coin_ids(req.coin_ids)?;
+        let fee = parse_amount(req.fee)?;
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let mut cats = Vec::with_capacity(coin_ids.len());
+
+        for coin_id in coin_ids {
+            cats.push(wallet.fetch_cat(&peer, coin_id).await?);
+        }
+
+        let coin_spends = wallet.revoke_cats(cats, fee).await?;
         self.transact(coin_spends, req.auto_submit).await
     }
 
+    async fn supply_changeable_cat(&self, asset_id: Bytes32) -> Result<IssuedCat> {
+        let wallet = self.wallet()?;
+
+        let issued_cat = wallet
+            .db
+            .issued_cat(asset_id)
+            .await?
+            .ok_or(Error::NotIssuedCat(asset_id))?;
+
+        if issued_cat.issuance_kind == CatIssuanceKind::SingleIssuance {
+            return Err(Error::SingleIssuanceCat(asset_id));
+        }
+
+        Ok(issued_cat)
+    }
+
     pub async fn send_cat(&self, req: SendCat) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let asset_id = parse_asset_id(req.asset_id)?;
@@ -281,6 +493,18 @@ impl Sage {
         let fee = parse_amount(req.fee)?;
         let did_id = parse_did_id(req.did_id)?;
 
+        if req.validate {
+            for (index, mint) in req.mints.iter().enumerate() {
+                let validation = self
+                    .validate_nft_mint(ValidateNftMint { mint: mint.clone() })
+                    .await?;
+
+                if let Some(warning) = validation.warnings.into_iter().next() {
+                    return Err(Error::InvalidNftMint(index, warning.message));
+                }
+            }
+        }
+
         let mut mints = Vec::with_capacity(req.mints.len());
         let mut info = ConfirmationInfo::default();
 
@@ -307,6 +531,207 @@ impl Sage {
         })
     }
 
+    pub async fn validate_nft_mint(&self, req: ValidateNftMint) -> Result<ValidateNftMintResponse> {
+        let mintgarden_url = self.network().services().mintgarden_assets;
+        let mint = req.mint;
+
+        let mut warnings = Vec::new();
+
+        let data = check_nft_uris(
+            &mint.data_uris,
+            mint.data_hash.map(parse_hash).transpose()?,
+            mintgarden_url.as_deref(),
+            &mut warnings,
+        )
+        .await;
+
+        let metadata = check_nft_uris(
+            &mint.metadata_uris,
+            mint.metadata_hash.map(parse_hash).transpose()?,
+            mintgarden_url.as_deref(),
+            &mut warnings,
+        )
+        .await;
+
+        let license = check_nft_uris(
+            &mint.license_uris,
+            mint.license_hash.map(parse_hash).transpose()?,
+            mintgarden_url.as_deref(),
+            &mut warnings,
+        )
+        .await;
+
+        if let Some(metadata) = &metadata {
+            match validate_chip0007_metadata(
+                &metadata.blob,
+                mint.edition_number.unwrap_or(1).into(),
+                mint.edition_total.unwrap_or(1).into(),
+            ) {
+                Ok(issues) => {
+                    for issue in issues {
+                        warnings.push(NftMintWarning {
+                            kind: NftMintWarningKind::InvalidMetadata,
+                            uri: None,
+                            message: if issue.field.is_empty() {
+                                issue.message
+                            } else {
+                                format!("{}: {}", issue.field, issue.message)
+                            },
+                            field: Some(issue.field),
+                        });
+                    }
+                }
+                Err(error) => {
+                    warnings.push(NftMintWarning {
+                        kind: NftMintWarningKind::InvalidMetadataJson,
+                        uri: None,
+                        field: None,
+                        message: format!("Metadata is not valid JSON: {error}"),
+                    });
+                }
+            }
+        }
+
+        Ok(ValidateNftMintResponse {
+            is_valid: warnings.is_empty(),
+            data_hash: data.map(|data| hex::encode(data.hash)),
+            metadata_hash: metadata.map(|metadata| hex::encode(metadata.hash)),
+            license_hash: license.map(|license| hex::encode(license.hash)),
+            warnings,
+        })
+    }
+
+    pub async fn create_mint_job(&self, req: CreateMintJob) -> Result<CreateMintJobResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let fee = parse_amount(req.fee)?;
+        let batch_size = req.batch_size.unwrap_or(DEFAULT_MINT_BATCH_SIZE);
+
+        if batch_size == 0 {
+            return Err(Error::InvalidBatchSize(batch_size));
+        }
+
+        let rows = parse_mint_manifest(&req.manifest, req.format)?;
+
+        if rows.is_empty() {
+            return Err(Error::InvalidMintManifest(
+                "The manifest doesn't have any rows".to_string(),
+            ));
+        }
+
+        let mut mints = Vec::with_capacity(rows.len());
+        let mut info = ConfirmationInfo::default();
+
+        for row in rows {
+            let item = row.into_nft_mint(req.royalty_address.clone(), req.royalty_ten_thousandths);
+            mints.push(self.convert_nft_mint(item, &mut info).await?);
+        }
+
+        let item_count = mints.len().try_into()?;
+
+        let mint_job_id = wallet
+            .create_mint_job(did_id, fee, batch_size, mints)
+            .await?;
+
+        self.configure_wallet(wallet.fingerprint).await?;
+
+        Ok(CreateMintJobResponse {
+            mint_job_id,
+            item_count,
+        })
+    }
+
+    pub async fn get_mint_jobs(&self, _req: GetMintJobs) -> Result<GetMintJobsResponse> {
+        let wallet = self.wallet()?;
+
+        let mut mint_jobs = Vec::new();
+
+        for mint_job in wallet.db.mint_jobs().await? {
+            mint_jobs.push(MintJobRecord {
+                mint_job_id: mint_job.id,
+                did_id: Address::new(mint_job.did_id, "did:chia:".to_string()).encode()?,
+                fee: Amount::u64(mint_job.fee),
+                batch_size: mint_job.batch_size,
+                is_active: mint_job.is_active,
+                item_count: mint_job.item_count,
+                submitted_count: mint_job.submitted_count,
+                minted_count: mint_job.minted_count,
+                creation_timestamp: mint_job.created_timestamp,
+            });
+        }
+
+        Ok(GetMintJobsResponse { mint_jobs })
+    }
+
+    pub async fn get_mint_job_items(
+        &self,
+        req: GetMintJobItems,
+    ) -> Result<GetMintJobItemsResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet.db.mint_job(req.mint_job_id).await?.is_none() {
+            return Err(Error::MissingMintJob(req.mint_job_id));
+        }
+
+        let mut items = Vec::new();
+
+        for item in wallet
+            .db
+            .mint_job_items(req.mint_job_id, None, None)
+            .await?
+        {
+            items.push(MintJobItemRecord {
+                row_index: item.row_index,
+                status: match item.status {
+                    sage_database::MintJobItemStatus::Pending => MintJobItemStatus::Pending,
+                    sage_database::MintJobItemStatus::Submitted => MintJobItemStatus::Submitted,
+                    sage_database::MintJobItemStatus::Minted => MintJobItemStatus::Minted,
+                },
+                nft_id: item
+                    .launcher_id
+                    .map(|launcher_id| Address::new(launcher_id, "nft".to_string()).encode())
+                    .transpose()?,
+                transaction_id: item.transaction_id.map(hex::encode),
+            });
+        }
+
+        Ok(GetMintJobItemsResponse { items })
+    }
+
+    pub async fn set_mint_job_active(
+        &self,
+        req: SetMintJobActive,
+    ) -> Result<SetMintJobActiveResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet.db.mint_job(req.mint_job_id).await?.is_none() {
+            return Err(Error::MissingMintJob(req.mint_job_id));
+        }
+
+        wallet
+            .db
+            .set_mint_job_active(req.mint_job_id, req.is_active)
+            .await?;
+
+        self.configure_wallet(wallet.fingerprint).await?;
+
+        Ok(SetMintJobActiveResponse {})
+    }
+
+    pub async fn delete_mint_job(&self, req: DeleteMintJob) -> Result<DeleteMintJobResponse> {
+        let wallet = self.wallet()?;
+
+        if wallet.db.mint_job(req.mint_job_id).await?.is_none() {
+            return Err(Error::MissingMintJob(req.mint_job_id));
+        }
+
+        wallet.db.delete_mint_job(req.mint_job_id).await?;
+
+        self.configure_wallet(wallet.fingerprint).await?;
+
+        Ok(DeleteMintJobResponse {})
+    }
+
     pub async fn transfer_nfts(&self, req: TransferNfts) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let nft_ids = req
@@ -347,6 +772,105 @@ impl Sage {
         self.transact(coin_spends, req.auto_submit).await
     }
 
+    pub async fn add_nft_uris(&self, req: AddNftUris) -> Result<AddNftUrisResponse> {
+        let wallet = self.wallet()?;
+        let collection_id = parse_collection_id(req.collection_id)?;
+        let fee = parse_amount(req.fee)?;
+        let batch_size = req.batch_size.unwrap_or(DEFAULT_NFT_UPDATE_BATCH_SIZE);
+
+        if batch_size == 0 {
+            return Err(Error::InvalidBatchSize(batch_size));
+        }
+
+        let metadata_updater = req
+            .metadata_updater_puzzle
+            .map(|puzzle| -> Result<_> {
+                let puzzle = parse_program(puzzle)?;
+                let mut allocator = Allocator::new();
+                let ptr = puzzle.to_clvm(&mut allocator)?;
+                Ok((Bytes32::from(tree_hash(&allocator, ptr)), puzzle))
+            })
+            .transpose()?;
+
+        let (nfts, _) = wallet
+            .db
+            .owned_nfts(
+                None,
+                Some(NftGroupSearch::Collection(collection_id)),
+                NftSortMode::Name,
+                true,
+                None,
+                u32::MAX,
+                0,
+            )
+            .await?;
+
+        let mut updates = Vec::new();
+        let mut skipped = Vec::new();
+        let mut wallet_updates = Vec::new();
+
+        for nft in nfts {
+            let nft_id = Address::new(nft.asset.hash, "nft".to_string()).encode()?;
+
+            let plan = if nft.coin_row.offer_hash.is_some() {
+                Err("Locked in an offer".to_string())
+            } else {
+                plan_nft_uri_update(
+                    &nft.nft_info,
+                    &nft_id,
+                    &req.uri,
+                    req.kind,
+                    metadata_updater.as_ref(),
+                )
+            };
+
+            match plan {
+                Ok((uri, metadata_updater)) => {
+                    updates.push(NftUriUpdate {
+                        nft_id,
+                        uri: uri.clone(),
+                    });
+
+                    wallet_updates.push(WalletNftUriUpdate {
+                        nft_id: nft.asset.hash,
+                        update: MetadataUpdate {
+                            kind: match req.kind {
+                                NftUriKind::Data => UriKind::Data,
+                                NftUriKind::Metadata => UriKind::Metadata,
+                                NftUriKind::License => UriKind::License,
+                            },
+                            uri,
+                        },
+                        metadata_updater,
+                    });
+                }
+                Err(reason) => skipped.push(NftUriSkip { nft_id, reason }),
+            }
+        }
+
+        let mut transactions = Vec::new();
+
+        if !req.dry_run {
+            // Each transaction pays the fee, since they can be confirmed in separate blocks.
+            // The coins spent by earlier batches are excluded, in case they aren't submitted yet.
+            let mut spent_coin_ids = HashSet::new();
+
+            for batch in wallet_updates.chunks(batch_size as usize) {
+                let coin_spends = wallet
+                    .add_nft_uris(batch.to_vec(), fee, &spent_coin_ids)
+                    .await?;
+                spent_coin_ids.extend(coin_spends.iter().map(|spend| spend.coin.coin_id()));
+                transactions.push(self.transact(coin_spends, req.auto_submit).await?);
+            }
+        }
+
+        Ok(AddNftUrisResponse {
+            updates,
+            skipped,
+            transactions,
+        })
+    }
+
     pub async fn assign_nfts_to_did(&self, req: AssignNftsToDid) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let nft_ids = req
@@ -390,6 +914,173 @@ impl Sage {
         self.transact(coin_spends, req.auto_submit).await
     }
 
+    pub async fn update_did_profile(&self, req: UpdateDidProfile) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let fee = parse_amount(req.fee)?;
+
+        let mut metadata = Vec::new();
+
+        if let Some(display_name) = req.display_name {
+            metadata.push(("name".to_string(), display_name));
+        }
+
+        if let Some(avatar_nft_id) = req.avatar_nft_id {
+            let nft_id = parse_nft_id(avatar_nft_id)?;
+            metadata.push((
+                "avatar".to_string(),
+                Address::new(nft_id, "nft".to_string()).encode()?,
+            ));
+        }
+
+        // Links can't contain whitespace, so they're stored as a single whitespace separated value.
+        if !req.links.is_empty() {
+            metadata.push(("links".to_string(), req.links.join(" ")));
+        }
+
+        let coin_spends = wallet.update_did_profile(did_id, metadata, fee).await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn set_did_recovery_list(
+        &self,
+        req: SetDidRecoveryList,
+    ) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let recovery_did_ids = req
+            .recovery_did_ids
+            .into_iter()
+            .map(parse_did_id)
+            .collect::<Result<Vec<_>>>()?;
+        let fee = parse_amount(req.fee)?;
+
+        if recovery_did_ids.iter().duplicates().next().is_some() {
+            return Err(Error::InvalidRecoveryList(
+                "The same DID is listed more than once".to_string(),
+            ));
+        }
+
+        if req.num_verifications_required > recovery_did_ids.len() as u64 {
+            return Err(Error::InvalidRecoveryList(format!(
+                "{} verifications are required, but there are only {} DIDs",
+                req.num_verifications_required,
+                recovery_did_ids.len()
+            )));
+        }
+
+        if req.num_verifications_required == 0 && !recovery_did_ids.is_empty() {
+            return Err(Error::InvalidRecoveryList(
+                "At least one verification is required".to_string(),
+            ));
+        }
+
+        let coin_spends = wallet
+            .set_did_recovery_list(
+                did_id,
+                recovery_did_ids,
+                req.num_verifications_required,
+                fee,
+            )
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
+    pub async fn get_did_recovery_request(
+        &self,
+        req: GetDidRecoveryRequest,
+    ) -> Result<GetDidRecoveryRequestResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let request = wallet.did_recovery_request(&peer, did_id).await?;
+
+        Ok(GetDidRecoveryRequestResponse {
+            coin_id: hex::encode(request.coin_id),
+            new_inner_puzzle_hash: hex::encode(request.new_inner_puzzle_hash),
+            public_key: hex::encode(request.public_key.to_bytes()),
+        })
+    }
+
+    pub async fn attest_did_recovery(
+        &self,
+        req: AttestDidRecovery,
+    ) -> Result<AttestDidRecoveryResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let request = WalletDidRecoveryRequest {
+            coin_id: parse_coin_id(req.coin_id)?,
+            new_inner_puzzle_hash: parse_hash(req.new_inner_puzzle_hash)?,
+            public_key: parse_public_key(req.public_key)?,
+        };
+        let fee = parse_amount(req.fee)?;
+
+        let (coin_spends, attestation) = wallet.attest_did_recovery(did_id, request, fee).await?;
+
+        let response = self.transact(coin_spends, req.auto_submit).await?;
+
+        Ok(AttestDidRecoveryResponse {
+            attestation: DidAttestation {
+                attestor_did_id: Address::new(attestation.attestor_did_id, "did:chia:".to_string())
+                    .encode()?,
+                parent_coin_id: hex::encode(attestation.parent_coin_id),
+                inner_puzzle_hash: hex::encode(attestation.inner_puzzle_hash),
+                amount: Amount::u64(attestation.amount),
+            },
+            summary: response.summary,
+            coin_spends: response.coin_spends,
+        })
+    }
+
+    pub async fn recover_did(&self, req: RecoverDid) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let public_key = parse_public_key(req.public_key)?;
+        let recovery_did_ids = req
+            .recovery_did_ids
+            .into_iter()
+            .map(parse_did_id)
+            .collect::<Result<Vec<_>>>()?;
+        let fee = parse_amount(req.fee)?;
+
+        let mut attestations = Vec::with_capacity(req.attestations.len());
+
+        for attestation in req.attestations {
+            attestations.push(WalletDidAttestation {
+                attestor_did_id: parse_did_id(attestation.attestor_did_id)?,
+                parent_coin_id: parse_coin_id(attestation.parent_coin_id)?,
+                inner_puzzle_hash: parse_hash(attestation.inner_puzzle_hash)?,
+                amount: parse_amount(attestation.amount)?,
+            });
+        }
+
+        let peer = self
+            .peer_state
+            .lock()
+            .await
+            .acquire_peer()
+            .ok_or(Error::NoPeers)?;
+
+        let coin_spends = wallet
+            .recover_did(
+                &peer,
+                did_id,
+                public_key,
+                recovery_did_ids,
+                attestations,
+                fee,
+            )
+            .await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
     pub async fn mint_option(&self, req: MintOption) -> Result<MintOptionResponse> {
         let wallet = self.wallet()?;
         let fee = parse_amount(req.fee)?;
@@ -429,6 +1120,23 @@ impl Sage {
 
         let wallet = self.wallet()?;
 
+        if let Ok(launcher_id) = parse_nft_id(asset_id.clone()) {
+            if amount != 1 {
+                return Err(Error::InvalidAmount(asset.amount.to_string()));
+            }
+
+            let peer = self.peer_state.lock().await.acquire_peer();
+
+            let Some(nft) = wallet
+                .fetch_offer_nft_info(peer.as_ref(), launcher_id)
+                .await?
+            else {
+                return Err(Error::CouldNotFetchNft(launcher_id));
+            };
+
+            return Ok(nft_option_type(launcher_id, &nft)?);
+        }
+
         let asset_id = parse_asset_id(asset_id)?;
 
         self.cache_cat(asset_id, None).await?;
@@ -479,6 +1187,23 @@ impl Sage {
         self.transact(coin_spends, req.auto_submit).await
     }
 
+    pub async fn reclaim_expired_options(
+        &self,
+        req: ReclaimExpiredOptions,
+    ) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let fee = parse_amount(req.fee)?;
+
+        let coin_ids = wallet.db.expired_option_underlying_coin_ids().await?;
+
+        if coin_ids.is_empty() {
+            return Err(Error::NoExpiredOptions);
+        }
+
+        let coin_spends = wallet.combine(coin_ids, fee).await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
     pub async fn finalize_clawback(&self, req: FinalizeClawback) -> Result<TransactionResponse> {
         let wallet = self.wallet()?;
         let coin_ids = parse_coin_ids(req.coin_ids)?;
@@ -488,6 +1213,40 @@ impl Sage {
         self.transact(coin_spends, req.auto_submit).await
     }
 
+    pub async fn claw_back_all_outgoing(
+        &self,
+        req: ClawBackAllOutgoing,
+    ) -> Result<TransactionResponse> {
+        let wallet = self.wallet()?;
+        let fee = parse_amount(req.fee)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch")
+            .as_secs();
+
+        let coin_ids = wallet
+            .db
+            .clawback_coins()
+            .await?
+            .into_iter()
+            .filter(|row| {
+                row.is_outgoing
+                    && !row.is_incoming
+                    && row.mempool_item_hash.is_none()
+                    && timestamp < row.expiration_seconds
+            })
+            .map(|row| row.coin.coin_id())
+            .collect_vec();
+
+        if coin_ids.is_empty() {
+            return Err(Error::NoClawbacks);
+        }
+
+        let coin_spends = wallet.combine(coin_ids, fee).await?;
+        self.transact(coin_spends, req.auto_submit).await
+    }
+
     pub async fn sign_coin_spends(&self, req: SignCoinSpends) -> Result<SignCoinSpendsResponse> {
         let coin_spends = req
             .coin_spends
@@ -563,7 +1322,7 @@ impl Sage {
         item: sage_api::NftMint,
         info: &mut ConfirmationInfo,
     ) -> Result<WalletNftMint> {
-        let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+        let mintgarden_url = self.network().services().mintgarden_assets;
 
         let royalty_puzzle_hash = item
             .royalty_address
@@ -579,7 +1338,7 @@ impl Sage {
         } else {
             let data = timeout(
                 Duration::from_secs(10),
-                fetch_uris_without_hash(item.data_uris.clone(), testnet),
+                fetch_uris_without_hash(item.data_uris.clone(), mintgarden_url.as_deref()),
             )
             .await??;
 
@@ -596,7 +1355,7 @@ impl Sage {
         } else {
             let metadata = timeout(
                 Duration::from_secs(10),
-                fetch_uris_without_hash(item.metadata_uris.clone(), testnet),
+                fetch_uris_without_hash(item.metadata_uris.clone(), mintgarden_url.as_deref()),
             )
             .await??;
 
@@ -613,7 +1372,7 @@ impl Sage {
         } else {
             let data = timeout(
                 Duration::from_secs(10),
-                fetch_uris_without_hash(item.license_uris.clone(), testnet),
+                fetch_uris_without_hash(item.license_uris.clone(), mintgarden_url.as_deref()),
             )
             .await??;
 
@@ -646,3 +1405,310 @@ impl Sage {
         })
     }
 }
+
+/// Fetches every URI, checking the content against the expected hash and the other URIs.
+/// Returns the content of the first URI which could be fetched.
+async fn check_nft_uris(
+    uris: &[String],
+    expected_hash: Option<Bytes32>,
+    mintgarden_url: Option<&str>,
+    warnings: &mut Vec<NftMintWarning>,
+) -> Option<Data> {
+    if uris.is_empty() {
+        if expected_hash.is_some() {
+            warnings.push(NftMintWarning {
+                kind: NftMintWarningKind::MissingUris,
+                uri: None,
+                field: None,
+                message: "A hash was provided without any URIs to fetch it from".to_string(),
+            });
+        }
+
+        return None;
+    }
+
+    let mut first: Option<(&String, Data)> = None;
+
+    for uri in uris {
+        let data = match timeout(
+            Duration::from_secs(10),
+            fetch_uri(uri.clone(), mintgarden_url),
+        )
+        .await
+        {
+            Ok(Ok(data)) => data,
+            Ok(Err(error)) => {
+                warnings.push(NftMintWarning {
+                    kind: NftMintWarningKind::UnreachableUri,
+                    uri: Some(uri.clone()),
+                    field: None,
+                    message: format!("Failed to fetch {uri}: {error}"),
+                });
+                continue;
+            }
+            Err(_) => {
+                warnings.push(NftMintWarning {
+                    kind: NftMintWarningKind::UnreachableUri,
+                    uri: Some(uri.clone()),
+                    field: None,
+                    message: format!("Timed out fetching {uri}"),
+                });
+                continue;
+            }
+        };
+
+        if let Some(expected_hash) = expected_hash
+            && data.hash != expected_hash
+        {
+            warnings.push(NftMintWarning {
+                kind: NftMintWarningKind::HashMismatch,
+                uri: Some(uri.clone()),
+                field: None,
+                message: format!(
+                    "Expected {uri} to have hash {expected_hash}, but it has hash {}",
+                    data.hash
+                ),
+            });
+        }
+
+        if let Some((first_uri, first_data)) = &first {
+            if first_data.hash != data.hash {
+                warnings.push(NftMintWarning {
+                    kind: NftMintWarningKind::InconsistentUris,
+                    uri: Some(uri.clone()),
+                    field: None,
+                    message: format!("The content of {uri} differs from {first_uri}"),
+                });
+            }
+        } else {
+            first = Some((uri, data));
+        }
+    }
+
+    first.map(|(_, data)| data)
+}
+
+/// Decides which URI to add to an NFT, and which metadata updater puzzle to reveal if it's not the default.
+fn plan_nft_uri_update(
+    nft_info: &NftCoinInfo,
+    nft_id: &str,
+    template: &str,
+    kind: NftUriKind,
+    metadata_updater: Option<&(Bytes32, Program)>,
+) -> std::result::Result<(String, Option<Program>), String> {
+    let updater_hash = nft_info.metadata_updater_puzzle_hash;
+
+    let metadata_updater = if updater_hash == Bytes32::from(NFT_METADATA_UPDATER_DEFAULT_HASH) {
+        None
+    } else {
+        match metadata_updater {
+            Some((hash, puzzle)) if *hash == updater_hash => Some(puzzle.clone()),
+            _ => {
+                return Err(format!(
+                    "Uses the custom metadata updater {updater_hash}, which wasn't provided"
+                ));
+            }
+        }
+    };
+
+    let mut allocator = Allocator::new();
+
+    let metadata = nft_info
+        .metadata
+        .to_clvm(&mut allocator)
+        .ok()
+        .and_then(|ptr| NftMetadata::from_clvm(&allocator, ptr).ok())
+        .ok_or_else(|| "Metadata isn't in the standard format".to_string())?;
+
+    let existing_uris = match kind {
+        NftUriKind::Data => metadata.data_uris,
+        NftUriKind::Metadata => metadata.metadata_uris,
+        NftUriKind::License => metadata.license_uris,
+    };
+
+    let mut uri = template
+        .replace("{edition_number}", &metadata.edition_number.to_string())
+        .replace("{nft_id}", nft_id);
+
+    if uri.contains("{file_name}") {
+        let Some(file_name) = existing_uris.first().and_then(|uri| uri_file_name(uri)) else {
+            return Err("Has no existing URI to take the file name from".to_string());
+        };
+
+        uri = uri.replace("{file_name}", file_name);
+    }
+
+    if existing_uris.contains(&uri) {
+        return Err("Already has this URI".to_string());
+    }
+
+    Ok((uri, metadata_updater))
+}
+
+/// The last path segment of a URI, without the query string or fragment.
+fn uri_file_name(uri: &str) -> Option<&str> {
+    let path = uri.split(['?', '#']).next()?;
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let (_, file_name) = path.rsplit_once('/')?;
+    (!file_name.is_empty()).then_some(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const NFT_ID: &str = "nft1example";
+
+    fn nft_info(metadata: &NftMetadata, metadata_updater_puzzle_hash: Bytes32) -> NftCoinInfo {
+        let mut allocator = Allocator::new();
+        let ptr = metadata.to_clvm(&mut allocator).unwrap();
+
+        NftCoinInfo {
+            collection_hash: Bytes32::default(),
+            collection_name: None,
+            minter_hash: None,
+            owner_hash: None,
+            metadata: Program::from_clvm(&allocator, ptr).unwrap(),
+            metadata_updater_puzzle_hash,
+            royalty_puzzle_hash: Bytes32::default(),
+            royalty_basis_points: 0,
+            data_hash: None,
+            metadata_hash: None,
+            license_hash: None,
+            edition_number: None,
+            edition_total: None,
+        }
+    }
+
+    fn default_nft(data_uris: &[&str]) -> NftCoinInfo {
+        let metadata = NftMetadata {
+            edition_number: 7,
+            data_uris: data_uris.iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        };
+
+        nft_info(&metadata, NFT_METADATA_UPDATER_DEFAULT_HASH.into())
+    }
+
+    fn custom_updater() -> (Bytes32, Program) {
+        let mut allocator = Allocator::new();
+        let ptr = 1u8.to_clvm(&mut allocator).unwrap();
+        let hash = tree_hash(&allocator, ptr).into();
+        (hash, Program::from_clvm(&allocator, ptr).unwrap())
+    }
+
+    #[test]
+    fn test_plan_nft_uri_update_template() {
+        let nft = default_nft(&["https://example.com/images/7.png?v=1"]);
+
+        let (uri, metadata_updater) = plan_nft_uri_update(
+            &nft,
+            NFT_ID,
+            "https://mirror.example/{nft_id}/{edition_number}/{file_name}",
+            NftUriKind::Data,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(uri, "https://mirror.example/nft1example/7/7.png");
+        assert!(metadata_updater.is_none());
+    }
+
+    #[test]
+    fn test_plan_nft_uri_update_skips() {
+        let nft = default_nft(&["https://example.com/7.png"]);
+
+        assert!(
+            plan_nft_uri_update(
+                &nft,
+                NFT_ID,
+                "https://example.com/{file_name}",
+                NftUriKind::Data,
+                None,
+            )
+            .is_err()
+        );
+
+        assert!(
+            plan_nft_uri_update(
+                &nft,
+                NFT_ID,
+                "https://example.com/{file_name}",
+                NftUriKind::License,
+                None,
+            )
+            .is_err()
+        );
+
+        let mut allocator = Allocator::new();
+        let ptr = 1u8.to_clvm(&mut allocator).unwrap();
+        let nft = NftCoinInfo {
+            metadata: Program::from_clvm(&allocator, ptr).unwrap(),
+            ..nft
+        };
+
+        assert!(
+            plan_nft_uri_update(
+                &nft,
+                NFT_ID,
+                "https://mirror.example/{nft_id}",
+                NftUriKind::Data,
+                None,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_plan_nft_uri_update_custom_updater() {
+        let (hash, puzzle) = custom_updater();
+        let nft = nft_info(&NftMetadata::default(), hash);
+
+        assert!(
+            plan_nft_uri_update(
+                &nft,
+                NFT_ID,
+                "https://mirror.example/{nft_id}",
+                NftUriKind::Metadata,
+                None,
+            )
+            .is_err()
+        );
+
+        assert!(
+            plan_nft_uri_update(
+                &nft,
+                NFT_ID,
+                "https://mirror.example/{nft_id}",
+                NftUriKind::Metadata,
+                Some(&(Bytes32::default(), puzzle.clone())),
+            )
+            .is_err()
+        );
+
+        let (uri, metadata_updater) = plan_nft_uri_update(
+            &nft,
+            NFT_ID,
+            "https://mirror.example/{nft_id}",
+            NftUriKind::Metadata,
+            Some(&(hash, puzzle.clone())),
+        )
+        .unwrap();
+
+        assert_eq!(uri, "https://mirror.example/nft1example");
+        assert_eq!(metadata_updater, Some(puzzle));
+    }
+
+    #[test]
+    fn test_uri_file_name() {
+        assert_eq!(
+            uri_file_name("https://example.com/a/b.png?x=1#y"),
+            Some("b.png")
+        );
+        assert_eq!(uri_file_name("ipfs://cid/1.json"), Some("1.json"));
+        assert_eq!(uri_file_name("https://example.com/"), None);
+        assert_eq!(uri_file_name("https://example.com"), None);
+    }
+}
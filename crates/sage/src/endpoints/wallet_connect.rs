@@ -2,8 +2,8 @@ use std::slice;
 
 use chia_wallet_sdk::{
     chia::{
-        bls::{master_to_wallet_hardened, master_to_wallet_unhardened, sign},
-        puzzle_types::{DeriveSynthetic, Proof},
+        bls::{master_to_wallet_hardened, master_to_wallet_unhardened, sign, verify},
+        puzzle_types::{DeriveSynthetic, Proof, standard::StandardArgs},
     },
     driver::P2DelegatedConditionsLayer,
     prelude::*,
@@ -14,6 +14,9 @@ use sage_api::wallet_connect::{
     SendTransactionImmediatelyResponse, SignMessageByAddress, SignMessageByAddressResponse,
     SignMessageWithPublicKey, SignMessageWithPublicKeyResponse, SpendableCoin,
 };
+use sage_api::{
+    SignMessageWithDid, SignMessageWithDidResponse, VerifyDidSignature, VerifyDidSignatureResponse,
+};
 use sage_database::{AssetFilter, CoinFilterMode, CoinSortMode, DeserializePrimitive, P2Puzzle};
 use sage_wallet::{Status, SyncCommand, Transaction, insert_transaction, submit_to_peers};
 use tracing::{debug, info, warn};
@@ -205,9 +208,80 @@ impl Sage {
         &self,
         req: SignMessageByAddress,
     ) -> Result<SignMessageByAddressResponse> {
+        let p2_puzzle_hash = self.parse_address(req.address)?;
+
+        let (public_key, signature) = self
+            .sign_message_by_p2_puzzle_hash(p2_puzzle_hash, req.message)
+            .await?;
+
+        Ok(SignMessageByAddressResponse {
+            public_key: hex::encode(public_key.to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+
+    pub async fn sign_message_with_did(
+        &self,
+        req: SignMessageWithDid,
+    ) -> Result<SignMessageWithDidResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+
+        let Some(did) = wallet.db.did(did_id).await? else {
+            return Err(Error::MissingDid(did_id));
+        };
+
+        let (public_key, signature) = self
+            .sign_message_by_p2_puzzle_hash(did.info.p2_puzzle_hash, req.message)
+            .await?;
+
+        Ok(SignMessageWithDidResponse {
+            public_key: hex::encode(public_key.to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+
+    pub async fn verify_did_signature(
+        &self,
+        req: VerifyDidSignature,
+    ) -> Result<VerifyDidSignatureResponse> {
+        let wallet = self.wallet()?;
+        let did_id = parse_did_id(req.did_id)?;
+        let public_key = parse_public_key(req.public_key)?;
+        let signature = parse_signature(req.signature)?;
+        let decoded_message = parse_signature_message(req.message)?;
+
+        // The DID may belong to someone else, in which case its current owner is looked up on-chain.
+        let p2_puzzle_hash = if let Some(did) = wallet.db.did(did_id).await? {
+            did.info.p2_puzzle_hash
+        } else {
+            let peer = self
+                .peer_state
+                .lock()
+                .await
+                .acquire_peer()
+                .ok_or(Error::NoPeers)?;
+
+            wallet.fetch_did_p2_puzzle_hash(&peer, did_id).await?
+        };
+
+        let is_valid = p2_puzzle_hash == Bytes32::from(StandardArgs::curry_tree_hash(public_key))
+            && verify(
+                &signature,
+                &public_key,
+                ("Chia Signed Message", decoded_message).tree_hash(),
+            );
+
+        Ok(VerifyDidSignatureResponse { is_valid })
+    }
+
+    async fn sign_message_by_p2_puzzle_hash(
+        &self,
+        p2_puzzle_hash: Bytes32,
+        message: String,
+    ) -> Result<(PublicKey, Signature)> {
         let wallet = self.wallet()?;
 
-        let p2_puzzle_hash = self.parse_address(req.address)?;
         let Some(public_key) = wallet.db.public_key(p2_puzzle_hash).await? else {
             return Err(Error::InvalidKey);
         };
@@ -229,16 +303,13 @@ impl Sage {
         }
         .derive_synthetic();
 
-        let decoded_message = parse_signature_message(req.message)?;
+        let decoded_message = parse_signature_message(message)?;
         let signature = sign(
             &secret_key,
             ("Chia Signed Message", decoded_message).tree_hash(),
         );
 
-        Ok(SignMessageByAddressResponse {
-            public_key: hex::encode(public_key.to_bytes()),
-            signature: hex::encode(signature.to_bytes()),
-        })
+        Ok((public_key, signature))
     }
 
     pub async fn send_transaction_immediately(
@@ -276,6 +347,7 @@ impl Sage {
 
                 self.command_sender
                     .send(SyncCommand::SubscribeCoins {
+                        fingerprint: wallet.fingerprint,
                         coin_ids: subscriptions,
                     })
                     .await?;
@@ -14,7 +14,7 @@ use chia_wallet_sdk::{
 };
 use hex::FromHexError;
 use sage_api::ErrorKind;
-use sage_assets::UriError;
+use sage_assets::{ExchangeError, ProxyError, UriError};
 use sage_database::DatabaseError;
 use sage_keychain::KeychainError;
 use sage_wallet::{SyncCommand, WalletError};
@@ -50,6 +50,12 @@ pub enum Error {
     #[error("URI error: {0}")]
     Uri(#[from] UriError),
 
+    #[error("Offer exchange error: {0}")]
+    Exchange(#[from] ExchangeError),
+
+    #[error("Proxy error: {0}")]
+    Proxy(#[from] ProxyError),
+
     #[error("To CLVM error: {0}")]
     ToClvm(#[from] ToClvmError),
 
@@ -80,6 +86,12 @@ pub enum Error {
     #[error("Bincode error: {0}")]
     Bincode(#[from] bincode::Error),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unsupported peers file version: {0}")]
+    UnsupportedPeersVersion(u32),
+
     #[error("Logging initialization error: {0}")]
     LogSubscriber(#[from] TryInitError),
 
@@ -119,6 +131,9 @@ pub enum Error {
     #[error("Not logged in")]
     NotLoggedIn,
 
+    #[error("Wallet {0} is not loaded")]
+    WalletNotLoaded(u32),
+
     #[error("Invalid key")]
     InvalidKey,
 
@@ -200,6 +215,33 @@ pub enum Error {
     #[error("Missing offer: {0}")]
     MissingOffer(Bytes32),
 
+    #[error("Missing standing offer: {0}")]
+    MissingStandingOffer(u32),
+
+    #[error("Missing mint job: {0}")]
+    MissingMintJob(u32),
+
+    #[error("Invalid mint manifest: {0}")]
+    InvalidMintManifest(String),
+
+    #[error("Invalid batch size: {0}")]
+    InvalidBatchSize(u32),
+
+    #[error("Invalid limit: {0}")]
+    InvalidLimit(u32),
+
+    #[error("NFT mint {0} failed validation: {1}")]
+    InvalidNftMint(usize, String),
+
+    #[error("Invalid recovery list: {0}")]
+    InvalidRecoveryList(String),
+
+    #[error("Invalid network: {0}")]
+    InvalidNetwork(String),
+
+    #[error("Dexie is not available on this network")]
+    DexieUnavailable,
+
     #[error("Coin already spent: {0}")]
     CoinSpent(Bytes32),
 
@@ -209,6 +251,18 @@ pub enum Error {
     #[error("No peers are currently available")]
     NoPeers,
 
+    #[error("No outgoing clawbacks are available to claw back")]
+    NoClawbacks,
+
+    #[error("No expired options are available to reclaim")]
+    NoExpiredOptions,
+
+    #[error("CAT with asset id {0} was not issued by this wallet")]
+    NotIssuedCat(Bytes32),
+
+    #[error("CAT with asset id {0} is single issuance, so its supply can't be changed")]
+    SingleIssuanceCat(Bytes32),
+
     #[error("Could not fetch NFT with id: {0}")]
     CouldNotFetchNft(Bytes32),
 
@@ -265,11 +319,15 @@ impl Error {
             | Self::ToClvm(..)
             | Self::FromClvm(..)
             | Self::Bincode(..)
+            | Self::Json(..)
+            | Self::UnsupportedPeersVersion(..)
             | Self::Eval(..)
             | Self::Driver(..)
+            | Self::Proxy(..)
             | Self::Timeout(..) => ErrorKind::Internal,
             Self::UnknownFingerprint
             | Self::UnknownNetwork
+            | Self::WalletNotLoaded(..)
             | Self::MissingCoin(..)
             | Self::MissingCatCoin(..)
             | Self::MissingDidCoin(..)
@@ -278,7 +336,9 @@ impl Error {
             | Self::MissingDid(..)
             | Self::MissingNft(..)
             | Self::MissingOption(..)
-            | Self::MissingOffer(..) => ErrorKind::NotFound,
+            | Self::MissingOffer(..)
+            | Self::MissingStandingOffer(..)
+            | Self::MissingMintJob(..) => ErrorKind::NotFound,
             Self::Bls(..)
             | Self::Hex(..)
             | Self::InvalidKey
@@ -301,10 +361,22 @@ impl Error {
             | Self::InvalidPercentage(..)
             | Self::InvalidSignature(..)
             | Self::InvalidPublicKey(..)
+            | Self::InvalidMintManifest(..)
+            | Self::InvalidBatchSize(..)
+            | Self::InvalidLimit(..)
+            | Self::InvalidNftMint(..)
+            | Self::InvalidRecoveryList(..)
+            | Self::InvalidNetwork(..)
+            | Self::DexieUnavailable
             | Self::CoinSpent(..)
             | Self::Uri(..)
+            | Self::Exchange(..)
             | Self::IpAddrParse(..)
             | Self::NoPeers
+            | Self::NoClawbacks
+            | Self::NoExpiredOptions
+            | Self::NotIssuedCat(..)
+            | Self::SingleIssuanceCat(..)
             | Self::CouldNotFetchNft(..)
             | Self::CouldNotFetchOption(..)
             | Self::MissingAssetId
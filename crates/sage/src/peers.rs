@@ -3,21 +3,135 @@ use std::{
     net::IpAddr,
 };
 
+use sage_wallet::AddressBookEntry;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+use crate::{Error, Result};
+
+const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Peers {
+    pub version: u32,
     pub connections: HashSet<IpAddr>,
     pub user_managed: HashSet<IpAddr>,
     pub banned: HashMap<IpAddr, u64>,
+    #[serde(default)]
+    pub address_book: Vec<AddressBookEntry>,
+}
+
+impl Default for Peers {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            connections: HashSet::new(),
+            user_managed: HashSet::new(),
+            banned: HashMap::new(),
+            address_book: Vec::new(),
+        }
+    }
+}
+
+/// The format used before the address book was added, which is migrated on load.
+#[derive(Deserialize)]
+struct LegacyPeers {
+    connections: HashSet<IpAddr>,
+    user_managed: HashSet<IpAddr>,
+    banned: HashMap<IpAddr, u64>,
 }
 
 impl Peers {
-    pub fn to_bytes(&self) -> bincode::Result<Vec<u8>> {
-        bincode::serialize(self)
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> bincode::Result<Self> {
-        bincode::deserialize(bytes)
+    pub fn from_json(json: &str) -> Result<Self> {
+        let peers: Self = serde_json::from_str(json)?;
+
+        if peers.version != VERSION {
+            return Err(Error::UnsupportedPeersVersion(peers.version));
+        }
+
+        Ok(peers)
+    }
+
+    pub fn from_legacy_bytes(bytes: &[u8]) -> Result<Self> {
+        let legacy: LegacyPeers = bincode::deserialize(bytes)?;
+
+        Ok(Self {
+            connections: legacy.connections,
+            user_managed: legacy.user_managed,
+            banned: legacy.banned,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::net::Ipv4Addr;
+
+    use sage_wallet::PeerSource;
+
+    use super::*;
+
+    #[test]
+    fn test_migrate_legacy_peers() {
+        let connection = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let user_managed = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let banned = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3));
+
+        // The legacy format was a bincode encoded struct, which has the same layout as a tuple.
+        let bytes = bincode::serialize(&(
+            HashSet::from([connection]),
+            HashSet::from([user_managed]),
+            HashMap::from([(banned, 1000u64)]),
+        ))
+        .unwrap();
+
+        let peers = Peers::from_legacy_bytes(&bytes).unwrap();
+
+        assert_eq!(peers.version, VERSION);
+        assert_eq!(peers.connections, HashSet::from([connection]));
+        assert_eq!(peers.user_managed, HashSet::from([user_managed]));
+        assert_eq!(peers.banned, HashMap::from([(banned, 1000)]));
+        assert!(peers.address_book.is_empty());
+
+        // Once migrated, the peers are saved and loaded as JSON instead.
+        let mut peers = peers;
+
+        peers.address_book.push(AddressBookEntry {
+            ip: connection,
+            port: 8444,
+            source: PeerSource::Dns,
+            last_seen: 100,
+            last_success: Some(100),
+            failures: 0,
+            peak_height: Some(10),
+        });
+
+        let loaded = Peers::from_json(&peers.to_json().unwrap()).unwrap();
+
+        assert_eq!(loaded.connections, peers.connections);
+        assert_eq!(loaded.user_managed, peers.user_managed);
+        assert_eq!(loaded.banned, peers.banned);
+        assert_eq!(loaded.address_book, peers.address_book);
+    }
+
+    #[test]
+    fn test_unsupported_peers_version() {
+        let json = Peers {
+            version: VERSION + 1,
+            ..Default::default()
+        }
+        .to_json()
+        .unwrap();
+
+        assert!(matches!(
+            Peers::from_json(&json),
+            Err(Error::UnsupportedPeersVersion(version)) if version == VERSION + 1
+        ));
     }
 }
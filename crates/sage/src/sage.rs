@@ -1,5 +1,7 @@
 use std::{
+    collections::HashMap,
     fs,
+    net::SocketAddr,
     path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, Once},
@@ -14,18 +16,20 @@ use chia_wallet_sdk::{
 use indexmap::IndexMap;
 use sage_api::{Unit, XCH};
 use sage_config::{
-    Config, Network, NetworkList, OldConfig, OldNetwork, WalletConfig, migrate_config,
-    migrate_networks,
+    Config, Network, NetworkList, OldConfig, OldNetwork, ReputationSource, WalletConfig,
+    migrate_config, migrate_networks,
 };
 use sage_database::Database;
 use sage_keychain::Keychain;
-use sage_wallet::{PeerState, SyncCommand, SyncEvent, SyncManager, SyncOptions, Timeouts, Wallet};
+use sage_wallet::{
+    AddressBook, PeerState, SyncCommand, SyncManager, SyncOptions, Timeouts, Wallet, WalletEvent,
+};
 use sqlx::{
     ConnectOptions, SqlitePool,
     sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous},
 };
-use tokio::sync::{Mutex, mpsc};
-use tracing::{Level, error, info};
+use tokio::sync::{Mutex, mpsc, watch};
+use tracing::{Level, error, info, warn};
 use tracing_appender::rolling::{Builder, Rotation};
 use tracing_subscriber::{
     EnvFilter, Layer, Registry, filter::filter_fn, fmt, layer::SubscriberExt,
@@ -43,6 +47,9 @@ pub struct Sage {
     pub network_list: NetworkList,
     pub keychain: Keychain,
     pub wallet: Option<Arc<Wallet>>,
+    pub background_wallets: HashMap<u32, Arc<Wallet>>,
+    pub active_fingerprint: watch::Sender<Option<u32>>,
+    pub wallet_scope: Option<u32>,
     pub peer_state: Arc<Mutex<PeerState>>,
     pub command_sender: mpsc::Sender<SyncCommand>,
     pub unit: Unit,
@@ -58,6 +65,9 @@ impl Sage {
             network_list: NetworkList::default(),
             keychain: Keychain::default(),
             wallet: None,
+            background_wallets: HashMap::new(),
+            active_fingerprint: watch::channel(None).0,
+            wallet_scope: None,
             peer_state: Arc::new(Mutex::new(PeerState::default())),
             command_sender: mpsc::channel(1).0,
             unit: XCH.clone(),
@@ -65,14 +75,19 @@ impl Sage {
         }
     }
 
-    pub async fn initialize(&mut self) -> Result<mpsc::Receiver<SyncEvent>> {
+    pub async fn initialize(&mut self) -> Result<mpsc::Receiver<WalletEvent>> {
         fs::create_dir_all(&self.path)?;
 
         self.setup_keys()?;
         self.setup_config()?;
         self.setup_logging()?;
 
+        sage_assets::set_proxy(self.proxy()?)?;
+
         let receiver = self.setup_sync_manager()?;
+        self.command_sender
+            .send(SyncCommand::SetReputationSources(self.reputation_sources()))
+            .await?;
         self.setup_peers().await?;
 
         info!("Sage wallet initialized");
@@ -223,7 +238,7 @@ impl Sage {
         Ok(create_rustls_connector(&cert)?)
     }
 
-    fn setup_sync_manager(&mut self) -> Result<mpsc::Receiver<SyncEvent>> {
+    fn setup_sync_manager(&mut self) -> Result<mpsc::Receiver<WalletEvent>> {
         let connector = self.setup_ssl()?;
 
         let (sync_manager, command_sender, receiver) = SyncManager::new(
@@ -233,12 +248,11 @@ impl Sage {
                 max_peer_age_seconds: 3600 * 8,
                 dns_batch_size: 10,
                 connection_batch_size: 30,
-                delta_sync: self
-                    .wallet_config()
-                    .cloned()
-                    .unwrap_or_default()
-                    .delta_sync(&self.wallet_config.defaults),
                 puzzle_batch_size_per_peer: 5,
+                paranoid_sync: self.config.network.paranoid_sync,
+                header_validation: self.config.network.header_validation,
+                proxy: self.proxy()?,
+                dns_discovery: self.config.network.dns_discovery,
                 timeouts: if self.test {
                     Timeouts {
                         sync_delay: Duration::from_millis(100),
@@ -247,6 +261,11 @@ impl Sage {
                         puzzle_delay: Duration::from_millis(100),
                         transaction_delay: Duration::from_millis(100),
                         offer_delay: Duration::from_millis(100),
+                        clawback_delay: Duration::from_millis(100),
+                        coin_management_delay: Duration::from_millis(100),
+                        standing_offer_delay: Duration::from_millis(100),
+                        mint_job_delay: Duration::from_millis(100),
+                        royalty_delay: Duration::from_millis(100),
                         ..Default::default()
                     }
                 } else {
@@ -255,7 +274,6 @@ impl Sage {
                 testing: self.test,
             },
             self.peer_state.clone(),
-            self.wallet.clone(),
             self.network().clone(),
             connector,
         );
@@ -266,6 +284,31 @@ impl Sage {
         Ok(receiver)
     }
 
+    pub(crate) fn proxy(&self) -> Result<Option<SocketAddr>> {
+        Ok(self
+            .config
+            .network
+            .proxy
+            .as_deref()
+            .map(SocketAddr::from_str)
+            .transpose()?)
+    }
+
+    /// Relative file paths are resolved against the data directory.
+    pub(crate) fn reputation_sources(&self) -> Vec<ReputationSource> {
+        self.config
+            .reputation
+            .sources
+            .iter()
+            .map(|source| match source {
+                ReputationSource::File { path } => ReputationSource::File {
+                    path: self.path.join(path).to_string_lossy().to_string(),
+                },
+                ReputationSource::Http { url } => ReputationSource::Http { url: url.clone() },
+            })
+            .collect()
+    }
+
     pub async fn switch_network(&mut self) -> Result<()> {
         self.command_sender
             .send(SyncCommand::SwitchNetwork(self.network().clone()))
@@ -277,57 +320,203 @@ impl Sage {
     pub async fn switch_wallet(&mut self) -> Result<()> {
         self.switch_network().await?;
 
-        let Some(fingerprint) = self.config.global.fingerprint else {
-            self.wallet = None;
+        let fingerprint = self.config.global.fingerprint;
+
+        let mut unloaded = std::mem::take(&mut self.background_wallets);
+
+        if let Some(previous) = self.wallet.take() {
+            unloaded.insert(previous.fingerprint, previous);
+        }
+
+        let wallet = if let Some(fingerprint) = fingerprint {
+            let existing = unloaded.remove(&fingerprint);
+            Some(self.load_wallet(fingerprint, existing).await?)
+        } else {
+            None
+        };
+
+        for fingerprint in self.background_fingerprints() {
+            if Some(fingerprint) == self.config.global.fingerprint {
+                continue;
+            }
+
+            // Wallets which are already syncing only have their config updated.
+            let existing = unloaded.remove(&fingerprint);
+
+            match self.load_wallet(fingerprint, existing).await {
+                Ok(wallet) => {
+                    self.background_wallets.insert(fingerprint, wallet);
+                }
+                Err(error) => {
+                    warn!("Failed to load wallet {fingerprint} for background sync: {error}");
+                }
+            }
+        }
 
+        for fingerprint in unloaded.into_keys() {
             self.command_sender
-                .send(SyncCommand::SwitchWallet {
-                    wallet: None,
-                    delta_sync: self.wallet_config.defaults.delta_sync,
-                })
+                .send(SyncCommand::UnloadWallet { fingerprint })
                 .await?;
+        }
 
-            return Ok(());
+        self.wallet = wallet;
+        self.active_fingerprint.send_replace(fingerprint);
+        self.unit = Unit {
+            ticker: self.network().ticker.clone(),
+            precision: self.network().precision,
         };
 
+        Ok(())
+    }
+
+    /// The wallets on the current network which keep syncing while they aren't logged in.
+    fn background_fingerprints(&self) -> Vec<u32> {
+        let network = &self.network().name;
+
+        self.wallet_config
+            .wallets
+            .iter()
+            .filter(|wallet| {
+                wallet.background_sync
+                    && self.keychain.contains(wallet.fingerprint)
+                    && wallet
+                        .network
+                        .as_ref()
+                        .unwrap_or(&self.config.network.default_network)
+                        == network
+            })
+            .map(|wallet| wallet.fingerprint)
+            .collect()
+    }
+
+    /// Opens the wallet's database and starts syncing it alongside the other loaded wallets.
+    /// If the wallet is already loaded for the same network and change address, it's kept
+    /// syncing and only the rest of its config is sent to the sync manager.
+    async fn load_wallet(
+        &self,
+        fingerprint: u32,
+        existing: Option<Arc<Wallet>>,
+    ) -> Result<Arc<Wallet>> {
         let Some(master_pk) = self.keychain.extract_public_key(fingerprint)? else {
             return Err(Error::UnknownFingerprint);
         };
 
-        let intermediate_pk = master_to_wallet_unhardened_intermediate(&master_pk);
+        let wallet_config = self
+            .wallet_config
+            .wallets
+            .iter()
+            .find(|wallet| wallet.fingerprint == fingerprint)
+            .cloned()
+            .unwrap_or_default();
+
+        let change_p2_puzzle_hash = wallet_config
+            .change_address
+            .as_ref()
+            .map(|address| Address::decode(address))
+            .transpose()?
+            .map(|address| address.puzzle_hash);
+
+        let wallet = if let Some(existing) = existing
+            && existing.genesis_challenge == self.network().genesis_challenge
+            && existing.change_p2_puzzle_hash == change_p2_puzzle_hash
+        {
+            existing
+        } else {
+            let intermediate_pk = master_to_wallet_unhardened_intermediate(&master_pk);
+
+            let pool = self.connect_to_database(fingerprint).await?;
+            let db = Database::new(pool);
+
+            db.run_rust_migrations(self.network().ticker.clone())
+                .await?;
+
+            Arc::new(Wallet::new(
+                db,
+                fingerprint,
+                intermediate_pk,
+                self.network().genesis_challenge,
+                self.network().agg_sig_constants(),
+                change_p2_puzzle_hash,
+            ))
+        };
 
-        let pool = self.connect_to_database(fingerprint).await?;
-        let db = Database::new(pool);
+        let master_sk = self.signing_key(&wallet, &wallet_config).await?;
 
-        db.run_rust_migrations(self.network().ticker.clone())
+        self.command_sender
+            .send(SyncCommand::LoadWallet {
+                wallet: wallet.clone(),
+                delta_sync: wallet_config.delta_sync(&self.wallet_config.defaults),
+                master_sk,
+                clawback_policy: wallet_config.clawback_policy,
+                coin_management: wallet_config.coin_management,
+            })
             .await?;
 
-        let wallet_config = self.wallet_config().cloned().unwrap_or_default();
-
-        let wallet = Arc::new(Wallet::new(
-            db.clone(),
-            fingerprint,
-            intermediate_pk,
-            self.network().genesis_challenge,
-            AggSigConstants::new(self.network().agg_sig_me()),
-            wallet_config
-                .change_address
-                .as_ref()
-                .map(|address| Address::decode(address))
-                .transpose()?
-                .map(|address| address.puzzle_hash),
-        ));
-
-        self.wallet = Some(wallet.clone());
-        self.unit = Unit {
-            ticker: self.network().ticker.clone(),
-            precision: self.network().precision,
+        Ok(wallet)
+    }
+
+    /// Background tasks which submit transactions on our behalf need to be able to sign them,
+    /// so the signing key is only extracted if any of them are enabled.
+    async fn signing_key(
+        &self,
+        wallet: &Wallet,
+        wallet_config: &sage_config::Wallet,
+    ) -> Result<Option<SecretKey>> {
+        let has_standing_offers = wallet
+            .db
+            .standing_offers()
+            .await?
+            .iter()
+            .any(|standing_offer| standing_offer.is_active);
+
+        let has_mint_jobs = wallet
+            .db
+            .mint_jobs()
+            .await?
+            .iter()
+            .any(|mint_job| mint_job.is_active);
+
+        if wallet_config.clawback_policy.is_some()
+            || wallet_config.coin_management.is_some()
+            || has_standing_offers
+            || has_mint_jobs
+        {
+            Ok(self.keychain.extract_secrets(wallet.fingerprint, b"")?.1)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Sends the current policies of a wallet to the sync manager after they've changed,
+    /// along with its signing key if any background task needs it. Unlike reloading the
+    /// wallet, this only affects the one wallet and doesn't resync anything.
+    pub async fn configure_wallet(&self, fingerprint: u32) -> Result<()> {
+        let Some(wallet) = self
+            .wallet
+            .iter()
+            .chain(self.background_wallets.values())
+            .find(|wallet| wallet.fingerprint == fingerprint)
+            .cloned()
+        else {
+            return Ok(());
         };
 
+        let wallet_config = self
+            .wallet_config
+            .wallets
+            .iter()
+            .find(|wallet| wallet.fingerprint == fingerprint)
+            .cloned()
+            .unwrap_or_default();
+
+        let master_sk = self.signing_key(&wallet, &wallet_config).await?;
+
         self.command_sender
-            .send(SyncCommand::SwitchWallet {
-                wallet: Some(wallet),
-                delta_sync: wallet_config.delta_sync(&self.wallet_config.defaults),
+            .send(SyncCommand::ConfigureWallet {
+                fingerprint,
+                master_sk,
+                clawback_policy: wallet_config.clawback_policy,
+                coin_management: wallet_config.coin_management,
             })
             .await?;
 
@@ -341,19 +530,26 @@ impl Sage {
             fs::create_dir_all(&peer_dir)?;
         }
 
-        let peer_path = peer_dir.join(format!("{}.bin", self.network_id()));
+        let peer_path = peer_dir.join(format!("{}.json", self.network_id()));
+        let legacy_path = peer_dir.join(format!("{}.bin", self.network_id()));
 
         let peers = if peer_path.try_exists()? {
-            Peers::from_bytes(&fs::read(&peer_path)?).unwrap_or_else(|error| {
-                error!("Failed to load peers, reverting to default: {error}");
-                Peers::default()
-            })
+            Peers::from_json(&fs::read_to_string(&peer_path)?)
+        } else if legacy_path.try_exists()? {
+            Peers::from_legacy_bytes(&fs::read(&legacy_path)?)
         } else {
-            Peers::default()
+            Ok(Peers::default())
         };
 
+        let peers = peers.unwrap_or_else(|error| {
+            error!("Failed to load peers, reverting to default: {error}");
+            Peers::default()
+        });
+
         let mut state = self.peer_state.lock().await;
 
+        state.set_address_book(AddressBook::from_entries(peers.address_book.clone()));
+
         for (&ip, &timestamp) in &peers.banned {
             let now = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -418,8 +614,16 @@ impl Sage {
             peers.banned.insert(ip, ban);
         }
 
-        let peer_path = peer_dir.join(format!("{}.bin", self.network_id()));
-        fs::write(&peer_path, peers.to_bytes()?)?;
+        peers.address_book = state.address_book().entries();
+
+        let peer_path = peer_dir.join(format!("{}.json", self.network_id()));
+        fs::write(&peer_path, peers.to_json()?)?;
+
+        let legacy_path = peer_dir.join(format!("{}.bin", self.network_id()));
+
+        if legacy_path.try_exists()? {
+            fs::remove_file(&legacy_path)?;
+        }
 
         Ok(())
     }
@@ -473,13 +677,16 @@ impl Sage {
         Ok(path)
     }
 
+    /// The config of the scoped wallet, or of the logged in wallet if there is no scope.
     pub fn wallet_config(&self) -> Option<&sage_config::Wallet> {
-        self.config.global.fingerprint.and_then(|fingerprint| {
-            self.wallet_config
-                .wallets
-                .iter()
-                .find(|w| w.fingerprint == fingerprint)
-        })
+        self.wallet_scope
+            .or(self.config.global.fingerprint)
+            .and_then(|fingerprint| {
+                self.wallet_config
+                    .wallets
+                    .iter()
+                    .find(|w| w.fingerprint == fingerprint)
+            })
     }
 
     pub fn network(&self) -> &Network {
@@ -502,6 +709,16 @@ impl Sage {
     }
 
     pub fn wallet(&self) -> Result<Arc<Wallet>> {
+        if let Some(fingerprint) = self.wallet_scope {
+            return self
+                .wallet
+                .iter()
+                .chain(self.background_wallets.values())
+                .find(|wallet| wallet.fingerprint == fingerprint)
+                .cloned()
+                .ok_or(Error::WalletNotLoaded(fingerprint));
+        }
+
         let Some(fingerprint) = self.config.global.fingerprint else {
             return Err(Error::NotLoggedIn);
         };
@@ -519,6 +736,12 @@ impl Sage {
         Ok(wallet.clone())
     }
 
+    /// Makes [`Sage::wallet`] return the given loaded wallet instead of the logged in one,
+    /// until the scope is cleared again.
+    pub fn scope_wallet(&mut self, fingerprint: Option<u32>) {
+        self.wallet_scope = fingerprint;
+    }
+
     pub fn save_config(&self) -> Result<()> {
         let config = toml::to_string_pretty(&self.config)?;
         fs::write(self.path.join("config.toml"), config)?;
@@ -1,12 +1,17 @@
 mod cache;
 mod confirmation;
 mod conversions;
+mod exchange;
+mod mint_manifest;
 mod offer_status;
 mod offer_summary;
 mod parse;
+mod royalty_income;
 mod spends;
 
 pub use confirmation::*;
 pub use conversions::*;
+pub use mint_manifest::*;
 pub use offer_status::*;
 pub use parse::*;
+pub use royalty_income::*;
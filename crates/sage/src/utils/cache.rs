@@ -19,10 +19,14 @@ impl Sage {
             return Ok(asset);
         }
 
-        let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
-
-        let asset = if let Ok(Ok(asset)) =
-            timeout(Duration::from_secs(5), DexieCat::fetch(asset_id, testnet)).await
+        let services = self.network().services();
+
+        let asset = if let Some(dexie_api) = &services.dexie_api
+            && let Ok(Ok(asset)) = timeout(
+                Duration::from_secs(5),
+                DexieCat::fetch(asset_id, dexie_api, services.dexie_icons.as_deref()),
+            )
+            .await
         {
             Asset {
                 hash: asset_id,
@@ -79,13 +83,17 @@ impl Sage {
         }
 
         let info = if let Ok(metadata) = NftMetadata::from_clvm(allocator, nft_metadata) {
-            let testnet = self.network().genesis_challenge == TESTNET11_CONSTANTS.genesis_challenge;
+            let mintgarden_url = self.network().services().mintgarden_assets;
 
             if let Some(hash) = metadata.data_hash
                 && let Entry::Vacant(entry) = confirmation_info.nft_data.entry(hash)
                 && let Ok(Some(data)) = timeout(
                     Duration::from_secs(10),
-                    fetch_uris_with_hash(metadata.data_uris.clone(), hash, testnet),
+                    fetch_uris_with_hash(
+                        metadata.data_uris.clone(),
+                        hash,
+                        mintgarden_url.as_deref(),
+                    ),
                 )
                 .await
             {
@@ -96,7 +104,11 @@ impl Sage {
                 && let Entry::Vacant(entry) = confirmation_info.nft_data.entry(hash)
                 && let Ok(Some(data)) = timeout(
                     Duration::from_secs(10),
-                    fetch_uris_with_hash(metadata.metadata_uris.clone(), hash, testnet),
+                    fetch_uris_with_hash(
+                        metadata.metadata_uris.clone(),
+                        hash,
+                        mintgarden_url.as_deref(),
+                    ),
                 )
                 .await
             {
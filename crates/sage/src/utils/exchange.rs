@@ -0,0 +1,60 @@
+use chia_wallet_sdk::prelude::*;
+use sage_api::OfferExchangeProvider;
+use sage_assets::{DexieExchange, ExchangeError, ExchangeOffer, HttpExchange, OfferExchange};
+
+use crate::{Error, Sage};
+
+#[derive(Debug, Clone)]
+pub enum Exchange {
+    Dexie(DexieExchange),
+    Http(HttpExchange),
+}
+
+impl OfferExchange for Exchange {
+    async fn post_offer(&self, offer: &str) -> Result<String, ExchangeError> {
+        match self {
+            Self::Dexie(exchange) => exchange.post_offer(offer).await,
+            Self::Http(exchange) => exchange.post_offer(offer).await,
+        }
+    }
+
+    async fn search_offers(
+        &self,
+        offered: Option<Bytes32>,
+        requested: Option<Bytes32>,
+    ) -> Result<Vec<ExchangeOffer>, ExchangeError> {
+        match self {
+            Self::Dexie(exchange) => exchange.search_offers(offered, requested).await,
+            Self::Http(exchange) => exchange.search_offers(offered, requested).await,
+        }
+    }
+
+    async fn fetch_offer(&self, id: &str) -> Result<ExchangeOffer, ExchangeError> {
+        match self {
+            Self::Dexie(exchange) => exchange.fetch_offer(id).await,
+            Self::Http(exchange) => exchange.fetch_offer(id).await,
+        }
+    }
+}
+
+impl Sage {
+    pub(crate) fn offer_exchange(
+        &self,
+        provider: OfferExchangeProvider,
+    ) -> Result<Exchange, Error> {
+        Ok(match provider {
+            OfferExchangeProvider::Dexie => {
+                let dexie_api = self
+                    .network()
+                    .services()
+                    .dexie_api
+                    .ok_or(Error::DexieUnavailable)?;
+                Exchange::Dexie(DexieExchange::new(dexie_api))
+            }
+            OfferExchangeProvider::DexieCompatible { url } => {
+                Exchange::Dexie(DexieExchange::new(url))
+            }
+            OfferExchangeProvider::Http { url } => Exchange::Http(HttpExchange::new(url)),
+        })
+    }
+}
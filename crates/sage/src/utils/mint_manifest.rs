@@ -0,0 +1,268 @@
+use std::{mem, str::FromStr};
+
+use sage_api::{MintManifestFormat, NftMint};
+use serde::Deserialize;
+
+use crate::{Error, Result};
+
+/// A single row of a mint manifest. The royalty is optional, so that rows can override the default.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MintManifestRow {
+    pub address: Option<String>,
+    pub edition_number: Option<u32>,
+    pub edition_total: Option<u32>,
+    pub data_uris: Vec<String>,
+    pub data_hash: Option<String>,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: Option<String>,
+    pub license_uris: Vec<String>,
+    pub license_hash: Option<String>,
+    pub royalty_address: Option<String>,
+    pub royalty_ten_thousandths: Option<u16>,
+}
+
+impl MintManifestRow {
+    pub fn into_nft_mint(
+        self,
+        royalty_address: Option<String>,
+        royalty_ten_thousandths: u16,
+    ) -> NftMint {
+        NftMint {
+            address: self.address,
+            edition_number: self.edition_number,
+            edition_total: self.edition_total,
+            data_hash: self.data_hash,
+            data_uris: self.data_uris,
+            metadata_hash: self.metadata_hash,
+            metadata_uris: self.metadata_uris,
+            license_hash: self.license_hash,
+            license_uris: self.license_uris,
+            royalty_address: self.royalty_address.or(royalty_address),
+            royalty_ten_thousandths: self
+                .royalty_ten_thousandths
+                .unwrap_or(royalty_ten_thousandths),
+        }
+    }
+}
+
+/// Parses a mint manifest. JSON manifests are an array of rows, and CSV manifests have a header
+/// with the same field names. In CSV, multiple URIs in a single column are separated by whitespace.
+pub fn parse_mint_manifest(
+    manifest: &str,
+    format: MintManifestFormat,
+) -> Result<Vec<MintManifestRow>> {
+    let manifest = manifest.trim_start_matches('\u{feff}');
+
+    match format {
+        MintManifestFormat::Json => serde_json::from_str(manifest)
+            .map_err(|error| Error::InvalidMintManifest(error.to_string())),
+        MintManifestFormat::Csv => parse_csv_manifest(manifest),
+    }
+}
+
+fn parse_csv_manifest(manifest: &str) -> Result<Vec<MintManifestRow>> {
+    let mut records = csv_records(manifest)?.into_iter();
+
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+
+    let header: Vec<String> = header
+        .into_iter()
+        .map(|column| column.trim().to_string())
+        .collect();
+
+    let mut rows = Vec::new();
+
+    for (index, record) in records.enumerate() {
+        let row_number = index + 1;
+
+        if record.len() != header.len() {
+            return Err(Error::InvalidMintManifest(format!(
+                "Row {row_number} has {} columns, but the header has {}",
+                record.len(),
+                header.len()
+            )));
+        }
+
+        let mut row = MintManifestRow::default();
+
+        for (column, value) in header.iter().zip(record) {
+            let value = value.trim();
+
+            if value.is_empty() {
+                continue;
+            }
+
+            match column.as_str() {
+                "address" => row.address = Some(value.to_string()),
+                "edition_number" => {
+                    row.edition_number = Some(parse_csv_value(column, value, row_number)?);
+                }
+                "edition_total" => {
+                    row.edition_total = Some(parse_csv_value(column, value, row_number)?);
+                }
+                "data_uris" => row.data_uris = split_uris(value),
+                "data_hash" => row.data_hash = Some(value.to_string()),
+                "metadata_uris" => row.metadata_uris = split_uris(value),
+                "metadata_hash" => row.metadata_hash = Some(value.to_string()),
+                "license_uris" => row.license_uris = split_uris(value),
+                "license_hash" => row.license_hash = Some(value.to_string()),
+                "royalty_address" => row.royalty_address = Some(value.to_string()),
+                "royalty_ten_thousandths" => {
+                    row.royalty_ten_thousandths = Some(parse_csv_value(column, value, row_number)?);
+                }
+                _ => {
+                    return Err(Error::InvalidMintManifest(format!(
+                        "Unknown column {column}"
+                    )));
+                }
+            }
+        }
+
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+fn parse_csv_value<T: FromStr>(column: &str, value: &str, row_number: usize) -> Result<T> {
+    value.parse().map_err(|_| {
+        Error::InvalidMintManifest(format!("Invalid {column} on row {row_number}: {value}"))
+    })
+}
+
+fn split_uris(value: &str) -> Vec<String> {
+    value.split_whitespace().map(ToString::to_string).collect()
+}
+
+/// Splits CSV into records of fields, handling quoted fields and skipping blank lines.
+fn csv_records(input: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if in_quotes {
+            match char {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(char),
+            }
+            continue;
+        }
+
+        match char {
+            '"' => in_quotes = true,
+            ',' => record.push(mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(mem::take(&mut field));
+                records.push(mem::take(&mut record));
+            }
+            _ => field.push(char),
+        }
+    }
+
+    if in_quotes {
+        return Err(Error::InvalidMintManifest(
+            "Unterminated quoted field".to_string(),
+        ));
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records.retain(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_manifest() {
+        let manifest = "data_uris,data_hash,edition_number,royalty_ten_thousandths\r\n\
+            \"https://a.com/1.png https://b.com/1.png\",aa,1,\r\n\
+            \r\n\
+            \"https://a.com/2,3.png\",bb,2,500\r\n";
+
+        let rows = parse_mint_manifest(manifest, MintManifestFormat::Csv).unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                MintManifestRow {
+                    data_uris: vec![
+                        "https://a.com/1.png".to_string(),
+                        "https://b.com/1.png".to_string()
+                    ],
+                    data_hash: Some("aa".to_string()),
+                    edition_number: Some(1),
+                    ..Default::default()
+                },
+                MintManifestRow {
+                    data_uris: vec!["https://a.com/2,3.png".to_string()],
+                    data_hash: Some("bb".to_string()),
+                    edition_number: Some(2),
+                    royalty_ten_thousandths: Some(500),
+                    ..Default::default()
+                },
+            ]
+        );
+
+        let mints: Vec<NftMint> = rows
+            .into_iter()
+            .map(|row| row.into_nft_mint(None, 300))
+            .collect();
+
+        assert_eq!(mints[0].royalty_ten_thousandths, 300);
+        assert_eq!(mints[1].royalty_ten_thousandths, 500);
+    }
+
+    #[test]
+    fn test_parse_json_manifest() {
+        let manifest = r#"[
+            { "data_uris": ["https://a.com/1.png"], "edition_number": 1 },
+            { "metadata_uris": ["https://a.com/2.json"], "royalty_ten_thousandths": 0 }
+        ]"#;
+
+        let rows = parse_mint_manifest(manifest, MintManifestFormat::Json).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].edition_number, Some(1));
+        assert_eq!(rows[1].royalty_ten_thousandths, Some(0));
+    }
+
+    #[test]
+    fn test_parse_invalid_manifest() {
+        for manifest in [
+            "data_uris,unknown\nhttps://a.com/1.png,1",
+            "data_uris,edition_number\nhttps://a.com/1.png,one",
+            "data_uris,edition_number\nhttps://a.com/1.png",
+            "data_uris\n\"https://a.com/1.png",
+        ] {
+            assert!(matches!(
+                parse_mint_manifest(manifest, MintManifestFormat::Csv),
+                Err(Error::InvalidMintManifest(..))
+            ));
+        }
+
+        assert!(matches!(
+            parse_mint_manifest(r#"[{ "unknown": 1 }]"#, MintManifestFormat::Json),
+            Err(Error::InvalidMintManifest(..))
+        ));
+    }
+}
@@ -0,0 +1,110 @@
+use chia_wallet_sdk::prelude::*;
+use indexmap::IndexMap;
+use sage_api::{Amount, RoyaltyIncomeInterval, RoyaltyIncomeRecord};
+use sage_database::{Asset, RoyaltyPayment};
+
+use crate::{Result, Sage};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Royalty payments summed up per asset, in the order they were first received.
+#[derive(Debug, Default, Clone)]
+pub struct RoyaltyIncome {
+    assets: IndexMap<Bytes32, (Asset, u128, u32)>,
+}
+
+impl RoyaltyIncome {
+    pub fn add(&mut self, payment: &RoyaltyPayment) {
+        let (_, amount, count) = self
+            .assets
+            .entry(payment.asset.hash)
+            .or_insert_with(|| (payment.asset.clone(), 0, 0));
+
+        *amount += u128::from(payment.coin.amount);
+        *count += 1;
+    }
+}
+
+impl Sage {
+    pub fn royalty_income_records(
+        &self,
+        income: RoyaltyIncome,
+    ) -> Result<Vec<RoyaltyIncomeRecord>> {
+        income
+            .assets
+            .into_values()
+            .map(|(asset, amount, payment_count)| {
+                Ok(RoyaltyIncomeRecord {
+                    asset: self.encode_asset(asset)?,
+                    amount: Amount::u128(amount),
+                    payment_count,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The start of the UTC day, week (starting on Monday) or month which contains the timestamp.
+pub fn royalty_period_start(timestamp: u64, interval: RoyaltyIncomeInterval) -> u64 {
+    let days = timestamp / SECONDS_PER_DAY;
+
+    let start_day = match interval {
+        RoyaltyIncomeInterval::Day => days,
+        // The Unix epoch was on a Thursday.
+        RoyaltyIncomeInterval::Week => days - (days + 3) % 7,
+        RoyaltyIncomeInterval::Month => days + 1 - day_of_month(days),
+    };
+
+    start_day * SECONDS_PER_DAY
+}
+
+/// Converts days since the Unix epoch to the day of the month, using Howard Hinnant's civil calendar algorithm.
+fn day_of_month(days: u64) -> u64 {
+    let days = days + 719_468;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    day_of_year - (153 * month_index + 2) / 5 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_royalty_period_start() {
+        // Wednesday, 2024-02-28 15:30:00 UTC
+        let timestamp = 1_709_134_200;
+
+        assert_eq!(
+            royalty_period_start(timestamp, RoyaltyIncomeInterval::Day),
+            1_709_078_400
+        );
+        assert_eq!(
+            royalty_period_start(timestamp, RoyaltyIncomeInterval::Week),
+            1_708_905_600
+        );
+        assert_eq!(
+            royalty_period_start(timestamp, RoyaltyIncomeInterval::Month),
+            1_706_745_600
+        );
+
+        // Leap day, 2024-02-29 00:00:00 UTC
+        assert_eq!(
+            royalty_period_start(1_709_164_800, RoyaltyIncomeInterval::Month),
+            1_706_745_600
+        );
+
+        // Friday, 2024-03-01 00:00:00 UTC
+        assert_eq!(
+            royalty_period_start(1_709_251_200, RoyaltyIncomeInterval::Month),
+            1_709_251_200
+        );
+        assert_eq!(
+            royalty_period_start(1_709_251_200, RoyaltyIncomeInterval::Week),
+            1_708_905_600
+        );
+    }
+}
@@ -20,7 +20,7 @@ impl Sage {
         let spend_bundle = wallet
             .sign_transaction(
                 SpendBundle::new(coin_spends, Signature::default()),
-                &AggSigConstants::new(self.network().agg_sig_me()),
+                &self.network().agg_sig_constants(),
                 master_sk,
                 partial,
             )
@@ -50,6 +50,7 @@ impl Sage {
 
         self.command_sender
             .send(SyncCommand::SubscribeCoins {
+                fingerprint: wallet.fingerprint,
                 coin_ids: subscriptions,
             })
             .await?;
@@ -0,0 +1,35 @@
+/*
+ * Standing offers are templates which are turned back into a fresh offer
+ * whenever the previous one completes or expires.
+ */
+CREATE TABLE standing_offers (
+  id INTEGER NOT NULL PRIMARY KEY,
+  offered_asset_id INTEGER NOT NULL,
+  offered_amount BLOB NOT NULL,
+  requested_asset_id INTEGER NOT NULL,
+  requested_amount BLOB NOT NULL,
+  fee BLOB NOT NULL,
+  expiration_seconds INTEGER,
+  max_fills INTEGER,
+  max_spend BLOB,
+  is_active BOOLEAN NOT NULL DEFAULT TRUE,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  FOREIGN KEY (offered_asset_id) REFERENCES assets(id) ON DELETE CASCADE,
+  FOREIGN KEY (requested_asset_id) REFERENCES assets(id) ON DELETE CASCADE
+);
+
+/*
+ * Each offer created from a standing offer, with the status it ended with.
+ * The status uses the same values as the offers table.
+ */
+CREATE TABLE standing_offer_history (
+  id INTEGER NOT NULL PRIMARY KEY,
+  standing_offer_id INTEGER NOT NULL,
+  offer_hash BLOB NOT NULL UNIQUE,
+  status INTEGER NOT NULL,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  finished_timestamp INTEGER,
+  FOREIGN KEY (standing_offer_id) REFERENCES standing_offers(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_standing_offer_history_standing_offer_id ON standing_offer_history(standing_offer_id);
@@ -0,0 +1,4 @@
+/*
+ * NFT strikes are paid to a puzzle hash which can't be derived from the strike asset alone
+ */
+ALTER TABLE options ADD COLUMN strike_settlement_puzzle_hash BLOB;
@@ -0,0 +1,14 @@
+/*
+ * CATs issued by this wallet, along with the TAIL needed to issue or melt more of them.
+ * The issuance kind is 0 for single issuance, 1 for the wallet's multi-issuance key, and 2 for a custom TAIL.
+ */
+CREATE TABLE issued_cats (
+  id INTEGER NOT NULL PRIMARY KEY,
+  asset_id INTEGER NOT NULL UNIQUE,
+  tail_reveal BLOB NOT NULL,
+  issuance_kind INTEGER NOT NULL,
+  total_issued BLOB NOT NULL,
+  total_melted BLOB NOT NULL,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch()),
+  FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+);
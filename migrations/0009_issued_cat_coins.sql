@@ -0,0 +1,9 @@
+/*
+ * The coins created by each issuance of a CAT, which are where its outstanding supply is traced from.
+ */
+CREATE TABLE issued_cat_coins (
+  id INTEGER NOT NULL PRIMARY KEY,
+  issued_cat_id INTEGER NOT NULL,
+  coin_hash BLOB NOT NULL UNIQUE,
+  FOREIGN KEY (issued_cat_id) REFERENCES issued_cats(id) ON DELETE CASCADE
+);
@@ -0,0 +1,35 @@
+/*
+ * Mint jobs mint the rows of a manifest from a DID in the background,
+ * a batch at a time so that large drops don't have to fit in a single spend.
+ */
+CREATE TABLE mint_jobs (
+  id INTEGER NOT NULL PRIMARY KEY,
+  did_hash BLOB NOT NULL,
+  fee BLOB NOT NULL,
+  batch_size INTEGER NOT NULL,
+  is_active BOOLEAN NOT NULL DEFAULT TRUE,
+  created_timestamp INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+/*
+ * Each row of the manifest, with its metadata serialized as CLVM.
+ * The status is 0 while pending, 1 once submitted, and 2 once the NFT has been minted.
+ * The transaction and launcher are cleared again if the submission fails.
+ */
+CREATE TABLE mint_job_items (
+  id INTEGER NOT NULL PRIMARY KEY,
+  mint_job_id INTEGER NOT NULL,
+  row_index INTEGER NOT NULL,
+  metadata BLOB NOT NULL,
+  p2_puzzle_hash BLOB,
+  royalty_puzzle_hash BLOB,
+  royalty_basis_points INTEGER NOT NULL,
+  status INTEGER NOT NULL DEFAULT 0,
+  transaction_hash BLOB,
+  launcher_hash BLOB,
+  UNIQUE (mint_job_id, row_index),
+  FOREIGN KEY (mint_job_id) REFERENCES mint_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_mint_job_items_mint_job_id ON mint_job_items(mint_job_id);
+CREATE INDEX idx_mint_job_items_transaction_hash ON mint_job_items(transaction_hash);
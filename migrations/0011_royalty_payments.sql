@@ -0,0 +1,20 @@
+/*
+ * Royalty payments that were received from offer settlements, keyed by the NFT launcher id
+ * that was used as the settlement nonce. The NFT itself doesn't need to be in the wallet,
+ * since creators usually don't own the NFTs they receive royalties for.
+ */
+CREATE TABLE royalty_payments (
+  id INTEGER NOT NULL PRIMARY KEY,
+  coin_id INTEGER NOT NULL UNIQUE,
+  nft_hash BLOB NOT NULL,
+  FOREIGN KEY (coin_id) REFERENCES coins(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_royalty_payments_nft_hash ON royalty_payments(nft_hash);
+
+/*
+ * Received coins only need to be checked for royalty payments once.
+ */
+ALTER TABLE coins ADD COLUMN is_royalty_checked BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_coins_royalty_checked ON coins(is_royalty_checked, created_height);
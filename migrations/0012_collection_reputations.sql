@@ -0,0 +1,12 @@
+/*
+ * Collection ids and minter DIDs from the configured reputation sources. Allowlisted entries
+ * mark collections as verified, and blocklisted entries hide their NFTs. If an entry is in both
+ * an allowlist and a blocklist, it's blocked.
+ */
+CREATE TABLE collection_reputations (
+  id INTEGER NOT NULL PRIMARY KEY,
+  hash BLOB NOT NULL UNIQUE,
+  is_blocked BOOLEAN NOT NULL
+);
+
+CREATE INDEX idx_collection_reputations_blocked ON collection_reputations(is_blocked, hash);
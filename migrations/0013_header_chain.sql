@@ -0,0 +1,10 @@
+/*
+ * Blocks on the header chain that have been linked to their parent by header hash. Only
+ * blocks which are part of the chain being followed are validated, and the weight is used
+ * to decide whether to switch to a different chain.
+ */
+ALTER TABLE blocks ADD COLUMN prev_header_hash BLOB;
+ALTER TABLE blocks ADD COLUMN weight BLOB;
+ALTER TABLE blocks ADD COLUMN is_validated BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_validated_blocks ON blocks(is_validated, height DESC);
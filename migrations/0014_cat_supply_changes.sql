@@ -0,0 +1,13 @@
+/*
+ * Changes to the supply of issued CATs, each tied to a coin created by the transaction that made it.
+ * A change only counts towards the totals once that coin has been created on chain, so transactions
+ * which never confirm don't affect the supply.
+ */
+CREATE TABLE cat_supply_changes (
+  id INTEGER NOT NULL PRIMARY KEY,
+  issued_cat_id INTEGER NOT NULL,
+  coin_hash BLOB NOT NULL UNIQUE,
+  issued BLOB NOT NULL,
+  melted BLOB NOT NULL,
+  FOREIGN KEY (issued_cat_id) REFERENCES issued_cats(id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+/*
+ * Fees paid by automatic coin management transactions, so that the daily fee budget
+ * is still enforced after the wallet is reloaded or the app is restarted.
+ */
+CREATE TABLE coin_management_fees (
+  id INTEGER NOT NULL PRIMARY KEY,
+  transaction_hash BLOB NOT NULL UNIQUE,
+  fee BLOB NOT NULL,
+  paid_timestamp INTEGER NOT NULL
+);
+
+CREATE INDEX idx_coin_management_fees_paid_timestamp ON coin_management_fees(paid_timestamp);
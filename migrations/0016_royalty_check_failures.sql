@@ -0,0 +1,9 @@
+/*
+ * The number of times checking a coin for a royalty payment has failed, so that coins which
+ * can't be checked are retried last and eventually given up on.
+ */
+ALTER TABLE coins ADD COLUMN royalty_check_failures INTEGER NOT NULL DEFAULT 0;
+
+DROP INDEX idx_coins_royalty_checked;
+
+CREATE INDEX idx_coins_royalty_checked ON coins(is_royalty_checked, royalty_check_failures, created_height);
@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
 use sage::{Result, Sage};
-use sage_api::SyncEvent as ApiEvent;
-use sage_wallet::SyncEvent;
+use sage_api::{
+    Amount, CoinManagementAction as ApiCoinManagementAction, SyncEvent as ApiEvent,
+    SyncPhase as ApiSyncPhase,
+};
+use sage_wallet::{CoinManagementAction, SyncEvent, SyncPhase, WalletEvent};
 use tauri::{AppHandle, Emitter};
 use tokio::{sync::Mutex, task::JoinHandle};
 
@@ -14,9 +17,15 @@ pub type AppState = Arc<Mutex<Sage>>;
 
 pub async fn initialize(app_handle: AppHandle, sage: &mut Sage) -> Result<()> {
     let mut receiver = sage.initialize().await?;
+    let active_fingerprint = sage.active_fingerprint.subscribe();
 
     tokio::spawn(async move {
-        while let Some(event) = receiver.recv().await {
+        while let Some(WalletEvent { fingerprint, event }) = receiver.recv().await {
+            // Wallets syncing in the background aren't shown in the app.
+            if *active_fingerprint.borrow() != Some(fingerprint) {
+                continue;
+            }
+
             let event = match event {
                 SyncEvent::Start(ip) => ApiEvent::Start { ip: ip.to_string() },
                 SyncEvent::Stop => ApiEvent::Stop,
@@ -37,6 +46,42 @@ pub async fn initialize(app_handle: AppHandle, sage: &mut Sage) -> Result<()> {
                 SyncEvent::CatInfo => ApiEvent::CatInfo,
                 SyncEvent::DidInfo => ApiEvent::DidInfo,
                 SyncEvent::NftData => ApiEvent::NftData,
+                SyncEvent::CoinsManaged {
+                    asset_id,
+                    action,
+                    coin_count,
+                    fee,
+                    transaction_id,
+                } => ApiEvent::CoinsManaged {
+                    asset_id: asset_id.map(|asset_id| asset_id.to_string()),
+                    action: match action {
+                        CoinManagementAction::Split => ApiCoinManagementAction::Split,
+                        CoinManagementAction::Combine => ApiCoinManagementAction::Combine,
+                    },
+                    coin_count: coin_count.try_into().unwrap_or(u32::MAX),
+                    fee: Amount::u64(fee),
+                    transaction_id: transaction_id.to_string(),
+                },
+                SyncEvent::MintJobUpdated { mint_job_id } => ApiEvent::MintJob { mint_job_id },
+                SyncEvent::Reorg { fork_height } => ApiEvent::Reorg { fork_height },
+                SyncEvent::Progress {
+                    phase,
+                    completed,
+                    remaining,
+                    eta,
+                } => ApiEvent::SyncProgress {
+                    phase: match phase {
+                        SyncPhase::PuzzleSubscriptions => ApiSyncPhase::PuzzleSubscriptions,
+                        SyncPhase::CoinSubscriptions => ApiSyncPhase::CoinSubscriptions,
+                        SyncPhase::PuzzleLookups => ApiSyncPhase::PuzzleLookups,
+                        SyncPhase::NftUris => ApiSyncPhase::NftUris,
+                        SyncPhase::CatInfo => ApiSyncPhase::CatInfo,
+                        SyncPhase::BlockTimes => ApiSyncPhase::BlockTimes,
+                    },
+                    completed,
+                    remaining,
+                    eta_seconds: eta.map(|eta| eta.as_secs()),
+                },
             };
             if app_handle.emit("sync-event", event).is_err() {
                 break;
@@ -191,7 +191,7 @@ pub async fn download_cni_offercode(code: String) -> Result<String> {
         offer: String,
     }
 
-    let response = reqwest::Client::new()
+    let response = sage_assets::http_client()
         .post("https://offercodes.chia.net/download_offer")
         .json(&Request { code: code.clone() })
         .send()
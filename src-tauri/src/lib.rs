@@ -45,22 +45,41 @@ pub fn run() {
             commands::bulk_send_cat,
             commands::auto_combine_cat,
             commands::issue_cat,
+            commands::issue_cat_with_tail,
+            commands::reissue_cat,
+            commands::melt_cat,
+            commands::revoke_cat_coins,
             commands::create_did,
             commands::bulk_mint_nfts,
+            commands::validate_nft_mint,
+            commands::create_mint_job,
+            commands::get_mint_jobs,
+            commands::get_mint_job_items,
+            commands::set_mint_job_active,
+            commands::delete_mint_job,
             commands::transfer_nfts,
             commands::transfer_dids,
             commands::normalize_dids,
+            commands::update_did_profile,
+            commands::set_did_recovery_list,
+            commands::get_did_recovery_request,
+            commands::attest_did_recovery,
+            commands::recover_did,
             commands::mint_option,
             commands::transfer_options,
             commands::exercise_options,
+            commands::reclaim_expired_options,
             commands::add_nft_uri,
+            commands::add_nft_uris,
             commands::assign_nfts_to_did,
             commands::finalize_clawback,
+            commands::claw_back_all_outgoing,
             commands::create_transaction,
             commands::sign_coin_spends,
             commands::view_coin_spends,
             commands::submit_transaction,
             commands::get_sync_status,
+            commands::get_sync_progress,
             commands::get_version,
             commands::get_database_stats,
             commands::perform_database_maintenance,
@@ -71,12 +90,17 @@ pub fn run() {
             commands::get_coins_by_ids,
             commands::get_coins,
             commands::get_cats,
+            commands::get_issued_cats,
+            commands::get_cat_holders,
             commands::get_all_cats,
             commands::get_token,
             commands::get_dids,
             commands::get_minter_did_ids,
+            commands::sign_message_with_did,
+            commands::verify_did_signature,
             commands::get_options,
             commands::get_option,
+            commands::get_option_chain,
             commands::get_nft_collections,
             commands::get_nft_collection,
             commands::get_nfts,
@@ -85,10 +109,13 @@ pub fn run() {
             commands::get_nft_icon,
             commands::get_nft_thumbnail,
             commands::get_pending_transactions,
+            commands::get_clawbacks,
             commands::get_transaction,
             commands::get_transactions,
+            commands::get_royalty_income,
             commands::validate_address,
             commands::make_offer,
+            commands::make_offer_ladder,
             commands::take_offer,
             commands::combine_offers,
             commands::view_offer,
@@ -99,18 +126,38 @@ pub fn run() {
             commands::delete_offer,
             commands::cancel_offer,
             commands::cancel_offers,
+            commands::post_offer,
+            commands::search_exchange_offers,
+            commands::fetch_exchange_offer,
+            commands::create_standing_offer,
+            commands::get_standing_offers,
+            commands::set_standing_offer_active,
+            commands::delete_standing_offer,
+            commands::get_standing_offer_fills,
             commands::network_config,
             commands::set_discover_peers,
+            commands::set_paranoid_sync,
+            commands::set_header_validation,
+            commands::set_proxy,
+            commands::set_dns_discovery,
             commands::set_target_peers,
             commands::set_network,
             commands::set_network_override,
             commands::wallet_config,
             commands::default_wallet_config,
+            commands::add_network,
             commands::get_networks,
             commands::get_network,
             commands::set_delta_sync,
             commands::set_delta_sync_override,
+            commands::set_background_sync,
             commands::set_change_address,
+            commands::get_clawback_policy,
+            commands::set_clawback_policy,
+            commands::get_coin_management,
+            commands::set_coin_management,
+            commands::get_reputation_sources,
+            commands::set_reputation_sources,
             commands::update_cat,
             commands::resync_cat,
             commands::update_did,
@@ -120,6 +167,7 @@ pub fn run() {
             commands::redownload_nft,
             commands::increase_derivation_index,
             commands::get_peers,
+            commands::get_peer_address_book,
             commands::get_user_theme,
             commands::get_user_themes,
             commands::save_user_theme,
@@ -71,12 +71,42 @@ async autoCombineCat(req: AutoCombineCat) : Promise<AutoCombineCatResponse> {
 async issueCat(req: IssueCat) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("issue_cat", { req });
 },
+async issueCatWithTail(req: IssueCatWithTail) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("issue_cat_with_tail", { req });
+},
+async reissueCat(req: ReissueCat) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("reissue_cat", { req });
+},
+async meltCat(req: MeltCat) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("melt_cat", { req });
+},
+async revokeCatCoins(req: RevokeCatCoins) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("revoke_cat_coins", { req });
+},
 async createDid(req: CreateDid) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("create_did", { req });
 },
 async bulkMintNfts(req: BulkMintNfts) : Promise<BulkMintNftsResponse> {
     return await TAURI_INVOKE("bulk_mint_nfts", { req });
 },
+async validateNftMint(req: ValidateNftMint) : Promise<ValidateNftMintResponse> {
+    return await TAURI_INVOKE("validate_nft_mint", { req });
+},
+async createMintJob(req: CreateMintJob) : Promise<CreateMintJobResponse> {
+    return await TAURI_INVOKE("create_mint_job", { req });
+},
+async getMintJobs(req: GetMintJobs) : Promise<GetMintJobsResponse> {
+    return await TAURI_INVOKE("get_mint_jobs", { req });
+},
+async getMintJobItems(req: GetMintJobItems) : Promise<GetMintJobItemsResponse> {
+    return await TAURI_INVOKE("get_mint_job_items", { req });
+},
+async setMintJobActive(req: SetMintJobActive) : Promise<SetMintJobActiveResponse> {
+    return await TAURI_INVOKE("set_mint_job_active", { req });
+},
+async deleteMintJob(req: DeleteMintJob) : Promise<DeleteMintJobResponse> {
+    return await TAURI_INVOKE("delete_mint_job", { req });
+},
 async transferNfts(req: TransferNfts) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("transfer_nfts", { req });
 },
@@ -86,6 +116,21 @@ async transferDids(req: TransferDids) : Promise<TransactionResponse> {
 async normalizeDids(req: NormalizeDids) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("normalize_dids", { req });
 },
+async updateDidProfile(req: UpdateDidProfile) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("update_did_profile", { req });
+},
+async setDidRecoveryList(req: SetDidRecoveryList) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("set_did_recovery_list", { req });
+},
+async getDidRecoveryRequest(req: GetDidRecoveryRequest) : Promise<GetDidRecoveryRequestResponse> {
+    return await TAURI_INVOKE("get_did_recovery_request", { req });
+},
+async attestDidRecovery(req: AttestDidRecovery) : Promise<AttestDidRecoveryResponse> {
+    return await TAURI_INVOKE("attest_did_recovery", { req });
+},
+async recoverDid(req: RecoverDid) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("recover_did", { req });
+},
 async mintOption(req: MintOption) : Promise<MintOptionResponse> {
     return await TAURI_INVOKE("mint_option", { req });
 },
@@ -95,15 +140,24 @@ async transferOptions(req: TransferOptions) : Promise<TransactionResponse> {
 async exerciseOptions(req: ExerciseOptions) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("exercise_options", { req });
 },
+async reclaimExpiredOptions(req: ReclaimExpiredOptions) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("reclaim_expired_options", { req });
+},
 async addNftUri(req: AddNftUri) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("add_nft_uri", { req });
 },
+async addNftUris(req: AddNftUris) : Promise<AddNftUrisResponse> {
+    return await TAURI_INVOKE("add_nft_uris", { req });
+},
 async assignNftsToDid(req: AssignNftsToDid) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("assign_nfts_to_did", { req });
 },
 async finalizeClawback(req: FinalizeClawback) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("finalize_clawback", { req });
 },
+async clawBackAllOutgoing(req: ClawBackAllOutgoing) : Promise<TransactionResponse> {
+    return await TAURI_INVOKE("claw_back_all_outgoing", { req });
+},
 async createTransaction(req: CreateTransaction) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("create_transaction", { req });
 },
@@ -119,6 +173,9 @@ async submitTransaction(req: SubmitTransaction) : Promise<SubmitTransactionRespo
 async getSyncStatus(req: GetSyncStatus) : Promise<GetSyncStatusResponse> {
     return await TAURI_INVOKE("get_sync_status", { req });
 },
+async getSyncProgress(req: GetSyncProgress) : Promise<GetSyncProgressResponse> {
+    return await TAURI_INVOKE("get_sync_progress", { req });
+},
 async getVersion(req: GetVersion) : Promise<GetVersionResponse> {
     return await TAURI_INVOKE("get_version", { req });
 },
@@ -149,6 +206,12 @@ async getCoins(req: GetCoins) : Promise<GetCoinsResponse> {
 async getCats(req: GetCats) : Promise<GetCatsResponse> {
     return await TAURI_INVOKE("get_cats", { req });
 },
+async getIssuedCats(req: GetIssuedCats) : Promise<GetIssuedCatsResponse> {
+    return await TAURI_INVOKE("get_issued_cats", { req });
+},
+async getCatHolders(req: GetCatHolders) : Promise<GetCatHoldersResponse> {
+    return await TAURI_INVOKE("get_cat_holders", { req });
+},
 async getAllCats(req: GetAllCats) : Promise<GetAllCatsResponse> {
     return await TAURI_INVOKE("get_all_cats", { req });
 },
@@ -161,12 +224,21 @@ async getDids(req: GetDids) : Promise<GetDidsResponse> {
 async getMinterDidIds(req: GetMinterDidIds) : Promise<GetMinterDidIdsResponse> {
     return await TAURI_INVOKE("get_minter_did_ids", { req });
 },
+async signMessageWithDid(req: SignMessageWithDid) : Promise<SignMessageWithDidResponse> {
+    return await TAURI_INVOKE("sign_message_with_did", { req });
+},
+async verifyDidSignature(req: VerifyDidSignature) : Promise<VerifyDidSignatureResponse> {
+    return await TAURI_INVOKE("verify_did_signature", { req });
+},
 async getOptions(req: GetOptions) : Promise<GetOptionsResponse> {
     return await TAURI_INVOKE("get_options", { req });
 },
 async getOption(req: GetOption) : Promise<GetOptionResponse> {
     return await TAURI_INVOKE("get_option", { req });
 },
+async getOptionChain(req: GetOptionChain) : Promise<GetOptionChainResponse> {
+    return await TAURI_INVOKE("get_option_chain", { req });
+},
 async getNftCollections(req: GetNftCollections) : Promise<GetNftCollectionsResponse> {
     return await TAURI_INVOKE("get_nft_collections", { req });
 },
@@ -191,18 +263,27 @@ async getNftThumbnail(req: GetNftThumbnail) : Promise<GetNftThumbnailResponse> {
 async getPendingTransactions(req: GetPendingTransactions) : Promise<GetPendingTransactionsResponse> {
     return await TAURI_INVOKE("get_pending_transactions", { req });
 },
+async getClawbacks(req: GetClawbacks) : Promise<GetClawbacksResponse> {
+    return await TAURI_INVOKE("get_clawbacks", { req });
+},
 async getTransaction(req: GetTransaction) : Promise<GetTransactionResponse> {
     return await TAURI_INVOKE("get_transaction", { req });
 },
 async getTransactions(req: GetTransactions) : Promise<GetTransactionsResponse> {
     return await TAURI_INVOKE("get_transactions", { req });
 },
+async getRoyaltyIncome(req: GetRoyaltyIncome) : Promise<GetRoyaltyIncomeResponse> {
+    return await TAURI_INVOKE("get_royalty_income", { req });
+},
 async validateAddress(address: string) : Promise<boolean> {
     return await TAURI_INVOKE("validate_address", { address });
 },
 async makeOffer(req: MakeOffer) : Promise<MakeOfferResponse> {
     return await TAURI_INVOKE("make_offer", { req });
 },
+async makeOfferLadder(req: MakeOfferLadder) : Promise<MakeOfferLadderResponse> {
+    return await TAURI_INVOKE("make_offer_ladder", { req });
+},
 async takeOffer(req: TakeOffer) : Promise<TakeOfferResponse> {
     return await TAURI_INVOKE("take_offer", { req });
 },
@@ -233,12 +314,48 @@ async cancelOffer(req: CancelOffer) : Promise<TransactionResponse> {
 async cancelOffers(req: CancelOffers) : Promise<TransactionResponse> {
     return await TAURI_INVOKE("cancel_offers", { req });
 },
+async postOffer(req: PostOffer) : Promise<PostOfferResponse> {
+    return await TAURI_INVOKE("post_offer", { req });
+},
+async searchExchangeOffers(req: SearchExchangeOffers) : Promise<SearchExchangeOffersResponse> {
+    return await TAURI_INVOKE("search_exchange_offers", { req });
+},
+async fetchExchangeOffer(req: FetchExchangeOffer) : Promise<FetchExchangeOfferResponse> {
+    return await TAURI_INVOKE("fetch_exchange_offer", { req });
+},
+async createStandingOffer(req: CreateStandingOffer) : Promise<CreateStandingOfferResponse> {
+    return await TAURI_INVOKE("create_standing_offer", { req });
+},
+async getStandingOffers(req: GetStandingOffers) : Promise<GetStandingOffersResponse> {
+    return await TAURI_INVOKE("get_standing_offers", { req });
+},
+async setStandingOfferActive(req: SetStandingOfferActive) : Promise<SetStandingOfferActiveResponse> {
+    return await TAURI_INVOKE("set_standing_offer_active", { req });
+},
+async deleteStandingOffer(req: DeleteStandingOffer) : Promise<DeleteStandingOfferResponse> {
+    return await TAURI_INVOKE("delete_standing_offer", { req });
+},
+async getStandingOfferFills(req: GetStandingOfferFills) : Promise<GetStandingOfferFillsResponse> {
+    return await TAURI_INVOKE("get_standing_offer_fills", { req });
+},
 async networkConfig() : Promise<NetworkConfig> {
     return await TAURI_INVOKE("network_config");
 },
 async setDiscoverPeers(req: SetDiscoverPeers) : Promise<EmptyResponse> {
     return await TAURI_INVOKE("set_discover_peers", { req });
 },
+async setParanoidSync(req: SetParanoidSync) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_paranoid_sync", { req });
+},
+async setHeaderValidation(req: SetHeaderValidation) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_header_validation", { req });
+},
+async setProxy(req: SetProxy) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_proxy", { req });
+},
+async setDnsDiscovery(req: SetDnsDiscovery) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_dns_discovery", { req });
+},
 async setTargetPeers(req: SetTargetPeers) : Promise<EmptyResponse> {
     return await TAURI_INVOKE("set_target_peers", { req });
 },
@@ -254,6 +371,9 @@ async walletConfig(fingerprint: number) : Promise<Wallet | null> {
 async defaultWalletConfig() : Promise<WalletDefaults> {
     return await TAURI_INVOKE("default_wallet_config");
 },
+async addNetwork(req: AddNetwork) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("add_network", { req });
+},
 async getNetworks(req: GetNetworks) : Promise<NetworkList> {
     return await TAURI_INVOKE("get_networks", { req });
 },
@@ -266,9 +386,30 @@ async setDeltaSync(req: SetDeltaSync) : Promise<EmptyResponse> {
 async setDeltaSyncOverride(req: SetDeltaSyncOverride) : Promise<EmptyResponse> {
     return await TAURI_INVOKE("set_delta_sync_override", { req });
 },
+async setBackgroundSync(req: SetBackgroundSync) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_background_sync", { req });
+},
 async setChangeAddress(req: SetChangeAddress) : Promise<EmptyResponse> {
     return await TAURI_INVOKE("set_change_address", { req });
 },
+async getClawbackPolicy(req: GetClawbackPolicy) : Promise<GetClawbackPolicyResponse> {
+    return await TAURI_INVOKE("get_clawback_policy", { req });
+},
+async setClawbackPolicy(req: SetClawbackPolicy) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_clawback_policy", { req });
+},
+async getCoinManagement(req: GetCoinManagement) : Promise<GetCoinManagementResponse> {
+    return await TAURI_INVOKE("get_coin_management", { req });
+},
+async setCoinManagement(req: SetCoinManagement) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_coin_management", { req });
+},
+async getReputationSources(req: GetReputationSources) : Promise<GetReputationSourcesResponse> {
+    return await TAURI_INVOKE("get_reputation_sources", { req });
+},
+async setReputationSources(req: SetReputationSources) : Promise<EmptyResponse> {
+    return await TAURI_INVOKE("set_reputation_sources", { req });
+},
 async updateCat(req: UpdateCat) : Promise<UpdateCatResponse> {
     return await TAURI_INVOKE("update_cat", { req });
 },
@@ -296,6 +437,9 @@ async increaseDerivationIndex(req: IncreaseDerivationIndex) : Promise<IncreaseDe
 async getPeers(req: GetPeers) : Promise<GetPeersResponse> {
     return await TAURI_INVOKE("get_peers", { req });
 },
+async getPeerAddressBook(req: GetPeerAddressBook) : Promise<GetPeerAddressBookResponse> {
+    return await TAURI_INVOKE("get_peer_address_book", { req });
+},
 async getUserTheme(req: GetUserTheme) : Promise<GetUserThemeResponse> {
     return await TAURI_INVOKE("get_user_theme", { req });
 },
@@ -377,6 +521,14 @@ syncEvent: "sync-event"
 /** user-defined types **/
 
 export type Action = ({ type: "send" } & SendAction) | ({ type: "mint_nft" } & MintNftAction) | ({ type: "update_nft" } & UpdateNftAction) | ({ type: "fee" } & FeeAction)
+/**
+ * Add a custom network
+ */
+export type AddNetwork = { 
+/**
+ * Network configuration, with a name that isn't already in use
+ */
+network: Network }
 /**
  * Add a URI to an NFT
  */
@@ -401,6 +553,60 @@ kind: NftUriKind;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Add a mirror URI to every NFT in a collection
+ */
+export type AddNftUris = { 
+/**
+ * Collection ID of the NFTs
+ */
+collection_id: string; 
+/**
+ * URI template, where `{file_name}`, `{edition_number}` and `{nft_id}` are replaced for each NFT
+ */
+uri: string; 
+/**
+ * Type of URI
+ */
+kind: NftUriKind; 
+/**
+ * Puzzle reveal of a custom metadata updater, for NFTs which don't use the default one.
+ * It's passed the same solution as the default updater (`("u" . uri)`, `("mu" . uri)` or
+ * `("lu" . uri)`), so only updaters which accept that solution are supported.
+ */
+metadata_updater_puzzle?: string | null; 
+/**
+ * Maximum number of NFTs to update in each transaction
+ */
+batch_size?: number | null; 
+/**
+ * Transaction fee, which is paid by each transaction
+ */
+fee: Amount; 
+/**
+ * Whether to only list the affected NFTs, without creating any transactions
+ */
+dry_run?: boolean; 
+/**
+ * Whether to automatically submit the transactions
+ */
+auto_submit?: boolean }
+/**
+ * Response with the NFTs which a URI was added to
+ */
+export type AddNftUrisResponse = { 
+/**
+ * NFTs which the URI is added to
+ */
+updates: NftUriUpdate[]; 
+/**
+ * NFTs in the collection which can't be updated
+ */
+skipped: NftUriSkip[]; 
+/**
+ * Transactions which update each batch of NFTs, or empty for a dry run
+ */
+transactions: TransactionResponse[] }
 /**
  * Add a new peer to connect to
  */
@@ -409,7 +615,13 @@ export type AddPeer = {
  * IP address or hostname with port
  */
 ip: string }
+export type AddressBookRecord = { ip_addr: string; port: number; source: PeerSource; last_seen: number; last_success: number | null; failures: number; peak_height: number | null }
 export type AddressKind = "own" | "burn" | "launcher" | "offer" | "external" | "unknown"
+/**
+ * The additional data appended to each kind of `AGG_SIG_*` message before it's signed.
+ * Forks of Chia change these to prevent signatures from being replayed across networks.
+ */
+export type AggSigData = { me: string; parent: string; puzzle: string; amount: string; puzzle_amount: string; parent_amount: string; parent_puzzle: string }
 export type Amount = string | number
 export type Asset = { asset_id: string | null; name: string | null; ticker: string | null; precision: number; icon_url: string | null; description: string | null; is_sensitive_content: boolean; is_visible: boolean; revocation_address: string | null; kind: AssetKind }
 /**
@@ -437,6 +649,50 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Attest to the recovery of another DID
+ */
+export type AttestDidRecovery = { 
+/**
+ * DID ID to attest with
+ */
+did_id: string; 
+/**
+ * ID of the DID coin being recovered
+ */
+coin_id: string; 
+/**
+ * Inner puzzle hash of the DID once it has been recovered
+ */
+new_inner_puzzle_hash: string; 
+/**
+ * Public key that the DID is recovered to
+ */
+public_key: string; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+/**
+ * Response for attesting to a DID recovery
+ */
+export type AttestDidRecoveryResponse = { 
+/**
+ * Attestation to send to the wallet recovering the DID
+ */
+attestation: DidAttestation; 
+/**
+ * Transaction summary
+ */
+summary: TransactionSummary; 
+/**
+ * Coin spends in the transaction
+ */
+coin_spends: CoinSpendJson[] }
 /**
  * Automatically combine CAT coins
  */
@@ -532,7 +788,11 @@ fee: Amount;
 /**
  * Whether to automatically submit the transaction
  */
-auto_submit?: boolean }
+auto_submit?: boolean; 
+/**
+ * Whether to validate each NFT before minting, failing on the first one with warnings
+ */
+validate?: boolean }
 /**
  * Response for bulk NFT minting
  */
@@ -637,6 +897,8 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+export type CatHolderRecord = { address: string; amount: Amount; coin_ids: string[] }
+export type CatIssuanceKind = "single_issuance" | "multi_issuance" | "custom"
 /**
  * Validate and check an address
  */
@@ -653,6 +915,39 @@ export type CheckAddressResponse = {
  * Whether the address is valid and belongs to this wallet
  */
 valid: boolean }
+/**
+ * Claw back all outgoing clawbacks
+ */
+export type ClawBackAllOutgoing = { 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+/**
+ * Policy for handling clawbacks automatically in the background
+ */
+export type ClawbackPolicy = { 
+/**
+ * Whether to finalize incoming clawbacks once they have expired
+ */
+finalize_incoming: boolean; 
+/**
+ * Whether to claw back outgoing clawbacks shortly before they expire
+ */
+reclaim_outgoing: boolean; 
+/**
+ * How many seconds before expiration outgoing clawbacks are reclaimed
+ */
+reclaim_window_seconds: number; 
+/**
+ * Fee to use for each automatic transaction
+ */
+fee: Amount }
+export type ClawbackRecord = { coin_id: string; asset: Asset; amount: Amount; sender_address: string; receiver_address: string; expiration_timestamp: number; seconds_remaining: number; transaction_id: string | null; created_height: number | null; created_timestamp: number | null }
 /**
  * Coin structure
  */
@@ -671,6 +966,39 @@ puzzle_hash: string;
 amount: number }
 export type CoinFilterMode = "all" | "selectable" | "owned" | "spent" | "clawback"
 export type CoinJson = { parent_coin_info: string; puzzle_hash: string; amount: Amount }
+/**
+ * Standing policies for automatically splitting and combining coins
+ */
+export type CoinManagement = { 
+/**
+ * Fee to use for each automatic transaction
+ */
+fee: Amount; 
+/**
+ * Maximum total fees to spend in any 24 hour period
+ */
+daily_fee_budget: Amount; 
+/**
+ * Policies for each asset
+ */
+policies: CoinPolicy[] }
+export type CoinManagementAction = "split" | "combine"
+/**
+ * Coin policy for a single asset
+ */
+export type CoinPolicy = { 
+/**
+ * CAT asset ID (null for XCH)
+ */
+asset_id: string | null; 
+/**
+ * Keep a minimum number of coins of a given size
+ */
+split: SplitPolicy | null; 
+/**
+ * Combine small coins once there are too many
+ */
+combine: CombinePolicy | null }
 export type CoinRecord = { coin_id: string; address: string; amount: Amount; transaction_id: string | null; offer_id: string | null; clawback_timestamp: number | null; created_height: number | null; spent_height: number | null; spent_timestamp: number | null; created_timestamp: number | null }
 export type CoinSortMode = "coin_id" | "amount" | "created_height" | "spent_height" | "clawback_timestamp"
 /**
@@ -690,6 +1018,7 @@ puzzle_reveal: string;
  */
 solution: string }
 export type CoinSpendJson = { coin: CoinJson; puzzle_reveal: string; solution: string }
+export type CollectionRoyaltyIncomeRecord = { collection_id: string | null; name: string | null; income: RoyaltyIncomeRecord[] }
 /**
  * Combine multiple coins into one
  */
@@ -722,6 +1051,18 @@ export type CombineOffersResponse = {
  * Combined offer string
  */
 offer: string }
+/**
+ * Policy for combining small coins
+ */
+export type CombinePolicy = { 
+/**
+ * Coins at or below this amount are combined
+ */
+max_coin_amount: Amount; 
+/**
+ * Only combine when the asset has more coins than this
+ */
+coin_count_threshold: number }
 /**
  * Create a new DID
  */
@@ -738,6 +1079,95 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Create a mint job from a manifest
+ */
+export type CreateMintJob = { 
+/**
+ * DID ID to mint the NFTs from
+ */
+did_id: string; 
+/**
+ * Contents of the manifest file
+ */
+manifest: string; 
+/**
+ * Format of the manifest file
+ */
+format: MintManifestFormat; 
+/**
+ * Royalty address for rows which don't specify one
+ */
+royalty_address?: string | null; 
+/**
+ * Royalty percentage in ten-thousandths for rows which don't specify one
+ */
+royalty_ten_thousandths?: number; 
+/**
+ * Transaction fee for each batch
+ */
+fee: Amount; 
+/**
+ * Maximum number of NFTs minted in each transaction
+ */
+batch_size?: number | null }
+/**
+ * Response with the created mint job
+ */
+export type CreateMintJobResponse = { 
+/**
+ * Mint job ID
+ */
+mint_job_id: number; 
+/**
+ * Number of rows in the manifest
+ */
+item_count: number }
+/**
+ * Create a standing offer
+ */
+export type CreateStandingOffer = { 
+/**
+ * Asset ID to offer (null for XCH)
+ */
+offered_asset_id?: string | null; 
+/**
+ * Amount offered in each offer
+ */
+offered_amount: Amount; 
+/**
+ * Asset ID to request (null for XCH)
+ */
+requested_asset_id?: string | null; 
+/**
+ * Amount requested in each offer
+ */
+requested_amount: Amount; 
+/**
+ * Fee included in each offer
+ */
+fee: Amount; 
+/**
+ * How many seconds each offer is valid for (null for no expiration)
+ */
+expiration_seconds?: number | null; 
+/**
+ * Maximum number of times the offer can be filled
+ */
+max_fills?: number | null; 
+/**
+ * Maximum total amount of the offered asset to spend across all fills, including fees when
+ * the offered asset is XCH
+ */
+max_spend?: Amount | null }
+/**
+ * Response with the created standing offer
+ */
+export type CreateStandingOfferResponse = { 
+/**
+ * Standing offer ID
+ */
+standing_offer_id: number }
 export type CreateTransaction = { 
 /**
  * Pre-selected coins to use in the transaction prior to coin selection
@@ -779,6 +1209,18 @@ fingerprint: number }
  * Response for key deletion
  */
 export type DeleteKeyResponse = Record<string, never>
+/**
+ * Delete a mint job
+ */
+export type DeleteMintJob = { 
+/**
+ * Mint job ID to delete
+ */
+mint_job_id: number }
+/**
+ * Response for mint job deletion
+ */
+export type DeleteMintJobResponse = Record<string, never>
 /**
  * Delete an offer
  */
@@ -791,6 +1233,18 @@ offer_id: string }
  * Response for offer deletion
  */
 export type DeleteOfferResponse = Record<string, never>
+/**
+ * Delete a standing offer
+ */
+export type DeleteStandingOffer = { 
+/**
+ * Standing offer ID to delete
+ */
+standing_offer_id: number }
+/**
+ * Response for standing offer deletion
+ */
+export type DeleteStandingOfferResponse = Record<string, never>
 /**
  * Delete a theme NFT from the wallet
  */
@@ -801,10 +1255,31 @@ export type DeleteUserTheme = {
 nft_id: string }
 export type DeleteUserThemeResponse = Record<string, never>
 export type DerivationRecord = { index: number; public_key: string; address: string }
+/**
+ * An approval of a DID recovery by one of the DIDs in its recovery list
+ */
+export type DidAttestation = { 
+/**
+ * The DID that attested to the recovery
+ */
+attestor_did_id: string; 
+/**
+ * Parent coin ID of the attestor DID coin that created the message coin
+ */
+parent_coin_id: string; 
+/**
+ * Inner puzzle hash of the attestor DID coin
+ */
+inner_puzzle_hash: string; 
+/**
+ * Amount of the attestor DID coin
+ */
+amount: Amount }
 export type DidRecord = { launcher_id: string; name: string | null; visible: boolean; coin_id: string; address: string; amount: Amount; recovery_hash: string | null; created_height: number | null }
 export type EmptyResponse = Record<string, never>
 export type Error = { kind: ErrorKind; reason: string }
 export type ErrorKind = "wallet" | "api" | "not_found" | "unauthorized" | "internal" | "database_migration" | "nfc"
+export type ExchangeOfferRecord = { id: string; offer: string }
 /**
  * Exercise options
  */
@@ -826,6 +1301,26 @@ export type FeeAction = {
  * The fee amount, in mojos
  */
 amount: Amount }
+/**
+ * Fetch an offer from an exchange
+ */
+export type FetchExchangeOffer = { 
+/**
+ * Exchange to fetch the offer from
+ */
+exchange: OfferExchangeProvider; 
+/**
+ * Offer ID or short code
+ */
+id: string }
+/**
+ * Response with the fetched offer
+ */
+export type FetchExchangeOfferResponse = { 
+/**
+ * The offer
+ */
+offer: ExchangeOfferRecord }
 /**
  * Filter unlocked coins from a list
  */
@@ -858,6 +1353,19 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+export type FullNodeRpc = { 
+/**
+ * Defaults to the local full node.
+ */
+url?: string; 
+/**
+ * Path to the full node's private RPC certificate, `private_full_node.crt`.
+ */
+cert_path: string; 
+/**
+ * Path to the full node's private RPC key, `private_full_node.key`.
+ */
+key_path: string }
 /**
  * Generate a new mnemonic phrase for wallet creation
  */
@@ -927,13 +1435,90 @@ offset?: number | null;
  */
 limit?: number | null }
 /**
- * Get CAT tokens in wallet
+ * Get the holders of a CAT token issued by this wallet
  */
-export type GetCats = Record<string, never>
+export type GetCatHolders = { 
 /**
- * Response with CAT tokens
+ * Asset ID of the CAT
+ */
+asset_id: string; 
+/**
+ * Coin IDs to continue tracing from, as returned by a previous request. Defaults to the
+ * coins the CAT was issued with.
+ */
+coin_ids?: string[] | null; 
+/**
+ * Maximum number of coins to look up
+ */
+limit?: number | null }
+/**
+ * Response with the holders of a CAT token
+ */
+export type GetCatHoldersResponse = { 
+/**
+ * Holders found within the limit
+ */
+holders: CatHolderRecord[]; 
+/**
+ * Coin IDs which weren't traced because of the limit, which can be passed to continue
+ */
+remaining_coin_ids: string[] }
+/**
+ * Get CAT tokens in wallet
+ */
+export type GetCats = Record<string, never>
+/**
+ * Response with CAT tokens
  */
 export type GetCatsResponse = { cats: TokenRecord[] }
+/**
+ * Get the automatic clawback policy
+ */
+export type GetClawbackPolicy = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number }
+/**
+ * Response with the automatic clawback policy
+ */
+export type GetClawbackPolicyResponse = { 
+/**
+ * Clawback policy (null if clawbacks are not handled automatically)
+ */
+policy: ClawbackPolicy | null }
+/**
+ * List pending clawbacks
+ */
+export type GetClawbacks = Record<string, never>
+/**
+ * Response with pending clawbacks
+ */
+export type GetClawbacksResponse = { 
+/**
+ * Clawbacks sent by this wallet which can still be clawed back
+ */
+outgoing: ClawbackRecord[]; 
+/**
+ * Clawbacks sent to this wallet which can be finalized once expired
+ */
+incoming: ClawbackRecord[] }
+/**
+ * Get the automatic coin management policies
+ */
+export type GetCoinManagement = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number }
+/**
+ * Response with the automatic coin management policies
+ */
+export type GetCoinManagementResponse = { 
+/**
+ * Coin management policies (null if coins are not managed automatically)
+ */
+coin_management: CoinManagement | null }
 /**
  * List coins with filtering and pagination
  */
@@ -1054,6 +1639,30 @@ derivations: DerivationRecord[];
  * Total number of derivations available
  */
 total: number }
+/**
+ * Start recovering a DID into this wallet
+ */
+export type GetDidRecoveryRequest = { 
+/**
+ * DID ID to recover
+ */
+did_id: string }
+/**
+ * Response with the details of a DID recovery
+ */
+export type GetDidRecoveryRequestResponse = { 
+/**
+ * ID of the DID coin being recovered
+ */
+coin_id: string; 
+/**
+ * Inner puzzle hash of the DID once it has been recovered
+ */
+new_inner_puzzle_hash: string; 
+/**
+ * Public key that the DID is recovered to
+ */
+public_key: string }
 /**
  * List all DIDs in the wallet
  */
@@ -1066,6 +1675,14 @@ export type GetDidsResponse = {
  * List of DIDs
  */
 dids: DidRecord[] }
+/**
+ * Get CAT tokens issued by this wallet
+ */
+export type GetIssuedCats = Record<string, never>
+/**
+ * Response with issued CAT tokens
+ */
+export type GetIssuedCatsResponse = { cats: IssuedCatRecord[] }
 /**
  * Get a specific wallet key
  */
@@ -1094,6 +1711,34 @@ export type GetKeysResponse = {
  * List of wallet keys
  */
 keys: KeyInfo[] }
+/**
+ * List the rows of a mint job
+ */
+export type GetMintJobItems = { 
+/**
+ * Mint job ID
+ */
+mint_job_id: number }
+/**
+ * Response with the rows of a mint job
+ */
+export type GetMintJobItemsResponse = { 
+/**
+ * Rows of the manifest, in order
+ */
+items: MintJobItemRecord[] }
+/**
+ * List mint jobs
+ */
+export type GetMintJobs = Record<string, never>
+/**
+ * Response with mint jobs
+ */
+export type GetMintJobsResponse = { 
+/**
+ * List of mint jobs
+ */
+mint_jobs: MintJobRecord[] }
 /**
  * Get minter DIDs with pagination
  */
@@ -1279,9 +1924,13 @@ limit: number;
  */
 sort_mode: NftSortMode; 
 /**
- * Include hidden NFTs
+ * Include hidden NFTs, including those blocklisted by a reputation source
  */
-include_hidden: boolean }
+include_hidden: boolean; 
+/**
+ * Filter by whether the collection or minter DID is on a reputation allowlist
+ */
+verification?: NftVerificationFilter }
 /**
  * Response with NFTs list
  */
@@ -1346,6 +1995,22 @@ export type GetOption = {
  * Option ID
  */
 option_id: string }
+/**
+ * Get the option chain
+ */
+export type GetOptionChain = { 
+/**
+ * Include hidden options
+ */
+include_hidden?: boolean }
+/**
+ * Response with the option chain
+ */
+export type GetOptionChainResponse = { 
+/**
+ * Options grouped by underlying and strike asset
+ */
+groups: OptionChainGroup[] }
 /**
  * Response with option details
  */
@@ -1394,6 +2059,18 @@ options: OptionRecord[];
  * Total number of options
  */
 total: number }
+/**
+ * List every peer in the address book
+ */
+export type GetPeerAddressBook = Record<string, never>
+/**
+ * Response containing the peer address book
+ */
+export type GetPeerAddressBookResponse = { 
+/**
+ * Known peers, ordered by IP address
+ */
+peers: AddressBookRecord[] }
 /**
  * List all network peers
  */
@@ -1418,6 +2095,54 @@ export type GetPendingTransactionsResponse = {
  * List of pending transactions
  */
 transactions: PendingTransactionRecord[] }
+/**
+ * Get the collection reputation sources
+ */
+export type GetReputationSources = Record<string, never>
+/**
+ * Response with the collection reputation sources
+ */
+export type GetReputationSourcesResponse = { 
+/**
+ * Reputation sources, which are combined with blocklist entries taking precedence
+ */
+sources: ReputationSource[] }
+/**
+ * Get royalty income from NFT sales
+ */
+export type GetRoyaltyIncome = { 
+/**
+ * Only include payments received at or after this timestamp
+ */
+start_timestamp?: number | null; 
+/**
+ * Only include payments received before this timestamp
+ */
+end_timestamp?: number | null; 
+/**
+ * Interval to group the totals over time by, in UTC
+ */
+interval?: RoyaltyIncomeInterval }
+/**
+ * Response with royalty income
+ */
+export type GetRoyaltyIncomeResponse = { 
+/**
+ * Total income for each asset
+ */
+total: RoyaltyIncomeRecord[]; 
+/**
+ * Income for each collection
+ */
+collections: CollectionRoyaltyIncomeRecord[]; 
+/**
+ * Income for each NFT
+ */
+nfts: NftRoyaltyIncomeRecord[]; 
+/**
+ * Income for each interval, oldest first
+ */
+periods: RoyaltyIncomePeriodRecord[] }
 /**
  * Get wallet secret key
  */
@@ -1450,6 +2175,50 @@ export type GetSpendableCoinCountResponse = {
  * Number of spendable coins
  */
 count: number }
+/**
+ * List the fill history of standing offers
+ */
+export type GetStandingOfferFills = { 
+/**
+ * Only include fills for this standing offer
+ */
+standing_offer_id?: number | null }
+/**
+ * Response with standing offer fills
+ */
+export type GetStandingOfferFillsResponse = { 
+/**
+ * List of fills
+ */
+fills: StandingOfferFillRecord[] }
+/**
+ * List standing offers
+ */
+export type GetStandingOffers = Record<string, never>
+/**
+ * Response with standing offers
+ */
+export type GetStandingOffersResponse = { 
+/**
+ * List of standing offers
+ */
+standing_offers: StandingOfferRecord[] }
+/**
+ * Get the progress of each sync phase
+ */
+export type GetSyncProgress = Record<string, never>
+/**
+ * Response with the progress of each sync phase
+ */
+export type GetSyncProgressResponse = { 
+/**
+ * Phases which have had work since the wallet was loaded
+ */
+phases: SyncPhaseRecord[]; 
+/**
+ * Estimated seconds until every phase has caught up, if it can be estimated
+ */
+eta_seconds: number | null }
 /**
  * Get the current synchronization status
  */
@@ -1501,7 +2270,11 @@ total_files: number;
 /**
  * Database size in bytes
  */
-database_size: number }
+database_size: number; 
+/**
+ * Highest block whose header has been validated, if header validation is enabled
+ */
+validated_height: number | null }
 /**
  * Get detailed token information
  */
@@ -1730,12 +2503,73 @@ amount: Amount;
  * Transaction fee
  */
 fee: Amount; 
+/**
+ * Whether more of the token can be issued or melted later with this wallet's key
+ */
+multi_issuance?: boolean; 
+/**
+ * Whether coins of the token can be revoked back to this wallet
+ */
+revocable?: boolean; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+/**
+ * Issue a new CAT token with a custom TAIL
+ */
+export type IssueCatWithTail = { 
+/**
+ * Token name
+ */
+name: string; 
+/**
+ * Token ticker symbol
+ */
+ticker: string; 
+/**
+ * Initial supply amount
+ */
+amount: Amount; 
+/**
+ * Serialized TAIL program in hex
+ */
+tail: string; 
+/**
+ * Serialized TAIL solution in hex, defaults to nil
+ */
+tail_solution?: string | null; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether coins of the token can be revoked back to this wallet
+ */
+revocable?: boolean; 
 /**
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
-export type KeyInfo = { name: string; fingerprint: number; public_key: string; kind: KeyKind; has_secrets: boolean; network_id: string; emoji: string | null }
+export type IssuedCatRecord = { asset_id: string; name: string | null; ticker: string | null; tail: string; issuance_kind: CatIssuanceKind; total_issued: Amount; total_melted: Amount; created_timestamp: number }
+export type KeyInfo = { name: string; fingerprint: number; public_key: string; kind: KeyKind; has_secrets: boolean; network_id: string; emoji: string | null; background_sync: boolean }
 export type KeyKind = "bls"
+/**
+ * Offer created as part of a ladder
+ */
+export type LadderOffer = { 
+/**
+ * Offer string (bech32 encoded)
+ */
+offer: string; 
+/**
+ * Offer ID
+ */
+offer_id: string; 
+/**
+ * Amount requested in this rung
+ */
+price: Amount }
 /**
  * Lineage proof for CAT coins
  */
@@ -1804,7 +2638,63 @@ auto_import?: boolean;
 /**
  * Optional specific coin IDs to use for the offer instead of auto-selecting
  */
-coin_ids?: string[] | null }
+coin_ids?: string[] | null; 
+/**
+ * Optional offer exchange to post the offer to, before it's imported
+ */
+post_to?: OfferExchangeProvider | null }
+/**
+ * Create a ladder of offers at increasing prices
+ */
+export type MakeOfferLadder = { 
+/**
+ * Asset ID to offer (null for XCH)
+ */
+offered_asset_id?: string | null; 
+/**
+ * Asset ID to request (null for XCH)
+ */
+requested_asset_id?: string | null; 
+/**
+ * Amount offered in each rung
+ */
+amount_per_rung: Amount; 
+/**
+ * Amount requested in the lowest rung
+ */
+min_price: Amount; 
+/**
+ * Amount requested in the highest rung
+ */
+max_price: Amount; 
+/**
+ * Increase in the requested amount between rungs
+ */
+price_step: Amount; 
+/**
+ * Total fee shared between the split transaction and the offers
+ */
+fee: Amount; 
+/**
+ * Optional expiration timestamp
+ */
+expires_at_second?: number | null }
+/**
+ * Response with the created offer ladder
+ */
+export type MakeOfferLadderResponse = { 
+/**
+ * Offers from the lowest to the highest price
+ */
+offers: LadderOffer[]; 
+/**
+ * Coin IDs set aside for the offers
+ */
+coin_ids: string[]; 
+/**
+ * ID of the transaction which split the coins, if one was needed
+ */
+split_transaction_id: string | null }
 /**
  * Response with created offer
  */
@@ -1816,7 +2706,42 @@ offer: string;
 /**
  * Offer ID
  */
-offer_id: string }
+offer_id: string; 
+/**
+ * ID assigned by the offer exchange, if the offer was posted to one
+ */
+exchange_offer_id: string | null }
+/**
+ * Melt (burn) supply of an existing CAT token
+ */
+export type MeltCat = { 
+/**
+ * Asset ID of the CAT
+ */
+asset_id: string; 
+/**
+ * Amount to melt
+ */
+amount: Amount; 
+/**
+ * Serialized TAIL solution in hex, defaults to nil
+ */
+tail_solution?: string | null; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+export type MintJobItemRecord = { row_index: number; status: MintJobItemStatus; nft_id: string | null; transaction_id: string | null }
+export type MintJobItemStatus = "pending" | "submitted" | "minted"
+export type MintJobRecord = { mint_job_id: number; did_id: string; fee: Amount; batch_size: number; is_active: boolean; item_count: number; submitted_count: number; minted_count: number; creation_timestamp: number }
+/**
+ * Format of a mint manifest
+ */
+export type MintManifestFormat = "csv" | "json"
 export type MintNftAction = { 
 /**
  * The parent asset id of the minted NFT
@@ -1902,10 +2827,29 @@ summary: TransactionSummary;
  * Coin spends in the transaction
  */
 coin_spends: CoinSpendJson[] }
-export type Network = { name: string; ticker: string; prefix?: string | null; precision: number; network_id?: string | null; default_port: number; genesis_challenge: string; agg_sig_me?: string | null; dns_introducers: string[]; peer_introducers: string[]; inherit?: InheritedNetwork | null }
-export type NetworkConfig = { default_network: string; target_peers: number; discover_peers: boolean }
+export type Network = { name: string; ticker: string; prefix?: string | null; precision: number; network_id?: string | null; default_port: number; genesis_challenge: string; agg_sig_me?: string | null; 
+/**
+ * Overrides every `AGG_SIG_*` additional data value, rather than deriving them from
+ * `agg_sig_me`.
+ */
+agg_sig?: AggSigData | null; dns_introducers: string[]; peer_introducers: string[]; 
+/**
+ * Full nodes, as `ip` or `ip:port`, which are synced from exclusively instead of
+ * discovering peers. They are never banned.
+ */
+trusted_peers: string[]; 
+/**
+ * The RPC of a trusted full node, which coin records are read from if no peer is connected.
+ */
+full_node_rpc?: FullNodeRpc | null; services: NetworkServices; inherit?: InheritedNetwork | null }
+export type NetworkConfig = { default_network: string; target_peers: number; discover_peers: boolean; paranoid_sync: boolean; header_validation: boolean; proxy: string | null; dns_discovery: boolean }
 export type NetworkKind = "mainnet" | "testnet" | "unknown"
 export type NetworkList = { networks: Network[] }
+/**
+ * Base URLs of the indexers used for asset metadata. Any which aren't set are inherited from
+ * the network this one is based on, and are otherwise not used.
+ */
+export type NetworkServices = { dexie_api?: string | null; dexie_icons?: string | null; mintgarden_assets?: string | null }
 export type NewNftUri = { 
 /**
  * The type of URI
@@ -1915,7 +2859,7 @@ kind: NftUriKind;
  * The URI to add
  */
 uri: string }
-export type NftCollectionRecord = { collection_id: string; did_id: string; metadata_collection_id: string; visible: boolean; name: string | null; icon: string | null }
+export type NftCollectionRecord = { collection_id: string; did_id: string; metadata_collection_id: string; visible: boolean; verified: boolean; name: string | null; icon: string | null }
 export type NftData = { blob: string | null; mime_type: string | null; hash_matches: boolean; metadata_json: string | null; metadata_hash_matches: boolean }
 /**
  * Individual NFT to mint
@@ -1965,8 +2909,11 @@ royalty_address?: string | null;
  * Royalty percentage in ten-thousandths (e.g., 300 = 3%)
  */
 royalty_ten_thousandths?: number }
+export type NftMintWarning = { kind: NftMintWarningKind; uri: string | null; field: string | null; message: string }
+export type NftMintWarningKind = "unreachable_uri" | "hash_mismatch" | "inconsistent_uris" | "missing_uris" | "invalid_metadata_json" | "invalid_metadata"
 export type NftRecord = { launcher_id: string; collection_id: string | null; collection_name: string | null; minter_did: string | null; owner_did: string | null; visible: boolean; sensitive_content: boolean; name: string | null; created_height: number | null; coin_id: string; address: string; royalty_address: string; royalty_ten_thousandths: number; data_uris: string[]; data_hash: string | null; metadata_uris: string[]; metadata_hash: string | null; license_uris: string[]; license_hash: string | null; edition_number: number | null; edition_total: number | null; icon_url: string | null; created_timestamp: number | null; special_use_type: NftSpecialUseType | null }
 export type NftRoyalty = { royalty_address: string; royalty_basis_points: number }
+export type NftRoyaltyIncomeRecord = { nft_id: string; name: string | null; collection_id: string | null; income: RoyaltyIncomeRecord[] }
 export type NftSortMode = "name" | "recent"
 export type NftSpecialUseType = "none" | "theme"
 export type NftTransfer = { 
@@ -1979,11 +2926,36 @@ did_id: Id | null }
  */
 export type NftUriKind = "data" | "metadata" | "license"
 /**
- * Normalize DIDs to latest state
+ * An NFT which a URI can't be added to
  */
-export type NormalizeDids = { 
+export type NftUriSkip = { 
 /**
- * DID IDs to normalize
+ * NFT ID
+ */
+nft_id: string; 
+/**
+ * Why the NFT was skipped
+ */
+reason: string }
+/**
+ * A URI which will be added to an NFT
+ */
+export type NftUriUpdate = { 
+/**
+ * NFT ID
+ */
+nft_id: string; 
+/**
+ * URI being added
+ */
+uri: string }
+export type NftVerificationFilter = "all" | "verified" | "unverified"
+/**
+ * Normalize DIDs to latest state
+ */
+export type NormalizeDids = { 
+/**
+ * DID IDs to normalize
  */
 did_ids: string[]; 
 /**
@@ -2011,6 +2983,22 @@ hidden_puzzle_hash?: string | null;
  */
 amount: Amount }
 export type OfferAsset = { asset: Asset; amount: Amount; royalty: Amount; nft_royalty: NftRoyalty | null; option_assets: OptionAssets | null }
+/**
+ * Offer exchange to upload offers to or download offers from
+ */
+export type OfferExchangeProvider = 
+/**
+ * Dexie, for the current network
+ */
+{ type: "dexie" } | 
+/**
+ * Any service which implements the Dexie API
+ */
+{ type: "dexie_compatible"; url: string } | 
+/**
+ * Any service which implements the generic offer exchange API
+ */
+{ type: "http"; url: string }
 export type OfferRecord = { offer_id: string; offer: string; status: OfferRecordStatus; creation_timestamp: number; summary: OfferSummary }
 export type OfferRecordStatus = "pending" | "active" | "completed" | "cancelled" | "expired"
 export type OfferSummary = { fee: Amount; maker: OfferAsset[]; taker: OfferAsset[]; expiration_height: number | null; expiration_timestamp: number | null }
@@ -2019,17 +3007,27 @@ export type OfferSummary = { fee: Amount; maker: OfferAsset[]; taker: OfferAsset
  */
 export type OptionAsset = { 
 /**
- * Asset ID (null for XCH)
+ * Asset ID or NFT ID (null for XCH)
  */
 asset_id: string | null; 
 /**
- * Amount
+ * Amount (must be 1 for NFTs)
  */
 amount: Amount }
 export type OptionAssets = { underlying_asset: Asset; underlying_amount: Amount; strike_asset: Asset; strike_amount: Amount; expiration_seconds: number }
+export type OptionChainExpiration = { expiration_seconds: number; options: OptionChainRecord[] }
+export type OptionChainGroup = { underlying_asset: Asset; strike_asset: Asset; expirations: OptionChainExpiration[] }
+export type OptionChainRecord = { launcher_id: string; name: string | null; underlying_amount: Amount; strike_amount: Amount; status: OptionStatus; is_owned: boolean; is_creator: boolean }
 export type OptionRecord = { launcher_id: string; name: string | null; visible: boolean; coin_id: string; address: string; amount: Amount; underlying_asset: Asset; underlying_amount: Amount; underlying_coin_id: string; strike_asset: Asset; strike_amount: Amount; expiration_seconds: number; created_height: number | null; created_timestamp: number | null }
 export type OptionSortMode = "name" | "created_height" | "expiration_seconds"
+export type OptionStatus = "live" | "exercised" | "expired" | "reclaimed" | 
+/**
+ * The underlying was spent, but the timestamp of the block isn't known yet, so it's unclear
+ * whether it was exercised or reclaimed.
+ */
+"unknown"
 export type PeerRecord = { ip_addr: string; port: number; peak_height: number; user_managed: boolean }
+export type PeerSource = "dns" | "introducer" | "peer_exchange" | "user"
 export type PendingTransactionRecord = { transaction_id: string; fee: Amount; submitted_at: number | null }
 /**
  * Perform database maintenance operations
@@ -2067,6 +3065,66 @@ pages_vacuumed: number;
  * Number of WAL pages checkpointed
  */
 wal_pages_checkpointed: number }
+/**
+ * Post an offer to an exchange
+ */
+export type PostOffer = { 
+/**
+ * Offer string (bech32 encoded)
+ */
+offer: string; 
+/**
+ * Exchange to post the offer to
+ */
+exchange: OfferExchangeProvider }
+/**
+ * Response with the posted offer
+ */
+export type PostOfferResponse = { 
+/**
+ * ID assigned by the offer exchange
+ */
+id: string }
+/**
+ * Reclaim expired options
+ */
+export type ReclaimExpiredOptions = { 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+/**
+ * Recover a DID into this wallet
+ */
+export type RecoverDid = { 
+/**
+ * DID ID to recover
+ */
+did_id: string; 
+/**
+ * Public key from the recovery request
+ */
+public_key: string; 
+/**
+ * The DID's recovery list, in order
+ */
+recovery_did_ids: string[]; 
+/**
+ * Attestations from the recovery DIDs
+ */
+attestations: DidAttestation[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Re-download an `NFT`'s data and metadata from its URIs
  */
@@ -2079,6 +3137,30 @@ nft_id: string }
  * Response after re-downloading an `NFT`
  */
 export type RedownloadNftResponse = Record<string, never>
+/**
+ * Issue more of an existing CAT token
+ */
+export type ReissueCat = { 
+/**
+ * Asset ID of the CAT
+ */
+asset_id: string; 
+/**
+ * Amount to issue
+ */
+amount: Amount; 
+/**
+ * Serialized TAIL solution in hex, defaults to nil
+ */
+tail_solution?: string | null; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Remove a peer from the connection list
  */
@@ -2107,6 +3189,18 @@ name: string }
  * Response for key rename
  */
 export type RenameKeyResponse = Record<string, never>
+/**
+ * Source of a collection allowlist or blocklist
+ */
+export type ReputationSource = 
+/**
+ * JSON file on disk, relative to the data directory unless absolute
+ */
+{ type: "file"; path: string } | 
+/**
+ * JSON feed fetched over HTTP
+ */
+{ type: "http"; url: string }
 /**
  * Resynchronize wallet data with the blockchain
  */
@@ -2155,6 +3249,25 @@ export type ResyncCatResponse = Record<string, never>
  * Response from resynchronizing the wallet
  */
 export type ResyncResponse = Record<string, never>
+/**
+ * Revoke CAT coins back to the issuer
+ */
+export type RevokeCatCoins = { 
+/**
+ * IDs of the CAT coins to revoke, which can be held by anyone
+ */
+coin_ids: string[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
+export type RoyaltyIncomeInterval = "day" | "week" | "month"
+export type RoyaltyIncomePeriodRecord = { start_timestamp: number; income: RoyaltyIncomeRecord[] }
+export type RoyaltyIncomeRecord = { asset: Asset; amount: Amount; payment_count: number }
 /**
  * Save a theme NFT to the wallet
  */
@@ -2164,6 +3277,30 @@ export type SaveUserTheme = {
  */
 nft_id: string }
 export type SaveUserThemeResponse = Record<string, never>
+/**
+ * Search an exchange for offers
+ */
+export type SearchExchangeOffers = { 
+/**
+ * Exchange to search
+ */
+exchange: OfferExchangeProvider; 
+/**
+ * Asset ID being offered (null for XCH)
+ */
+offered_asset_id?: string | null; 
+/**
+ * Asset ID being requested (null for XCH)
+ */
+requested_asset_id?: string | null }
+/**
+ * Response with offers found on the exchange
+ */
+export type SearchExchangeOffersResponse = { 
+/**
+ * List of offers
+ */
+offers: ExchangeOfferRecord[] }
 export type SecretKeyInfo = { mnemonic: string | null; secret_key: string }
 export type SendAction = { 
 /**
@@ -2270,6 +3407,18 @@ clawback?: number | null;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Keep a wallet syncing while it isn't logged in
+ */
+export type SetBackgroundSync = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number; 
+/**
+ * Whether to sync the wallet in the background
+ */
+background_sync: boolean }
 /**
  * Set the change address for transactions
  */
@@ -2282,6 +3431,30 @@ fingerprint: number;
  * Change address (null to use default derivation)
  */
 change_address: string | null }
+/**
+ * Set the automatic clawback policy
+ */
+export type SetClawbackPolicy = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number; 
+/**
+ * Clawback policy (null to disable automatic clawback handling)
+ */
+policy: ClawbackPolicy | null }
+/**
+ * Set the automatic coin management policies
+ */
+export type SetCoinManagement = { 
+/**
+ * Wallet fingerprint
+ */
+fingerprint: number; 
+/**
+ * Coin management policies (null to disable automatic coin management)
+ */
+coin_management: CoinManagement | null }
 /**
  * Enable or disable delta sync
  */
@@ -2302,6 +3475,30 @@ fingerprint: number;
  * Delta sync setting (null to use default)
  */
 delta_sync: boolean | null }
+/**
+ * Set a DID's recovery list
+ */
+export type SetDidRecoveryList = { 
+/**
+ * DID ID to update
+ */
+did_id: string; 
+/**
+ * DIDs that can attest to a recovery, or empty to disable recovery
+ */
+recovery_did_ids: string[]; 
+/**
+ * Number of attestations required for a recovery
+ */
+num_verifications_required: number; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Enable or disable automatic peer discovery
  */
@@ -2310,6 +3507,38 @@ export type SetDiscoverPeers = {
  * Whether to enable peer discovery
  */
 discover_peers: boolean }
+/**
+ * Enable or disable DNS peer discovery
+ */
+export type SetDnsDiscovery = { 
+/**
+ * Whether to use DNS and peer introducers
+ */
+dns_discovery: boolean }
+/**
+ * Enable or disable header validation
+ */
+export type SetHeaderValidation = { 
+/**
+ * Whether to validate the header chain
+ */
+header_validation: boolean }
+/**
+ * Pause or resume a mint job
+ */
+export type SetMintJobActive = { 
+/**
+ * Mint job ID
+ */
+mint_job_id: number; 
+/**
+ * Whether new batches should be submitted
+ */
+is_active: boolean }
+/**
+ * Response for pausing or resuming a mint job
+ */
+export type SetMintJobActiveResponse = Record<string, never>
 /**
  * Set the active network
  */
@@ -2330,6 +3559,46 @@ fingerprint: number;
  * Network name (null to reset to default)
  */
 name: string | null }
+/**
+ * Enable or disable paranoid sync
+ */
+export type SetParanoidSync = { 
+/**
+ * Whether to verify coin states against a second peer
+ */
+paranoid_sync: boolean }
+/**
+ * Set the SOCKS5 proxy for network connections
+ */
+export type SetProxy = { 
+/**
+ * Address of the SOCKS5 proxy, or null to connect directly
+ */
+proxy: string | null }
+/**
+ * Set the collection reputation sources
+ */
+export type SetReputationSources = { 
+/**
+ * Reputation sources (empty to disable collection verification and spam filtering)
+ */
+sources: ReputationSource[] }
+/**
+ * Pause or resume a standing offer
+ */
+export type SetStandingOfferActive = { 
+/**
+ * Standing offer ID
+ */
+standing_offer_id: number; 
+/**
+ * Whether new offers should be created
+ */
+is_active: boolean }
+/**
+ * Response for pausing or resuming a standing offer
+ */
+export type SetStandingOfferActiveResponse = Record<string, never>
 /**
  * Set target number of peers to maintain
  */
@@ -2402,6 +3671,30 @@ publicKey: string;
  * Signature
  */
 signature: string }
+/**
+ * Sign a message with a DID
+ */
+export type SignMessageWithDid = { 
+/**
+ * DID ID to sign with
+ */
+did_id: string; 
+/**
+ * Message to sign
+ */
+message: string }
+/**
+ * Response with the DID signature
+ */
+export type SignMessageWithDidResponse = { 
+/**
+ * Public key that owns the DID
+ */
+public_key: string; 
+/**
+ * Signature
+ */
+signature: string }
 /**
  * Sign a message with a public key
  */
@@ -2483,6 +3776,20 @@ fee: Amount;
  * Whether to automatically submit the transaction
  */
 auto_submit?: boolean }
+/**
+ * Policy for keeping a minimum number of coins of a given size
+ */
+export type SplitPolicy = { 
+/**
+ * Minimum number of coins to keep
+ */
+min_coins: number; 
+/**
+ * Target size of each coin (coins within half of this amount are counted)
+ */
+coin_amount: Amount }
+export type StandingOfferFillRecord = { standing_offer_id: number; offer_id: string; offered_amount: Amount; requested_amount: Amount; creation_timestamp: number; fill_timestamp: number | null }
+export type StandingOfferRecord = { standing_offer_id: number; offered_asset: Asset; offered_amount: Amount; requested_asset: Asset; requested_amount: Amount; fee: Amount; expiration_seconds: number | null; max_fills: number | null; max_spend: Amount | null; is_active: boolean; fill_count: number; total_spent: Amount; current_offer_id: string | null; creation_timestamp: number }
 /**
  * Submit a transaction to the network
  */
@@ -2495,7 +3802,13 @@ spend_bundle: SpendBundleJson }
  * Response for transaction submission
  */
 export type SubmitTransactionResponse = Record<string, never>
-export type SyncEvent = { type: "start"; ip: string } | { type: "stop" } | { type: "subscribed" } | { type: "derivation" } | { type: "coin_state" } | { type: "transaction_failed"; transaction_id: string; error: string | null } | { type: "puzzle_batch_synced" } | { type: "cat_info" } | { type: "did_info" } | { type: "nft_data" }
+export type SyncEvent = { type: "start"; ip: string } | { type: "stop" } | { type: "subscribed" } | { type: "derivation" } | { type: "coin_state" } | { type: "transaction_failed"; transaction_id: string; error: string | null } | { type: "puzzle_batch_synced" } | { type: "cat_info" } | { type: "did_info" } | { type: "nft_data" } | { type: "coins_managed"; asset_id: string | null; action: CoinManagementAction; coin_count: number; fee: Amount; transaction_id: string } | { type: "mint_job"; mint_job_id: number } | { type: "reorg"; fork_height: number } | { type: "sync_progress"; phase: SyncPhase; completed: number; remaining: number; eta_seconds: number | null }
+export type SyncPhase = "puzzle_subscriptions" | "coin_subscriptions" | "puzzle_lookups" | "nft_uris" | "cat_info" | "block_times"
+export type SyncPhaseRecord = { phase: SyncPhase; completed: number; remaining: number; 
+/**
+ * Items completed per second since the phase last started
+ */
+rate: number; eta_seconds: number | null }
 /**
  * Accept an offer
  */
@@ -2529,7 +3842,7 @@ spend_bundle: SpendBundleJson;
  */
 transaction_id: string }
 export type TokenRecord = { asset_id: string | null; name: string | null; ticker: string | null; precision: number; description: string | null; icon_url: string | null; visible: boolean; balance: Amount; selectable_balance: Amount; revocation_address: string | null }
-export type TransactionCoinRecord = { coin_id: string; amount: Amount; address: string | null; address_kind: AddressKind; asset: Asset }
+export type TransactionCoinRecord = { coin_id: string; amount: Amount; address: string | null; address_kind: AddressKind; asset: Asset; royalty_nft_id: string | null }
 export type TransactionInput = { coin_id: string; amount: Amount; address: string; asset: Asset | null; outputs: TransactionOutput[] }
 export type TransactionOutput = { coin_id: string; amount: Amount; address: string; receiving: boolean; burning: boolean }
 export type TransactionRecord = { height: number; timestamp: number | null; spent: TransactionCoinRecord[]; created: TransactionCoinRecord[] }
@@ -2647,6 +3960,34 @@ name: string | null;
  * Whether the `DID` should be visible in the UI
  */
 visible: boolean }
+/**
+ * Update a DID's profile metadata
+ */
+export type UpdateDidProfile = { 
+/**
+ * DID ID to update
+ */
+did_id: string; 
+/**
+ * Display name
+ */
+display_name?: string | null; 
+/**
+ * NFT ID to use as the avatar
+ */
+avatar_nft_id?: string | null; 
+/**
+ * Links, such as a website or social profiles
+ */
+links?: string[]; 
+/**
+ * Transaction fee
+ */
+fee: Amount; 
+/**
+ * Whether to automatically submit the transaction
+ */
+auto_submit?: boolean }
 /**
  * Response after updating a `DID`
  */
@@ -2712,6 +4053,66 @@ visible: boolean }
  * Response after updating an option
  */
 export type UpdateOptionResponse = Record<string, never>
+/**
+ * Validate an NFT before minting it
+ */
+export type ValidateNftMint = { 
+/**
+ * NFT to validate
+ */
+mint: NftMint }
+/**
+ * Response with the validation result of an NFT
+ */
+export type ValidateNftMintResponse = { 
+/**
+ * Whether there were no warnings
+ */
+is_valid: boolean; 
+/**
+ * Hash of the data, computed from the first URI which could be fetched
+ */
+data_hash: string | null; 
+/**
+ * Hash of the metadata, computed from the first URI which could be fetched
+ */
+metadata_hash: string | null; 
+/**
+ * Hash of the license, computed from the first URI which could be fetched
+ */
+license_hash: string | null; 
+/**
+ * Problems found with the URIs, hashes or metadata
+ */
+warnings: NftMintWarning[] }
+/**
+ * Verify a message signed with a DID
+ */
+export type VerifyDidSignature = { 
+/**
+ * DID ID that signed the message
+ */
+did_id: string; 
+/**
+ * Message that was signed
+ */
+message: string; 
+/**
+ * Public key that signed the message
+ */
+public_key: string; 
+/**
+ * Signature
+ */
+signature: string }
+/**
+ * Response with the DID signature verification result
+ */
+export type VerifyDidSignatureResponse = { 
+/**
+ * Whether the signature is valid and the public key owns the DID
+ */
+is_valid: boolean }
 /**
  * View coin spends without signing
  */
@@ -2748,8 +4149,17 @@ offer: OfferSummary;
  * Offer status
  */
 status: OfferRecordStatus }
-export type Wallet = { name: string; fingerprint: number; network?: string | null; delta_sync: boolean | null; emoji?: string | null; change_address?: string | null }
+export type Wallet = { name: string; fingerprint: number; network?: string | null; delta_sync: boolean | null; emoji?: string | null; change_address?: string | null; clawback_policy?: WalletClawbackPolicy | null; coin_management?: WalletCoinManagement | null; 
+/**
+ * Keeps the wallet syncing in the background while another wallet is logged in.
+ */
+background_sync: boolean }
+export type WalletClawbackPolicy = { finalize_incoming: boolean; reclaim_outgoing: boolean; reclaim_window_seconds: number; fee: number }
+export type WalletCoinManagement = { fee: number; daily_fee_budget: number; policies: WalletCoinPolicy[] }
+export type WalletCoinPolicy = { asset_id?: string | null; split?: WalletSplitPolicy | null; combine?: WalletCombinePolicy | null }
+export type WalletCombinePolicy = { max_coin_amount: number; coin_count_threshold: number }
 export type WalletDefaults = { delta_sync: boolean }
+export type WalletSplitPolicy = { min_coins: number; coin_amount: number }
 
 /** tauri-specta globals **/
 
@@ -47,6 +47,7 @@ export async function exportTransactions(params: TransactionQueryParams) {
       'Coin Type',
       'Item ID',
       'Coin Name',
+      'Royalty NFT ID',
     ];
     const rows = allTransactions.flatMap((tx) => {
       // Convert timestamp to UTC
@@ -78,6 +79,7 @@ export async function exportTransactions(params: TransactionQueryParams) {
             type.toUpperCase(),
             type === 'xch' ? 'XCH' : coin.asset.asset_id,
             (coin.asset.name ?? coin.asset.ticker ?? '').replace(/,/g, ''),
+            '',
           ];
         }),
         ...tx.created.map((coin) => {
@@ -102,6 +104,7 @@ export async function exportTransactions(params: TransactionQueryParams) {
             type.toUpperCase(),
             type === 'xch' ? 'XCH' : coin.asset.asset_id,
             (coin.asset.name ?? coin.asset.ticker ?? '').replace(/,/g, ''),
+            coin.royalty_nft_id ?? '',
           ];
         }),
       ];